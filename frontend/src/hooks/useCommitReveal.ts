@@ -17,6 +17,8 @@ import {
   getReserveVaultPDA,
   getSecurelpProgram,
   getStakePoolProgram,
+  quoteDeposit,
+  quoteWithdraw,
 } from "../lib/program";
 import {
   MIN_DELAY_SECONDS,
@@ -80,7 +82,7 @@ export function useCommitReveal(): CommitRevealState & CommitRevealActions {
   const program = useProgram();
   const { commitment, refetch: refetchCommitment } = useCommitment(); // Get commitment to validate in reveal
   const { refetch: refetchBalances, slpSolMint } = useBalances();
-  const { poolConfig, calculateSlpForSol, calculateSolForSlp, exchangeRate } = useStakePool();
+  const { poolConfig, exchangeRate } = useStakePool();
 
   const [state, setState] = useState<CommitRevealState>({
     phase: "idle",
@@ -126,8 +128,9 @@ export function useCommitReveal(): CommitRevealState & CommitRevealActions {
       // Convert SOL to lamports
       const amountLamports = BigInt(Math.floor(amountSol * LAMPORTS_PER_SOL));
 
-      // Calculate expected slpSOL output
-      const expectedSlpSol = calculateSlpForSol(amountLamports);
+      // Quote the deposit on chain so fees and dead shares are accounted for
+      const provider = new AnchorProvider(connection, wallet as any, { commitment: "confirmed" });
+      const { userSlp: expectedSlpSol } = await quoteDeposit(getStakePoolProgram(provider), amountLamports);
 
       // Apply slippage tolerance
       const minOut = expectedSlpSol - (expectedSlpSol * BigInt(slippageBps) / BigInt(10000));
@@ -216,7 +219,7 @@ export function useCommitReveal(): CommitRevealState & CommitRevealActions {
         error: error instanceof Error ? error.message : "Failed to initiate stake",
       }));
     }
-  }, [publicKey, program, signTransaction, connection, refetchCommitment, refetchBalances, poolConfig, calculateSlpForSol, exchangeRate]);
+  }, [publicKey, program, signTransaction, connection, refetchCommitment, refetchBalances, poolConfig, exchangeRate, wallet]);
 
   const executeStakeReveal = useCallback(async () => {
     // Validate requirements - check both state and commitment from context
@@ -375,8 +378,9 @@ export function useCommitReveal(): CommitRevealState & CommitRevealActions {
       // Convert slpSOL to lamports (same decimals as SOL)
       const amountLamports = BigInt(Math.floor(amountSlpSol * LAMPORTS_PER_SOL));
 
-      // Calculate expected SOL output
-      const expectedSol = calculateSolForSlp(amountLamports);
+      // Quote the withdrawal on chain so the withdrawal fee is accounted for
+      const provider = new AnchorProvider(connection, wallet as any, { commitment: "confirmed" });
+      const { solOut: expectedSol } = await quoteWithdraw(getStakePoolProgram(provider), amountLamports);
 
       // Apply slippage tolerance
       const minOut = expectedSol - (expectedSol * BigInt(slippageBps) / BigInt(10000));
@@ -463,7 +467,7 @@ export function useCommitReveal(): CommitRevealState & CommitRevealActions {
         error: error instanceof Error ? error.message : "Failed to initiate unstake",
       }));
     }
-  }, [publicKey, program, signTransaction, connection, refetchCommitment, refetchBalances, poolConfig, calculateSolForSlp, exchangeRate]);
+  }, [publicKey, program, signTransaction, connection, refetchCommitment, refetchBalances, poolConfig, exchangeRate, wallet]);
 
   const executeUnstakeReveal = useCallback(async () => {
     // Validate requirements - check both state and commitment from context
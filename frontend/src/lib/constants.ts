@@ -123,6 +123,12 @@ export const DEFAULT_STAKE_POOL_FEE_BPS = 100;
 /** Reserve ratio in basis points (10% kept liquid) */
 export const RESERVE_RATIO_BPS = 1000;
 
+/** slpSOL locked out of the first deposit so the supply never returns to zero */
+export const MINIMUM_SLP_SUPPLY = 1_000_000;
+
+/** Referral fee denominator: the referrer's cut is a share of the deposit fee */
+export const MAX_REFERRAL_FEE_BPS = 10_000;
+
 /** SOL decimals */
 export const SOL_DECIMALS = 9;
 
@@ -1,4 +1,5 @@
 import { Program, AnchorProvider, Idl } from "@coral-xyz/anchor";
+import BN from "bn.js";
 import { Connection, PublicKey } from "@solana/web3.js";
 import { 
   SECURELP_PROGRAM_ID, 
@@ -12,6 +13,8 @@ import {
   AMM_AUTHORITY_SEED,
   VAULT_A_SEED,
   VAULT_B_SEED,
+  MINIMUM_SLP_SUPPLY,
+  MAX_REFERRAL_FEE_BPS,
 } from "./constants";
 
 import securelpIdl from "../idl/securelp.json";
@@ -239,6 +242,27 @@ export interface PoolConfig {
   validatorCount: number;
   bump: number;
   authorityBump: number;
+  feeRecipient: PublicKey;
+  depositFeeBps: number;
+  withdrawalFeeBps: number;
+  referralFeeBps: number;
+}
+
+/** Breakdown of a deposit_sol, as returned by quote_deposit */
+export interface DepositQuote {
+  slpMinted: bigint;
+  userSlp: bigint;
+  depositFee: bigint;
+  referralFee: bigint;
+  deadShares: bigint;
+}
+
+/** Breakdown of a withdraw_sol, as returned by quote_withdraw */
+export interface WithdrawQuote {
+  withdrawalFee: bigint;
+  slpBurned: bigint;
+  solOut: bigint;
+  liquidityFee: bigint;
 }
 
 export interface AmmPool {
@@ -311,12 +335,60 @@ export async function fetchPoolConfig(
       validatorCount: account.validatorCount,
       bump: account.bump,
       authorityBump: account.authorityBump,
+      feeRecipient: account.feeRecipient,
+      depositFeeBps: account.depositFeeBps,
+      withdrawalFeeBps: account.withdrawalFeeBps,
+      referralFeeBps: account.referralFeeBps,
     };
   } catch (error) {
     return null;
   }
 }
 
+/**
+ * Quote a deposit_sol by simulating the stake pool's quote_deposit view.
+ * The simulation needs a funded fee payer, so pass a program built on the
+ * user's wallet.
+ */
+export async function quoteDeposit(
+  program: Program<StakePool>,
+  solLamports: bigint,
+  withReferrer = false
+): Promise<DepositQuote> {
+  const [poolConfigPda] = getPoolConfigPDA();
+  const quote = await program.methods
+    .quoteDeposit(new BN(solLamports.toString()), withReferrer)
+    .accounts({ poolConfig: poolConfigPda } as any)
+    .view();
+  return {
+    slpMinted: BigInt(quote.slpMinted.toString()),
+    userSlp: BigInt(quote.userSlp.toString()),
+    depositFee: BigInt(quote.depositFee.toString()),
+    referralFee: BigInt(quote.referralFee.toString()),
+    deadShares: BigInt(quote.deadShares.toString()),
+  };
+}
+
+/**
+ * Quote a withdraw_sol by simulating the stake pool's quote_withdraw view
+ */
+export async function quoteWithdraw(
+  program: Program<StakePool>,
+  slpAmount: bigint
+): Promise<WithdrawQuote> {
+  const [poolConfigPda] = getPoolConfigPDA();
+  const quote = await program.methods
+    .quoteWithdraw(new BN(slpAmount.toString()))
+    .accounts({ poolConfig: poolConfigPda } as any)
+    .view();
+  return {
+    withdrawalFee: BigInt(quote.withdrawalFee.toString()),
+    slpBurned: BigInt(quote.slpBurned.toString()),
+    solOut: BigInt(quote.solOut.toString()),
+    liquidityFee: BigInt(quote.liquidityFee.toString()),
+  };
+}
+
 /**
  * Fetch an AMM pool
  */
@@ -376,25 +448,97 @@ export function calculateExchangeRate(poolConfig: PoolConfig): number {
 }
 
 /**
- * Calculate slpSOL to mint for a given SOL deposit
+ * Protocol fee of `feeBps` on `amount`, mirrors PoolConfig::protocol_fee:
+ * nothing is charged while no fee recipient is set
  */
-export function calculateSlpForDeposit(poolConfig: PoolConfig, solLamports: bigint): bigint {
-  if (poolConfig.totalSlpSupply === BigInt(0)) {
-    return solLamports; // 1:1 for first deposit
+export function protocolFee(poolConfig: PoolConfig, amount: bigint, feeBps: number): bigint {
+  if (poolConfig.feeRecipient.equals(PublicKey.default)) {
+    return BigInt(0);
+  }
+  return (amount * BigInt(feeBps)) / BigInt(10000);
+}
+
+/**
+ * Estimate a deposit_sol locally, mirrors PoolConfig::quote_deposit.
+ * Returns null when the deposit would not cover the first deposit's dead shares.
+ */
+export function estimateDeposit(
+  poolConfig: PoolConfig,
+  solLamports: bigint,
+  withReferrer = false
+): DepositQuote | null {
+  const totalSol = poolConfig.totalStakedLamports + poolConfig.reserveLamports;
+  const slpMinted = poolConfig.totalSlpSupply === BigInt(0)
+    ? solLamports // 1:1 for first deposit
+    : (solLamports * poolConfig.totalSlpSupply) / totalSol;
+  const depositFee = protocolFee(poolConfig, slpMinted, poolConfig.depositFeeBps);
+  const deadShares = poolConfig.totalSlpSupply === BigInt(0)
+    ? BigInt(MINIMUM_SLP_SUPPLY)
+    : BigInt(0);
+  if (slpMinted < depositFee + deadShares) {
+    return null;
   }
+  const referralFee = withReferrer
+    ? (depositFee * BigInt(poolConfig.referralFeeBps)) / BigInt(MAX_REFERRAL_FEE_BPS)
+    : BigInt(0);
+  return {
+    slpMinted,
+    userSlp: slpMinted - depositFee - deadShares,
+    depositFee,
+    referralFee,
+    deadShares,
+  };
+}
+
+/**
+ * Calculate slpSOL the depositor receives for a given SOL deposit, after
+ * the deposit fee and the first deposit's dead shares
+ */
+export function calculateSlpForDeposit(poolConfig: PoolConfig, solLamports: bigint): bigint {
+  return estimateDeposit(poolConfig, solLamports)?.userSlp ?? BigInt(0);
+}
+
+/**
+ * Instant-unstake fee in bps, mirrors PoolConfig::instant_unstake_fee_bps:
+ * 0.3% while the reserve stays at its 10% target, up to 3% as it is drained
+ */
+export function instantUnstakeFeeBps(poolConfig: PoolConfig, solLamports: bigint): bigint {
+  const minFeeBps = BigInt(30);
+  const maxFeeBps = BigInt(300);
   const totalSol = poolConfig.totalStakedLamports + poolConfig.reserveLamports;
-  return (solLamports * poolConfig.totalSlpSupply) / totalSol;
+  const target = (totalSol * BigInt(1000)) / BigInt(10000);
+  const remaining = poolConfig.reserveLamports > solLamports
+    ? poolConfig.reserveLamports - solLamports
+    : BigInt(0);
+  if (target === BigInt(0) || remaining >= target) {
+    return minFeeBps;
+  }
+  return maxFeeBps - ((maxFeeBps - minFeeBps) * remaining) / target;
 }
 
 /**
- * Calculate SOL to return for a given slpSOL burn
+ * Estimate a withdraw_sol locally, mirrors PoolConfig::quote_withdraw:
+ * the withdrawal fee is taken in slpSOL, the rest is burned and pays out
+ * less the instant-unstake fee
  */
-export function calculateSolForWithdrawal(poolConfig: PoolConfig, slpAmount: bigint): bigint {
+export function estimateWithdraw(poolConfig: PoolConfig, slpAmount: bigint): WithdrawQuote {
+  const withdrawalFee = protocolFee(poolConfig, slpAmount, poolConfig.withdrawalFeeBps);
+  const slpBurned = slpAmount - withdrawalFee;
   if (poolConfig.totalSlpSupply === BigInt(0)) {
-    return BigInt(0);
+    return { withdrawalFee, slpBurned, solOut: BigInt(0), liquidityFee: BigInt(0) };
   }
   const totalSol = poolConfig.totalStakedLamports + poolConfig.reserveLamports;
-  return (slpAmount * totalSol) / poolConfig.totalSlpSupply;
+  const gross = (slpBurned * totalSol) / poolConfig.totalSlpSupply;
+  const liquidityFee = (gross * instantUnstakeFeeBps(poolConfig, gross)) / BigInt(10000);
+  return { withdrawalFee, slpBurned, solOut: gross - liquidityFee, liquidityFee };
+}
+
+/**
+ * Calculate SOL to return for a given slpSOL amount, after the withdrawal
+ * and instant-unstake fees
+ */
+export function calculateSolForWithdrawal(poolConfig: PoolConfig, slpAmount: bigint): bigint {
+  return estimateWithdraw(poolConfig, slpAmount).solOut;
 }
 
 /**
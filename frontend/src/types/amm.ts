@@ -13,6 +13,62 @@ export type Amm = {
     "description": "SecureLiquidPool - Constant Product AMM for slpSOL/SOL Trading"
   },
   "instructions": [
+    {
+      "name": "acceptAuthority",
+      "docs": [
+        "Accept a pending authority transfer (step 2 of 2).",
+        "The new authority must sign, so it can be a wallet or a",
+        "multisig PDA signing through CPI."
+      ],
+      "discriminator": [
+        107,
+        86,
+        198,
+        91,
+        33,
+        12,
+        107,
+        160
+      ],
+      "accounts": [
+        {
+          "name": "newAuthority",
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
     {
       "name": "addLiquidity",
       "docs": [
@@ -139,41 +195,81 @@ export type Amm = {
       ]
     },
     {
-      "name": "initializePool",
+      "name": "cancelParameterChange",
       "docs": [
-        "Initialize a new AMM pool for token pair"
+        "Admin: Cancel the queued parameter change"
       ],
       "discriminator": [
-        95,
-        180,
-        10,
-        172,
-        84,
-        174,
-        232,
-        40
+        195,
+        184,
+        113,
+        7,
+        219,
+        142,
+        137,
+        115
       ],
       "accounts": [
         {
           "name": "authority",
-          "writable": true,
           "signer": true
         },
         {
-          "name": "tokenAMint",
-          "docs": [
-            "Token A mint (e.g., wSOL)"
-          ]
-        },
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimFarmRewards",
+      "docs": [
+        "Claim all pending farm rewards"
+      ],
+      "discriminator": [
+        102,
+        40,
+        223,
+        149,
+        90,
+        81,
+        228,
+        23
+      ],
+      "accounts": [
         {
-          "name": "tokenBMint",
-          "docs": [
-            "Token B mint (e.g., slpSOL)"
-          ]
+          "name": "user",
+          "signer": true
         },
         {
           "name": "pool",
-          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -191,11 +287,13 @@ export type Amm = {
               },
               {
                 "kind": "account",
-                "path": "tokenAMint"
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
               },
               {
                 "kind": "account",
-                "path": "tokenBMint"
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
               }
             ]
           }
@@ -230,20 +328,17 @@ export type Amm = {
           }
         },
         {
-          "name": "tokenAVault",
+          "name": "farm",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  118,
+                  102,
                   97,
-                  117,
-                  108,
-                  116,
-                  95,
-                  97
+                  114,
+                  109
                 ]
               },
               {
@@ -254,78 +349,75 @@ export type Amm = {
           }
         },
         {
-          "name": "tokenBVault",
+          "name": "position",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  118,
-                  97,
-                  117,
                   108,
-                  116,
+                  112,
                   95,
-                  98
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
                 ]
               },
               {
                 "kind": "account",
                 "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "user"
               }
             ]
           }
         },
         {
-          "name": "lpMint",
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "rewardVault",
+          "writable": true
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "userRewardAccount",
+          "writable": true
         },
         {
-          "name": "rent",
-          "address": "SysvarRent111111111111111111111111111111111"
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
         }
       ],
-      "args": [
-        {
-          "name": "feeBps",
-          "type": "u16"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "removeLiquidity",
+      "name": "createLpMetadata",
       "docs": [
-        "Remove liquidity from the pool"
+        "Admin: Create LP token metadata"
       ],
       "discriminator": [
-        80,
-        85,
-        209,
-        72,
-        24,
-        206,
-        177,
-        108
+        148,
+        193,
+        160,
+        116,
+        87,
+        25,
+        123,
+        103
       ],
       "accounts": [
         {
-          "name": "user",
+          "name": "admin",
           "writable": true,
           "signer": true
         },
         {
           "name": "pool",
-          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -384,63 +476,54 @@ export type Amm = {
           }
         },
         {
-          "name": "tokenAVault",
-          "writable": true
-        },
-        {
-          "name": "tokenBVault",
-          "writable": true
-        },
-        {
-          "name": "lpMint",
-          "writable": true
+          "name": "lpMint"
         },
         {
-          "name": "userTokenA",
+          "name": "metadata",
           "writable": true
         },
         {
-          "name": "userTokenB",
-          "writable": true
+          "name": "tokenMetadataProgram",
+          "address": "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
         },
         {
-          "name": "userLpAccount",
-          "writable": true
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
         }
       ],
       "args": [
         {
-          "name": "lpAmount",
-          "type": "u64"
+          "name": "name",
+          "type": "string"
         },
         {
-          "name": "minAOut",
-          "type": "u64"
+          "name": "symbol",
+          "type": "string"
         },
         {
-          "name": "minBOut",
-          "type": "u64"
+          "name": "uri",
+          "type": "string"
         }
       ]
     },
     {
-      "name": "setPaused",
+      "name": "executeParameterChange",
       "docs": [
-        "Admin: Pause/unpause the pool"
+        "Admin: Apply the queued parameter change once the timelock has elapsed"
       ],
       "discriminator": [
-        91,
-        60,
-        125,
-        192,
-        176,
-        225,
-        166,
-        218
+        27,
+        94,
+        168,
+        154,
+        84,
+        114,
+        101,
+        0
       ],
       "accounts": [
         {
@@ -479,32 +562,27 @@ export type Amm = {
           }
         }
       ],
-      "args": [
-        {
-          "name": "paused",
-          "type": "bool"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "swap",
+      "name": "flashBorrow",
       "docs": [
-        "Swap tokens using constant product formula"
+        "Flash loan: borrow `amount` from the vault passed in. A matching",
+        "`flash_repay` for this pool must appear later in the same transaction."
       ],
       "discriminator": [
-        248,
-        198,
-        158,
-        145,
-        225,
-        117,
-        135,
-        200
+        166,
+        221,
+        220,
+        25,
+        61,
+        73,
+        127,
+        240
       ],
       "accounts": [
         {
           "name": "user",
-          "writable": true,
           "signer": true
         },
         {
@@ -568,27 +646,23 @@ export type Amm = {
           }
         },
         {
-          "name": "tokenAVault",
-          "writable": true
-        },
-        {
-          "name": "tokenBVault",
-          "writable": true
-        },
-        {
-          "name": "userTokenIn",
+          "name": "vault",
           "docs": [
-            "User's input token account (A if a_to_b, B otherwise)"
+            "Vault to borrow from (token A or token B vault)"
           ],
           "writable": true
         },
         {
-          "name": "userTokenOut",
+          "name": "userTokenAccount",
           "docs": [
-            "User's output token account (B if a_to_b, A otherwise)"
+            "Receives the borrowed tokens"
           ],
           "writable": true
         },
+        {
+          "name": "instructions",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        },
         {
           "name": "tokenProgram",
           "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
@@ -596,41 +670,37 @@ export type Amm = {
       ],
       "args": [
         {
-          "name": "amountIn",
-          "type": "u64"
-        },
-        {
-          "name": "minAmountOut",
+          "name": "amount",
           "type": "u64"
-        },
-        {
-          "name": "aToB",
-          "type": "bool"
         }
       ]
     },
     {
-      "name": "updateFee",
+      "name": "flashRepay",
       "docs": [
-        "Admin: Update fee"
+        "Flash loan: repay the active loan plus fee. The fee is added to the",
+        "reserves, so it accrues to LPs."
       ],
       "discriminator": [
-        232,
-        253,
-        195,
-        247,
-        148,
-        212,
-        73,
-        222
+        182,
+        143,
+        19,
+        23,
+        39,
+        221,
+        184,
+        78
       ],
       "accounts": [
         {
-          "name": "authority",
+          "name": "user",
           "signer": true
         },
         {
           "name": "pool",
+          "docs": [
+            "Must stay at index 1: flash_borrow looks for the pool here"
+          ],
           "writable": true,
           "pda": {
             "seeds": [
@@ -659,342 +729,2698 @@ export type Amm = {
               }
             ]
           }
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "newFeeBps",
-          "type": "u16"
+          "name": "vault",
+          "docs": [
+            "Vault the loan was taken from"
+          ],
+          "writable": true
+        },
+        {
+          "name": "userTokenAccount",
+          "docs": [
+            "Pays back the loan plus fee"
+          ],
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
         }
-      ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "ammPool",
-      "discriminator": [
-        54,
-        82,
-        185,
-        138,
-        179,
-        191,
-        211,
-        169
-      ]
-    }
-  ],
-  "events": [
-    {
-      "name": "liquidityAdded",
-      "discriminator": [
-        154,
-        26,
-        221,
-        108,
-        238,
-        64,
-        217,
-        161
-      ]
-    },
-    {
-      "name": "liquidityRemoved",
-      "discriminator": [
-        225,
-        105,
-        216,
-        39,
-        124,
-        116,
-        169,
-        189
-      ]
-    },
-    {
-      "name": "poolInitialized",
-      "discriminator": [
-        100,
-        118,
-        173,
-        87,
-        12,
-        198,
-        254,
-        229
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "swapped",
+      "name": "fundFarm",
+      "docs": [
+        "Admin: Fund the farm and emit `amount` rewards evenly over `duration` seconds"
+      ],
       "discriminator": [
-        217,
-        52,
-        52,
-        83,
-        147,
-        135,
-        96,
-        109
+        24,
+        200,
+        152,
+        129,
+        117,
+        142,
+        212,
+        252
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "farm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "rewardVault",
+          "writable": true
+        },
+        {
+          "name": "adminRewardAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "duration",
+          "type": "i64"
+        }
       ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "invalidAuthority",
-      "msg": "Invalid authority"
-    },
-    {
-      "code": 6001,
-      "name": "poolPaused",
-      "msg": "Pool is paused"
-    },
-    {
-      "code": 6002,
-      "name": "insufficientInput",
-      "msg": "Insufficient input amount"
     },
     {
-      "code": 6003,
-      "name": "insufficientOutput",
-      "msg": "Insufficient output amount"
-    },
-    {
-      "code": 6004,
-      "name": "slippageExceeded",
-      "msg": "Slippage tolerance exceeded"
-    },
-    {
-      "code": 6005,
-      "name": "mathOverflow",
-      "msg": "Arithmetic overflow"
-    },
-    {
-      "code": 6006,
-      "name": "invalidMint",
-      "msg": "Invalid token mint"
-    },
-    {
-      "code": 6007,
-      "name": "poolAlreadyInitialized",
-      "msg": "Pool already initialized"
-    },
-    {
-      "code": 6008,
-      "name": "poolNotInitialized",
-      "msg": "Pool not initialized"
-    },
-    {
-      "code": 6009,
-      "name": "zeroLiquidity",
-      "msg": "Zero liquidity"
-    },
-    {
-      "code": 6010,
-      "name": "insufficientLiquidity",
-      "msg": "Insufficient liquidity"
-    },
-    {
-      "code": 6011,
-      "name": "invalidLpAmount",
-      "msg": "Invalid LP amount"
-    },
-    {
-      "code": 6012,
-      "name": "minimumLiquidityNotMet",
-      "msg": "Minimum liquidity not met"
-    },
-    {
-      "code": 6013,
-      "name": "invalidFee",
-      "msg": "Invalid fee"
-    },
-    {
-      "code": 6014,
-      "name": "sameTokenSwap",
-      "msg": "Same token swap not allowed"
-    }
-  ],
-  "types": [
-    {
-      "name": "ammPool",
+      "name": "initializeFarm",
       "docs": [
-        "AMM Pool configuration"
+        "Admin: Create the LP farm for this pool"
+      ],
+      "discriminator": [
+        252,
+        28,
+        185,
+        172,
+        244,
+        74,
+        117,
+        165
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "farm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "lpMint"
+        },
+        {
+          "name": "rewardMint",
+          "docs": [
+            "Reward token mint"
+          ]
+        },
+        {
+          "name": "lpVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109,
+                  95,
+                  108,
+                  112,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm"
+              }
+            ]
+          }
+        },
+        {
+          "name": "rewardVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109,
+                  95,
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializePool",
+      "docs": [
+        "Initialize a new AMM pool for token pair"
+      ],
+      "discriminator": [
+        95,
+        180,
+        10,
+        172,
+        84,
+        174,
+        232,
+        40
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenAMint",
+          "docs": [
+            "Token A mint (e.g., wSOL)"
+          ]
+        },
+        {
+          "name": "tokenBMint",
+          "docs": [
+            "Token B mint (e.g., slpSOL)"
+          ]
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenAMint"
+              },
+              {
+                "kind": "account",
+                "path": "tokenBMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenAVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116,
+                  95,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenBVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  117,
+                  108,
+                  116,
+                  95,
+                  98
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "lpMint",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "feeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "migratePool",
+      "docs": [
+        "Admin: Rewrite a pool created before the flash loan, authority",
+        "transfer and timelock fields in the current AmmPool layout, growing",
+        "the account and topping up its rent from the authority"
+      ],
+      "discriminator": [
+        55,
+        170,
+        171,
+        123,
+        210,
+        69,
+        39,
+        172
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "docs": [
+            "AmmPool; the discriminator, authority and PDA are checked in the handler"
+          ],
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "proposeAuthority",
+      "docs": [
+        "Admin: Propose a new pool authority (step 1 of 2).",
+        "Passing the default pubkey cancels a pending proposal."
+      ],
+      "discriminator": [
+        20,
+        148,
+        236,
+        198,
+        76,
+        119,
+        99,
+        142
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "newAuthority",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "quoteAddLiquidity",
+      "docs": [
+        "View: LP tokens add_liquidity would mint, returned through return data"
+      ],
+      "discriminator": [
+        51,
+        249,
+        149,
+        68,
+        151,
+        126,
+        110,
+        88
+      ],
+      "accounts": [
+        {
+          "name": "pool",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "amountA",
+          "type": "u64"
+        },
+        {
+          "name": "amountB",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": {
+          "name": "addLiquidityQuote"
+        }
+      }
+    },
+    {
+      "name": "quoteSwap",
+      "docs": [
+        "View: What swap would pay out for `amount_in`, returned through return",
+        "data so clients can simulate it and programs can CPI it"
+      ],
+      "discriminator": [
+        20,
+        139,
+        100,
+        190,
+        67,
+        4,
+        13,
+        141
+      ],
+      "accounts": [
+        {
+          "name": "pool",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "amountIn",
+          "type": "u64"
+        },
+        {
+          "name": "aToB",
+          "type": "bool"
+        }
+      ],
+      "returns": {
+        "defined": {
+          "name": "swapQuote"
+        }
+      }
+    },
+    {
+      "name": "removeLiquidity",
+      "docs": [
+        "Remove liquidity from the pool"
+      ],
+      "discriminator": [
+        80,
+        85,
+        209,
+        72,
+        24,
+        206,
+        177,
+        108
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenAVault",
+          "writable": true
+        },
+        {
+          "name": "tokenBVault",
+          "writable": true
+        },
+        {
+          "name": "lpMint",
+          "writable": true
+        },
+        {
+          "name": "userTokenA",
+          "writable": true
+        },
+        {
+          "name": "userTokenB",
+          "writable": true
+        },
+        {
+          "name": "userLpAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "lpAmount",
+          "type": "u64"
+        },
+        {
+          "name": "minAOut",
+          "type": "u64"
+        },
+        {
+          "name": "minBOut",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setPaused",
+      "docs": [
+        "Admin: Pause the pool immediately, or queue an unpause behind the timelock"
+      ],
+      "discriminator": [
+        91,
+        60,
+        125,
+        192,
+        176,
+        225,
+        166,
+        218
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "paused",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "skim",
+      "docs": [
+        "Admin: Send vault tokens above the tracked reserves to the given",
+        "accounts, recovering tokens sent to the pool by mistake"
+      ],
+      "discriminator": [
+        238,
+        120,
+        221,
+        138,
+        82,
+        60,
+        100,
+        218
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenAVault",
+          "writable": true
+        },
+        {
+          "name": "tokenBVault",
+          "writable": true
+        },
+        {
+          "name": "recipientA",
+          "docs": [
+            "Receives the excess token A"
+          ],
+          "writable": true
+        },
+        {
+          "name": "recipientB",
+          "docs": [
+            "Receives the excess token B"
+          ],
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "stakeLp",
+      "docs": [
+        "Stake LP tokens into the farm"
+      ],
+      "discriminator": [
+        48,
+        168,
+        125,
+        78,
+        82,
+        71,
+        152,
+        117
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "farm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  108,
+                  112,
+                  95,
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "lpVault",
+          "writable": true
+        },
+        {
+          "name": "userLpAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "swap",
+      "docs": [
+        "Swap tokens using constant product formula"
+      ],
+      "discriminator": [
+        248,
+        198,
+        158,
+        145,
+        225,
+        117,
+        135,
+        200
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenAVault",
+          "writable": true
+        },
+        {
+          "name": "tokenBVault",
+          "writable": true
+        },
+        {
+          "name": "userTokenIn",
+          "docs": [
+            "User's input token account (A if a_to_b, B otherwise)"
+          ],
+          "writable": true
+        },
+        {
+          "name": "userTokenOut",
+          "docs": [
+            "User's output token account (B if a_to_b, A otherwise)"
+          ],
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amountIn",
+          "type": "u64"
+        },
+        {
+          "name": "minAmountOut",
+          "type": "u64"
+        },
+        {
+          "name": "aToB",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "sync",
+      "docs": [
+        "Admin: Set the reserves to the vault balances. Tokens sent straight to",
+        "a vault are added to the reserves, so they accrue to LPs; skim instead",
+        "returns them. Both are admin-only, so nobody can sync a donation into",
+        "the reserves before the admin can skim it."
+      ],
+      "discriminator": [
+        4,
+        219,
+        40,
+        164,
+        21,
+        157,
+        189,
+        88
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenAVault"
+        },
+        {
+          "name": "tokenBVault"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "unstakeLp",
+      "docs": [
+        "Unstake LP tokens from the farm (rewards stay pending until claimed)"
+      ],
+      "discriminator": [
+        114,
+        4,
+        7,
+        206,
+        251,
+        176,
+        233,
+        119
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "farm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  108,
+                  112,
+                  95,
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "lpVault",
+          "writable": true
+        },
+        {
+          "name": "userLpAccount",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateFee",
+      "docs": [
+        "Admin: Queue a fee update, applied by execute_parameter_change after the timelock"
+      ],
+      "discriminator": [
+        232,
+        253,
+        195,
+        247,
+        148,
+        212,
+        73,
+        222
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  109,
+                  109,
+                  95,
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_a_mint",
+                "account": "ammPool"
+              },
+              {
+                "kind": "account",
+                "path": "pool.token_b_mint",
+                "account": "ammPool"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "newFeeBps",
+          "type": "u16"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "ammPool",
+      "discriminator": [
+        54,
+        82,
+        185,
+        138,
+        179,
+        191,
+        211,
+        169
+      ]
+    },
+    {
+      "name": "farm",
+      "discriminator": [
+        161,
+        156,
+        211,
+        253,
+        250,
+        64,
+        53,
+        250
+      ]
+    },
+    {
+      "name": "userLpPosition",
+      "discriminator": [
+        115,
+        204,
+        229,
+        204,
+        54,
+        180,
+        29,
+        195
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "authorityAccepted",
+      "discriminator": [
+        166,
+        192,
+        219,
+        188,
+        41,
+        209,
+        195,
+        26
+      ]
+    },
+    {
+      "name": "authorityProposed",
+      "discriminator": [
+        244,
+        117,
+        94,
+        112,
+        53,
+        151,
+        35,
+        89
+      ]
+    },
+    {
+      "name": "farmFunded",
+      "discriminator": [
+        209,
+        47,
+        70,
+        106,
+        159,
+        59,
+        116,
+        247
+      ]
+    },
+    {
+      "name": "farmInitialized",
+      "discriminator": [
+        168,
+        204,
+        170,
+        96,
+        98,
+        108,
+        73,
+        121
+      ]
+    },
+    {
+      "name": "farmRewardsClaimed",
+      "discriminator": [
+        106,
+        44,
+        102,
+        241,
+        125,
+        119,
+        255,
+        163
+      ]
+    },
+    {
+      "name": "flashLoanBorrowed",
+      "discriminator": [
+        37,
+        86,
+        25,
+        222,
+        83,
+        163,
+        251,
+        242
+      ]
+    },
+    {
+      "name": "flashLoanRepaid",
+      "discriminator": [
+        9,
+        204,
+        105,
+        115,
+        70,
+        7,
+        25,
+        198
+      ]
+    },
+    {
+      "name": "liquidityAdded",
+      "discriminator": [
+        154,
+        26,
+        221,
+        108,
+        238,
+        64,
+        217,
+        161
+      ]
+    },
+    {
+      "name": "liquidityRemoved",
+      "discriminator": [
+        225,
+        105,
+        216,
+        39,
+        124,
+        116,
+        169,
+        189
+      ]
+    },
+    {
+      "name": "lpStaked",
+      "discriminator": [
+        16,
+        161,
+        17,
+        74,
+        248,
+        40,
+        134,
+        153
+      ]
+    },
+    {
+      "name": "lpUnstaked",
+      "discriminator": [
+        98,
+        121,
+        215,
+        234,
+        194,
+        68,
+        182,
+        8
+      ]
+    },
+    {
+      "name": "parameterChangeCancelled",
+      "discriminator": [
+        128,
+        235,
+        99,
+        224,
+        243,
+        240,
+        219,
+        207
+      ]
+    },
+    {
+      "name": "parameterChangeExecuted",
+      "discriminator": [
+        103,
+        81,
+        151,
+        152,
+        144,
+        77,
+        163,
+        72
+      ]
+    },
+    {
+      "name": "parameterChangeQueued",
+      "discriminator": [
+        85,
+        113,
+        75,
+        29,
+        174,
+        190,
+        226,
+        168
+      ]
+    },
+    {
+      "name": "poolInitialized",
+      "discriminator": [
+        100,
+        118,
+        173,
+        87,
+        12,
+        198,
+        254,
+        229
+      ]
+    },
+    {
+      "name": "poolMigrated",
+      "discriminator": [
+        250,
+        204,
+        24,
+        195,
+        37,
+        253,
+        152,
+        6
+      ]
+    },
+    {
+      "name": "reservesSynced",
+      "discriminator": [
+        223,
+        110,
+        75,
+        68,
+        168,
+        42,
+        115,
+        136
+      ]
+    },
+    {
+      "name": "skimmed",
+      "discriminator": [
+        25,
+        39,
+        18,
+        44,
+        46,
+        131,
+        59,
+        76
+      ]
+    },
+    {
+      "name": "swapped",
+      "discriminator": [
+        217,
+        52,
+        52,
+        83,
+        147,
+        135,
+        96,
+        109
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "invalidAuthority",
+      "msg": "Invalid authority"
+    },
+    {
+      "code": 6001,
+      "name": "poolPaused",
+      "msg": "Pool is paused"
+    },
+    {
+      "code": 6002,
+      "name": "insufficientInput",
+      "msg": "Insufficient input amount"
+    },
+    {
+      "code": 6003,
+      "name": "insufficientOutput",
+      "msg": "Insufficient output amount"
+    },
+    {
+      "code": 6004,
+      "name": "slippageExceeded",
+      "msg": "Slippage tolerance exceeded"
+    },
+    {
+      "code": 6005,
+      "name": "mathOverflow",
+      "msg": "Arithmetic overflow"
+    },
+    {
+      "code": 6006,
+      "name": "invalidMint",
+      "msg": "Invalid token mint"
+    },
+    {
+      "code": 6007,
+      "name": "poolAlreadyInitialized",
+      "msg": "Pool already initialized"
+    },
+    {
+      "code": 6008,
+      "name": "poolNotInitialized",
+      "msg": "Pool not initialized"
+    },
+    {
+      "code": 6009,
+      "name": "zeroLiquidity",
+      "msg": "Zero liquidity"
+    },
+    {
+      "code": 6010,
+      "name": "insufficientLiquidity",
+      "msg": "Insufficient liquidity"
+    },
+    {
+      "code": 6011,
+      "name": "invalidLpAmount",
+      "msg": "Invalid LP amount"
+    },
+    {
+      "code": 6012,
+      "name": "minimumLiquidityNotMet",
+      "msg": "Minimum liquidity not met"
+    },
+    {
+      "code": 6013,
+      "name": "invalidFee",
+      "msg": "Invalid fee"
+    },
+    {
+      "code": 6014,
+      "name": "sameTokenSwap",
+      "msg": "Same token swap not allowed"
+    },
+    {
+      "code": 6015,
+      "name": "invalidRewardDuration",
+      "msg": "Invalid reward duration"
+    },
+    {
+      "code": 6016,
+      "name": "insufficientStakedLp",
+      "msg": "Insufficient staked LP tokens"
+    },
+    {
+      "code": 6017,
+      "name": "noRewardsToClaim",
+      "msg": "No rewards to claim"
+    },
+    {
+      "code": 6018,
+      "name": "flashLoanActive",
+      "msg": "A flash loan is already active on this pool"
+    },
+    {
+      "code": 6019,
+      "name": "flashLoanNotActive",
+      "msg": "No active flash loan to repay"
+    },
+    {
+      "code": 6020,
+      "name": "flashRepayMissing",
+      "msg": "Flash loan must be repaid later in the same transaction"
+    },
+    {
+      "code": 6021,
+      "name": "flashLoanCpiNotAllowed",
+      "msg": "Flash loans cannot be taken through CPI"
+    },
+    {
+      "code": 6022,
+      "name": "noPendingAuthority",
+      "msg": "No pending authority transfer"
+    },
+    {
+      "code": 6023,
+      "name": "parameterChangePending",
+      "msg": "A parameter change is already queued"
+    },
+    {
+      "code": 6024,
+      "name": "noPendingParameterChange",
+      "msg": "No parameter change queued"
+    },
+    {
+      "code": 6025,
+      "name": "timelockNotElapsed",
+      "msg": "Timelock has not elapsed"
+    },
+    {
+      "code": 6026,
+      "name": "poolNotPaused",
+      "msg": "Pool is not paused"
+    },
+    {
+      "code": 6027,
+      "name": "poolAlreadyMigrated",
+      "msg": "Pool account is not in the pre-upgrade layout"
+    }
+  ],
+  "types": [
+    {
+      "name": "addLiquidityQuote",
+      "docs": [
+        "Result of adding liquidity, as returned by quote_add_liquidity"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lpMinted",
+            "docs": [
+              "LP tokens minted to the user"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ammPool",
+      "docs": [
+        "AMM Pool configuration"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "authority",
+            "docs": [
+              "Pool authority (admin)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "tokenAMint",
+            "docs": [
+              "Token A mint (typically wSOL for native SOL)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "tokenBMint",
+            "docs": [
+              "Token B mint (slpSOL)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "tokenAVault",
+            "docs": [
+              "Token A vault (PDA-owned)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "tokenBVault",
+            "docs": [
+              "Token B vault (PDA-owned)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "lpMint",
+            "docs": [
+              "LP token mint"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "reserveA",
+            "docs": [
+              "Current reserve of token A"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserveB",
+            "docs": [
+              "Current reserve of token B"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalLpSupply",
+            "docs": [
+              "Total LP tokens minted"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feeBps",
+            "docs": [
+              "Swap fee in basis points (e.g., 30 = 0.3%)"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "paused",
+            "docs": [
+              "Whether pool is paused"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "cumulativeFeeA",
+            "docs": [
+              "Cumulative fees collected for token A"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "cumulativeFeeB",
+            "docs": [
+              "Cumulative fees collected for token B"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump for this PDA"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "authorityBump",
+            "docs": [
+              "Bump for authority PDA"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "flashLoanAmount",
+            "docs": [
+              "Amount currently out on flash loan (0 = no active loan)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "flashLoanIsA",
+            "docs": [
+              "Whether the active flash loan is in token A (false = token B)"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "pendingAuthority",
+            "docs": [
+              "Proposed new authority, awaiting acceptance (default = none)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingChange",
+            "docs": [
+              "Queued parameter change"
+            ],
+            "type": {
+              "defined": {
+                "name": "parameterChange"
+              }
+            }
+          },
+          {
+            "name": "pendingChangeSlot",
+            "docs": [
+              "Slot at which the queued change can be executed"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Reserved for future use"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                12
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "authorityAccepted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "previousAuthority",
+            "type": "pubkey"
+          },
+          {
+            "name": "newAuthority",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "authorityProposed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "currentAuthority",
+            "type": "pubkey"
+          },
+          {
+            "name": "proposedAuthority",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "farm",
+      "docs": [
+        "LP farm: distributes admin-funded rewards to staked LP tokens"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "authority",
+            "name": "pool",
             "docs": [
-              "Pool authority (admin)"
+              "AMM pool whose LP tokens are staked"
             ],
             "type": "pubkey"
           },
           {
-            "name": "tokenAMint",
+            "name": "rewardMint",
             "docs": [
-              "Token A mint (typically wSOL for native SOL)"
+              "Reward token mint"
             ],
             "type": "pubkey"
           },
           {
-            "name": "tokenBMint",
+            "name": "lpVault",
             "docs": [
-              "Token B mint (slpSOL)"
+              "Vault holding staked LP tokens (PDA-owned)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "rewardVault",
+            "docs": [
+              "Vault holding undistributed rewards (PDA-owned)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "totalStaked",
+            "docs": [
+              "Total LP tokens staked in the farm"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "rewardRate",
+            "docs": [
+              "Rewards emitted per second during the current period"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "periodFinish",
+            "docs": [
+              "Timestamp when the current reward period ends"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "lastUpdateTs",
+            "docs": [
+              "Timestamp the accumulator was last updated"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "accRewardPerShare",
+            "docs": [
+              "Accumulated rewards per staked LP token (scaled by REWARD_PRECISION)"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump for this PDA"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "unallocatedRewards",
+            "docs": [
+              "Rewards emitted while nothing was staked, added to the next period"
             ],
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Reserved for future use"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                24
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "farmFunded",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "farm",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "rewardRate",
+            "type": "u64"
+          },
+          {
+            "name": "periodFinish",
+            "type": "i64"
+          },
+          {
+            "name": "accRewardPerShare",
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "farmInitialized",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "farm",
+            "type": "pubkey"
+          },
+          {
+            "name": "rewardMint",
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "farmRewardsClaimed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "farm",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "positionLpTokens",
+            "type": "u64"
+          },
+          {
+            "name": "accRewardPerShare",
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "flashLoanBorrowed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "isA",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "flashLoanRepaid",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "fee",
+            "type": "u64"
+          },
+          {
+            "name": "isA",
+            "type": "bool"
+          },
+          {
+            "name": "reserveA",
+            "type": "u64"
+          },
+          {
+            "name": "reserveB",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "liquidityAdded",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "amountA",
+            "type": "u64"
+          },
+          {
+            "name": "amountB",
+            "type": "u64"
+          },
+          {
+            "name": "lpMinted",
+            "type": "u64"
+          },
+          {
+            "name": "reserveA",
+            "type": "u64"
+          },
+          {
+            "name": "reserveB",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "liquidityRemoved",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "lpBurned",
+            "type": "u64"
+          },
+          {
+            "name": "amountA",
+            "type": "u64"
+          },
+          {
+            "name": "amountB",
+            "type": "u64"
+          },
+          {
+            "name": "reserveA",
+            "type": "u64"
+          },
+          {
+            "name": "reserveB",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "lpStaked",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "farm",
             "type": "pubkey"
           },
           {
-            "name": "tokenAVault",
-            "docs": [
-              "Token A vault (PDA-owned)"
-            ],
-            "type": "pubkey"
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "positionLpTokens",
+            "type": "u64"
+          },
+          {
+            "name": "pendingRewards",
+            "type": "u64"
           },
           {
-            "name": "tokenBVault",
-            "docs": [
-              "Token B vault (PDA-owned)"
-            ],
+            "name": "totalStaked",
+            "type": "u64"
+          },
+          {
+            "name": "accRewardPerShare",
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "lpUnstaked",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
             "type": "pubkey"
           },
           {
-            "name": "lpMint",
-            "docs": [
-              "LP token mint"
-            ],
+            "name": "farm",
             "type": "pubkey"
           },
           {
-            "name": "reserveA",
-            "docs": [
-              "Current reserve of token A"
-            ],
+            "name": "amount",
             "type": "u64"
           },
           {
-            "name": "reserveB",
-            "docs": [
-              "Current reserve of token B"
-            ],
+            "name": "positionLpTokens",
             "type": "u64"
           },
           {
-            "name": "totalLpSupply",
-            "docs": [
-              "Total LP tokens minted"
-            ],
+            "name": "pendingRewards",
             "type": "u64"
           },
           {
-            "name": "feeBps",
-            "docs": [
-              "Swap fee in basis points (e.g., 30 = 0.3%)"
-            ],
-            "type": "u16"
+            "name": "totalStaked",
+            "type": "u64"
           },
           {
-            "name": "paused",
-            "docs": [
-              "Whether pool is paused"
-            ],
-            "type": "bool"
-          },
+            "name": "accRewardPerShare",
+            "type": "u128"
+          }
+        ]
+      }
+    },
+    {
+      "name": "parameterChange",
+      "docs": [
+        "Parameter change waiting out the timelock"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
           {
-            "name": "cumulativeFeeA",
-            "docs": [
-              "Cumulative fees collected for token A"
-            ],
-            "type": "u64"
+            "name": "none"
           },
           {
-            "name": "cumulativeFeeB",
-            "docs": [
-              "Cumulative fees collected for token B"
-            ],
-            "type": "u64"
+            "name": "fee",
+            "fields": [
+              {
+                "name": "newFeeBps",
+                "type": "u16"
+              }
+            ]
           },
           {
-            "name": "bump",
-            "docs": [
-              "Bump for this PDA"
-            ],
-            "type": "u8"
+            "name": "unpause"
+          }
+        ]
+      }
+    },
+    {
+      "name": "parameterChangeCancelled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
           },
           {
-            "name": "authorityBump",
-            "docs": [
-              "Bump for authority PDA"
-            ],
-            "type": "u8"
+            "name": "change",
+            "type": {
+              "defined": {
+                "name": "parameterChange"
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "parameterChangeExecuted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
           },
           {
-            "name": "reserved",
-            "docs": [
-              "Reserved for future use"
-            ],
+            "name": "change",
             "type": {
-              "array": [
-                "u8",
-                32
-              ]
+              "defined": {
+                "name": "parameterChange"
+              }
             }
           }
         ]
       }
     },
     {
-      "name": "liquidityAdded",
+      "name": "parameterChangeQueued",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "user",
+            "name": "pool",
             "type": "pubkey"
           },
           {
-            "name": "amountA",
-            "type": "u64"
+            "name": "change",
+            "type": {
+              "defined": {
+                "name": "parameterChange"
+              }
+            }
           },
           {
-            "name": "amountB",
+            "name": "activationSlot",
             "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "poolInitialized",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
           },
           {
-            "name": "lpMinted",
-            "type": "u64"
+            "name": "tokenAMint",
+            "type": "pubkey"
           },
           {
-            "name": "reserveA",
-            "type": "u64"
+            "name": "tokenBMint",
+            "type": "pubkey"
           },
           {
-            "name": "reserveB",
-            "type": "u64"
+            "name": "feeBps",
+            "type": "u16"
           }
         ]
       }
     },
     {
-      "name": "liquidityRemoved",
+      "name": "poolMigrated",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "user",
+            "name": "pool",
             "type": "pubkey"
           },
           {
-            "name": "lpBurned",
-            "type": "u64"
+            "name": "previousLen",
+            "type": "u32"
           },
           {
-            "name": "amountA",
+            "name": "newLen",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "reservesSynced",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "type": "pubkey"
+          },
+          {
+            "name": "previousReserveA",
             "type": "u64"
           },
           {
-            "name": "amountB",
+            "name": "previousReserveB",
             "type": "u64"
           },
           {
@@ -1009,7 +3435,7 @@ export type Amm = {
       }
     },
     {
-      "name": "poolInitialized",
+      "name": "skimmed",
       "type": {
         "kind": "struct",
         "fields": [
@@ -1018,16 +3444,37 @@ export type Amm = {
             "type": "pubkey"
           },
           {
-            "name": "tokenAMint",
-            "type": "pubkey"
+            "name": "amountA",
+            "type": "u64"
           },
           {
-            "name": "tokenBMint",
-            "type": "pubkey"
+            "name": "amountB",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "swapQuote",
+      "docs": [
+        "Result of a swap, as returned by quote_swap"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "amountOut",
+            "docs": [
+              "Tokens the user receives"
+            ],
+            "type": "u64"
           },
           {
-            "name": "feeBps",
-            "type": "u16"
+            "name": "feeAmount",
+            "docs": [
+              "Input tokens taken as the swap fee"
+            ],
+            "type": "u64"
           }
         ]
       }
@@ -1067,6 +3514,85 @@ export type Amm = {
           }
         ]
       }
+    },
+    {
+      "name": "userLpPosition",
+      "docs": [
+        "User's LP position staked in the pool's farm"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "docs": [
+              "User's wallet"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pool",
+            "docs": [
+              "Pool this position is for"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "lpTokens",
+            "docs": [
+              "LP tokens staked in the farm"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "firstDepositTs",
+            "docs": [
+              "Timestamp of first deposit"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "lastActionTs",
+            "docs": [
+              "Timestamp of last action"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "rewardDebt",
+            "docs": [
+              "Rewards already accounted for (lp_tokens * acc_reward_per_share)"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "pendingRewards",
+            "docs": [
+              "Rewards settled but not yet claimed"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump seed"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Reserved"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          }
+        ]
+      }
     }
   ]
 };
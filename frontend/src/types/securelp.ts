@@ -18,7 +18,8 @@ export type Securelp = {
       "docs": [
         "Cancel Commitment: Allow user to cancel their commitment and reclaim rent",
         "",
-        "This can only be called by the original user who created the commitment."
+        "This can only be called by the original user who created the commitment.",
+        "Expired commitments can only be cancelled."
       ],
       "discriminator": [
         36,
@@ -155,13 +156,208 @@ export type Securelp = {
         }
       ]
     },
+    {
+      "name": "revealAndRequestWithdrawal",
+      "docs": [
+        "Reveal and Request Withdrawal: Verify commitment and open a delayed-unstake ticket",
+        "",
+        "This instruction:",
+        "1. Verifies the minimum delay has passed since commit, and the",
+        "commitment hasn't expired",
+        "2. Verifies the hash matches the provided SwapDetails",
+        "3. Executes stake_pool request_withdrawal via CPI",
+        "4. Checks the ticket is worth at least min_out lamports",
+        "5. Closes the commitment PDA (returns rent to user)"
+      ],
+      "discriminator": [
+        234,
+        163,
+        115,
+        50,
+        219,
+        92,
+        250,
+        215
+      ],
+      "accounts": [
+        {
+          "name": "commitment",
+          "docs": [
+            "The commitment PDA to verify and close"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  109,
+                  109,
+                  105,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "user",
+          "docs": [
+            "The user executing the reveal (must match commitment creator)"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "stakePoolProgram",
+          "docs": [
+            "Stake pool program"
+          ],
+          "address": "EyWBdqo6J5KEzQSvPYhsGFXjJfC6kkmTMGo8JTEzqhZ7"
+        },
+        {
+          "name": "poolConfig",
+          "docs": [
+            "Pool config PDA"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "stakePoolProgram"
+            }
+          }
+        },
+        {
+          "name": "withdrawalTicket",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  105,
+                  116,
+                  104,
+                  100,
+                  114,
+                  97,
+                  119,
+                  97,
+                  108,
+                  95,
+                  116,
+                  105,
+                  99,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "arg",
+                "path": "ticketId"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "stakePoolProgram"
+            }
+          }
+        },
+        {
+          "name": "slpMint",
+          "docs": [
+            "slpSOL mint"
+          ],
+          "writable": true
+        },
+        {
+          "name": "userSlpAccount",
+          "docs": [
+            "User's slpSOL token account"
+          ],
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "docs": [
+            "Pool's slpSOL fee account (validated by stake_pool)"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "docs": [
+            "Token program"
+          ],
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "docs": [
+            "System program"
+          ],
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "details",
+          "type": {
+            "defined": {
+              "name": "swapDetails"
+            }
+          }
+        },
+        {
+          "name": "ticketId",
+          "type": "u64"
+        }
+      ]
+    },
     {
       "name": "revealAndStake",
       "docs": [
         "Reveal and Stake: Verify commitment and execute SOL -> slpSOL deposit",
         "",
         "This instruction:",
-        "1. Verifies the minimum delay has passed since commit",
+        "1. Verifies the minimum delay has passed since commit, and the",
+        "commitment hasn't expired",
         "2. Verifies the hash matches the provided SwapDetails",
         "3. Executes stake_pool deposit via CPI",
         "4. Closes the commitment PDA (returns rent to user)"
@@ -331,6 +527,30 @@ export type Securelp = {
           ],
           "writable": true
         },
+        {
+          "name": "feeRecipient",
+          "docs": [
+            "Pool's slpSOL fee account (validated by stake_pool)"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "referrer",
+          "docs": [
+            "Referring partner's slpSOL account (validated by stake_pool)"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "userStake",
+          "docs": [
+            "User's stake position (validated by stake_pool)"
+          ],
+          "writable": true,
+          "optional": true
+        },
         {
           "name": "tokenProgram",
           "docs": [
@@ -363,7 +583,8 @@ export type Securelp = {
         "Reveal and Swap: Verify commitment and execute AMM swap",
         "",
         "This instruction:",
-        "1. Verifies the minimum delay has passed since commit",
+        "1. Verifies the minimum delay has passed since commit, and the",
+        "commitment hasn't expired",
         "2. Verifies the hash matches the provided SwapDetails",
         "3. Executes AMM swap via CPI",
         "4. Closes the commitment PDA (returns rent to user)"
@@ -524,7 +745,8 @@ export type Securelp = {
         "Reveal and Unstake: Verify commitment and execute slpSOL -> SOL withdrawal",
         "",
         "This instruction:",
-        "1. Verifies the minimum delay has passed since commit",
+        "1. Verifies the minimum delay has passed since commit, and the",
+        "commitment hasn't expired",
         "2. Verifies the hash matches the provided SwapDetails",
         "3. Executes stake_pool withdrawal via CPI",
         "4. Closes the commitment PDA (returns rent to user)"
@@ -660,6 +882,22 @@ export type Securelp = {
           ],
           "writable": true
         },
+        {
+          "name": "feeRecipient",
+          "docs": [
+            "Pool's slpSOL fee account (validated by stake_pool)"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "userStake",
+          "docs": [
+            "User's stake position (validated by stake_pool)"
+          ],
+          "writable": true,
+          "optional": true
+        },
         {
           "name": "tokenProgram",
           "docs": [
@@ -767,6 +1005,19 @@ export type Securelp = {
         79,
         197
       ]
+    },
+    {
+      "name": "withdrawalRequestEvent",
+      "discriminator": [
+        223,
+        105,
+        92,
+        147,
+        28,
+        49,
+        115,
+        238
+      ]
     }
   ],
   "errors": [
@@ -814,6 +1065,16 @@ export type Securelp = {
       "code": 6008,
       "name": "insufficientBalance",
       "msg": "Insufficient balance for this operation."
+    },
+    {
+      "code": 6009,
+      "name": "minOutputNotMet",
+      "msg": "Output below the committed minimum."
+    },
+    {
+      "code": 6010,
+      "name": "commitmentExpired",
+      "msg": "Commitment expired. Cancel it and commit again."
     }
   ],
   "types": [
@@ -930,6 +1191,45 @@ export type Securelp = {
             ],
             "type": "u8"
           },
+          {
+            "name": "flashLoanAmount",
+            "docs": [
+              "Amount currently out on flash loan (0 = no active loan)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "flashLoanIsA",
+            "docs": [
+              "Whether the active flash loan is in token A (false = token B)"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "pendingAuthority",
+            "docs": [
+              "Proposed new authority, awaiting acceptance (default = none)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingChange",
+            "docs": [
+              "Queued parameter change"
+            ],
+            "type": {
+              "defined": {
+                "name": "ammStateParameterChange"
+              }
+            }
+          },
+          {
+            "name": "pendingChangeSlot",
+            "docs": [
+              "Slot at which the queued change can be executed"
+            ],
+            "type": "u64"
+          },
           {
             "name": "reserved",
             "docs": [
@@ -938,7 +1238,7 @@ export type Securelp = {
             "type": {
               "array": [
                 "u8",
-                32
+                12
               ]
             }
           }
@@ -1004,6 +1304,29 @@ export type Securelp = {
         ]
       }
     },
+    {
+      "name": "feeType",
+      "docs": [
+        "Which protocol fee a fee update applies to"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "deposit"
+          },
+          {
+            "name": "withdrawal"
+          },
+          {
+            "name": "reward"
+          },
+          {
+            "name": "referral"
+          }
+        ]
+      }
+    },
     {
       "name": "poolConfig",
       "docs": [
@@ -1050,7 +1373,7 @@ export type Securelp = {
           {
             "name": "feeBps",
             "docs": [
-              "Protocol fee in basis points (e.g., 100 = 1%)"
+              "Protocol fee on staking rewards in basis points (e.g., 100 = 1%)"
             ],
             "type": "u16"
           },
@@ -1073,7 +1396,7 @@ export type Securelp = {
             "docs": [
               "Number of validators in the pool"
             ],
-            "type": "u8"
+            "type": "u32"
           },
           {
             "name": "bump",
@@ -1089,6 +1412,123 @@ export type Securelp = {
             ],
             "type": "u8"
           },
+          {
+            "name": "pendingAdmin",
+            "docs": [
+              "Proposed new admin, awaiting acceptance (default = none)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingChange",
+            "docs": [
+              "Queued parameter change"
+            ],
+            "type": {
+              "defined": {
+                "name": "stakePoolStateParameterChange"
+              }
+            }
+          },
+          {
+            "name": "pendingChangeSlot",
+            "docs": [
+              "Slot at which the queued change can be executed"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "ticketLamportsOwed",
+            "docs": [
+              "SOL owed to open withdrawal tickets (already removed from total_staked_lamports)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "deactivatingLamports",
+            "docs": [
+              "Lamports in deactivating stake accounts, incl. their rent"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "claimableLamports",
+            "docs": [
+              "SOL back in the reserve vault, set aside for ticket claims"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalValidatorWeight",
+            "docs": [
+              "Sum of target weights over active validators"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "scoreOracle",
+            "docs": [
+              "Oracle allowed to push validator scores"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "feeRecipient",
+            "docs": [
+              "slpSOL token account receiving protocol fees (default = fees off)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingFeeRecipient",
+            "docs": [
+              "Fee recipient queued behind the timelock (default = none)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "depositFeeBps",
+            "docs": [
+              "Protocol fee on deposits, in bps of the slpSOL minted"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "withdrawalFeeBps",
+            "docs": [
+              "Protocol fee on withdrawals, in bps of the slpSOL withdrawn"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "referralFeeBps",
+            "docs": [
+              "Referrer's share of the deposit fee, in bps of the fee"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "lastBalanceUpdateEpoch",
+            "docs": [
+              "Last epoch update_pool_balance recognised reserve donations as rewards"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "ticketLamportsRequested",
+            "docs": [
+              "SOL ever owed to withdrawal tickets, in request order"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "ticketLamportsFunded",
+            "docs": [
+              "SOL ever set aside for ticket claims; tickets are paid in request order",
+              "up to this amount"
+            ],
+            "type": "u64"
+          },
           {
             "name": "reserved",
             "docs": [
@@ -1097,7 +1537,7 @@ export type Securelp = {
             "type": {
               "array": [
                 "u8",
-                32
+                4
               ]
             }
           }
@@ -1272,6 +1712,115 @@ export type Securelp = {
           }
         ]
       }
+    },
+    {
+      "name": "withdrawalRequestEvent",
+      "docs": [
+        "Event emitted when a delayed-unstake ticket is opened"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "docs": [
+              "User who requested the withdrawal"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "ticket",
+            "docs": [
+              "Withdrawal ticket created in stake_pool"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "amountIn",
+            "docs": [
+              "Amount of slpSOL burned"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "minOut",
+            "docs": [
+              "Minimum SOL expected"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "docs": [
+              "Timestamp of the request"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "ammStateParameterChange",
+      "docs": [
+        "Parameter change waiting out the timelock"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "none"
+          },
+          {
+            "name": "fee",
+            "fields": [
+              {
+                "name": "newFeeBps",
+                "type": "u16"
+              }
+            ]
+          },
+          {
+            "name": "unpause"
+          }
+        ]
+      }
+    },
+    {
+      "name": "stakePoolStateParameterChange",
+      "docs": [
+        "Parameter change waiting out the timelock"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "none"
+          },
+          {
+            "name": "fee",
+            "fields": [
+              {
+                "name": "feeType",
+                "type": {
+                  "defined": {
+                    "name": "feeType"
+                  }
+                }
+              },
+              {
+                "name": "newFeeBps",
+                "type": "u16"
+              }
+            ]
+          },
+          {
+            "name": "unpause"
+          },
+          {
+            "name": "feeRecipient"
+          }
+        ]
+      }
     }
   ]
 };
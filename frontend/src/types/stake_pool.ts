@@ -13,6 +13,55 @@ export type StakePool = {
     "description": "SecureLiquidPool - Liquid Staking Pool with Real Validator Delegation"
   },
   "instructions": [
+    {
+      "name": "acceptAuthority",
+      "docs": [
+        "Accept a pending admin transfer (step 2 of 2).",
+        "The new admin must sign, so it can be a wallet or a",
+        "multisig PDA signing through CPI."
+      ],
+      "discriminator": [
+        107,
+        86,
+        198,
+        91,
+        33,
+        12,
+        107,
+        160
+      ],
+      "accounts": [
+        {
+          "name": "newAdmin",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
     {
       "name": "addValidator",
       "docs": [
@@ -31,7 +80,6 @@ export type StakePool = {
       "accounts": [
         {
           "name": "admin",
-          "writable": true,
           "signer": true
         },
         {
@@ -59,10 +107,7 @@ export type StakePool = {
           }
         },
         {
-          "name": "voteAccount"
-        },
-        {
-          "name": "validatorEntry",
+          "name": "validatorList",
           "writable": true,
           "pda": {
             "seeds": [
@@ -79,50 +124,43 @@ export type StakePool = {
                   111,
                   114,
                   95,
+                  108,
+                  105,
                   115,
-                  116,
-                  97,
-                  107,
-                  101
+                  116
                 ]
               },
               {
                 "kind": "account",
                 "path": "poolConfig"
-              },
-              {
-                "kind": "account",
-                "path": "voteAccount"
               }
             ]
           }
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "voteAccount"
         }
       ],
       "args": []
     },
     {
-      "name": "delegateStake",
+      "name": "cancelParameterChange",
       "docs": [
-        "Crank: Move SOL from reserve to validators",
-        "This is called periodically to actually stake the deposited SOL"
+        "Admin: Cancel the queued parameter change"
       ],
       "discriminator": [
-        50,
-        110,
-        95,
-        179,
-        194,
-        75,
-        140,
-        246
+        195,
+        184,
+        113,
+        7,
+        219,
+        142,
+        137,
+        115
       ],
       "accounts": [
         {
-          "name": "cranker",
+          "name": "admin",
           "signer": true
         },
         {
@@ -148,70 +186,24 @@ export type StakePool = {
               }
             ]
           }
-        },
-        {
-          "name": "validatorEntry",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  118,
-                  97,
-                  108,
-                  105,
-                  100,
-                  97,
-                  116,
-                  111,
-                  114,
-                  95,
-                  115,
-                  116,
-                  97,
-                  107,
-                  101
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "poolConfig"
-              },
-              {
-                "kind": "account",
-                "path": "validator_entry.vote_account",
-                "account": "validatorEntry"
-              }
-            ]
-          }
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
         }
       ],
-      "args": [
-        {
-          "name": "amountLamports",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "depositSol",
+      "name": "claimWithdrawal",
       "docs": [
-        "Deposit SOL and receive slpSOL tokens"
+        "Claim the SOL owed by a withdrawal ticket and close it"
       ],
       "discriminator": [
-        108,
-        81,
-        78,
-        117,
-        125,
-        155,
-        56,
-        200
+        118,
+        206,
+        173,
+        38,
+        239,
+        165,
+        65,
+        30
       ],
       "accounts": [
         {
@@ -244,31 +236,44 @@ export type StakePool = {
           }
         },
         {
-          "name": "poolAuthority",
+          "name": "withdrawalTicket",
+          "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  112,
-                  111,
-                  111,
-                  108,
-                  95,
-                  97,
-                  117,
+                  119,
+                  105,
                   116,
                   104,
-                  111,
+                  100,
                   114,
-                  105,
+                  97,
+                  119,
+                  97,
+                  108,
+                  95,
                   116,
-                  121
+                  105,
+                  99,
+                  107,
+                  101,
+                  116
                 ]
               },
               {
                 "kind": "account",
                 "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "account",
+                "path": "withdrawal_ticket.ticket_id",
+                "account": "withdrawalTicket"
               }
             ]
           }
@@ -303,54 +308,36 @@ export type StakePool = {
             ]
           }
         },
-        {
-          "name": "slpMint",
-          "writable": true
-        },
-        {
-          "name": "userSlpAccount",
-          "writable": true
-        },
-        {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
-        },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
         }
       ],
-      "args": [
-        {
-          "name": "amountLamports",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "harvestRewards",
+      "name": "closeUserStake",
       "docs": [
-        "Crank: Simulate harvesting epoch rewards",
-        "On devnet, we simulate rewards based on ~7% APY"
+        "Close a stake position account and return its rent to the owner"
       ],
       "discriminator": [
-        213,
-        164,
-        27,
-        71,
-        21,
-        69,
-        10,
-        104
+        174,
+        85,
+        143,
+        99,
+        76,
+        100,
+        99,
+        26
       ],
       "accounts": [
         {
-          "name": "cranker",
+          "name": "user",
+          "writable": true,
           "signer": true
         },
         {
           "name": "poolConfig",
-          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -371,24 +358,55 @@ export type StakePool = {
               }
             ]
           }
+        },
+        {
+          "name": "userStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
         }
       ],
       "args": []
     },
     {
-      "name": "initializePool",
+      "name": "createTokenMetadata",
       "docs": [
-        "Initialize the staking pool with slpSOL mint"
+        "Admin: Create token metadata for secuSOL"
       ],
       "discriminator": [
-        95,
-        180,
-        10,
-        172,
-        84,
-        174,
-        232,
-        40
+        221,
+        80,
+        176,
+        37,
+        153,
+        188,
+        160,
+        68
       ],
       "accounts": [
         {
@@ -398,7 +416,6 @@ export type StakePool = {
         },
         {
           "name": "poolConfig",
-          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -451,43 +468,15 @@ export type StakePool = {
           }
         },
         {
-          "name": "reserveVault",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  114,
-                  101,
-                  115,
-                  101,
-                  114,
-                  118,
-                  101,
-                  95,
-                  118,
-                  97,
-                  117,
-                  108,
-                  116
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "poolConfig"
-              }
-            ]
-          }
+          "name": "slpMint"
         },
         {
-          "name": "slpMint",
-          "writable": true,
-          "signer": true
+          "name": "metadata",
+          "writable": true
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "tokenMetadataProgram",
+          "address": "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
         },
         {
           "name": "systemProgram",
@@ -500,29 +489,39 @@ export type StakePool = {
       ],
       "args": [
         {
-          "name": "feeBps",
-          "type": "u16"
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "symbol",
+          "type": "string"
+        },
+        {
+          "name": "uri",
+          "type": "string"
         }
       ]
     },
     {
-      "name": "setPaused",
+      "name": "deactivateStake",
       "docs": [
-        "Admin: Pause/unpause the pool"
+        "Crank: Split stake owed to withdrawal tickets off a validator's stake",
+        "account and deactivate it. Only one deactivating account per validator",
+        "can be in flight; withdraw_deactivated_stake empties it after cooldown."
       ],
       "discriminator": [
-        91,
-        60,
-        125,
-        192,
-        176,
-        225,
-        166,
-        218
+        165,
+        158,
+        229,
+        97,
+        168,
+        220,
+        187,
+        225
       ],
       "accounts": [
         {
-          "name": "admin",
+          "name": "cranker",
           "signer": true
         },
         {
@@ -548,38 +547,9 @@ export type StakePool = {
               }
             ]
           }
-        }
-      ],
-      "args": [
-        {
-          "name": "paused",
-          "type": "bool"
-        }
-      ]
-    },
-    {
-      "name": "updateFee",
-      "docs": [
-        "Admin: Update fee"
-      ],
-      "discriminator": [
-        232,
-        253,
-        195,
-        247,
-        148,
-        212,
-        73,
-        222
-      ],
-      "accounts": [
-        {
-          "name": "admin",
-          "signer": true
         },
         {
-          "name": "poolConfig",
-          "writable": true,
+          "name": "poolAuthority",
           "pda": {
             "seeds": [
               {
@@ -590,318 +560,4718 @@ export type StakePool = {
                   111,
                   108,
                   95,
-                  99,
+                  97,
+                  117,
+                  116,
+                  104,
                   111,
-                  110,
-                  102,
+                  114,
                   105,
-                  103
+                  116,
+                  121
                 ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
               }
             ]
           }
-        }
-      ],
-      "args": [
-        {
-          "name": "newFeeBps",
-          "type": "u16"
-        }
-      ]
-    },
-    {
-      "name": "withdrawSol",
-      "docs": [
-        "Withdraw SOL by burning slpSOL tokens"
-      ],
-      "discriminator": [
-        145,
-        131,
-        74,
-        136,
-        65,
-        137,
-        42,
-        38
-      ],
-      "accounts": [
+        },
         {
-          "name": "user",
+          "name": "reserveVault",
           "writable": true,
-          "signer": true
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  115,
+                  101,
+                  114,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
         },
         {
-          "name": "poolConfig",
+          "name": "validatorList",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  112,
-                  111,
-                  111,
+                  118,
+                  97,
                   108,
-                  95,
-                  99,
+                  105,
+                  100,
+                  97,
+                  116,
                   111,
-                  110,
-                  102,
+                  114,
+                  95,
+                  108,
                   105,
-                  103
+                  115,
+                  116
                 ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
               }
             ]
           }
         },
         {
-          "name": "reserveVault",
+          "name": "voteAccount"
+        },
+        {
+          "name": "validatorStake",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
                   114,
-                  101,
+                  95,
                   115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "voteAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "deactivatingStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
                   101,
-                  114,
+                  97,
+                  99,
+                  116,
+                  105,
                   118,
-                  101,
+                  97,
+                  116,
+                  105,
+                  110,
+                  103,
                   95,
-                  118,
+                  115,
+                  116,
                   97,
-                  117,
-                  108,
-                  116
+                  107,
+                  101
                 ]
               },
               {
                 "kind": "account",
                 "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "voteAccount"
               }
             ]
           }
         },
         {
-          "name": "slpMint",
-          "writable": true
-        },
-        {
-          "name": "userSlpAccount",
-          "writable": true
+          "name": "clock",
+          "address": "SysvarC1ock11111111111111111111111111111111"
         },
         {
-          "name": "tokenProgram",
-          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+          "name": "stakeProgram",
+          "address": "Stake11111111111111111111111111111111111111"
         },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
         }
       ],
-      "args": [
-        {
-          "name": "slpAmount",
-          "type": "u64"
-        }
-      ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "poolConfig",
-      "discriminator": [
-        26,
-        108,
-        14,
-        123,
-        116,
-        230,
-        129,
-        43
-      ]
+      "args": []
     },
     {
-      "name": "validatorEntry",
+      "name": "deactivateValidator",
+      "docs": [
+        "Admin: Stop delegating to a validator. Its stake is drained by",
+        "rebalance, after which it can be removed."
+      ],
       "discriminator": [
-        174,
-        87,
-        76,
-        168,
-        228,
-        42,
-        70,
-        4
+        188,
+        227,
+        224,
+        187,
+        231,
+        52,
+        3,
+        147
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "validatorList",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "voteAccount"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "delegateStake",
+      "docs": [
+        "Crank: Move SOL from reserve to validators, split by target weight",
+        "The first delegation creates the validator stake account; later ones go",
+        "through a transient stake account that merge_transient_stake folds in."
+      ],
+      "discriminator": [
+        50,
+        110,
+        95,
+        179,
+        194,
+        75,
+        140,
+        246
+      ],
+      "accounts": [
+        {
+          "name": "cranker",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "reserveVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  115,
+                  101,
+                  114,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "validatorList",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "voteAccount",
+          "docs": [
+            "list and validated by the stake program"
+          ]
+        },
+        {
+          "name": "validatorStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "voteAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "transientStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  97,
+                  110,
+                  115,
+                  105,
+                  101,
+                  110,
+                  116,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "voteAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "clock",
+          "address": "SysvarC1ock11111111111111111111111111111111"
+        },
+        {
+          "name": "stakeHistory",
+          "address": "SysvarStakeHistory1111111111111111111111111"
+        },
+        {
+          "name": "stakeConfig",
+          "address": "StakeConfig11111111111111111111111111111111"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        },
+        {
+          "name": "stakeProgram",
+          "address": "Stake11111111111111111111111111111111111111"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "depositSol",
+      "docs": [
+        "Deposit SOL and receive slpSOL tokens"
+      ],
+      "discriminator": [
+        108,
+        81,
+        78,
+        117,
+        125,
+        155,
+        56,
+        200
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "reserveVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  115,
+                  101,
+                  114,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "slpMint",
+          "writable": true
+        },
+        {
+          "name": "userSlpAccount",
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "docs": [
+            "Pool's slpSOL fee account; required once a fee recipient is set"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "referrer",
+          "docs": [
+            "Referring partner's slpSOL account, paid a share of the deposit fee"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "userStake",
+          "docs": [
+            "Depositor's stake position, updated when passed in"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amountLamports",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "depositStake",
+      "docs": [
+        "Deposit an active stake account delegated to a pool validator and",
+        "receive slpSOL. The account is handed to the pool authority and merged",
+        "into the validator's stake account; its full balance counts as stake."
+      ],
+      "discriminator": [
+        160,
+        167,
+        9,
+        220,
+        74,
+        243,
+        228,
+        43
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "validatorList",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "voteAccount",
+          "docs": [
+            "the validator list"
+          ]
+        },
+        {
+          "name": "validatorStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "voteAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "depositStake",
+          "docs": [
+            "closed by the merge"
+          ],
+          "writable": true
+        },
+        {
+          "name": "slpMint",
+          "writable": true
+        },
+        {
+          "name": "userSlpAccount",
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "docs": [
+            "Pool's slpSOL fee account; required once a fee recipient is set"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "clock",
+          "address": "SysvarC1ock11111111111111111111111111111111"
+        },
+        {
+          "name": "stakeHistory",
+          "address": "SysvarStakeHistory1111111111111111111111111"
+        },
+        {
+          "name": "stakeProgram",
+          "address": "Stake11111111111111111111111111111111111111"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "executeParameterChange",
+      "docs": [
+        "Admin: Apply the queued parameter change once the timelock has elapsed"
+      ],
+      "discriminator": [
+        27,
+        94,
+        168,
+        154,
+        84,
+        114,
+        101,
+        0
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializePool",
+      "docs": [
+        "Initialize the staking pool with slpSOL mint"
+      ],
+      "discriminator": [
+        95,
+        180,
+        10,
+        172,
+        84,
+        174,
+        232,
+        40
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "reserveVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  115,
+                  101,
+                  114,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "slpMint",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "rent",
+          "address": "SysvarRent111111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "feeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "initializeUserStake",
+      "docs": [
+        "Open a stake position account that deposit_sol and withdraw_sol keep",
+        "up to date when it is passed in"
+      ],
+      "discriminator": [
+        248,
+        96,
+        76,
+        185,
+        77,
+        56,
+        18,
+        0
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "userStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializeValidatorList",
+      "docs": [
+        "Admin: Create the validator list with room for",
+        "INITIAL_VALIDATOR_LIST_CAPACITY validators"
+      ],
+      "discriminator": [
+        40,
+        187,
+        31,
+        130,
+        143,
+        84,
+        73,
+        235
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "validatorList",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "mergeTransientStake",
+      "docs": [
+        "Crank: Merge the transient stake account into the validator stake account",
+        "Succeeds once both accounts are in a mergeable state (e.g. both active)"
+      ],
+      "discriminator": [
+        178,
+        133,
+        130,
+        214,
+        243,
+        170,
+        144,
+        213
+      ],
+      "accounts": [
+        {
+          "name": "cranker",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "validatorList",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "voteAccount"
+        },
+        {
+          "name": "validatorStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "voteAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "transientStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  114,
+                  97,
+                  110,
+                  115,
+                  105,
+                  101,
+                  110,
+                  116,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "voteAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "clock",
+          "address": "SysvarC1ock11111111111111111111111111111111"
+        },
+        {
+          "name": "stakeHistory",
+          "address": "SysvarStakeHistory1111111111111111111111111"
+        },
+        {
+          "name": "stakeProgram",
+          "address": "Stake11111111111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migratePoolConfig",
+      "docs": [
+        "Admin: Rewrite a pool config created before the authority transfer,",
+        "timelock, ticket, validator weight and fee fields in the current",
+        "PoolConfig layout, growing the account and topping up its rent from",
+        "the admin"
+      ],
+      "discriminator": [
+        240,
+        220,
+        251,
+        238,
+        21,
+        44,
+        129,
+        39
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "docs": [
+            "PoolConfig; the discriminator and admin are checked in the handler"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "proposeAuthority",
+      "docs": [
+        "Admin: Propose a new pool admin (step 1 of 2).",
+        "Passing the default pubkey cancels a pending proposal."
+      ],
+      "discriminator": [
+        20,
+        148,
+        236,
+        198,
+        76,
+        119,
+        99,
+        142
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "newAdmin",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "quoteDeposit",
+      "docs": [
+        "View: What deposit_sol would mint for `amount_lamports`, returned",
+        "through return data so clients can simulate it and programs can CPI it"
+      ],
+      "discriminator": [
+        181,
+        208,
+        37,
+        150,
+        90,
+        54,
+        87,
+        204
+      ],
+      "accounts": [
+        {
+          "name": "poolConfig",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "amountLamports",
+          "type": "u64"
+        },
+        {
+          "name": "withReferrer",
+          "type": "bool"
+        }
+      ],
+      "returns": {
+        "defined": {
+          "name": "depositQuote"
+        }
+      }
+    },
+    {
+      "name": "quoteWithdraw",
+      "docs": [
+        "View: What withdraw_sol would pay out for `slp_amount`, returned",
+        "through return data"
+      ],
+      "discriminator": [
+        200,
+        103,
+        235,
+        129,
+        119,
+        9,
+        220,
+        200
+      ],
+      "accounts": [
+        {
+          "name": "poolConfig",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "slpAmount",
+          "type": "u64"
+        }
+      ],
+      "returns": {
+        "defined": {
+          "name": "withdrawQuote"
+        }
+      }
+    },
+    {
+      "name": "reallocValidatorList",
+      "docs": [
+        "Admin: Grow the validator list to hold `max_validators` validators.",
+        "The runtime caps growth at 10KiB per instruction, so large lists take",
+        "several calls."
+      ],
+      "discriminator": [
+        215,
+        59,
+        218,
+        133,
+        93,
+        138,
+        60,
+        123
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "validatorList",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "maxValidators",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "rebalance",
+      "docs": [
+        "Crank: Deactivate a validator's stake above its target weight, or all of",
+        "it once the validator is deactivated. The SOL returns to the reserve via",
+        "withdraw_deactivated_stake, and delegate_stake moves it to validators",
+        "below target."
+      ],
+      "discriminator": [
+        108,
+        158,
+        77,
+        9,
+        210,
+        52,
+        88,
+        62
+      ],
+      "accounts": [
+        {
+          "name": "cranker",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "reserveVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  115,
+                  101,
+                  114,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "validatorList",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "voteAccount"
+        },
+        {
+          "name": "validatorStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "voteAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "deactivatingStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  97,
+                  99,
+                  116,
+                  105,
+                  118,
+                  97,
+                  116,
+                  105,
+                  110,
+                  103,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "voteAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "clock",
+          "address": "SysvarC1ock11111111111111111111111111111111"
+        },
+        {
+          "name": "stakeProgram",
+          "address": "Stake11111111111111111111111111111111111111"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "removeValidator",
+      "docs": [
+        "Admin: Remove a deactivated validator that no longer holds any stake"
+      ],
+      "discriminator": [
+        25,
+        96,
+        211,
+        155,
+        161,
+        14,
+        168,
+        188
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "validatorList",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "voteAccount"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "requestWithdrawal",
+      "docs": [
+        "Delayed unstake: burn slpSOL for a ticket on SOL that is still delegated.",
+        "The SOL owed is fixed at the current rate and can be claimed with",
+        "claim_withdrawal once cranks have deactivated and withdrawn the stake."
+      ],
+      "discriminator": [
+        251,
+        85,
+        121,
+        205,
+        56,
+        201,
+        12,
+        177
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "withdrawalTicket",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  105,
+                  116,
+                  104,
+                  100,
+                  114,
+                  97,
+                  119,
+                  97,
+                  108,
+                  95,
+                  116,
+                  105,
+                  99,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "arg",
+                "path": "ticketId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "slpMint",
+          "writable": true
+        },
+        {
+          "name": "userSlpAccount",
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "docs": [
+            "Pool's slpSOL fee account; required once a fee recipient is set"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "slpAmount",
+          "type": "u64"
+        },
+        {
+          "name": "ticketId",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setFeeRecipient",
+      "docs": [
+        "Admin: Queue a new slpSOL token account to receive protocol fees,",
+        "applied by execute_parameter_change after the timelock"
+      ],
+      "discriminator": [
+        227,
+        18,
+        215,
+        42,
+        237,
+        246,
+        151,
+        66
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeRecipient"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "setPaused",
+      "docs": [
+        "Admin: Pause the pool immediately, or queue an unpause behind the timelock"
+      ],
+      "discriminator": [
+        91,
+        60,
+        125,
+        192,
+        176,
+        225,
+        166,
+        218
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "paused",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setScoreOracle",
+      "docs": [
+        "Admin: Set the oracle allowed to push validator scores"
+      ],
+      "discriminator": [
+        43,
+        85,
+        229,
+        28,
+        28,
+        163,
+        166,
+        171
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "scoreOracle",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "setValidatorWeight",
+      "docs": [
+        "Admin: Set a validator's target weight for delegation and rebalancing"
+      ],
+      "discriminator": [
+        34,
+        168,
+        208,
+        147,
+        10,
+        44,
+        86,
+        194
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "validatorList",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "voteAccount"
+        }
+      ],
+      "args": [
+        {
+          "name": "weight",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "updateFee",
+      "docs": [
+        "Admin: Queue a fee update, applied by execute_parameter_change after the timelock"
+      ],
+      "discriminator": [
+        232,
+        253,
+        195,
+        247,
+        148,
+        212,
+        73,
+        222
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "feeType",
+          "type": {
+            "defined": {
+              "name": "feeType"
+            }
+          }
+        },
+        {
+          "name": "newFeeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "updatePoolBalance",
+      "docs": [
+        "Crank: Reconcile reserve_lamports with the reserve vault's real balance.",
+        "SOL sent straight to the vault is recognised as rewards, at most once",
+        "per epoch and within the epoch reward cap; a shortfall is written off",
+        "the reserve in full."
+      ],
+      "discriminator": [
+        182,
+        198,
+        143,
+        199,
+        32,
+        196,
+        58,
+        212
+      ],
+      "accounts": [
+        {
+          "name": "cranker",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "reserveVault",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  115,
+                  101,
+                  114,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "slpMint",
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "docs": [
+            "Pool's slpSOL fee account; required once a fee recipient is set"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "updateValidatorListBalance",
+      "docs": [
+        "Crank: Sync validator stake with the lamports actually held by their",
+        "stake accounts, walking the validator list from `start_index`.",
+        "remaining_accounts holds a (validator stake, transient stake) pair per",
+        "validator; validators already updated this epoch are skipped. Growth",
+        "since the last update is staking rewards, shrinkage is slashing; both",
+        "flow into the exchange rate."
+      ],
+      "discriminator": [
+        98,
+        93,
+        78,
+        124,
+        109,
+        4,
+        165,
+        194
+      ],
+      "accounts": [
+        {
+          "name": "cranker",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "validatorList",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "slpMint",
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "docs": [
+            "Pool's slpSOL fee account; required once a fee recipient is set"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "startIndex",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "updateValidatorScore",
+      "docs": [
+        "Oracle: Push a validator's commission and uptime. Its target weight is",
+        "recomputed from the score, replacing any weight set by the admin."
+      ],
+      "discriminator": [
+        206,
+        194,
+        5,
+        159,
+        178,
+        183,
+        240,
+        213
+      ],
+      "accounts": [
+        {
+          "name": "oracle",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "validatorList",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "voteAccount"
+        }
+      ],
+      "args": [
+        {
+          "name": "commissionBps",
+          "type": "u16"
+        },
+        {
+          "name": "uptimeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "withdrawDeactivatedStake",
+      "docs": [
+        "Crank: Withdraw a fully deactivated stake account into the reserve vault,",
+        "setting the ticket share aside for claims"
+      ],
+      "discriminator": [
+        155,
+        94,
+        119,
+        57,
+        60,
+        76,
+        87,
+        36
+      ],
+      "accounts": [
+        {
+          "name": "cranker",
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "reserveVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  115,
+                  101,
+                  114,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "validatorList",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "voteAccount"
+        },
+        {
+          "name": "deactivatingStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  97,
+                  99,
+                  116,
+                  105,
+                  118,
+                  97,
+                  116,
+                  105,
+                  110,
+                  103,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "voteAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "clock",
+          "address": "SysvarC1ock11111111111111111111111111111111"
+        },
+        {
+          "name": "stakeHistory",
+          "address": "SysvarStakeHistory1111111111111111111111111"
+        },
+        {
+          "name": "stakeProgram",
+          "address": "Stake11111111111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawSol",
+      "docs": [
+        "Withdraw SOL by burning slpSOL tokens"
+      ],
+      "discriminator": [
+        145,
+        131,
+        74,
+        136,
+        65,
+        137,
+        42,
+        38
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "reserveVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  115,
+                  101,
+                  114,
+                  118,
+                  101,
+                  95,
+                  118,
+                  97,
+                  117,
+                  108,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "slpMint",
+          "writable": true
+        },
+        {
+          "name": "userSlpAccount",
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "docs": [
+            "Pool's slpSOL fee account; required once a fee recipient is set"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "userStake",
+          "docs": [
+            "Depositor's stake position, updated when passed in"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  117,
+                  115,
+                  101,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "slpAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "withdrawStake",
+      "docs": [
+        "Burn slpSOL for a slice of a validator's stake, split into a new stake",
+        "account owned by the user. Exits at the exchange rate without touching",
+        "the reserve; the user funds the new account's rent."
+      ],
+      "discriminator": [
+        153,
+        8,
+        22,
+        138,
+        105,
+        176,
+        87,
+        66
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "poolConfig",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "poolAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "validatorList",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  108,
+                  105,
+                  115,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              }
+            ]
+          }
+        },
+        {
+          "name": "voteAccount"
+        },
+        {
+          "name": "validatorStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  97,
+                  108,
+                  105,
+                  100,
+                  97,
+                  116,
+                  111,
+                  114,
+                  95,
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "poolConfig"
+              },
+              {
+                "kind": "account",
+                "path": "voteAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "splitStake",
+          "docs": [
+            "New stake account for the user, allocated by the split"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "slpMint",
+          "writable": true
+        },
+        {
+          "name": "userSlpAccount",
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "docs": [
+            "Pool's slpSOL fee account; required once a fee recipient is set"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "clock",
+          "address": "SysvarC1ock11111111111111111111111111111111"
+        },
+        {
+          "name": "stakeProgram",
+          "address": "Stake11111111111111111111111111111111111111"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "slpAmount",
+          "type": "u64"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "poolConfig",
+      "discriminator": [
+        26,
+        108,
+        14,
+        123,
+        116,
+        230,
+        129,
+        43
+      ]
+    },
+    {
+      "name": "userStake",
+      "discriminator": [
+        102,
+        53,
+        163,
+        107,
+        9,
+        138,
+        87,
+        153
+      ]
+    },
+    {
+      "name": "validatorList",
+      "discriminator": [
+        131,
+        181,
+        125,
+        127,
+        46,
+        36,
+        40,
+        167
+      ]
+    },
+    {
+      "name": "withdrawalTicket",
+      "discriminator": [
+        92,
+        140,
+        181,
+        69,
+        244,
+        220,
+        233,
+        156
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "authorityAccepted",
+      "discriminator": [
+        166,
+        192,
+        219,
+        188,
+        41,
+        209,
+        195,
+        26
+      ]
+    },
+    {
+      "name": "authorityProposed",
+      "discriminator": [
+        244,
+        117,
+        94,
+        112,
+        53,
+        151,
+        35,
+        89
+      ]
+    },
+    {
+      "name": "balanceReconciled",
+      "discriminator": [
+        204,
+        26,
+        98,
+        14,
+        71,
+        150,
+        95,
+        139
+      ]
+    },
+    {
+      "name": "deactivatedStakeWithdrawn",
+      "discriminator": [
+        21,
+        193,
+        163,
+        152,
+        225,
+        123,
+        217,
+        5
+      ]
+    },
+    {
+      "name": "deposited",
+      "discriminator": [
+        111,
+        141,
+        26,
+        45,
+        161,
+        35,
+        100,
+        57
+      ]
+    },
+    {
+      "name": "feeRecipientUpdated",
+      "discriminator": [
+        24,
+        150,
+        233,
+        92,
+        169,
+        221,
+        233,
+        244
+      ]
+    },
+    {
+      "name": "parameterChangeCancelled",
+      "discriminator": [
+        128,
+        235,
+        99,
+        224,
+        243,
+        240,
+        219,
+        207
+      ]
+    },
+    {
+      "name": "parameterChangeExecuted",
+      "discriminator": [
+        103,
+        81,
+        151,
+        152,
+        144,
+        77,
+        163,
+        72
+      ]
+    },
+    {
+      "name": "parameterChangeQueued",
+      "discriminator": [
+        85,
+        113,
+        75,
+        29,
+        174,
+        190,
+        226,
+        168
+      ]
+    },
+    {
+      "name": "poolConfigMigrated",
+      "discriminator": [
+        154,
+        181,
+        10,
+        210,
+        241,
+        25,
+        143,
+        188
+      ]
+    },
+    {
+      "name": "poolInitialized",
+      "discriminator": [
+        100,
+        118,
+        173,
+        87,
+        12,
+        198,
+        254,
+        229
+      ]
+    },
+    {
+      "name": "rewardFeeMinted",
+      "discriminator": [
+        89,
+        43,
+        108,
+        93,
+        98,
+        203,
+        242,
+        85
+      ]
+    },
+    {
+      "name": "rewardsHarvested",
+      "discriminator": [
+        27,
+        248,
+        121,
+        187,
+        166,
+        132,
+        212,
+        89
+      ]
+    },
+    {
+      "name": "scoreOracleUpdated",
+      "discriminator": [
+        147,
+        72,
+        211,
+        216,
+        3,
+        223,
+        237,
+        72
+      ]
+    },
+    {
+      "name": "stakeDeactivated",
+      "discriminator": [
+        202,
+        88,
+        80,
+        236,
+        248,
+        129,
+        102,
+        236
+      ]
+    },
+    {
+      "name": "stakeDelegated",
+      "discriminator": [
+        126,
+        201,
+        132,
+        208,
+        255,
+        188,
+        95,
+        225
+      ]
+    },
+    {
+      "name": "stakeDeposited",
+      "discriminator": [
+        69,
+        152,
+        144,
+        109,
+        232,
+        34,
+        225,
+        19
+      ]
+    },
+    {
+      "name": "stakeRebalanced",
+      "discriminator": [
+        13,
+        218,
+        122,
+        28,
+        61,
+        83,
+        62,
+        68
+      ]
+    },
+    {
+      "name": "stakeWithdrawn",
+      "discriminator": [
+        33,
+        120,
+        159,
+        58,
+        140,
+        255,
+        174,
+        79
+      ]
+    },
+    {
+      "name": "transientStakeMerged",
+      "discriminator": [
+        22,
+        192,
+        226,
+        161,
+        49,
+        132,
+        170,
+        30
+      ]
+    },
+    {
+      "name": "validatorAdded",
+      "discriminator": [
+        67,
+        26,
+        43,
+        25,
+        58,
+        219,
+        99,
+        48
+      ]
+    },
+    {
+      "name": "validatorBalanceUpdated",
+      "discriminator": [
+        185,
+        216,
+        9,
+        98,
+        222,
+        10,
+        186,
+        139
+      ]
+    },
+    {
+      "name": "validatorDeactivated",
+      "discriminator": [
+        191,
+        72,
+        169,
+        54,
+        94,
+        180,
+        103,
+        15
+      ]
+    },
+    {
+      "name": "validatorListResized",
+      "discriminator": [
+        28,
+        185,
+        82,
+        82,
+        28,
+        128,
+        239,
+        147
+      ]
+    },
+    {
+      "name": "validatorRemoved",
+      "discriminator": [
+        133,
+        140,
+        80,
+        83,
+        7,
+        209,
+        70,
+        130
+      ]
+    },
+    {
+      "name": "validatorScoreUpdated",
+      "discriminator": [
+        155,
+        182,
+        113,
+        59,
+        199,
+        31,
+        135,
+        237
+      ]
+    },
+    {
+      "name": "validatorWeightUpdated",
+      "discriminator": [
+        165,
+        199,
+        237,
+        228,
+        231,
+        85,
+        162,
+        3
+      ]
+    },
+    {
+      "name": "withdrawalClaimed",
+      "discriminator": [
+        9,
+        131,
+        99,
+        68,
+        7,
+        105,
+        180,
+        36
+      ]
+    },
+    {
+      "name": "withdrawalRequested",
+      "discriminator": [
+        75,
+        207,
+        21,
+        12,
+        160,
+        102,
+        150,
+        55
+      ]
+    },
+    {
+      "name": "withdrawn",
+      "discriminator": [
+        20,
+        89,
+        223,
+        198,
+        194,
+        124,
+        219,
+        13
       ]
     }
   ],
-  "events": [
+  "errors": [
+    {
+      "code": 6000,
+      "name": "invalidAuthority",
+      "msg": "Invalid authority"
+    },
+    {
+      "code": 6001,
+      "name": "insufficientSol",
+      "msg": "Insufficient SOL for deposit"
+    },
+    {
+      "code": 6002,
+      "name": "insufficientSlpSol",
+      "msg": "Insufficient slpSOL for withdrawal"
+    },
+    {
+      "code": 6003,
+      "name": "poolPaused",
+      "msg": "Pool is paused"
+    },
+    {
+      "code": 6004,
+      "name": "invalidValidator",
+      "msg": "Invalid validator vote account"
+    },
+    {
+      "code": 6005,
+      "name": "validatorAlreadyExists",
+      "msg": "Validator already exists in pool"
+    },
+    {
+      "code": 6006,
+      "name": "maxValidatorsReached",
+      "msg": "Maximum validators reached"
+    },
+    {
+      "code": 6007,
+      "name": "validatorNotFound",
+      "msg": "Validator not found in pool"
+    },
+    {
+      "code": 6008,
+      "name": "mathOverflow",
+      "msg": "Arithmetic overflow"
+    },
+    {
+      "code": 6009,
+      "name": "insufficientReserve",
+      "msg": "Insufficient reserve for instant unstake"
+    },
+    {
+      "code": 6010,
+      "name": "belowMinimumStake",
+      "msg": "Minimum stake amount not met (0.01 SOL)"
+    },
+    {
+      "code": 6011,
+      "name": "invalidStakeState",
+      "msg": "Invalid stake account state"
+    },
+    {
+      "code": 6012,
+      "name": "epochNotChanged",
+      "msg": "Epoch has not changed since last harvest"
+    },
+    {
+      "code": 6013,
+      "name": "noRewardsToHarvest",
+      "msg": "No rewards to harvest"
+    },
+    {
+      "code": 6014,
+      "name": "invalidMintAuthority",
+      "msg": "Invalid mint authority"
+    },
+    {
+      "code": 6015,
+      "name": "reserveRatioExceeded",
+      "msg": "Reserve ratio exceeded"
+    },
+    {
+      "code": 6016,
+      "name": "noPendingAuthority",
+      "msg": "No pending authority transfer"
+    },
+    {
+      "code": 6017,
+      "name": "parameterChangePending",
+      "msg": "A parameter change is already queued"
+    },
+    {
+      "code": 6018,
+      "name": "noPendingParameterChange",
+      "msg": "No parameter change queued"
+    },
+    {
+      "code": 6019,
+      "name": "timelockNotElapsed",
+      "msg": "Timelock has not elapsed"
+    },
+    {
+      "code": 6020,
+      "name": "poolNotPaused",
+      "msg": "Pool is not paused"
+    },
+    {
+      "code": 6021,
+      "name": "insufficientStake",
+      "msg": "Not enough delegated stake to cover the withdrawal"
+    },
+    {
+      "code": 6022,
+      "name": "noUnstakeNeeded",
+      "msg": "No SOL owed to withdrawal tickets is left to unstake"
+    },
+    {
+      "code": 6023,
+      "name": "ticketNotClaimable",
+      "msg": "Withdrawal ticket is not claimable yet"
+    },
+    {
+      "code": 6024,
+      "name": "noRebalanceNeeded",
+      "msg": "Validator stake is already at or below target"
+    },
+    {
+      "code": 6025,
+      "name": "validatorStillActive",
+      "msg": "Validator must be deactivated first"
+    },
+    {
+      "code": 6026,
+      "name": "validatorNotEmpty",
+      "msg": "Validator still holds stake"
+    },
+    {
+      "code": 6027,
+      "name": "invalidValidatorScore",
+      "msg": "Commission and uptime must be at most 10000 bps"
+    },
+    {
+      "code": 6028,
+      "name": "noDelegationNeeded",
+      "msg": "Validator is already at its delegation target"
+    },
+    {
+      "code": 6029,
+      "name": "invalidValidatorList",
+      "msg": "Validator list account is malformed"
+    },
+    {
+      "code": 6030,
+      "name": "validatorListFull",
+      "msg": "Validator list is full, grow it with realloc_validator_list"
+    },
+    {
+      "code": 6031,
+      "name": "invalidValidatorListSize",
+      "msg": "Validator list can only grow, by at most 10KiB and up to MAX_VALIDATORS"
+    },
+    {
+      "code": 6032,
+      "name": "validatorListOutdated",
+      "msg": "Validator balances must be updated this epoch"
+    },
+    {
+      "code": 6033,
+      "name": "invalidFee",
+      "msg": "Fee exceeds the maximum for its fee type"
+    },
+    {
+      "code": 6034,
+      "name": "invalidFeeRecipient",
+      "msg": "Fee recipient must be the pool's slpSOL fee account"
+    },
+    {
+      "code": 6035,
+      "name": "invalidReferrer",
+      "msg": "Referrer must be an slpSOL token account"
+    },
+    {
+      "code": 6036,
+      "name": "invalidDepositStake",
+      "msg": "Stake account must be active, unlocked and delegated to a pool validator"
+    },
+    {
+      "code": 6037,
+      "name": "depositBelowDeadShares",
+      "msg": "First deposit must exceed the locked minimum slpSOL supply"
+    },
+    {
+      "code": 6038,
+      "name": "exchangeRateDecreased",
+      "msg": "Exchange rate must not decrease on deposits and withdrawals"
+    },
+    {
+      "code": 6039,
+      "name": "reserveOutOfSync",
+      "msg": "Reserve vault holds less SOL than the pool accounts for"
+    },
+    {
+      "code": 6040,
+      "name": "poolConfigAlreadyMigrated",
+      "msg": "Pool config is not in the pre-upgrade layout"
+    }
+  ],
+  "types": [
     {
-      "name": "deposited",
-      "discriminator": [
-        111,
-        141,
-        26,
-        45,
-        161,
-        35,
-        100,
-        57
-      ]
+      "name": "authorityAccepted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "previousAdmin",
+            "type": "pubkey"
+          },
+          {
+            "name": "newAdmin",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "name": "poolInitialized",
-      "discriminator": [
-        100,
-        118,
-        173,
-        87,
-        12,
-        198,
-        254,
-        229
-      ]
+      "name": "authorityProposed",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "currentAdmin",
+            "type": "pubkey"
+          },
+          {
+            "name": "proposedAdmin",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "name": "rewardsHarvested",
-      "discriminator": [
-        27,
-        248,
-        121,
-        187,
-        166,
-        132,
-        212,
-        89
-      ]
+      "name": "balanceReconciled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "vaultLamports",
+            "type": "u64"
+          },
+          {
+            "name": "bookedLamports",
+            "type": "u64"
+          },
+          {
+            "name": "rewards",
+            "type": "u64"
+          },
+          {
+            "name": "deferredRewards",
+            "type": "u64"
+          },
+          {
+            "name": "shortfall",
+            "type": "u64"
+          },
+          {
+            "name": "feeSlpMinted",
+            "type": "u64"
+          },
+          {
+            "name": "newExchangeRate",
+            "type": "u64"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "name": "stakeDelegated",
-      "discriminator": [
-        126,
-        201,
-        132,
-        208,
-        255,
-        188,
-        95,
-        225
-      ]
+      "name": "deactivatedStakeWithdrawn",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "validator",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "claimable",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "name": "validatorAdded",
-      "discriminator": [
-        67,
-        26,
-        43,
-        25,
-        58,
-        219,
-        99,
-        48
-      ]
+      "name": "depositQuote",
+      "docs": [
+        "Breakdown of a deposit_sol, as returned by quote_deposit"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "slpMinted",
+            "docs": [
+              "slpSOL issued in total: the user's, the fees and the dead shares"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "userSlp",
+            "docs": [
+              "slpSOL the depositor receives"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "depositFee",
+            "docs": [
+              "slpSOL taken as the deposit fee"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "referralFee",
+            "docs": [
+              "Referrer's share of the deposit fee"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "deadShares",
+            "docs": [
+              "slpSOL booked to supply but never minted, on the first deposit only"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "deposited",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "solAmount",
+            "type": "u64"
+          },
+          {
+            "name": "slpMinted",
+            "type": "u64"
+          },
+          {
+            "name": "depositFee",
+            "type": "u64"
+          },
+          {
+            "name": "referrer",
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "referralFee",
+            "type": "u64"
+          },
+          {
+            "name": "exchangeRate",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "feeRecipientUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "feeRecipient",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "name": "withdrawn",
-      "discriminator": [
-        20,
-        89,
-        223,
-        198,
-        194,
-        124,
-        219,
-        13
-      ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "invalidAuthority",
-      "msg": "Invalid authority"
+      "name": "feeType",
+      "docs": [
+        "Which protocol fee a fee update applies to"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "deposit"
+          },
+          {
+            "name": "withdrawal"
+          },
+          {
+            "name": "reward"
+          },
+          {
+            "name": "referral"
+          }
+        ]
+      }
     },
     {
-      "code": 6001,
-      "name": "insufficientSol",
-      "msg": "Insufficient SOL for deposit"
+      "name": "parameterChange",
+      "docs": [
+        "Parameter change waiting out the timelock"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "none"
+          },
+          {
+            "name": "fee",
+            "fields": [
+              {
+                "name": "feeType",
+                "type": {
+                  "defined": {
+                    "name": "feeType"
+                  }
+                }
+              },
+              {
+                "name": "newFeeBps",
+                "type": "u16"
+              }
+            ]
+          },
+          {
+            "name": "unpause"
+          },
+          {
+            "name": "feeRecipient"
+          }
+        ]
+      }
     },
     {
-      "code": 6002,
-      "name": "insufficientSlpSol",
-      "msg": "Insufficient slpSOL for withdrawal"
+      "name": "parameterChangeCancelled",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "change",
+            "type": {
+              "defined": {
+                "name": "parameterChange"
+              }
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6003,
-      "name": "poolPaused",
-      "msg": "Pool is paused"
+      "name": "parameterChangeExecuted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "change",
+            "type": {
+              "defined": {
+                "name": "parameterChange"
+              }
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6004,
-      "name": "invalidValidator",
-      "msg": "Invalid validator vote account"
+      "name": "parameterChangeQueued",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "change",
+            "type": {
+              "defined": {
+                "name": "parameterChange"
+              }
+            }
+          },
+          {
+            "name": "activationSlot",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6005,
-      "name": "validatorAlreadyExists",
-      "msg": "Validator already exists in pool"
+      "name": "poolConfig",
+      "docs": [
+        "Main pool configuration account"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Admin who can update pool settings"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "slpMint",
+            "docs": [
+              "slpSOL token mint"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "totalStakedLamports",
+            "docs": [
+              "Total SOL currently staked with validators"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalSlpSupply",
+            "docs": [
+              "Total slpSOL tokens minted (supply)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserveLamports",
+            "docs": [
+              "SOL held in reserve for instant unstakes"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feeBps",
+            "docs": [
+              "Protocol fee on staking rewards in basis points (e.g., 100 = 1%)"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "paused",
+            "docs": [
+              "Whether the pool is paused"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "lastHarvestEpoch",
+            "docs": [
+              "Last epoch when rewards were harvested"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "validatorCount",
+            "docs": [
+              "Number of validators in the pool"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump seed for this PDA"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "authorityBump",
+            "docs": [
+              "Bump seed for pool authority PDA"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "pendingAdmin",
+            "docs": [
+              "Proposed new admin, awaiting acceptance (default = none)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingChange",
+            "docs": [
+              "Queued parameter change"
+            ],
+            "type": {
+              "defined": {
+                "name": "parameterChange"
+              }
+            }
+          },
+          {
+            "name": "pendingChangeSlot",
+            "docs": [
+              "Slot at which the queued change can be executed"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "ticketLamportsOwed",
+            "docs": [
+              "SOL owed to open withdrawal tickets (already removed from total_staked_lamports)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "deactivatingLamports",
+            "docs": [
+              "Lamports in deactivating stake accounts, incl. their rent"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "claimableLamports",
+            "docs": [
+              "SOL back in the reserve vault, set aside for ticket claims"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalValidatorWeight",
+            "docs": [
+              "Sum of target weights over active validators"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "scoreOracle",
+            "docs": [
+              "Oracle allowed to push validator scores"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "feeRecipient",
+            "docs": [
+              "slpSOL token account receiving protocol fees (default = fees off)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pendingFeeRecipient",
+            "docs": [
+              "Fee recipient queued behind the timelock (default = none)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "depositFeeBps",
+            "docs": [
+              "Protocol fee on deposits, in bps of the slpSOL minted"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "withdrawalFeeBps",
+            "docs": [
+              "Protocol fee on withdrawals, in bps of the slpSOL withdrawn"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "referralFeeBps",
+            "docs": [
+              "Referrer's share of the deposit fee, in bps of the fee"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "lastBalanceUpdateEpoch",
+            "docs": [
+              "Last epoch update_pool_balance recognised reserve donations as rewards"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "ticketLamportsRequested",
+            "docs": [
+              "SOL ever owed to withdrawal tickets, in request order"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "ticketLamportsFunded",
+            "docs": [
+              "SOL ever set aside for ticket claims; tickets are paid in request order",
+              "up to this amount"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Reserved for future use"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                4
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6006,
-      "name": "maxValidatorsReached",
-      "msg": "Maximum validators reached"
+      "name": "poolConfigMigrated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "previousLen",
+            "type": "u32"
+          },
+          {
+            "name": "newLen",
+            "type": "u32"
+          }
+        ]
+      }
     },
     {
-      "code": 6007,
-      "name": "validatorNotFound",
-      "msg": "Validator not found in pool"
+      "name": "poolInitialized",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "pubkey"
+          },
+          {
+            "name": "slpMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "feeBps",
+            "type": "u16"
+          }
+        ]
+      }
     },
     {
-      "code": 6008,
-      "name": "mathOverflow",
-      "msg": "Arithmetic overflow"
+      "name": "rewardFeeMinted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "protocolFee",
+            "type": "u64"
+          },
+          {
+            "name": "feeSlpMinted",
+            "type": "u64"
+          },
+          {
+            "name": "newExchangeRate",
+            "type": "u64"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6009,
-      "name": "insufficientReserve",
-      "msg": "Insufficient reserve for instant unstake"
+      "name": "rewardsHarvested",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "grossRewards",
+            "type": "u64"
+          },
+          {
+            "name": "protocolFee",
+            "type": "u64"
+          },
+          {
+            "name": "feeSlpMinted",
+            "type": "u64"
+          },
+          {
+            "name": "netRewards",
+            "type": "u64"
+          },
+          {
+            "name": "newExchangeRate",
+            "type": "u64"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6010,
-      "name": "belowMinimumStake",
-      "msg": "Minimum stake amount not met (0.01 SOL)"
+      "name": "scoreOracleUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "scoreOracle",
+            "type": "pubkey"
+          }
+        ]
+      }
     },
     {
-      "code": 6011,
-      "name": "invalidStakeState",
-      "msg": "Invalid stake account state"
+      "name": "stakeDeactivated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "validator",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6012,
-      "name": "epochNotChanged",
-      "msg": "Epoch has not changed since last harvest"
+      "name": "stakeDelegated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "validator",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6013,
-      "name": "noRewardsToHarvest",
-      "msg": "No rewards to harvest"
+      "name": "stakeDeposited",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "user",
+            "type": "pubkey"
+          },
+          {
+            "name": "stakeAccount",
+            "type": "pubkey"
+          },
+          {
+            "name": "validator",
+            "type": "pubkey"
+          },
+          {
+            "name": "lamports",
+            "type": "u64"
+          },
+          {
+            "name": "slpMinted",
+            "type": "u64"
+          },
+          {
+            "name": "depositFee",
+            "type": "u64"
+          },
+          {
+            "name": "exchangeRate",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6014,
-      "name": "invalidMintAuthority",
-      "msg": "Invalid mint authority"
+      "name": "stakeRebalanced",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "validator",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "targetLamports",
+            "type": "u64"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6015,
-      "name": "reserveRatioExceeded",
-      "msg": "Reserve ratio exceeded"
-    }
-  ],
-  "types": [
-    {
-      "name": "deposited",
+      "name": "stakeWithdrawn",
       "type": {
         "kind": "struct",
         "fields": [
@@ -910,11 +5280,23 @@ export type StakePool = {
             "type": "pubkey"
           },
           {
-            "name": "solAmount",
+            "name": "stakeAccount",
+            "type": "pubkey"
+          },
+          {
+            "name": "validator",
+            "type": "pubkey"
+          },
+          {
+            "name": "lamports",
             "type": "u64"
           },
           {
-            "name": "slpMinted",
+            "name": "slpBurned",
+            "type": "u64"
+          },
+          {
+            "name": "withdrawalFee",
             "type": "u64"
           },
           {
@@ -925,99 +5307,228 @@ export type StakePool = {
       }
     },
     {
-      "name": "poolConfig",
+      "name": "transientStakeMerged",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "validator",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "userStake",
       "docs": [
-        "Main pool configuration account"
+        "User's staking position (optional, for tracking)",
+        "Seeds: [\"user_stake\", pool_config, owner]"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "admin",
+            "name": "owner",
             "docs": [
-              "Admin who can update pool settings"
+              "User's wallet"
             ],
             "type": "pubkey"
           },
           {
-            "name": "slpMint",
+            "name": "totalSlpReceived",
             "docs": [
-              "slpSOL token mint"
+              "Total slpSOL the user has received from this pool"
             ],
-            "type": "pubkey"
+            "type": "u64"
           },
           {
-            "name": "totalStakedLamports",
+            "name": "totalSolDeposited",
             "docs": [
-              "Total SOL currently staked with validators"
+              "Total SOL the user has deposited"
             ],
             "type": "u64"
           },
           {
-            "name": "totalSlpSupply",
+            "name": "firstDepositTs",
             "docs": [
-              "Total slpSOL tokens minted (supply)"
+              "Timestamp of first deposit"
             ],
-            "type": "u64"
+            "type": "i64"
           },
           {
-            "name": "reserveLamports",
+            "name": "lastActionTs",
             "docs": [
-              "SOL held in reserve for instant unstakes"
+              "Timestamp of last action"
             ],
-            "type": "u64"
+            "type": "i64"
           },
           {
-            "name": "feeBps",
+            "name": "bump",
             "docs": [
-              "Protocol fee in basis points (e.g., 100 = 1%)"
+              "Bump seed"
             ],
-            "type": "u16"
+            "type": "u8"
           },
           {
-            "name": "paused",
+            "name": "totalSlpRedeemed",
             "docs": [
-              "Whether the pool is paused"
+              "Total slpSOL the user has given up on withdrawals, incl. fees"
             ],
-            "type": "bool"
+            "type": "u64"
           },
           {
-            "name": "lastHarvestEpoch",
+            "name": "totalSolWithdrawn",
             "docs": [
-              "Last epoch when rewards were harvested"
+              "Total SOL the user has withdrawn"
             ],
             "type": "u64"
           },
           {
-            "name": "validatorCount",
+            "name": "reserved",
             "docs": [
-              "Number of validators in the pool"
+              "Reserved"
             ],
-            "type": "u8"
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "validatorAdded",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "voteAccount",
+            "type": "pubkey"
           },
           {
-            "name": "bump",
+            "name": "index",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "validatorBalanceUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "validator",
+            "type": "pubkey"
+          },
+          {
+            "name": "previousLamports",
+            "type": "u64"
+          },
+          {
+            "name": "currentLamports",
+            "type": "u64"
+          },
+          {
+            "name": "rewards",
+            "type": "u64"
+          },
+          {
+            "name": "deferredRewards",
+            "type": "u64"
+          },
+          {
+            "name": "slashed",
+            "type": "u64"
+          },
+          {
+            "name": "newExchangeRate",
+            "type": "u64"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "validatorDeactivated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "voteAccount",
+            "type": "pubkey"
+          },
+          {
+            "name": "stakedLamports",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "validatorList",
+      "docs": [
+        "Growable list of the pool's validators. Zero-copy: the header is followed",
+        "by `max_validators` ValidatorEntry slots, the first `validator_count` in use.",
+        "Seeds: [\"validator_list\", pool_config]"
+      ],
+      "serialization": "bytemuck",
+      "repr": {
+        "kind": "c"
+      },
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
             "docs": [
-              "Bump seed for this PDA"
+              "Pool this list belongs to"
             ],
-            "type": "u8"
+            "type": "pubkey"
           },
           {
-            "name": "authorityBump",
+            "name": "maxValidators",
             "docs": [
-              "Bump seed for pool authority PDA"
+              "Slots allocated in the account"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "validatorCount",
+            "docs": [
+              "Slots in use"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "Bump seed for this PDA"
             ],
             "type": "u8"
           },
           {
-            "name": "reserved",
+            "name": "padding",
             "docs": [
-              "Reserved for future use"
+              "Padding to an 8-byte boundary"
             ],
             "type": {
               "array": [
                 "u8",
-                32
+                7
               ]
             }
           }
@@ -1025,155 +5536,251 @@ export type StakePool = {
       }
     },
     {
-      "name": "poolInitialized",
+      "name": "validatorListResized",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "admin",
+            "name": "maxValidators",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "validatorRemoved",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "voteAccount",
             "type": "pubkey"
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "validatorScoreUpdated",
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "slpMint",
+            "name": "voteAccount",
             "type": "pubkey"
           },
           {
-            "name": "feeBps",
+            "name": "commissionBps",
+            "type": "u16"
+          },
+          {
+            "name": "uptimeBps",
             "type": "u16"
+          },
+          {
+            "name": "weight",
+            "type": "u16"
+          },
+          {
+            "name": "totalWeight",
+            "type": "u64"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "rewardsHarvested",
+      "name": "validatorWeightUpdated",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "grossRewards",
-            "type": "u64"
+            "name": "voteAccount",
+            "type": "pubkey"
           },
           {
-            "name": "protocolFee",
+            "name": "weight",
+            "type": "u16"
+          },
+          {
+            "name": "totalWeight",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "withdrawQuote",
+      "docs": [
+        "Breakdown of a withdraw_sol, as returned by quote_withdraw"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "withdrawalFee",
+            "docs": [
+              "slpSOL sent to the fee recipient"
+            ],
             "type": "u64"
           },
           {
-            "name": "netRewards",
+            "name": "slpBurned",
+            "docs": [
+              "slpSOL burned"
+            ],
             "type": "u64"
           },
           {
-            "name": "newExchangeRate",
+            "name": "solOut",
+            "docs": [
+              "Lamports paid to the user"
+            ],
             "type": "u64"
           },
           {
-            "name": "epoch",
+            "name": "liquidityFee",
+            "docs": [
+              "Instant-unstake fee kept by the pool, in lamports"
+            ],
             "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "stakeDelegated",
+      "name": "withdrawalClaimed",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "validator",
+            "name": "user",
             "type": "pubkey"
           },
           {
-            "name": "amount",
-            "type": "u64"
+            "name": "ticket",
+            "type": "pubkey"
           },
           {
-            "name": "epoch",
+            "name": "solAmount",
             "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "validatorAdded",
+      "name": "withdrawalRequested",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "voteAccount",
+            "name": "user",
             "type": "pubkey"
           },
           {
-            "name": "index",
-            "type": "u8"
+            "name": "ticket",
+            "type": "pubkey"
+          },
+          {
+            "name": "solAmount",
+            "type": "u64"
+          },
+          {
+            "name": "slpBurned",
+            "type": "u64"
+          },
+          {
+            "name": "withdrawalFee",
+            "type": "u64"
+          },
+          {
+            "name": "claimableEpoch",
+            "type": "u64"
+          },
+          {
+            "name": "exchangeRate",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "validatorEntry",
+      "name": "withdrawalTicket",
       "docs": [
-        "Validator entry in the pool"
+        "Delayed unstake claim: SOL owed to a user who burned slpSOL",
+        "Seeds: [\"withdrawal_ticket\", pool_config, owner, ticket_id]"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "voteAccount",
+            "name": "owner",
             "docs": [
-              "The validator's vote account"
+              "User who can claim the SOL"
             ],
             "type": "pubkey"
           },
           {
-            "name": "stakeAccount",
+            "name": "ticketId",
             "docs": [
-              "The stake account delegated to this validator"
+              "Caller-chosen id, lets a user hold several tickets"
             ],
-            "type": "pubkey"
+            "type": "u64"
           },
           {
-            "name": "stakedLamports",
+            "name": "lamports",
             "docs": [
-              "Amount of lamports staked with this validator"
+              "SOL owed, fixed at the exchange rate when the ticket was created"
             ],
             "type": "u64"
           },
           {
-            "name": "lastUpdateEpoch",
+            "name": "slpBurned",
             "docs": [
-              "Last epoch this validator's stake was updated"
+              "slpSOL burned for this ticket"
             ],
             "type": "u64"
           },
           {
-            "name": "active",
+            "name": "createdEpoch",
             "docs": [
-              "Whether this validator is active"
+              "Epoch the ticket was created"
             ],
-            "type": "bool"
+            "type": "u64"
           },
           {
-            "name": "stakeBump",
+            "name": "claimableEpoch",
             "docs": [
-              "Bump seed for stake account PDA"
+              "First epoch the ticket can be claimed (after stake cooldown)"
             ],
-            "type": "u8"
+            "type": "u64"
           },
           {
-            "name": "index",
+            "name": "bump",
             "docs": [
-              "Index in validator list"
+              "Bump seed"
             ],
             "type": "u8"
           },
+          {
+            "name": "queuePosition",
+            "docs": [
+              "Pool's ticket_lamports_requested including this ticket; claimable once",
+              "ticket_lamports_funded reaches it"
+            ],
+            "type": "u64"
+          },
           {
             "name": "reserved",
             "docs": [
-              "Reserved for future use"
+              "Reserved"
             ],
             "type": {
               "array": [
                 "u8",
-                16
+                8
               ]
             }
           }
@@ -1197,6 +5804,14 @@ export type StakePool = {
             "name": "slpBurned",
             "type": "u64"
           },
+          {
+            "name": "withdrawalFee",
+            "type": "u64"
+          },
+          {
+            "name": "liquidityFee",
+            "type": "u64"
+          },
           {
             "name": "exchangeRate",
             "type": "u64"
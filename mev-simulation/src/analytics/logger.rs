@@ -1,5 +1,6 @@
 //! Structured logging for simulation results
 
+use crate::config::FLASH_LOAN_CAVEAT;
 use crate::simulation::SimulationResults;
 use anyhow::{Context, Result};
 use serde_json;
@@ -80,6 +81,15 @@ impl SimulationLogger {
 
 /// Format results as a text summary
 pub fn format_summary(results: &SimulationResults) -> String {
+    let summary = format_results_box(results);
+    if results.config.flash_loans {
+        format!("{}\n⚠ {}\n", summary, FLASH_LOAN_CAVEAT)
+    } else {
+        summary
+    }
+}
+
+fn format_results_box(results: &SimulationResults) -> String {
     let s = &results.summary;
     let lamports_to_sol = |l: u64| l as f64 / 1_000_000_000.0;
     let lamports_to_sol_i64 = |l: i64| l as f64 / 1_000_000_000.0;
@@ -107,6 +117,7 @@ pub fn format_summary(results: &SimulationResults) -> String {
 ║  Attack Success Rate:   {:>10.1}%                                ║
 ║                                                                  ║
 ║  Total MEV Extracted:   {:>10.6} SOL                             ║
+║  Flash Loan Fees Paid:  {:>10.6} SOL                             ║
 ║  Total Victim Losses:   {:>10.6} SOL                             ║
 ║  Avg Loss per Attack:   {:>10.6} SOL                             ║
 ║                                                                  ║
@@ -142,6 +153,7 @@ Generated: {}
         s.successful_attacks,
         s.attack_success_rate,
         lamports_to_sol_i64(s.total_mev_extracted),
+        lamports_to_sol(s.total_flash_loan_fees),
         lamports_to_sol(s.total_victim_losses),
         lamports_to_sol(s.avg_loss_per_attack as u64),
         // Protected trading
@@ -1,5 +1,6 @@
 //! HTML Report Generation with Chart.js
 
+use crate::config::FLASH_LOAN_CAVEAT;
 use crate::simulation::SimulationResults;
 use crate::analytics::metrics::MetricsCalculator;
 use anyhow::{Context, Result};
@@ -338,7 +339,7 @@ fn generate_html(
     <header>
         <h1>🛡️ MEV Simulation Report</h1>
         <p class="subtitle">SecureLiquidPool - Commit-Reveal Protection Analysis</p>
-        <p class="timestamp">Generated: {timestamp}</p>
+        <p class="timestamp">Generated: {timestamp}</p>{flash_loan_note}
     </header>
     
     <div class="container">
@@ -614,6 +615,11 @@ fn generate_html(
 </html>
 "#,
         timestamp = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC"),
+        flash_loan_note = if results.config.flash_loans {
+            format!("\n        <p class=\"timestamp\">⚠ {}</p>", FLASH_LOAN_CAVEAT)
+        } else {
+            String::new()
+        },
         total_transactions = s.total_transactions,
         total_mev = lamports_to_sol_i64(s.total_mev_extracted),
         total_losses = lamports_to_sol(s.total_victim_losses),
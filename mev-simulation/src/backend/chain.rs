@@ -0,0 +1,365 @@
+//! On-chain execution
+//!
+//! Builds, signs and sends the real `amm` and `securelp` instructions and
+//! reads balances and reserves back from account state. Where the
+//! transactions are processed is up to a [`Connection`]: program-test for
+//! [`SvmBackend`](super::SvmBackend), a validator's JSON-RPC for
+//! [`RpcBackend`](super::RpcBackend).
+//!
+//! Token A and token B are plain SPL mints with 9 decimals standing in for
+//! SOL and secuSOL.
+
+use crate::backend::{ExecutionBackend, Scenario, SwapOutcome};
+use crate::config::{ProgramIds, SimulationConfig};
+use crate::utils::amm_math::PoolState;
+use crate::utils::hash::{hash_swap_details, SwapDetails};
+use amm::state::AmmPool;
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::{system_program, AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token::{self, solana_program::program_pack::Pack};
+use anyhow::{Context, Result};
+use securelp_integration_tests::token::{create_mint_ixs, create_token_account_ixs};
+use securelp_integration_tests::AmmPoolAccounts;
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    hash::Hash,
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+};
+use std::collections::{HashMap, HashSet};
+use tracing::info;
+
+/// SOL each trader needs for transaction fees and commitment rent
+pub const TRADER_LAMPORTS: u64 = LAMPORTS_PER_SOL;
+
+/// Where an on-chain backend's transactions are processed
+pub trait Connection {
+    /// Latest blockhash to sign with
+    fn latest_blockhash(&mut self) -> Result<Hash>;
+
+    /// A blockhash newer than `blockhash`
+    fn new_blockhash(&mut self, blockhash: &Hash) -> Result<Hash>;
+
+    /// Process a signed transaction, returning once it is confirmed
+    fn process_transaction(&mut self, transaction: Transaction) -> Result<()>;
+
+    /// Fetch an account, `None` if it doesn't exist
+    fn get_account(&mut self, address: &Pubkey) -> Result<Option<Account>>;
+
+    /// Rent-exempt balance for `data_len` bytes
+    fn minimum_balance(&mut self, data_len: usize) -> Result<u64>;
+
+    /// Current clock
+    fn clock(&mut self) -> Result<Clock>;
+
+    /// Move on to a later slot whose clock is at least a second ahead
+    ///
+    /// Reveal delays are checked against the clock's one-second resolution,
+    /// so a reveal sent after this is accepted.
+    fn advance_slot(&mut self) -> Result<()>;
+}
+
+/// Token accounts of a trader, on the pool of their scenario
+struct TraderAccounts {
+    scenario: Scenario,
+    token_a: Pubkey,
+    token_b: Pubkey,
+}
+
+/// Runs the simulation's transactions against the real programs
+pub struct ChainBackend<C> {
+    connection: C,
+    programs: ProgramIds,
+    /// Pays for mints, pools and trader accounts, and holds the mint authority
+    payer: Keypair,
+    /// Each scenario's pool, with the payer as the user who seeded it
+    normal_pool: AmmPoolAccounts,
+    protected_pool: AmmPoolAccounts,
+    traders: HashMap<Pubkey, TraderAccounts>,
+    /// Blockhash the last transaction was signed with
+    blockhash: Hash,
+    /// Signatures already sent with `blockhash`; an identical transaction
+    /// would be rejected as already processed
+    sent: HashSet<Signature>,
+}
+
+impl<C: Connection> ChainBackend<C> {
+    /// Create both scenario pools with the configured liquidity and fee,
+    /// paid for by `payer`
+    pub(crate) fn connect(mut connection: C, config: &SimulationConfig, payer: Keypair) -> Result<Self> {
+        let blockhash = connection.latest_blockhash()?;
+        let mut backend = Self {
+            connection,
+            programs: config.programs.clone(),
+            payer,
+            // Created below, once the backend can send transactions
+            normal_pool: AmmPoolAccounts::default(),
+            protected_pool: AmmPoolAccounts::default(),
+            traders: HashMap::new(),
+            blockhash,
+            sent: HashSet::new(),
+        };
+        backend.normal_pool = backend.create_pool(config)?;
+        backend.protected_pool = backend.create_pool(config)?;
+
+        info!("Pools ready: normal pool {}, protected pool {}",
+              backend.normal_pool.address,
+              backend.protected_pool.address);
+        Ok(backend)
+    }
+
+    fn pool(&self, scenario: Scenario) -> &AmmPoolAccounts {
+        match scenario {
+            Scenario::Normal => &self.normal_pool,
+            Scenario::Protected => &self.protected_pool,
+        }
+    }
+
+    fn trader(&self, trader: &Pubkey) -> Result<&TraderAccounts> {
+        self.traders
+            .get(trader)
+            .with_context(|| format!("trader {} was never added", trader))
+    }
+
+    /// The trader's pool, with the trader as its user
+    fn trader_pool(&self, trader: &Pubkey) -> Result<AmmPoolAccounts> {
+        let accounts = self.trader(trader)?;
+        Ok(AmmPoolAccounts {
+            user: *trader,
+            user_token_a: accounts.token_a,
+            user_token_b: accounts.token_b,
+            user_lp: Pubkey::default(),
+            ..*self.pool(accounts.scenario)
+        })
+    }
+
+    fn commitment_address(&self, trader: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[securelp::state::Commitment::SEED_PREFIX, trader.as_ref()],
+            &self.programs.securelp,
+        )
+        .0
+    }
+
+    /// Send a swap and measure what it paid out to `token_out`
+    fn send_swap(&mut self, instruction: Instruction, trader: &Keypair, token_out: &Pubkey) -> Result<SwapOutcome> {
+        let before = self.token_balance(token_out)?;
+        let signature = self.send(&[instruction], &[trader])?;
+        let after = self.token_balance(token_out)?;
+
+        Ok(SwapOutcome {
+            signature: signature.to_string(),
+            amount_out: after - before,
+        })
+    }
+
+    /// Sign with `signers`, the first paying the fee, and process the
+    /// transaction
+    fn send(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<Signature> {
+        let latest = self.connection.latest_blockhash()?;
+        if latest != self.blockhash {
+            self.blockhash = latest;
+            self.sent.clear();
+        }
+
+        let mut transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.blockhash,
+        );
+        if self.sent.contains(&transaction.signatures[0]) {
+            // An identical transaction already landed with this blockhash
+            self.blockhash = self.connection.new_blockhash(&self.blockhash)?;
+            self.sent.clear();
+            transaction.sign(signers, self.blockhash);
+        }
+
+        let signature = transaction.signatures[0];
+        self.connection
+            .process_transaction(transaction)
+            .context("transaction failed")?;
+        self.sent.insert(signature);
+        Ok(signature)
+    }
+
+    fn token_balance(&mut self, account: &Pubkey) -> Result<u64> {
+        let account = self.connection.get_account(account)?.context("token account not found")?;
+        Ok(spl_token::state::Account::unpack(&account.data)?.amount)
+    }
+
+    fn create_mint(&mut self) -> Result<Pubkey> {
+        let payer = self.payer.insecure_clone();
+        let mint = Keypair::new();
+        let rent = self.connection.minimum_balance(spl_token::state::Mint::LEN)?;
+        self.send(&create_mint_ixs(&payer.pubkey(), &mint.pubkey(), rent), &[&payer, &mint])?;
+        Ok(mint.pubkey())
+    }
+
+    /// Create a token account for `mint` owned by `owner`, holding `amount`
+    /// freshly minted tokens
+    fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Result<Pubkey> {
+        let payer = self.payer.insecure_clone();
+        let account = Keypair::new();
+        let rent = self.connection.minimum_balance(spl_token::state::Account::LEN)?;
+        let instructions = create_token_account_ixs(&payer.pubkey(), &account.pubkey(), mint, owner, amount, rent);
+        self.send(&instructions, &[&payer, &account])?;
+        Ok(account.pubkey())
+    }
+
+    /// Create two mints and a pool between them seeded with the configured
+    /// liquidity
+    fn create_pool(&mut self, config: &SimulationConfig) -> Result<AmmPoolAccounts> {
+        let payer = self.payer.insecure_clone();
+        let token_a_mint = self.create_mint()?;
+        let token_b_mint = self.create_mint()?;
+        let lp_mint = Keypair::new();
+        let mut pool = AmmPoolAccounts {
+            user: payer.pubkey(),
+            ..AmmPoolAccounts::new(self.programs.amm, token_a_mint, token_b_mint, lp_mint.pubkey())
+        };
+        self.send(&[pool.initialize_ix(config.fee_bps)], &[&payer, &lp_mint])?;
+
+        pool.user_token_a = self.create_token_account(&token_a_mint, &payer.pubkey(), config.initial_pool_a)?;
+        pool.user_token_b = self.create_token_account(&token_b_mint, &payer.pubkey(), config.initial_pool_b)?;
+        pool.user_lp = self.create_token_account(&pool.lp_mint, &payer.pubkey(), 0)?;
+        let add_liquidity = pool.add_liquidity_ix(config.initial_pool_a, config.initial_pool_b, 0);
+        self.send(&[add_liquidity], &[&payer])?;
+
+        Ok(pool)
+    }
+}
+
+impl<C: Connection> ExecutionBackend for ChainBackend<C> {
+    /// Also tops the trader up to [`TRADER_LAMPORTS`] of SOL for fees, if
+    /// its wallet wasn't funded already
+    fn add_trader(
+        &mut self,
+        trader: &Pubkey,
+        scenario: Scenario,
+        balance_a: u64,
+        balance_b: u64,
+    ) -> Result<()> {
+        let payer = self.payer.insecure_clone();
+        let lamports = self.connection.get_account(trader)?.map_or(0, |account| account.lamports);
+        if lamports < TRADER_LAMPORTS {
+            let transfer = system_instruction::transfer(&payer.pubkey(), trader, TRADER_LAMPORTS - lamports);
+            self.send(&[transfer], &[&payer])?;
+        }
+
+        let (mint_a, mint_b) = {
+            let pool = self.pool(scenario);
+            (pool.token_a_mint, pool.token_b_mint)
+        };
+        let token_a = self.create_token_account(&mint_a, trader, balance_a)?;
+        let token_b = self.create_token_account(&mint_b, trader, balance_b)?;
+        self.traders.insert(*trader, TraderAccounts { scenario, token_a, token_b });
+        Ok(())
+    }
+
+    /// Swap with `amm::swap`
+    fn swap(
+        &mut self,
+        trader: &Keypair,
+        amount_in: u64,
+        min_out: u64,
+        a_to_b: bool,
+    ) -> Result<SwapOutcome> {
+        let pool = self.trader_pool(&trader.pubkey())?;
+        let user_token_out = if a_to_b { pool.user_token_b } else { pool.user_token_a };
+        let instruction = pool.swap_ix(amount_in, min_out, a_to_b);
+        self.send_swap(instruction, trader, &user_token_out)
+    }
+
+    /// Commit with `securelp::commit`
+    fn commit(&mut self, trader: &Keypair, details: &SwapDetails) -> Result<String> {
+        let instruction = Instruction {
+            program_id: self.programs.securelp,
+            accounts: securelp::accounts::Commit {
+                commitment: self.commitment_address(&trader.pubkey()),
+                user: trader.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: securelp::instruction::Commit {
+                hash: hash_swap_details(details),
+                amount_lamports: details.amount_in,
+                // Swap commitments don't record a direction
+                is_stake: false,
+            }
+            .data(),
+        };
+        let signature = self.send(&[instruction], &[trader])?;
+        Ok(signature.to_string())
+    }
+
+    /// Reveal and swap with `securelp::reveal_and_swap`
+    fn reveal(&mut self, trader: &Keypair, details: &SwapDetails, a_to_b: bool) -> Result<SwapOutcome> {
+        let pool = self.trader_pool(&trader.pubkey())?;
+        let (user_token_in, user_token_out) = if a_to_b {
+            (pool.user_token_a, pool.user_token_b)
+        } else {
+            (pool.user_token_b, pool.user_token_a)
+        };
+        let instruction = Instruction {
+            program_id: self.programs.securelp,
+            accounts: securelp::accounts::RevealAndSwap {
+                commitment: self.commitment_address(&trader.pubkey()),
+                user: trader.pubkey(),
+                amm_program: self.programs.amm,
+                amm_pool: pool.address,
+                amm_authority: pool.authority,
+                token_a_vault: pool.token_a_vault,
+                token_b_vault: pool.token_b_vault,
+                user_token_in,
+                user_token_out,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: securelp::instruction::RevealAndSwap {
+                details: securelp::state::SwapDetails {
+                    amount_in: details.amount_in,
+                    min_out: details.min_out,
+                    slippage_bps: details.slippage_bps,
+                    nonce: details.nonce,
+                },
+                a_to_b,
+            }
+            .data(),
+        };
+        self.send_swap(instruction, trader, &user_token_out)
+    }
+
+    fn balances(&mut self, trader: &Pubkey) -> Result<(u64, u64)> {
+        let accounts = self.trader(trader)?;
+        let (token_a, token_b) = (accounts.token_a, accounts.token_b);
+        Ok((self.token_balance(&token_a)?, self.token_balance(&token_b)?))
+    }
+
+    fn slot(&mut self) -> Result<u64> {
+        Ok(self.connection.clock()?.slot)
+    }
+
+    fn advance_slot(&mut self) -> Result<()> {
+        self.connection.advance_slot()
+    }
+
+    /// Reserves and fee as stored on chain
+    fn pool_state(&mut self, scenario: Scenario) -> Result<PoolState> {
+        let address = self.pool(scenario).address;
+        let account = self.connection.get_account(&address)?.context("AMM pool not found")?;
+        let pool = AmmPool::try_deserialize(&mut account.data.as_slice())?;
+
+        Ok(PoolState {
+            reserve_a: pool.reserve_a,
+            reserve_b: pool.reserve_b,
+            fee_bps: pool.fee_bps,
+            total_lp_supply: pool.total_lp_supply,
+        })
+    }
+}
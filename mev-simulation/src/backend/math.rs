@@ -0,0 +1,246 @@
+//! Math backend
+//!
+//! Executes swaps with the constant-product math on in-memory pools and
+//! keeps trader balances and commitments in memory. Nothing is signed or
+//! sent, so signatures are placeholders, but it enforces what the programs
+//! do: balances must cover the input, outputs must meet `min_out`, and a
+//! reveal must match its commitment one slot or more after it.
+
+use crate::backend::{ExecutionBackend, Scenario, SwapOutcome};
+use crate::config::SimulationConfig;
+use crate::utils::amm_math::PoolState;
+use crate::utils::hash::{hash_swap_details, SwapDetails};
+use anyhow::{bail, Context, Result};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use std::collections::HashMap;
+
+/// A trader's balances and commitment
+struct TraderState {
+    scenario: Scenario,
+    balance_a: u64,
+    balance_b: u64,
+    /// Committed hash and the slot it was committed in
+    commitment: Option<([u8; 32], u64)>,
+}
+
+impl TraderState {
+    fn balance_mut(&mut self, token_a: bool) -> &mut u64 {
+        if token_a {
+            &mut self.balance_a
+        } else {
+            &mut self.balance_b
+        }
+    }
+}
+
+/// Runs the simulation on in-memory pools
+pub struct MathBackend {
+    normal_pool: PoolState,
+    protected_pool: PoolState,
+    traders: HashMap<Pubkey, TraderState>,
+    slot: u64,
+    /// Transactions executed, numbering the placeholder signatures
+    transactions: u64,
+}
+
+impl MathBackend {
+    /// Create both scenario pools with the configured liquidity and fee
+    pub fn new(config: &SimulationConfig) -> Self {
+        let pool = PoolState::new(config.initial_pool_a, config.initial_pool_b, config.fee_bps);
+        Self {
+            normal_pool: pool.clone(),
+            protected_pool: pool,
+            traders: HashMap::new(),
+            slot: 0,
+            transactions: 0,
+        }
+    }
+
+    fn trader_mut(&mut self, trader: &Pubkey) -> Result<&mut TraderState> {
+        self.traders
+            .get_mut(trader)
+            .with_context(|| format!("trader {} was never added", trader))
+    }
+
+    /// Placeholder signature for the next transaction
+    fn signature(&mut self, kind: &str) -> String {
+        self.transactions += 1;
+        format!("simulated_{}_{}", kind, self.transactions)
+    }
+
+    /// Swap on the trader's pool, returning the output amount
+    fn execute_swap(&mut self, trader: &Pubkey, amount_in: u64, min_out: u64, a_to_b: bool) -> Result<u64> {
+        let state = self.traders
+            .get_mut(trader)
+            .with_context(|| format!("trader {} was never added", trader))?;
+        let pool = match state.scenario {
+            Scenario::Normal => &mut self.normal_pool,
+            Scenario::Protected => &mut self.protected_pool,
+        };
+
+        if *state.balance_mut(a_to_b) < amount_in {
+            bail!("insufficient balance for a swap of {}", amount_in);
+        }
+        let quote = pool.calculate_swap_output(amount_in, a_to_b);
+        if quote.amount_out < min_out {
+            bail!("slippage exceeded: {} < {}", quote.amount_out, min_out);
+        }
+
+        let result = pool.apply_swap(amount_in, a_to_b);
+        *state.balance_mut(a_to_b) -= amount_in;
+        *state.balance_mut(!a_to_b) += result.amount_out;
+        Ok(result.amount_out)
+    }
+}
+
+impl ExecutionBackend for MathBackend {
+    fn add_trader(
+        &mut self,
+        trader: &Pubkey,
+        scenario: Scenario,
+        balance_a: u64,
+        balance_b: u64,
+    ) -> Result<()> {
+        self.traders.insert(*trader, TraderState {
+            scenario,
+            balance_a,
+            balance_b,
+            commitment: None,
+        });
+        Ok(())
+    }
+
+    fn swap(
+        &mut self,
+        trader: &Keypair,
+        amount_in: u64,
+        min_out: u64,
+        a_to_b: bool,
+    ) -> Result<SwapOutcome> {
+        let amount_out = self.execute_swap(&trader.pubkey(), amount_in, min_out, a_to_b)?;
+        Ok(SwapOutcome {
+            signature: self.signature("swap"),
+            amount_out,
+        })
+    }
+
+    fn commit(&mut self, trader: &Keypair, details: &SwapDetails) -> Result<String> {
+        let slot = self.slot;
+        let state = self.trader_mut(&trader.pubkey())?;
+        if state.commitment.is_some() {
+            bail!("commitment already exists");
+        }
+        state.commitment = Some((hash_swap_details(details), slot));
+        Ok(self.signature("commit"))
+    }
+
+    fn reveal(&mut self, trader: &Keypair, details: &SwapDetails, a_to_b: bool) -> Result<SwapOutcome> {
+        let slot = self.slot;
+        let state = self.trader_mut(&trader.pubkey())?;
+        let Some((hash, commit_slot)) = state.commitment else {
+            bail!("no commitment to reveal");
+        };
+        if hash_swap_details(details) != hash {
+            bail!("revealed details don't match the commitment");
+        }
+        if slot <= commit_slot {
+            bail!("reveal delay not met");
+        }
+
+        // The commitment is only closed if the swap succeeds
+        let amount_out = self.execute_swap(&trader.pubkey(), details.amount_in, details.min_out, a_to_b)?;
+        self.trader_mut(&trader.pubkey())?.commitment = None;
+        Ok(SwapOutcome {
+            signature: self.signature("reveal"),
+            amount_out,
+        })
+    }
+
+    fn balances(&mut self, trader: &Pubkey) -> Result<(u64, u64)> {
+        let state = self.trader_mut(trader)?;
+        Ok((state.balance_a, state.balance_b))
+    }
+
+    fn slot(&mut self) -> Result<u64> {
+        Ok(self.slot)
+    }
+
+    fn advance_slot(&mut self) -> Result<()> {
+        self.slot += 1;
+        Ok(())
+    }
+
+    fn pool_state(&mut self, scenario: Scenario) -> Result<PoolState> {
+        Ok(match scenario {
+            Scenario::Normal => self.normal_pool.clone(),
+            Scenario::Protected => self.protected_pool.clone(),
+        })
+    }
+
+    fn flash_borrow(&mut self, trader: &Pubkey, amount: u64, token_a: bool) -> Result<()> {
+        *self.trader_mut(trader)?.balance_mut(token_a) += amount;
+        Ok(())
+    }
+
+    fn flash_repay(&mut self, trader: &Pubkey, amount: u64, token_a: bool) -> Result<()> {
+        let balance = self.trader_mut(trader)?.balance_mut(token_a);
+        if *balance < amount {
+            bail!("insufficient balance to repay a flash loan of {}", amount);
+        }
+        *balance -= amount;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend_with_trader(scenario: Scenario) -> (MathBackend, Keypair) {
+        let mut backend = MathBackend::new(&SimulationConfig::quick_test());
+        let trader = Keypair::new();
+        backend
+            .add_trader(&trader.pubkey(), scenario, 50_000_000_000, 50_000_000_000)
+            .unwrap();
+        (backend, trader)
+    }
+
+    #[test]
+    fn test_swap_moves_balances_and_reserves() {
+        let (mut backend, trader) = backend_with_trader(Scenario::Normal);
+        let quote = backend
+            .pool_state(Scenario::Normal)
+            .unwrap()
+            .calculate_swap_output(1_000_000_000, true);
+
+        let outcome = backend.swap(&trader, 1_000_000_000, 0, true).unwrap();
+
+        assert_eq!(outcome.amount_out, quote.amount_out);
+        assert_eq!(
+            backend.balances(&trader.pubkey()).unwrap(),
+            (49_000_000_000, 50_000_000_000 + quote.amount_out)
+        );
+        assert_eq!(backend.pool_state(Scenario::Normal).unwrap().reserve_a, 1_001_000_000_000);
+        // The other scenario's pool is untouched
+        assert_eq!(backend.pool_state(Scenario::Protected).unwrap().reserve_a, 1_000_000_000_000);
+        assert!(backend.swap(&trader, 1_000_000_000, u64::MAX, true).is_err());
+    }
+
+    #[test]
+    fn test_reveal_is_checked_against_the_commitment() {
+        let (mut backend, trader) = backend_with_trader(Scenario::Protected);
+        let details = SwapDetails::new(1_000_000_000, 0, 100);
+        backend.commit(&trader, &details).unwrap();
+
+        // Too early
+        assert!(backend.reveal(&trader, &details, true).is_err());
+        backend.advance_slot().unwrap();
+
+        let altered = SwapDetails::new(1_000_000_000, 0, 100);
+        assert!(backend.reveal(&trader, &altered, true).is_err());
+        backend.reveal(&trader, &details, true).unwrap();
+
+        // The commitment is spent
+        assert!(backend.reveal(&trader, &details, true).is_err());
+    }
+}
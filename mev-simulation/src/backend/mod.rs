@@ -0,0 +1,99 @@
+//! Execution backends
+//!
+//! Where the simulation's swaps, commitments and reveals actually run. The
+//! bots only talk to an [`ExecutionBackend`], so the same scenario can run on
+//! the constant-product math, on the real programs in an in-process SVM, or
+//! against a local validator, and the results compared.
+
+pub mod chain;
+pub mod math;
+pub mod rpc;
+pub mod svm;
+
+pub use math::MathBackend;
+pub use rpc::RpcBackend;
+pub use svm::SvmBackend;
+
+use crate::utils::amm_math::PoolState;
+use crate::utils::hash::SwapDetails;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+
+/// Which of the simulation's two pools an action runs against
+///
+/// The vulnerable and the protected scenario each trade on their own pool,
+/// seeded with the same liquidity, so their results can be compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Scenario {
+    /// Direct AMM swaps, visible to the sandwich attacker
+    Normal,
+    /// Commit-reveal swaps through securelp
+    Protected,
+}
+
+/// Outcome of a swap that was executed by a backend
+#[derive(Debug, Clone)]
+pub struct SwapOutcome {
+    /// Transaction signature
+    pub signature: String,
+    /// Output tokens the trader received
+    pub amount_out: u64,
+}
+
+/// Executes swaps, commitments and reveals for the bots
+///
+/// Token A stands in for SOL and token B for secuSOL. Each trader trades on
+/// the pool of the scenario it was added with.
+pub trait ExecutionBackend {
+    /// Give `trader` `balance_a` and `balance_b` of its scenario's tokens
+    fn add_trader(
+        &mut self,
+        trader: &Pubkey,
+        scenario: Scenario,
+        balance_a: u64,
+        balance_b: u64,
+    ) -> Result<()>;
+
+    /// Swap directly on the trader's pool
+    fn swap(
+        &mut self,
+        trader: &Keypair,
+        amount_in: u64,
+        min_out: u64,
+        a_to_b: bool,
+    ) -> Result<SwapOutcome>;
+
+    /// Commit to `details`, returning the signature
+    fn commit(&mut self, trader: &Keypair, details: &SwapDetails) -> Result<String>;
+
+    /// Reveal the trader's commitment to `details` and swap on its pool
+    fn reveal(&mut self, trader: &Keypair, details: &SwapDetails, a_to_b: bool) -> Result<SwapOutcome>;
+
+    /// Token A and token B balances of a trader
+    fn balances(&mut self, trader: &Pubkey) -> Result<(u64, u64)>;
+
+    /// Current slot
+    fn slot(&mut self) -> Result<u64>;
+
+    /// Move to the next slot, far enough for a pending reveal to be accepted
+    fn advance_slot(&mut self) -> Result<()>;
+
+    /// Reserves and fee of a scenario's pool
+    fn pool_state(&mut self, scenario: Scenario) -> Result<PoolState>;
+
+    /// Lend `amount` of token A (or B) to `trader` until
+    /// [`ExecutionBackend::flash_repay`]
+    ///
+    /// On chain a flash loan is repaid in the transaction that borrows it, so
+    /// it can't fund a front-run and back-run around another transaction.
+    /// Only the math backend can simulate one.
+    fn flash_borrow(&mut self, _trader: &Pubkey, _amount: u64, _token_a: bool) -> Result<()> {
+        bail!("Flash-loan sandwiches are only simulated by the math backend")
+    }
+
+    /// Take `amount` of token A (or B) back from `trader` to repay a flash loan
+    fn flash_repay(&mut self, _trader: &Pubkey, _amount: u64, _token_a: bool) -> Result<()> {
+        bail!("Flash-loan sandwiches are only simulated by the math backend")
+    }
+}
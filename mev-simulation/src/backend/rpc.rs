@@ -0,0 +1,115 @@
+//! JSON-RPC backend
+//!
+//! Sends every swap, commit and reveal to a running validator, such as
+//! `solana-test-validator`, that has the `amm` and `securelp` programs
+//! deployed at the configured program IDs.
+
+use crate::backend::chain::{ChainBackend, Connection};
+use crate::config::SimulationConfig;
+use anyhow::{bail, Context, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::Keypair,
+    sysvar,
+    transaction::Transaction,
+};
+use std::time::{Duration, Instant};
+
+/// How often to poll the clock while waiting for the next slot
+const SLOT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long to wait for the next slot before giving up
+const SLOT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Runs transactions against the programs deployed on a validator
+pub type RpcBackend = ChainBackend<RpcConnection>;
+
+impl RpcBackend {
+    /// Connect to `config.rpc_url` and create both scenario pools with the
+    /// configured liquidity and fee, paid for by `payer`
+    pub fn new(config: &SimulationConfig, payer: Keypair) -> Result<Self> {
+        let client = RpcClient::new_with_commitment(config.rpc_url.clone(), CommitmentConfig::confirmed());
+        for (program, id) in [("amm", config.programs.amm), ("securelp", config.programs.securelp)] {
+            let account = client
+                .get_account_with_commitment(&id, client.commitment())
+                .with_context(|| format!("cannot reach {}; is solana-test-validator running?", config.rpc_url))?
+                .value;
+            if !account.is_some_and(|account| account.executable) {
+                bail!("{} is not deployed at {} on {}", program, id, config.rpc_url);
+            }
+        }
+
+        ChainBackend::connect(RpcConnection { client }, config, payer)
+    }
+}
+
+/// Sends transactions to a validator over JSON-RPC
+pub struct RpcConnection {
+    client: RpcClient,
+}
+
+impl Connection for RpcConnection {
+    fn latest_blockhash(&mut self) -> Result<Hash> {
+        Ok(self.client.get_latest_blockhash()?)
+    }
+
+    fn new_blockhash(&mut self, blockhash: &Hash) -> Result<Hash> {
+        Ok(self.client.get_new_latest_blockhash(blockhash)?)
+    }
+
+    fn process_transaction(&mut self, transaction: Transaction) -> Result<()> {
+        self.client.send_and_confirm_transaction(&transaction)?;
+        Ok(())
+    }
+
+    fn get_account(&mut self, address: &Pubkey) -> Result<Option<Account>> {
+        Ok(self.client.get_account_with_commitment(address, self.client.commitment())?.value)
+    }
+
+    fn minimum_balance(&mut self, data_len: usize) -> Result<u64> {
+        Ok(self.client.get_minimum_balance_for_rent_exemption(data_len)?)
+    }
+
+    fn clock(&mut self) -> Result<Clock> {
+        let account = self.get_account(&sysvar::clock::ID)?.context("clock sysvar not found")?;
+        Ok(account.deserialize_data()?)
+    }
+
+    /// Wait for the validator to produce a later slot a second or more ahead
+    fn advance_slot(&mut self) -> Result<()> {
+        let before = self.clock()?;
+        let started = Instant::now();
+        loop {
+            std::thread::sleep(SLOT_POLL_INTERVAL);
+            let clock = self.clock()?;
+            if clock.slot > before.slot && clock.unix_timestamp > before.unix_timestamp {
+                return Ok(());
+            }
+            if started.elapsed() > SLOT_TIMEOUT {
+                bail!("validator is stuck at slot {}", clock.slot);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requires_a_validator() {
+        let config = SimulationConfig {
+            rpc_url: "http://127.0.0.1:1".to_string(),
+            ..SimulationConfig::quick_test()
+        };
+        let error = RpcBackend::new(&config, Keypair::new())
+            .err()
+            .expect("nothing listens on port 1");
+        assert!(error.to_string().contains("solana-test-validator"));
+    }
+}
@@ -0,0 +1,184 @@
+//! In-process SVM backend
+//!
+//! Loads the compiled `amm` and `securelp` programs into `solana-program-test`,
+//! so every swap, commit and reveal is a real signed transaction and balances
+//! and reserves are read back from account state. Build the programs first
+//! with `anchor build` in `securelp/`.
+
+use crate::backend::chain::{ChainBackend, Connection};
+use crate::config::SimulationConfig;
+use anyhow::{bail, Result};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{account::Account, clock::Clock, hash::Hash, pubkey::Pubkey, transaction::Transaction};
+use std::path::{Path, PathBuf};
+use tokio::runtime::Runtime;
+
+/// Runs transactions against the real programs in program-test
+pub type SvmBackend = ChainBackend<ProgramTestConnection>;
+
+/// Where `anchor build` puts the programs, unless `SBF_OUT_DIR` says otherwise
+pub fn default_programs_dir() -> PathBuf {
+    std::env::var_os("SBF_OUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../securelp/target/deploy")))
+}
+
+impl SvmBackend {
+    /// Start program-test with the programs in `programs_dir` and create both
+    /// scenario pools with the configured liquidity and fee
+    pub fn new(config: &SimulationConfig, programs_dir: &Path) -> Result<Self> {
+        for program in ["amm", "securelp"] {
+            let path = programs_dir.join(format!("{program}.so"));
+            if !path.exists() {
+                bail!(
+                    "{} not found; run `anchor build` in securelp/ or pass --programs-dir",
+                    path.display()
+                );
+            }
+        }
+        std::env::set_var("SBF_OUT_DIR", programs_dir);
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let mut program_test = ProgramTest::new("amm", config.programs.amm, None);
+        program_test.add_program("securelp", config.programs.securelp, None);
+        let context = runtime.block_on(program_test.start_with_context());
+        let payer = context.payer.insecure_clone();
+
+        ChainBackend::connect(ProgramTestConnection { runtime, context }, config, payer)
+    }
+}
+
+/// Processes transactions in program-test's bank
+pub struct ProgramTestConnection {
+    runtime: Runtime,
+    context: ProgramTestContext,
+}
+
+impl Connection for ProgramTestConnection {
+    fn latest_blockhash(&mut self) -> Result<Hash> {
+        Ok(self.runtime.block_on(self.context.banks_client.get_latest_blockhash())?)
+    }
+
+    fn new_blockhash(&mut self, _blockhash: &Hash) -> Result<Hash> {
+        Ok(self.runtime.block_on(self.context.get_new_latest_blockhash())?)
+    }
+
+    fn process_transaction(&mut self, transaction: Transaction) -> Result<()> {
+        Ok(self.runtime.block_on(self.context.banks_client.process_transaction(transaction))?)
+    }
+
+    fn get_account(&mut self, address: &Pubkey) -> Result<Option<Account>> {
+        Ok(self.runtime.block_on(self.context.banks_client.get_account(*address))?)
+    }
+
+    fn minimum_balance(&mut self, data_len: usize) -> Result<u64> {
+        let rent = self.runtime.block_on(self.context.banks_client.get_rent())?;
+        Ok(rent.minimum_balance(data_len))
+    }
+
+    fn clock(&mut self) -> Result<Clock> {
+        Ok(self.runtime.block_on(self.context.banks_client.get_sysvar::<Clock>())?)
+    }
+
+    /// Warp to the next slot and move the clock a whole second forward
+    fn advance_slot(&mut self) -> Result<()> {
+        let before = self.clock()?;
+        self.context.warp_to_slot(before.slot + 1)?;
+
+        let mut clock = self.clock()?;
+        clock.unix_timestamp = clock.unix_timestamp.max(before.unix_timestamp + 1);
+        self.context.set_sysvar(&clock);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requires_built_programs() {
+        let missing = std::env::temp_dir().join("mev-sim-no-programs");
+        let error = SvmBackend::new(&SimulationConfig::quick_test(), &missing)
+            .err()
+            .expect("programs are missing");
+        assert!(error.to_string().contains("anchor build"));
+    }
+
+    /// Needs the programs from `anchor build` in securelp/
+    #[cfg(feature = "test-sbf")]
+    #[test]
+    fn test_seeded_scenario_runs_on_the_programs() {
+        use crate::backend::{ExecutionBackend, Scenario};
+        use crate::simulation::Orchestrator;
+
+        let config = SimulationConfig {
+            total_transactions: 5,
+            seed: Some(7),
+            ..SimulationConfig::quick_test()
+        };
+        let mut backend = SvmBackend::new(&config, &default_programs_dir()).unwrap();
+        let results = Orchestrator::new(config).run_with(&mut backend).unwrap();
+
+        assert_eq!(results.normal_trades.len(), 5);
+        assert_eq!(results.protected_trades.len(), 5);
+        assert!(!results.sandwich_results.is_empty());
+        assert!(results.protected_trades.iter().all(|trade| !trade.was_attacked && trade.actual_out > 0));
+
+        // The recorded reserves are the pools' account state
+        for (scenario, name) in [(Scenario::Normal, "normal"), (Scenario::Protected, "protected")] {
+            let state = backend.pool_state(scenario).unwrap();
+            let last = results.pool_history.iter().rev().find(|record| record.scenario == name).unwrap();
+            assert_eq!((last.reserve_a, last.reserve_b), (state.reserve_a, state.reserve_b));
+        }
+    }
+
+    /// Needs the programs from `anchor build` in securelp/
+    #[cfg(feature = "test-sbf")]
+    #[test]
+    fn test_math_backend_matches_the_programs() {
+        use crate::backend::MathBackend;
+        use crate::simulation::{Orchestrator, SimulationResults};
+
+        let config = SimulationConfig {
+            total_transactions: 10,
+            seed: Some(11),
+            ..SimulationConfig::quick_test()
+        };
+        let math = Orchestrator::new(config.clone())
+            .run_with(&mut MathBackend::new(&config))
+            .unwrap();
+        let mut svm_backend = SvmBackend::new(&config, &default_programs_dir()).unwrap();
+        let svm = Orchestrator::new(config).run_with(&mut svm_backend).unwrap();
+
+        let trades = |results: &SimulationResults| -> Vec<(u64, bool, u64, u64, bool)> {
+            results.normal_trades.iter()
+                .chain(&results.protected_trades)
+                .map(|trade| (trade.amount_in, trade.a_to_b, trade.expected_out, trade.actual_out, trade.was_attacked))
+                .collect()
+        };
+        let sandwiches = |results: &SimulationResults| -> Vec<(bool, u64, u64, u64, i64)> {
+            results.sandwich_results.iter()
+                .map(|sandwich| {
+                    (
+                        sandwich.success,
+                        sandwich.frontrun_received,
+                        sandwich.backrun_received,
+                        sandwich.victim_loss_lamports,
+                        sandwich.profit_lamports,
+                    )
+                })
+                .collect()
+        };
+        let reserves = |results: &SimulationResults| -> Vec<(String, u64, u64)> {
+            results.pool_history.iter()
+                .map(|record| (record.scenario.clone(), record.reserve_a, record.reserve_b))
+                .collect()
+        };
+        assert_eq!(trades(&math), trades(&svm));
+        assert_eq!(sandwiches(&math), sandwiches(&svm));
+        assert_eq!(reserves(&math), reserves(&svm));
+    }
+}
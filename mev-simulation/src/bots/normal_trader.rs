@@ -3,15 +3,17 @@
 //! Simulates a regular user making direct AMM swaps.
 //! These trades are vulnerable to MEV sandwich attacks.
 
+use crate::backend::{ExecutionBackend, Scenario};
 use crate::utils::amm_math::PoolState;
+use anyhow::Result;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
-use tracing::debug;
 
 /// Result of a normal trade
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeResult {
-    /// Transaction signature (simulated)
+    /// Transaction signature (a placeholder on the math backend)
     pub signature: String,
     /// Trader's public key
     pub trader: String,
@@ -102,57 +104,40 @@ impl NormalTrader {
 
     /// Execute a trade on the AMM
     /// 
-    /// In a real implementation, this would build and submit a transaction.
-    /// In simulation, we update local state based on pool math.
+    /// The swap runs on `backend`, and the trader's balances are read back
+    /// from it afterwards.
     pub fn execute_trade(
         &mut self,
+        backend: &mut dyn ExecutionBackend,
         amount: u64,
+        min_out: u64,
         a_to_b: bool,
-        pool: &mut PoolState,
         expected_out: u64,
         was_attacked: bool,
-    ) -> Option<TradeResult> {
+    ) -> Result<TradeResult> {
         let timestamp = chrono::Utc::now().timestamp();
 
-        // Check balance
-        if !self.can_trade(amount, a_to_b) {
-            debug!("Insufficient balance for trade");
-            return None;
-        }
-
-        // Deduct input
-        if a_to_b {
-            self.balance_a -= amount;
-        } else {
-            self.balance_b -= amount;
-        }
-
-        // Execute swap
-        let result = pool.apply_swap(amount, a_to_b);
-
-        // Credit output
-        if a_to_b {
-            self.balance_b += result.amount_out;
-        } else {
-            self.balance_a += result.amount_out;
-        }
+        // Fee and price impact at the pool state the swap executes against
+        let quote = backend.pool_state(Scenario::Normal)?.calculate_swap_output(amount, a_to_b);
+        let outcome = backend.swap(&self.keypair, amount, min_out, a_to_b)?;
+        (self.balance_a, self.balance_b) = backend.balances(&self.pubkey())?;
 
         // Calculate loss
-        let slippage_loss = expected_out.saturating_sub(result.amount_out);
+        let slippage_loss = expected_out.saturating_sub(outcome.amount_out);
         self.total_loss += slippage_loss;
         self.total_trades += 1;
 
-        Some(TradeResult {
-            signature: format!("simulated_trade_{}", self.total_trades),
+        Ok(TradeResult {
+            signature: outcome.signature,
             trader: self.keypair.pubkey().to_string(),
             amount_in: amount,
             a_to_b,
             expected_out,
-            actual_out: result.amount_out,
+            actual_out: outcome.amount_out,
             slippage_loss,
             was_attacked,
-            fee_paid: result.fee,
-            price_impact_bps: result.price_impact_bps,
+            fee_paid: quote.fee,
+            price_impact_bps: quote.price_impact_bps,
             timestamp,
         })
     }
@@ -167,21 +152,20 @@ impl NormalTrader {
 }
 
 /// Generate a random trade amount within the configured range
-pub fn random_trade_amount(min: u64, max: u64) -> u64 {
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
+pub fn random_trade_amount(rng: &mut impl Rng, min: u64, max: u64) -> u64 {
     rng.gen_range(min..=max)
 }
 
 /// Generate a random trade direction
-pub fn random_direction() -> bool {
-    use rand::Rng;
-    rand::thread_rng().gen_bool(0.5)
+pub fn random_direction(rng: &mut impl Rng) -> bool {
+    rng.gen_bool(0.5)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::MathBackend;
+    use crate::config::SimulationConfig;
 
     #[test]
     fn test_normal_trade() {
@@ -192,23 +176,22 @@ mod tests {
             50_000_000_000,  // 50 secuSOL
         );
 
-        let mut pool = PoolState::new(
-            1_000_000_000_000,  // 1000 SOL
-            1_000_000_000_000,  // 1000 secuSOL
-            30,
-        );
+        // 1000 SOL / 1000 secuSOL, 0.3% fee
+        let mut backend = MathBackend::new(&SimulationConfig::quick_test());
+        backend.add_trader(&trader.pubkey(), Scenario::Normal, 50_000_000_000, 50_000_000_000).unwrap();
 
         let amount = 1_000_000_000; // 1 SOL
+        let pool = backend.pool_state(Scenario::Normal).unwrap();
         let expected = trader.calculate_expected(amount, true, &pool);
         
-        let result = trader.execute_trade(amount, true, &mut pool, expected, false);
+        let result = trader.execute_trade(&mut backend, amount, 0, true, expected, false);
         
-        assert!(result.is_some());
+        assert!(result.is_ok());
         let trade = result.unwrap();
         
         // Actual should be close to expected (small difference due to pool state)
         println!("Expected: {}, Actual: {}", trade.expected_out, trade.actual_out);
         assert!(trade.actual_out > 0);
+        assert_eq!(trader.balances(), (49_000_000_000, 50_000_000_000 + trade.actual_out));
     }
 }
-
@@ -3,11 +3,10 @@
 //! Simulates a user making trades using the commit-reveal scheme.
 //! These trades are protected from MEV sandwich attacks.
 
-use crate::utils::{
-    amm_math::PoolState,
-    hash::{hash_swap_details, SwapDetails},
-};
+use crate::utils::hash::{hash_swap_details, SwapDetails};
+use crate::backend::{ExecutionBackend, Scenario};
 use crate::bots::normal_trader::TradeResult;
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
 use tracing::{debug, info};
@@ -23,6 +22,7 @@ pub enum CommitmentState {
         details: SwapDetails,
         a_to_b: bool,
         commit_slot: u64,
+        commit_sig: String,
     },
     /// Revealed and executed
     Revealed,
@@ -55,8 +55,6 @@ pub struct ProtectedTrader {
     commitment_state: CommitmentState,
     /// Total trades executed
     total_trades: u32,
-    /// Current simulated slot
-    current_slot: u64,
 }
 
 impl ProtectedTrader {
@@ -68,7 +66,6 @@ impl ProtectedTrader {
             balance_b: initial_balance_b,
             commitment_state: CommitmentState::None,
             total_trades: 0,
-            current_slot: 0,
         }
     }
 
@@ -96,15 +93,16 @@ impl ProtectedTrader {
     /// - Minimum output
     pub fn commit(
         &mut self,
+        backend: &mut dyn ExecutionBackend,
         amount_in: u64,
         min_out: u64,
         slippage_bps: u16,
         a_to_b: bool,
-    ) -> Option<[u8; 32]> {
+    ) -> Result<Option<[u8; 32]>> {
         // Check if there's already an active commitment
         if self.has_commitment() {
             debug!("Already has active commitment");
-            return None;
+            return Ok(None);
         }
 
         // Check balance
@@ -116,7 +114,7 @@ impl ProtectedTrader {
 
         if !has_balance {
             debug!("Insufficient balance for commitment");
-            return None;
+            return Ok(None);
         }
 
         // Create swap details with random nonce
@@ -125,12 +123,14 @@ impl ProtectedTrader {
         // Compute hash
         let hash = hash_swap_details(&details);
 
-        // Store commitment
+        // Submit and store commitment
+        let commit_sig = backend.commit(&self.keypair, &details)?;
         self.commitment_state = CommitmentState::Committed {
             hash,
             details,
             a_to_b,
-            commit_slot: self.current_slot,
+            commit_slot: backend.slot()?,
+            commit_sig,
         };
 
         info!(
@@ -140,134 +140,115 @@ impl ProtectedTrader {
             if a_to_b { "A->B" } else { "B->A" }
         );
 
-        Some(hash)
+        Ok(Some(hash))
     }
 
     /// Reveal and execute the trade (Phase 2 of commit-reveal)
     /// 
     /// This must be called after at least 1 slot has passed.
     /// The reveal verifies the hash matches and executes atomically.
-    pub fn reveal_and_execute(&mut self, pool: &mut PoolState) -> Option<ProtectedTradeResult> {
+    pub fn reveal_and_execute(
+        &mut self,
+        backend: &mut dyn ExecutionBackend,
+    ) -> Result<Option<ProtectedTradeResult>> {
         let timestamp = chrono::Utc::now().timestamp();
 
         // Extract commitment details
-        let (hash, details, a_to_b, commit_slot) = match &self.commitment_state {
-            CommitmentState::Committed { hash, details, a_to_b, commit_slot } => {
-                (*hash, details.clone(), *a_to_b, *commit_slot)
+        let (hash, details, a_to_b, commit_slot, commit_sig) = match &self.commitment_state {
+            CommitmentState::Committed { hash, details, a_to_b, commit_slot, commit_sig } => {
+                (*hash, details.clone(), *a_to_b, *commit_slot, commit_sig.clone())
             }
             _ => {
                 debug!("No active commitment to reveal");
-                return None;
+                return Ok(None);
             }
         };
 
         // Check if enough slots have passed (minimum 1)
-        let slots_waited = self.current_slot.saturating_sub(commit_slot);
+        let slots_waited = backend.slot()?.saturating_sub(commit_slot);
         if slots_waited < 1 {
             debug!("Must wait at least 1 slot before reveal");
-            return None;
+            return Ok(None);
         }
 
         // Verify hash matches
         let computed_hash = hash_swap_details(&details);
         if computed_hash != hash {
             debug!("Hash mismatch!");
-            return None;
-        }
-
-        // Deduct input
-        if a_to_b {
-            if self.balance_a < details.amount_in {
-                return None;
-            }
-            self.balance_a -= details.amount_in;
-        } else {
-            if self.balance_b < details.amount_in {
-                return None;
-            }
-            self.balance_b -= details.amount_in;
+            return Ok(None);
         }
 
         // Calculate expected output BEFORE any manipulation
         // (This is what the user expects based on current pool state)
-        let expected_out = pool.calculate_swap_output(details.amount_in, a_to_b).amount_out;
+        let expected = backend
+            .pool_state(Scenario::Protected)?
+            .calculate_swap_output(details.amount_in, a_to_b);
 
         // Execute swap
-        let result = pool.apply_swap(details.amount_in, a_to_b);
-
-        // Credit output
-        if a_to_b {
-            self.balance_b += result.amount_out;
-        } else {
-            self.balance_a += result.amount_out;
-        }
+        let outcome = backend.reveal(&self.keypair, &details, a_to_b)?;
+        (self.balance_a, self.balance_b) = backend.balances(&self.pubkey())?;
 
         // Update state
         self.commitment_state = CommitmentState::Revealed;
         self.total_trades += 1;
 
         // Slippage should be minimal (only from fee, no MEV)
-        let slippage_loss = expected_out.saturating_sub(result.amount_out);
+        let slippage_loss = expected.amount_out.saturating_sub(outcome.amount_out);
 
         info!(
             "Reveal executed: expected={}, actual={}, loss={}",
-            expected_out, result.amount_out, slippage_loss
+            expected.amount_out, outcome.amount_out, slippage_loss
         );
 
         let trade = TradeResult {
-            signature: format!("protected_reveal_{}", self.total_trades),
+            signature: outcome.signature.clone(),
             trader: self.keypair.pubkey().to_string(),
             amount_in: details.amount_in,
             a_to_b,
-            expected_out,
-            actual_out: result.amount_out,
+            expected_out: expected.amount_out,
+            actual_out: outcome.amount_out,
             slippage_loss,
             was_attacked: false, // Protected trades cannot be attacked
-            fee_paid: result.fee,
-            price_impact_bps: result.price_impact_bps,
+            fee_paid: expected.fee,
+            price_impact_bps: expected.price_impact_bps,
             timestamp,
         };
 
-        Some(ProtectedTradeResult {
-            commit_sig: format!("protected_commit_{}", self.total_trades),
-            reveal_sig: format!("protected_reveal_{}", self.total_trades),
+        Ok(Some(ProtectedTradeResult {
+            commit_sig,
+            reveal_sig: outcome.signature,
             trade,
             slots_waited,
             commitment_hash: hex::encode(hash),
-        })
+        }))
     }
 
     /// Execute a complete protected trade (commit + wait + reveal)
     /// 
-    /// This is a convenience method that simulates the full flow.
+    /// This is a convenience method that runs the full flow, letting one
+    /// slot pass between the commit and the reveal.
     pub fn execute_protected_trade(
         &mut self,
+        backend: &mut dyn ExecutionBackend,
         amount: u64,
         a_to_b: bool,
-        pool: &mut PoolState,
         slippage_bps: u16,
-    ) -> Option<ProtectedTradeResult> {
+    ) -> Result<Option<ProtectedTradeResult>> {
         // Calculate min_out with slippage
-        let min_out = pool.calculate_min_output(amount, a_to_b, slippage_bps);
+        let min_out = backend
+            .pool_state(Scenario::Protected)?
+            .calculate_min_output(amount, a_to_b, slippage_bps);
 
         // Phase 1: Commit
-        let _hash = self.commit(amount, min_out, slippage_bps, a_to_b)?;
+        if self.commit(backend, amount, min_out, slippage_bps, a_to_b)?.is_none() {
+            return Ok(None);
+        }
 
-        // Simulate waiting for 1 slot
-        self.advance_slot();
+        // Wait for 1 slot
+        backend.advance_slot()?;
 
         // Phase 2: Reveal and execute
-        self.reveal_and_execute(pool)
-    }
-
-    /// Advance the simulated slot counter
-    pub fn advance_slot(&mut self) {
-        self.current_slot += 1;
-    }
-
-    /// Set the current slot
-    pub fn set_slot(&mut self, slot: u64) {
-        self.current_slot = slot;
+        self.reveal_and_execute(backend)
     }
 
     /// Cancel an active commitment
@@ -284,7 +265,6 @@ impl ProtectedTrader {
         self.balance_b = balance_b;
         self.commitment_state = CommitmentState::None;
         self.total_trades = 0;
-        self.current_slot = 0;
     }
 }
 
@@ -298,34 +278,41 @@ mod hex {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::MathBackend;
+    use crate::config::SimulationConfig;
 
-    #[test]
-    fn test_protected_trade() {
+    /// 1000 SOL / 1000 secuSOL pool with a 0.3% fee, and a trader with 50 of
+    /// each
+    fn setup() -> (MathBackend, ProtectedTrader) {
         let keypair = Keypair::new();
-        let mut trader = ProtectedTrader::new(
+        let trader = ProtectedTrader::new(
             keypair,
             50_000_000_000,  // 50 SOL
             50_000_000_000,  // 50 secuSOL
         );
 
-        let mut pool = PoolState::new(
-            1_000_000_000_000,  // 1000 SOL
-            1_000_000_000_000,  // 1000 secuSOL
-            30,
-        );
+        let mut backend = MathBackend::new(&SimulationConfig::quick_test());
+        backend.add_trader(&trader.pubkey(), Scenario::Protected, 50_000_000_000, 50_000_000_000).unwrap();
+        (backend, trader)
+    }
+
+    #[test]
+    fn test_protected_trade() {
+        let (mut backend, mut trader) = setup();
 
         let result = trader.execute_protected_trade(
+            &mut backend,
             1_000_000_000,  // 1 SOL
             true,           // SOL -> secuSOL
-            &mut pool,
             100,            // 1% slippage
-        );
+        ).unwrap();
 
         assert!(result.is_some());
         let trade = result.unwrap();
         
         // Protected trade should not be attacked
         assert!(!trade.trade.was_attacked);
+        assert_eq!(trade.slots_waited, 1);
         
         println!("Expected: {}", trade.trade.expected_out);
         println!("Actual: {}", trade.trade.actual_out);
@@ -334,25 +321,22 @@ mod tests {
 
     #[test]
     fn test_commit_reveal_flow() {
-        let keypair = Keypair::new();
-        let mut trader = ProtectedTrader::new(keypair, 50_000_000_000, 50_000_000_000);
-        let mut pool = PoolState::new(1_000_000_000_000, 1_000_000_000_000, 30);
+        let (mut backend, mut trader) = setup();
 
         // Phase 1: Commit
-        let hash = trader.commit(1_000_000_000, 900_000_000, 100, true);
+        let hash = trader.commit(&mut backend, 1_000_000_000, 900_000_000, 100, true).unwrap();
         assert!(hash.is_some());
         assert!(trader.has_commitment());
 
         // Try to reveal too early (should fail)
-        let early_result = trader.reveal_and_execute(&mut pool);
+        let early_result = trader.reveal_and_execute(&mut backend).unwrap();
         assert!(early_result.is_none());
 
         // Advance slot
-        trader.advance_slot();
+        backend.advance_slot().unwrap();
 
         // Now reveal should work
-        let result = trader.reveal_and_execute(&mut pool);
+        let result = trader.reveal_and_execute(&mut backend).unwrap();
         assert!(result.is_some());
     }
 }
-
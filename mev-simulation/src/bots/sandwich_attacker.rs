@@ -3,7 +3,10 @@
 //! Implements MEV sandwich attacks on AMM swaps.
 //! This bot front-runs victim transactions to extract value.
 
-use crate::utils::amm_math::{PoolState, SandwichCalculation};
+use crate::backend::{ExecutionBackend, Scenario};
+use crate::bots::normal_trader::{NormalTrader, TradeResult};
+use crate::utils::amm_math::{flash_loan_fee, PoolState, SandwichCalculation};
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use solana_sdk::{
     pubkey::Pubkey,
@@ -33,6 +36,9 @@ pub struct SandwichResult {
     pub backrun_amount: u64,
     /// Amount received from back-run
     pub backrun_received: u64,
+    /// Flash loan fee paid to fund the front-run (0 if own capital was used)
+    #[serde(default)]
+    pub flash_loan_fee: u64,
     /// Whether the attack was successful (profitable)
     pub success: bool,
     /// Timestamp of the attack
@@ -52,6 +58,7 @@ impl SandwichResult {
             frontrun_received: 0,
             backrun_amount: 0,
             backrun_received: 0,
+            flash_loan_fee: 0,
             success: false,
             timestamp: chrono::Utc::now().timestamp(),
         }
@@ -85,6 +92,8 @@ pub struct SandwichAttacker {
     successful_attacks: u32,
     /// Number of failed attacks
     failed_attacks: u32,
+    /// Flash loan fee in bps when front-runs are funded by flash loans
+    flash_loan_fee_bps: Option<u16>,
 }
 
 impl SandwichAttacker {
@@ -101,9 +110,36 @@ impl SandwichAttacker {
             total_profit: 0,
             successful_attacks: 0,
             failed_attacks: 0,
+            flash_loan_fee_bps: None,
         }
     }
 
+    /// Fund front-runs with flash loans instead of the attacker's own capital
+    pub fn with_flash_loans(mut self, fee_bps: u16) -> Self {
+        info!("  Flash loans enabled ({} bps fee)", fee_bps);
+        self.flash_loan_fee_bps = Some(fee_bps);
+        self
+    }
+
+    /// Capital available for a front-run in the input token
+    fn available_capital(&self, a_to_b: bool) -> u64 {
+        if self.flash_loan_fee_bps.is_some() {
+            // Borrowed capital is only bounded by the pool (see calculate_optimal_frontrun)
+            u64::MAX
+        } else if a_to_b {
+            self.balance_a
+        } else {
+            self.balance_b
+        }
+    }
+
+    /// Flash loan fee for borrowing `amount` (0 without flash loans)
+    fn flash_fee(&self, amount: u64) -> u64 {
+        self.flash_loan_fee_bps
+            .map(|fee_bps| flash_loan_fee(amount, fee_bps))
+            .unwrap_or(0)
+    }
+
     /// Get attacker's public key
     pub fn pubkey(&self) -> Pubkey {
         self.keypair.pubkey()
@@ -122,11 +158,7 @@ impl SandwichAttacker {
     /// Analyze a pending swap and decide whether to attack
     pub fn should_attack(&self, pending: &PendingSwap, pool: &PoolState) -> Option<SandwichCalculation> {
         // Calculate potential profit
-        let max_capital = if pending.a_to_b {
-            self.balance_a
-        } else {
-            self.balance_b
-        };
+        let max_capital = self.available_capital(pending.a_to_b);
 
         if max_capital == 0 {
             debug!("No capital available for attack");
@@ -139,8 +171,8 @@ impl SandwichAttacker {
             max_capital,
         );
 
-        // Only attack if profitable
-        if calc.expected_profit > 0 {
+        // Only attack if profitable after any flash loan fee
+        if calc.expected_profit - self.flash_fee(calc.frontrun_amount) as i64 > 0 {
             debug!("Profitable sandwich found: {} lamports profit", calc.expected_profit);
             Some(calc)
         } else {
@@ -149,137 +181,84 @@ impl SandwichAttacker {
         }
     }
 
-    /// Execute a sandwich attack (simulation mode - updates local state)
+    /// Sandwich `victim`'s pending swap on `backend`: front-run, the victim's
+    /// swap, then back-run
     /// 
-    /// In a real implementation, this would:
-    /// 1. Submit front-run transaction
-    /// 2. Wait for victim transaction to land
-    /// 3. Submit back-run transaction
-    /// 
-    /// In simulation, we calculate the outcome deterministically.
+    /// The victim's swap executes whether or not the attack goes ahead, and
+    /// its result is returned with the sandwich's. `victim_expected` is what
+    /// the victim would get without the attack.
     pub fn execute_sandwich(
         &mut self,
+        backend: &mut dyn ExecutionBackend,
         pending: &PendingSwap,
-        pool: &mut PoolState,
-    ) -> SandwichResult {
+        victim: &mut NormalTrader,
+        victim_expected: u64,
+    ) -> Result<(SandwichResult, TradeResult)> {
         let timestamp = chrono::Utc::now().timestamp();
         
         // Calculate optimal attack
-        let max_capital = if pending.a_to_b {
-            self.balance_a
-        } else {
-            self.balance_b
-        };
+        let max_capital = self.available_capital(pending.a_to_b);
 
+        let pool = backend.pool_state(Scenario::Normal)?;
         let calc = pool.calculate_optimal_frontrun(
             pending.amount_in,
             pending.a_to_b,
             max_capital,
         );
 
-        if calc.frontrun_amount == 0 || calc.expected_profit <= 0 {
+        let flash_fee = self.flash_fee(calc.frontrun_amount);
+        if calc.frontrun_amount == 0 || calc.expected_profit - flash_fee as i64 <= 0 {
             self.failed_attacks += 1;
-            return SandwichResult::skipped();
+            let trade = victim.execute_trade(
+                backend,
+                pending.amount_in,
+                pending.min_out,
+                pending.a_to_b,
+                victim_expected,
+                false,
+            )?;
+            return Ok((SandwichResult::skipped(), trade));
         }
 
-        // Store pre-attack state
-        let victim_expected = pool.calculate_swap_output(pending.amount_in, pending.a_to_b);
+        // Borrow the front-run capital; repaid with the fee after the back-run
+        let flash_loan = self.flash_loan_fee_bps.is_some();
+        if flash_loan {
+            debug!("Flash borrowing {} lamports", calc.frontrun_amount);
+            backend.flash_borrow(&self.pubkey(), calc.frontrun_amount, pending.a_to_b)?;
+        }
 
         // === EXECUTE FRONT-RUN ===
         debug!("Front-running with {} lamports", calc.frontrun_amount);
-        
-        // Deduct from attacker's balance
-        if pending.a_to_b {
-            if self.balance_a < calc.frontrun_amount {
-                self.failed_attacks += 1;
-                return SandwichResult::skipped();
-            }
-            self.balance_a -= calc.frontrun_amount;
-        } else {
-            if self.balance_b < calc.frontrun_amount {
-                self.failed_attacks += 1;
-                return SandwichResult::skipped();
-            }
-            self.balance_b -= calc.frontrun_amount;
-        }
-
-        // Execute front-run swap
-        let frontrun_result = pool.apply_swap(calc.frontrun_amount, pending.a_to_b);
-        
-        // Credit received tokens
-        if pending.a_to_b {
-            self.balance_b += frontrun_result.amount_out;
-        } else {
-            self.balance_a += frontrun_result.amount_out;
-        }
+        let frontrun = backend.swap(&self.keypair, calc.frontrun_amount, 0, pending.a_to_b)?;
 
         // === VICTIM TRANSACTION ===
         debug!("Victim swap: {} lamports", pending.amount_in);
-        let victim_result = pool.apply_swap(pending.amount_in, pending.a_to_b);
-        let victim_actual = victim_result.amount_out;
-        let victim_loss = victim_expected.amount_out.saturating_sub(victim_actual);
+        let victim_trade = victim.execute_trade(
+            backend,
+            pending.amount_in,
+            pending.min_out,
+            pending.a_to_b,
+            victim_expected,
+            true,
+        )?;
 
         // === EXECUTE BACK-RUN ===
         // Sell what we got from front-run
-        let backrun_amount = frontrun_result.amount_out;
-        debug!("Back-running with {} lamports", backrun_amount);
-
-        // Deduct from attacker's balance
-        if pending.a_to_b {
-            // We got B tokens from front-run, sell them for A
-            if self.balance_b < backrun_amount {
-                // This shouldn't happen, but handle it
-                self.failed_attacks += 1;
-                return SandwichResult {
-                    frontrun_sig: Some("simulated_frontrun".to_string()),
-                    victim_sig: Some("simulated_victim".to_string()),
-                    backrun_sig: None,
-                    profit_lamports: -(calc.frontrun_amount as i64),
-                    victim_loss_lamports: victim_loss,
-                    frontrun_amount: calc.frontrun_amount,
-                    frontrun_received: frontrun_result.amount_out,
-                    backrun_amount: 0,
-                    backrun_received: 0,
-                    success: false,
-                    timestamp,
-                };
-            }
-            self.balance_b -= backrun_amount;
-        } else {
-            // We got A tokens from front-run, sell them for B
-            if self.balance_a < backrun_amount {
-                self.failed_attacks += 1;
-                return SandwichResult {
-                    frontrun_sig: Some("simulated_frontrun".to_string()),
-                    victim_sig: Some("simulated_victim".to_string()),
-                    backrun_sig: None,
-                    profit_lamports: -(calc.frontrun_amount as i64),
-                    victim_loss_lamports: victim_loss,
-                    frontrun_amount: calc.frontrun_amount,
-                    frontrun_received: frontrun_result.amount_out,
-                    backrun_amount: 0,
-                    backrun_received: 0,
-                    success: false,
-                    timestamp,
-                };
-            }
-            self.balance_a -= backrun_amount;
-        }
-
-        // Execute back-run swap (opposite direction)
-        let backrun_result = pool.apply_swap(backrun_amount, !pending.a_to_b);
-
-        // Credit received tokens
-        if pending.a_to_b {
-            // Back-run: B -> A, we get A tokens back
-            self.balance_a += backrun_result.amount_out;
-        } else {
-            // Back-run: A -> B, we get B tokens back
-            self.balance_b += backrun_result.amount_out;
+        debug!("Back-running with {} lamports", frontrun.amount_out);
+        let backrun = backend.swap(&self.keypair, frontrun.amount_out, 0, !pending.a_to_b)?;
+
+        // === REPAY FLASH LOAN ===
+        if flash_loan {
+            let repay = calc.frontrun_amount + flash_fee;
+            debug!("Repaying flash loan: {} lamports", repay);
+            backend.flash_repay(&self.pubkey(), repay, pending.a_to_b)?;
         }
+        (self.balance_a, self.balance_b) = backend.balances(&self.pubkey())?;
 
         // === CALCULATE PROFIT ===
-        let profit = (backrun_result.amount_out as i64) - (calc.frontrun_amount as i64);
+        let profit = (backrun.amount_out as i64)
+            - (calc.frontrun_amount as i64)
+            - (flash_fee as i64);
         self.total_profit += profit;
 
         let success = profit > 0;
@@ -291,19 +270,21 @@ impl SandwichAttacker {
             info!("Sandwich failed. Loss: {} lamports", -profit);
         }
 
-        SandwichResult {
-            frontrun_sig: Some("simulated_frontrun".to_string()),
-            victim_sig: Some("simulated_victim".to_string()),
-            backrun_sig: Some("simulated_backrun".to_string()),
+        let sandwich = SandwichResult {
+            frontrun_sig: Some(frontrun.signature),
+            victim_sig: Some(victim_trade.signature.clone()),
+            backrun_sig: Some(backrun.signature),
             profit_lamports: profit,
-            victim_loss_lamports: victim_loss,
+            victim_loss_lamports: victim_trade.slippage_loss,
             frontrun_amount: calc.frontrun_amount,
-            frontrun_received: frontrun_result.amount_out,
-            backrun_amount,
-            backrun_received: backrun_result.amount_out,
+            frontrun_received: frontrun.amount_out,
+            backrun_amount: frontrun.amount_out,
+            backrun_received: backrun.amount_out,
+            flash_loan_fee: flash_fee,
             success,
             timestamp,
-        }
+        };
+        Ok((sandwich, victim_trade))
     }
 
     /// Reset the attacker's state (for running multiple simulations)
@@ -319,6 +300,29 @@ impl SandwichAttacker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::MathBackend;
+    use crate::config::SimulationConfig;
+
+    /// 1000 SOL / 1000 secuSOL pool with a 0.3% fee, and a victim with
+    /// 100 of each
+    fn setup(attacker: &SandwichAttacker) -> (MathBackend, NormalTrader) {
+        let mut backend = MathBackend::new(&SimulationConfig::quick_test());
+        let (capital_a, capital_b) = attacker.balances();
+        backend.add_trader(&attacker.pubkey(), Scenario::Normal, capital_a, capital_b).unwrap();
+
+        let victim = NormalTrader::new(Keypair::new(), 100_000_000_000, 100_000_000_000);
+        backend.add_trader(&victim.pubkey(), Scenario::Normal, 100_000_000_000, 100_000_000_000).unwrap();
+        (backend, victim)
+    }
+
+    fn pending_swap(victim: &NormalTrader, amount_in: u64) -> PendingSwap {
+        PendingSwap {
+            amount_in,
+            a_to_b: true,
+            victim: victim.pubkey(),
+            min_out: 0,
+        }
+    }
 
     #[test]
     fn test_sandwich_attack() {
@@ -328,21 +332,16 @@ mod tests {
             100_000_000_000,  // 100 SOL
             100_000_000_000,  // 100 secuSOL
         );
+        let (mut backend, mut victim) = setup(&attacker);
 
-        let mut pool = PoolState::new(
-            1_000_000_000_000,  // 1000 SOL
-            1_000_000_000_000,  // 1000 secuSOL
-            30,  // 0.3% fee
-        );
+        let pending = pending_swap(&victim, 10_000_000_000);  // 10 SOL
+        let expected = backend.pool_state(Scenario::Normal).unwrap()
+            .calculate_swap_output(pending.amount_in, true)
+            .amount_out;
 
-        let pending = PendingSwap {
-            amount_in: 10_000_000_000,  // 10 SOL
-            a_to_b: true,
-            victim: Pubkey::new_unique(),
-            min_out: 0,
-        };
-
-        let result = attacker.execute_sandwich(&pending, &mut pool);
+        let (result, trade) = attacker
+            .execute_sandwich(&mut backend, &pending, &mut victim, expected)
+            .unwrap();
         
         println!("Profit: {} lamports", result.profit_lamports);
         println!("Victim loss: {} lamports", result.victim_loss_lamports);
@@ -350,6 +349,31 @@ mod tests {
         
         // The attack should cause some victim loss
         assert!(result.victim_loss_lamports > 0);
+        assert!(trade.was_attacked);
+        assert_eq!(trade.slippage_loss, result.victim_loss_lamports);
     }
-}
 
+    #[test]
+    fn test_flash_loan_sandwich_needs_no_capital() {
+        let mut attacker = SandwichAttacker::new(Keypair::new(), 0, 0).with_flash_loans(9);
+        let (mut backend, mut victim) = setup(&attacker);
+
+        let pending = pending_swap(&victim, 50_000_000_000);  // 50 SOL
+        let expected = backend.pool_state(Scenario::Normal).unwrap()
+            .calculate_swap_output(pending.amount_in, true)
+            .amount_out;
+
+        let (result, _) = attacker
+            .execute_sandwich(&mut backend, &pending, &mut victim, expected)
+            .unwrap();
+
+        assert!(result.success);
+        assert!(result.flash_loan_fee > 0);
+        assert!(result.victim_loss_lamports > 0);
+
+        // Only the net profit is left over after repaying the loan
+        let (balance_a, balance_b) = attacker.balances();
+        assert_eq!(balance_a as i64, result.profit_lamports);
+        assert_eq!(balance_b, 0);
+    }
+}
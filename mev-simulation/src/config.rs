@@ -4,6 +4,11 @@ use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
+/// Why flash-loan sandwiches are a what-if rather than an attack on the real AMM
+pub const FLASH_LOAN_CAVEAT: &str = "Flash-loan sandwiches are hypothetical: the AMM only lends \
+    within a single transaction, which must repay the loan, so a loan cannot fund a front-run \
+    and back-run placed around the victim's transaction. This is not feasible on chain.";
+
 /// Program IDs for the deployed Solana programs
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProgramIds {
@@ -49,6 +54,14 @@ pub struct SimulationConfig {
     /// Attacker's initial capital in lamports
     pub attacker_capital: u64,
     
+    /// Fund sandwich front-runs with AMM flash loans instead of attacker capital
+    ///
+    /// A what-if only, see [`FLASH_LOAN_CAVEAT`].
+    pub flash_loans: bool,
+    
+    /// Flash loan fee in basis points (matches the AMM's FLASH_LOAN_FEE_BPS)
+    pub flash_loan_fee_bps: u16,
+    
     /// Number of victim wallets to create
     pub num_victims: u32,
     
@@ -66,6 +79,9 @@ pub struct SimulationConfig {
     
     /// Output directory for logs and reports
     pub output_dir: String,
+    
+    /// Seed for the random trades, so runs can be reproduced (random if unset)
+    pub seed: Option<u64>,
 }
 
 impl Default for SimulationConfig {
@@ -75,16 +91,19 @@ impl Default for SimulationConfig {
             attack_probability: 0.8,
             min_swap_lamports: 100_000_000,      // 0.1 SOL
             max_swap_lamports: 5_000_000_000,    // 5 SOL
-            initial_pool_a: 1000_000_000_000,    // 1000 SOL
-            initial_pool_b: 1000_000_000_000,    // 1000 secuSOL
+            initial_pool_a: 1_000_000_000_000,   // 1000 SOL
+            initial_pool_b: 1_000_000_000_000,   // 1000 secuSOL
             fee_bps: 30,                          // 0.3%
             attacker_capital: 100_000_000_000,   // 100 SOL
+            flash_loans: false,
+            flash_loan_fee_bps: 9,                // 0.09%
             num_victims: 10,
             victim_sol: 50_000_000_000,          // 50 SOL
             victim_secusol: 50_000_000_000,      // 50 secuSOL
             rpc_url: "http://127.0.0.1:8899".to_string(),
             programs: ProgramIds::default(),
             output_dir: "output".to_string(),
+            seed: None,
         }
     }
 }
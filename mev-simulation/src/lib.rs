@@ -3,6 +3,7 @@
 //! This framework simulates MEV sandwich attacks on an AMM to demonstrate
 //! the effectiveness of commit-reveal protection mechanisms.
 
+pub mod backend;
 pub mod bots;
 pub mod simulation;
 pub mod analytics;
@@ -2,20 +2,23 @@
 //!
 //! Command-line interface for the MEV sandwich attack simulation framework.
 
-use anyhow::Result;
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
 use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
 use mev_simulation::{
+    backend::{self, chain::TRADER_LAMPORTS, ExecutionBackend, MathBackend, RpcBackend, SvmBackend},
     config::SimulationConfig,
     simulation::Orchestrator,
     analytics::{
         logger::{SimulationLogger, print_summary},
         report::generate_report,
     },
+    utils::wallet::WalletManager,
 };
+use solana_sdk::signature::Keypair;
 
 #[derive(Parser)]
 #[command(name = "mev-sim")]
@@ -31,6 +34,17 @@ struct Cli {
     verbose: bool,
 }
 
+/// Where the simulated transactions are executed
+#[derive(Clone, Copy, ValueEnum)]
+enum Backend {
+    /// Constant-product math on in-memory pools
+    Math,
+    /// Real transactions against the compiled programs on an in-process SVM
+    Svm,
+    /// Real transactions sent to a local validator over JSON-RPC
+    Rpc,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Run the MEV simulation
@@ -59,6 +73,15 @@ enum Commands {
         #[arg(long, default_value = "30")]
         fee_bps: u16,
         
+        /// Fund sandwiches with AMM flash loans instead of attacker capital
+        /// (hypothetical: not feasible on chain, math backend only)
+        ///
+        /// The AMM requires a flash loan to be repaid in the transaction that
+        /// borrows it, so no real loan can span a front-run, the victim's swap
+        /// and a back-run. This mode shows what such an attacker would extract.
+        #[arg(long)]
+        flash_loans: bool,
+        
         /// Output directory for results
         #[arg(short, long, default_value = "output")]
         output: String,
@@ -66,6 +89,24 @@ enum Commands {
         /// Skip HTML report generation
         #[arg(long)]
         no_report: bool,
+        
+        /// Execution backend
+        #[arg(long, value_enum, default_value = "math")]
+        backend: Backend,
+        
+        /// Directory with the compiled programs for the SVM backend
+        /// (defaults to securelp/target/deploy)
+        #[arg(long)]
+        programs_dir: Option<PathBuf>,
+        
+        /// Validator RPC endpoint for the RPC backend
+        #[arg(long, default_value = "http://127.0.0.1:8899")]
+        rpc_url: String,
+        
+        /// Seed for the random trades; runs with the same seed see the same
+        /// trades on every backend
+        #[arg(long)]
+        seed: Option<u64>,
     },
     
     /// Generate report from existing simulation results
@@ -95,7 +136,7 @@ fn main() -> Result<()> {
     
     // Setup logging
     let log_level = if cli.verbose { Level::DEBUG } else { Level::INFO };
-    let _subscriber = FmtSubscriber::builder()
+    FmtSubscriber::builder()
         .with_max_level(log_level)
         .with_target(false)
         .with_thread_ids(false)
@@ -110,9 +151,15 @@ fn main() -> Result<()> {
             max_swap,
             pool_liquidity,
             fee_bps,
+            flash_loans,
             output,
             no_report,
+            backend,
+            programs_dir,
+            rpc_url,
+            seed,
         } => {
+            let programs_dir = programs_dir.unwrap_or_else(backend::svm::default_programs_dir);
             run_simulation(
                 transactions,
                 attack_probability,
@@ -120,13 +167,18 @@ fn main() -> Result<()> {
                 max_swap,
                 pool_liquidity,
                 fee_bps,
+                flash_loans,
                 &output,
                 !no_report,
+                backend,
+                &programs_dir,
+                &rpc_url,
+                seed,
             )?;
         }
         
         Commands::Report { input, output } => {
-            generate_report_from_file(input.as_path(), output.as_ref().map(|p| p.as_path()))?;
+            generate_report_from_file(input.as_path(), output.as_deref())?;
         }
         
         Commands::Quick { transactions } => {
@@ -141,6 +193,7 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_simulation(
     transactions: u32,
     attack_probability: f64,
@@ -148,8 +201,13 @@ fn run_simulation(
     max_swap: f64,
     pool_liquidity: f64,
     fee_bps: u16,
+    flash_loans: bool,
     output_dir: &str,
     generate_html: bool,
+    backend: Backend,
+    programs_dir: &Path,
+    rpc_url: &str,
+    seed: Option<u64>,
 ) -> Result<()> {
     println!();
     println!("╔══════════════════════════════════════════════════════════╗");
@@ -169,21 +227,44 @@ fn run_simulation(
         initial_pool_a: sol_to_lamports(pool_liquidity),
         initial_pool_b: sol_to_lamports(pool_liquidity),
         fee_bps,
+        flash_loans,
         output_dir: output_dir.to_string(),
+        rpc_url: rpc_url.to_string(),
+        seed,
         ..Default::default()
     };
     
+    if flash_loans && !matches!(backend, Backend::Math) {
+        bail!("Flash-loan sandwiches are only simulated by the math backend");
+    }
+    
     info!("Configuration:");
     info!("  Transactions:        {}", transactions);
     info!("  Attack Probability:  {:.0}%", attack_probability * 100.0);
     info!("  Swap Range:          {:.2} - {:.2} SOL", min_swap, max_swap);
     info!("  Pool Liquidity:      {:.2} SOL each", pool_liquidity);
     info!("  Fee:                 {:.2}%", fee_bps as f64 / 100.0);
+    info!("  Flash Loans:         {}", if flash_loans { "enabled (hypothetical, not feasible on chain)" } else { "disabled" });
+    info!("  Backend:             {}", match backend {
+        Backend::Math => "math".to_string(),
+        Backend::Svm => format!("svm ({})", programs_dir.display()),
+        Backend::Rpc => format!("rpc ({})", rpc_url),
+    });
     println!();
     
     // Create orchestrator and run simulation
-    let mut orchestrator = Orchestrator::new(config);
-    let results = orchestrator.run()?;
+    let (mut orchestrator, mut execution): (Orchestrator, Box<dyn ExecutionBackend>) = match backend {
+        Backend::Math => (Orchestrator::new(config.clone()), Box::new(MathBackend::new(&config))),
+        Backend::Svm => (
+            Orchestrator::new(config.clone()),
+            Box::new(SvmBackend::new(&config, programs_dir)?),
+        ),
+        Backend::Rpc => {
+            let (orchestrator, rpc) = provision_rpc(&config)?;
+            (orchestrator, Box::new(rpc))
+        }
+    };
+    let results = orchestrator.run_with(execution.as_mut())?;
     
     // Print summary to terminal
     print_summary(&results);
@@ -210,6 +291,36 @@ fn run_simulation(
     Ok(())
 }
 
+/// Fund the bots' wallets on the validator at `config.rpc_url` and create the
+/// pools there
+fn provision_rpc(config: &SimulationConfig) -> Result<(Orchestrator, RpcBackend)> {
+    // The funder is airdropped what the wallets need, and pays for the pools
+    let funder = Keypair::new();
+    let payer = funder.insecure_clone();
+    let mut wallets = WalletManager::new(&config.rpc_url, funder);
+
+    // Wallets only need SOL for fees: their tokens are minted on the pools' mints
+    let num_wallets = config.num_victims * 2;
+    wallets
+        .setup_simulation_wallets(TRADER_LAMPORTS, num_wallets, TRADER_LAMPORTS)
+        .with_context(|| format!("cannot fund wallets on {}; is solana-test-validator running?", config.rpc_url))?;
+
+    let keypair = |name: &str| {
+        wallets
+            .get_wallet(name)
+            .map(Keypair::insecure_clone)
+            .with_context(|| format!("wallet '{}' was not created", name))
+    };
+    let attacker = keypair("attacker")?;
+    let mut normal = (0..num_wallets)
+        .map(|i| keypair(&format!("victim_{}", i)))
+        .collect::<Result<Vec<_>>>()?;
+    let protected = normal.split_off(config.num_victims as usize);
+
+    let rpc = RpcBackend::new(config, payer)?;
+    Ok((Orchestrator::with_keypairs(config.clone(), attacker, normal, protected), rpc))
+}
+
 fn generate_report_from_file(input: &std::path::Path, output: Option<&std::path::Path>) -> Result<()> {
     info!("Loading results from: {:?}", input);
     
@@ -262,6 +373,9 @@ fn print_info() {
     println!();
     println!("USAGE:");
     println!("  mev-sim run --transactions 1000    # Run full simulation");
+    println!("  mev-sim run --backend svm          # Run against the real programs");
+    println!("  mev-sim run --backend rpc          # Run on solana-test-validator");
+    println!("  mev-sim run --seed 42              # Reproducible trades");
     println!("  mev-sim quick                       # Quick 100 tx test");
     println!("  mev-sim report -i results.json     # Generate report");
     println!();
@@ -3,15 +3,16 @@
 //! Controls the flow of the MEV simulation, running both scenarios
 //! (vulnerable normal trading vs protected commit-reveal) and collecting results.
 
+use crate::backend::{ExecutionBackend, MathBackend, Scenario};
 use crate::bots::{
     normal_trader::{NormalTrader, TradeResult, random_trade_amount, random_direction},
     protected_trader::ProtectedTrader,
     sandwich_attacker::{SandwichAttacker, SandwichResult, PendingSwap},
 };
 use crate::config::SimulationConfig;
-use crate::simulation::pool_state::SimulatedPool;
+use crate::utils::amm_math::PoolState;
 use anyhow::Result;
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use solana_sdk::signature::Keypair;
 #[allow(unused_imports)]
@@ -45,6 +46,12 @@ pub struct SimulationConfigSummary {
     pub initial_pool_a: u64,
     pub initial_pool_b: u64,
     pub fee_bps: u16,
+    /// Whether sandwiches were funded by flash loans
+    #[serde(default)]
+    pub flash_loans: bool,
+    /// Seed the trades were drawn with, if fixed
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 /// Summary statistics
@@ -60,6 +67,9 @@ pub struct SimulationSummary {
     pub attack_success_rate: f64,
     /// Total MEV extracted (lamports)
     pub total_mev_extracted: i64,
+    /// Total flash loan fees paid by the attacker (lamports)
+    #[serde(default)]
+    pub total_flash_loan_fees: u64,
     /// Total victim losses (lamports)
     pub total_victim_losses: u64,
     /// Average loss per attacked transaction (lamports)
@@ -92,8 +102,6 @@ pub struct Orchestrator {
     normal_traders: Vec<NormalTrader>,
     /// Protected traders (for commit-reveal scenario)
     protected_traders: Vec<ProtectedTrader>,
-    /// Simulated pool
-    pool: SimulatedPool,
     /// Current transaction counter
     transaction_counter: u32,
 }
@@ -101,18 +109,36 @@ pub struct Orchestrator {
 impl Orchestrator {
     /// Create a new orchestrator with the given configuration
     pub fn new(config: SimulationConfig) -> Self {
+        let victims = || (0..config.num_victims).map(|_| Keypair::new()).collect();
+        let (normal_keypairs, protected_keypairs) = (victims(), victims());
+        Self::with_keypairs(config, Keypair::new(), normal_keypairs, protected_keypairs)
+    }
+
+    /// Create an orchestrator whose bots trade from existing wallets
+    ///
+    /// There must be as many protected traders as normal traders: each trade
+    /// is made by the trader at the same index in both scenarios.
+    pub fn with_keypairs(
+        config: SimulationConfig,
+        attacker_keypair: Keypair,
+        normal_keypairs: Vec<Keypair>,
+        protected_keypairs: Vec<Keypair>,
+    ) -> Self {
+        assert_eq!(normal_keypairs.len(), protected_keypairs.len());
+
         // Create attacker
-        let attacker_keypair = Keypair::new();
-        let attacker = SandwichAttacker::new(
+        let mut attacker = SandwichAttacker::new(
             attacker_keypair,
             config.attacker_capital,
             config.attacker_capital, // Give attacker both tokens
         );
+        if config.flash_loans {
+            attacker = attacker.with_flash_loans(config.flash_loan_fee_bps);
+        }
 
         // Create normal traders
         let mut normal_traders = Vec::new();
-        for _ in 0..config.num_victims {
-            let keypair = Keypair::new();
+        for keypair in normal_keypairs {
             let trader = NormalTrader::new(
                 keypair,
                 config.victim_sol,
@@ -123,8 +149,7 @@ impl Orchestrator {
 
         // Create protected traders (same balances as normal traders)
         let mut protected_traders = Vec::new();
-        for _ in 0..config.num_victims {
-            let keypair = Keypair::new();
+        for keypair in protected_keypairs {
             let trader = ProtectedTrader::new(
                 keypair,
                 config.victim_sol,
@@ -133,97 +158,100 @@ impl Orchestrator {
             protected_traders.push(trader);
         }
 
-        // Create pool
-        let pool = SimulatedPool::new(
-            config.initial_pool_a,
-            config.initial_pool_b,
-            config.fee_bps,
-        );
-
         Self {
             config,
             attacker,
             normal_traders,
             protected_traders,
-            pool,
             transaction_counter: 0,
         }
     }
 
-    /// Run the complete simulation
+    /// Run the complete simulation on the constant-product math
     pub fn run(&mut self) -> Result<SimulationResults> {
+        let mut backend = MathBackend::new(&self.config);
+        self.run_with(&mut backend)
+    }
+
+    /// Run the complete simulation on `backend`
+    ///
+    /// Each scenario trades on its own pool, seeded with the same liquidity.
+    /// With a fixed [`SimulationConfig::seed`] every backend sees the same
+    /// trades, so their results can be compared.
+    pub fn run_with(&mut self, backend: &mut dyn ExecutionBackend) -> Result<SimulationResults> {
         info!("Starting MEV simulation...");
         info!("Total transactions: {}", self.config.total_transactions);
         info!("Attack probability: {:.0}%", self.config.attack_probability * 100.0);
 
+        let (capital_a, capital_b) = self.attacker.balances();
+        backend.add_trader(&self.attacker.pubkey(), Scenario::Normal, capital_a, capital_b)?;
+        for trader in &self.normal_traders {
+            let (balance_a, balance_b) = trader.balances();
+            backend.add_trader(&trader.pubkey(), Scenario::Normal, balance_a, balance_b)?;
+        }
+        for trader in &self.protected_traders {
+            let (balance_a, balance_b) = trader.balances();
+            backend.add_trader(&trader.pubkey(), Scenario::Protected, balance_a, balance_b)?;
+        }
+
         let mut normal_trades = Vec::new();
         let mut protected_trades = Vec::new();
         let mut sandwich_results = Vec::new();
         let mut pool_history = Vec::new();
 
-        let mut rng = rand::thread_rng();
+        let mut rng = match self.config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
 
         for i in 0..self.config.total_transactions {
             self.transaction_counter = i;
 
             // Generate random trade parameters
             let amount = random_trade_amount(
+                &mut rng,
                 self.config.min_swap_lamports,
                 self.config.max_swap_lamports,
             );
-            let a_to_b = random_direction();
+            let a_to_b = random_direction(&mut rng);
             let trader_idx = rng.gen_range(0..self.normal_traders.len());
 
             // Decide if attacker will attempt a sandwich
             let should_attack = rng.gen::<f64>() < self.config.attack_probability;
 
             // === SCENARIO A: Normal Trading (Vulnerable) ===
-            // Save pool state before normal scenario
-            let pool_state_before = self.pool.clone_state();
-
             let normal_trade = self.run_normal_scenario(
+                backend,
                 trader_idx,
                 amount,
                 a_to_b,
                 should_attack,
                 &mut sandwich_results,
-            );
+            )?;
 
             if let Some(trade) = normal_trade {
                 normal_trades.push(trade);
             }
 
             // Record pool state after normal scenario
-            pool_history.push(PoolStateRecord {
-                transaction_id: i,
-                reserve_a: self.pool.current().reserve_a,
-                reserve_b: self.pool.current().reserve_b,
-                price_a_in_b: self.pool.price_a_in_b(),
-                scenario: "normal".to_string(),
-            });
+            let state = backend.pool_state(Scenario::Normal)?;
+            pool_history.push(pool_record(i, &state, "normal"));
 
             // === SCENARIO B: Protected Trading (Commit-Reveal) ===
-            // Reset pool to same state as before normal scenario
-            self.pool.set_state(pool_state_before);
-
             let protected_trade = self.run_protected_scenario(
+                backend,
                 trader_idx,
                 amount,
                 a_to_b,
-            );
+            )?;
 
             if let Some(trade) = protected_trade {
                 protected_trades.push(trade);
             }
 
             // Record pool state after protected scenario
-            pool_history.push(PoolStateRecord {
-                transaction_id: i,
-                reserve_a: self.pool.current().reserve_a,
-                reserve_b: self.pool.current().reserve_b,
-                price_a_in_b: self.pool.price_a_in_b(),
-                scenario: "protected".to_string(),
-            });
+            let state = backend.pool_state(Scenario::Protected)?;
+            pool_history.push(pool_record(i, &state, "protected"));
 
             // Progress logging
             if (i + 1) % 100 == 0 || i == 0 {
@@ -231,6 +259,17 @@ impl Orchestrator {
             }
         }
 
+        Ok(self.results(normal_trades, protected_trades, sandwich_results, pool_history))
+    }
+
+    /// Summarize a finished run
+    fn results(
+        &self,
+        normal_trades: Vec<TradeResult>,
+        protected_trades: Vec<TradeResult>,
+        sandwich_results: Vec<SandwichResult>,
+        pool_history: Vec<PoolStateRecord>,
+    ) -> SimulationResults {
         // Calculate summary statistics
         let summary = self.calculate_summary(&normal_trades, &protected_trades, &sandwich_results);
 
@@ -242,7 +281,7 @@ impl Orchestrator {
               summary.total_victim_losses,
               summary.total_victim_losses as f64 / 1_000_000_000.0);
 
-        Ok(SimulationResults {
+        SimulationResults {
             config: SimulationConfigSummary {
                 total_transactions: self.config.total_transactions,
                 attack_probability: self.config.attack_probability,
@@ -251,102 +290,78 @@ impl Orchestrator {
                 initial_pool_a: self.config.initial_pool_a,
                 initial_pool_b: self.config.initial_pool_b,
                 fee_bps: self.config.fee_bps,
+                flash_loans: self.config.flash_loans,
+                seed: self.config.seed,
             },
             normal_trades,
             protected_trades,
             sandwich_results,
             summary,
             pool_history,
-        })
+        }
     }
 
     /// Run the normal (vulnerable) trading scenario
     fn run_normal_scenario(
         &mut self,
+        backend: &mut dyn ExecutionBackend,
         trader_idx: usize,
         amount: u64,
         a_to_b: bool,
         should_attack: bool,
         sandwich_results: &mut Vec<SandwichResult>,
-    ) -> Option<TradeResult> {
+    ) -> Result<Option<TradeResult>> {
         let trader = &mut self.normal_traders[trader_idx];
 
         // Check if trader has sufficient balance
         if !trader.can_trade(amount, a_to_b) {
             debug!("Trader {} has insufficient balance", trader_idx);
-            return None;
+            return Ok(None);
         }
 
         // Calculate expected output BEFORE any attack
-        let expected_out = trader.calculate_expected(amount, a_to_b, self.pool.current());
+        let pool = backend.pool_state(Scenario::Normal)?;
+        let expected_out = trader.calculate_expected(amount, a_to_b, &pool);
 
-        let was_attacked = if should_attack {
-            // Create pending swap that attacker can see
-            let pending = PendingSwap {
-                amount_in: amount,
-                a_to_b,
-                victim: trader.pubkey(),
-                min_out: 0, // Normal trades often don't set this properly
-            };
-
-            // Attacker executes sandwich
-            let sandwich = self.attacker.execute_sandwich(&pending, self.pool.current_mut());
-            
-            let attacked = sandwich.success;
-            sandwich_results.push(sandwich);
-            attacked
-        } else {
+        if !should_attack {
             // No attack, just execute victim's trade
-            let _ = self.pool.current_mut().apply_swap(amount, a_to_b);
-            false
-        };
+            return trader.execute_trade(backend, amount, 0, a_to_b, expected_out, false).map(Some);
+        }
 
-        // Now the victim's trade has already been executed as part of the sandwich
-        // We just need to record the result
-        Some(TradeResult {
-            signature: format!("normal_trade_{}", self.transaction_counter),
-            trader: trader.pubkey().to_string(),
+        // Create pending swap that attacker can see
+        let pending = PendingSwap {
             amount_in: amount,
             a_to_b,
-            expected_out,
-            actual_out: if was_attacked {
-                // The actual output was calculated during sandwich
-                let last_sandwich = sandwich_results.last().unwrap();
-                expected_out.saturating_sub(last_sandwich.victim_loss_lamports)
-            } else {
-                expected_out
-            },
-            slippage_loss: if was_attacked {
-                sandwich_results.last().unwrap().victim_loss_lamports
-            } else {
-                0
-            },
-            was_attacked,
-            fee_paid: (amount as u128 * self.config.fee_bps as u128 / 10000) as u64,
-            price_impact_bps: 0,
-            timestamp: chrono::Utc::now().timestamp(),
-        })
+            victim: trader.pubkey(),
+            min_out: 0, // Normal trades often don't set this properly
+        };
+
+        // Attacker executes sandwich around the victim's trade
+        let (sandwich, trade) = self.attacker.execute_sandwich(backend, &pending, trader, expected_out)?;
+        sandwich_results.push(sandwich);
+        Ok(Some(trade))
     }
 
     /// Run the protected (commit-reveal) trading scenario
     fn run_protected_scenario(
         &mut self,
+        backend: &mut dyn ExecutionBackend,
         trader_idx: usize,
         amount: u64,
         a_to_b: bool,
-    ) -> Option<TradeResult> {
+    ) -> Result<Option<TradeResult>> {
         let trader = &mut self.protected_traders[trader_idx];
 
         // Execute protected trade
         let result = trader.execute_protected_trade(
+            backend,
             amount,
             a_to_b,
-            self.pool.current_mut(),
             100, // 1% slippage tolerance
         )?;
 
         // Return the underlying trade result
-        Some(result.trade)
+        Ok(result.map(|result| result.trade))
     }
 
     /// Calculate summary statistics
@@ -376,6 +391,10 @@ impl Orchestrator {
             .map(|s| s.victim_loss_lamports)
             .sum();
 
+        let total_flash_loan_fees: u64 = sandwich_results.iter()
+            .map(|s| s.flash_loan_fee)
+            .sum();
+
         let avg_loss_per_attack = if successful_attacks > 0 {
             total_victim_losses as f64 / successful_attacks as f64
         } else {
@@ -401,6 +420,7 @@ impl Orchestrator {
             successful_attacks,
             attack_success_rate,
             total_mev_extracted,
+            total_flash_loan_fees,
             total_victim_losses,
             avg_loss_per_attack,
             total_protected_savings,
@@ -424,11 +444,21 @@ impl Orchestrator {
             trader.reset(self.config.victim_sol, self.config.victim_secusol);
         }
 
-        self.pool.reset();
         self.transaction_counter = 0;
     }
 }
 
+/// Pool state record for the history
+fn pool_record(transaction_id: u32, state: &PoolState, scenario: &str) -> PoolStateRecord {
+    PoolStateRecord {
+        transaction_id,
+        reserve_a: state.reserve_a,
+        reserve_b: state.reserve_b,
+        price_a_in_b: state.price_a_in_b(),
+        scenario: scenario.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -450,5 +480,48 @@ mod tests {
         println!("Victim losses: {} lamports", results.summary.total_victim_losses);
         println!("Attack success rate: {:.1}%", results.summary.attack_success_rate);
     }
-}
 
+    #[test]
+    fn test_orchestrator_flash_loan_run() {
+        let config = SimulationConfig {
+            total_transactions: 10,
+            attack_probability: 1.0,
+            attacker_capital: 0,
+            flash_loans: true,
+            ..SimulationConfig::quick_test()
+        };
+
+        let mut orchestrator = Orchestrator::new(config);
+        let results = orchestrator.run().unwrap();
+
+        assert!(results.config.flash_loans);
+        assert_eq!(results.sandwich_results.len(), 10);
+        if results.summary.successful_attacks > 0 {
+            assert!(results.summary.total_flash_loan_fees > 0);
+        }
+        // The summary says the mode can't happen on chain
+        let summary = crate::analytics::logger::format_summary(&results);
+        assert!(summary.contains(crate::config::FLASH_LOAN_CAVEAT));
+    }
+
+    #[test]
+    fn test_seeded_runs_match() {
+        let config = SimulationConfig {
+            total_transactions: 20,
+            seed: Some(7),
+            ..SimulationConfig::quick_test()
+        };
+
+        let first = Orchestrator::new(config.clone()).run().unwrap();
+        let second = Orchestrator::new(config).run().unwrap();
+
+        let outputs = |results: &SimulationResults| -> Vec<(u64, u64)> {
+            results.normal_trades.iter()
+                .chain(&results.protected_trades)
+                .map(|trade| (trade.amount_in, trade.actual_out))
+                .collect()
+        };
+        assert_eq!(outputs(&first), outputs(&second));
+        assert_eq!(first.summary.total_mev_extracted, second.summary.total_mev_extracted);
+    }
+}
@@ -1,8 +1,9 @@
 //! AMM Math Utilities
 //!
-//! Implements constant-product (x * y = k) AMM calculations
-//! matching the on-chain AMM program logic.
+//! Implements constant-product (x * y = k) AMM calculations on top of the
+//! `securelp-math` curve the on-chain AMM program uses.
 
+use securelp_math::curve;
 use serde::{Deserialize, Serialize};
 
 /// Represents the current state of an AMM pool
@@ -59,7 +60,7 @@ impl PoolState {
 
     /// Calculate the constant product k
     pub fn k(&self) -> u128 {
-        (self.reserve_a as u128) * (self.reserve_b as u128)
+        curve::k(self.reserve_a, self.reserve_b)
     }
 
     /// Calculate the current price of A in terms of B
@@ -88,22 +89,19 @@ impl PoolState {
             (self.reserve_b, self.reserve_a)
         };
 
-        // Calculate fee
-        let fee = ((amount_in as u128) * (self.fee_bps as u128) / 10000) as u64;
-        let amount_in_after_fee = amount_in.saturating_sub(fee);
-
-        if reserve_in == 0 || reserve_out == 0 {
+        // Same curve and fee rounding as the on-chain AMM; it fails on an
+        // empty pool, which the simulation treats as a swap that pays nothing
+        let Some(output) = curve::swap_output(amount_in, reserve_in, reserve_out, self.fee_bps)
+        else {
             return SwapResult {
                 amount_out: 0,
-                fee,
+                fee: 0,
                 price_impact_bps: 10000, // 100% impact if no liquidity
             };
-        }
-
-        // Constant product formula
-        let numerator = (amount_in_after_fee as u128) * (reserve_out as u128);
-        let denominator = (reserve_in as u128) + (amount_in_after_fee as u128);
-        let amount_out = (numerator / denominator) as u64;
+        };
+        let amount_out = output.amount_out;
+        let fee = output.fee;
+        let amount_in_after_fee = amount_in - fee;
 
         // Calculate price impact
         // Ideal output (no impact) = amount_in_after_fee * (reserve_out / reserve_in)
@@ -220,9 +218,37 @@ impl PoolState {
     }
 }
 
+/// Fee owed on a flash loan, rounded up like the on-chain AMM
+pub fn flash_loan_fee(amount: u64, fee_bps: u16) -> u64 {
+    curve::flash_loan_fee(amount, fee_bps).unwrap_or(u64::MAX)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_swap_matches_on_chain_curve(
+            reserve_a in 1..1u64 << 60,
+            reserve_b in 1..1u64 << 60,
+            amount_in in 0..1u64 << 60,
+            fee_bps in 0u16..=1000,
+            a_to_b: bool,
+        ) {
+            let pool = PoolState::new(reserve_a, reserve_b, fee_bps);
+            let (reserve_in, reserve_out) = if a_to_b {
+                (reserve_a, reserve_b)
+            } else {
+                (reserve_b, reserve_a)
+            };
+
+            let expected = curve::swap_output(amount_in, reserve_in, reserve_out, fee_bps).unwrap();
+            let result = pool.calculate_swap_output(amount_in, a_to_b);
+            prop_assert_eq!((result.amount_out, result.fee), (expected.amount_out, expected.fee));
+        }
+    }
 
     #[test]
     fn test_swap_calculation() {
@@ -263,5 +289,12 @@ mod tests {
         println!("Expected profit: {} lamports", sandwich.expected_profit);
         println!("Victim loss: {} lamports", sandwich.victim_loss);
     }
+
+    #[test]
+    fn test_flash_loan_fee_rounds_up() {
+        assert_eq!(flash_loan_fee(10_000, 9), 9);
+        assert_eq!(flash_loan_fee(10_001, 9), 10);
+        assert_eq!(flash_loan_fee(0, 9), 0);
+    }
 }
 
@@ -1,10 +1,11 @@
 //! Hash Utilities for Commit-Reveal
 //!
 //! Implements SHA256 hashing that matches the on-chain program's
-//! Borsh serialization format.
+//! Borsh serialization format, via the `securelp-math` commitment helpers
+//! the program itself uses.
 
+use securelp_math::commitment;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 
 /// Swap details that are hashed for the commit-reveal scheme
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,35 +48,25 @@ impl SwapDetails {
     /// Layout: amount_in (u64 LE) + min_out (u64 LE) + slippage_bps (u16 LE) + nonce ([u8; 32])
     /// Total: 8 + 8 + 2 + 32 = 50 bytes
     pub fn serialize(&self) -> Vec<u8> {
-        let mut bytes = Vec::with_capacity(50);
-        
-        // amount_in: u64 little-endian
-        bytes.extend_from_slice(&self.amount_in.to_le_bytes());
-        
-        // min_out: u64 little-endian
-        bytes.extend_from_slice(&self.min_out.to_le_bytes());
-        
-        // slippage_bps: u16 little-endian
-        bytes.extend_from_slice(&self.slippage_bps.to_le_bytes());
-        
-        // nonce: [u8; 32]
-        bytes.extend_from_slice(&self.nonce);
-        
-        bytes
+        commitment::serialize_swap_details(
+            self.amount_in,
+            self.min_out,
+            self.slippage_bps,
+            &self.nonce,
+        )
+        .to_vec()
     }
 }
 
 /// Hash swap details using SHA256
 /// Returns a 32-byte hash matching the on-chain commitment
 pub fn hash_swap_details(details: &SwapDetails) -> [u8; 32] {
-    let serialized = details.serialize();
-    let mut hasher = Sha256::new();
-    hasher.update(&serialized);
-    let result = hasher.finalize();
-    
-    let mut hash = [0u8; 32];
-    hash.copy_from_slice(&result);
-    hash
+    commitment::hash_swap_details(
+        details.amount_in,
+        details.min_out,
+        details.slippage_bps,
+        &details.nonce,
+    )
 }
 
 /// Convert hash to hex string for display
@@ -95,6 +86,23 @@ pub fn generate_nonce() -> [u8; 32] {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_hash_matches_on_chain_commitment(
+            amount_in: u64,
+            min_out: u64,
+            slippage_bps: u16,
+            nonce: [u8; 32],
+        ) {
+            let details = SwapDetails::with_nonce(amount_in, min_out, slippage_bps, nonce);
+            prop_assert_eq!(
+                hash_swap_details(&details),
+                commitment::hash_swap_details(amount_in, min_out, slippage_bps, &nonce)
+            );
+        }
+    }
 
     #[test]
     fn test_serialization_length() {
@@ -2,6 +2,7 @@
 //!
 //! Handles creation and funding of test keypairs for the simulation.
 
+use anchor_lang::solana_program::system_instruction;
 use anyhow::{Context, Result};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
@@ -9,7 +10,6 @@ use solana_sdk::{
     native_token::LAMPORTS_PER_SOL,
     pubkey::Pubkey,
     signature::{Keypair, Signer},
-    system_instruction,
     transaction::Transaction,
 };
 use std::collections::HashMap;
@@ -0,0 +1,29 @@
+//! Commit-reveal tests across the securelp, stake_pool and amm programs, and
+//! the fixtures shared by every program-test suite in the workspace.
+//!
+//! The tests in `tests/` load the SBF builds of all three programs into
+//! `solana-program-test`, so they run in-process with no validator or network.
+//! Build the programs first, then enable the `test-sbf` feature:
+//!
+//! ```bash
+//! anchor build
+//! cargo test -p securelp-integration-tests --features test-sbf
+//! ```
+//!
+//! The programs are loaded from `SBF_OUT_DIR`, or from the workspace's
+//! `target/deploy` when it is unset.
+//!
+//! The library builds the mints, token accounts and pools those tests, the
+//! programs' own SBF tests and mev-sim's on-chain backends start from:
+//! [`pools`] and [`token`] return plain instructions for any client to send,
+//! and [`program_test`] sends them on a program-test context.
+
+pub mod pools;
+pub mod program_test;
+pub mod token;
+
+pub use pools::{AmmPoolAccounts, StakePoolAccounts};
+pub use program_test::{
+    assert_error, create_amm_pool, create_mint, create_stake_pool, create_token_account, send,
+    simulate, token_balance,
+};
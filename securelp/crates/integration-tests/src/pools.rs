@@ -0,0 +1,236 @@
+//! Addresses and instructions of the pools the fixtures create
+
+use amm::state::{AMM_AUTHORITY_SEED, AMM_POOL_SEED, VAULT_A_SEED, VAULT_B_SEED};
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, sysvar};
+use stake_pool::state::{POOL_AUTHORITY_SEED, POOL_CONFIG_SEED, RESERVE_VAULT_SEED, VALIDATOR_LIST_SEED};
+
+/// An AMM pool and one user's token accounts on it
+///
+/// The pool's authority is the user who initializes it. The user fields are
+/// left default until the accounts exist; set them with struct update syntax
+/// to act as another user on the same pool.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AmmPoolAccounts {
+    pub program_id: Pubkey,
+    pub address: Pubkey,
+    pub authority: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub token_a_vault: Pubkey,
+    pub token_b_vault: Pubkey,
+    pub lp_mint: Pubkey,
+    pub user: Pubkey,
+    pub user_token_a: Pubkey,
+    pub user_token_b: Pubkey,
+    pub user_lp: Pubkey,
+}
+
+impl AmmPoolAccounts {
+    /// Derive the addresses of the pool between `token_a_mint` and
+    /// `token_b_mint` under the amm program at `program_id`
+    pub fn new(program_id: Pubkey, token_a_mint: Pubkey, token_b_mint: Pubkey, lp_mint: Pubkey) -> Self {
+        let address = Pubkey::find_program_address(
+            &[AMM_POOL_SEED, token_a_mint.as_ref(), token_b_mint.as_ref()],
+            &program_id,
+        )
+        .0;
+        Self {
+            program_id,
+            address,
+            authority: Pubkey::find_program_address(&[AMM_AUTHORITY_SEED, address.as_ref()], &program_id).0,
+            token_a_mint,
+            token_b_mint,
+            token_a_vault: Pubkey::find_program_address(&[VAULT_A_SEED, address.as_ref()], &program_id).0,
+            token_b_vault: Pubkey::find_program_address(&[VAULT_B_SEED, address.as_ref()], &program_id).0,
+            lp_mint,
+            ..Self::default()
+        }
+    }
+
+    /// Initialize the pool with the user as its authority. The LP mint must
+    /// sign.
+    pub fn initialize_ix(&self, fee_bps: u16) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts: amm::accounts::InitializePool {
+                authority: self.user,
+                token_a_mint: self.token_a_mint,
+                token_b_mint: self.token_b_mint,
+                pool: self.address,
+                pool_authority: self.authority,
+                token_a_vault: self.token_a_vault,
+                token_b_vault: self.token_b_vault,
+                lp_mint: self.lp_mint,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            }
+            .to_account_metas(None),
+            data: amm::instruction::InitializePool { fee_bps }.data(),
+        }
+    }
+
+    pub fn add_liquidity_ix(&self, amount_a: u64, amount_b: u64, min_lp_out: u64) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts: amm::accounts::AddLiquidity {
+                user: self.user,
+                pool: self.address,
+                pool_authority: self.authority,
+                token_a_vault: self.token_a_vault,
+                token_b_vault: self.token_b_vault,
+                lp_mint: self.lp_mint,
+                user_token_a: self.user_token_a,
+                user_token_b: self.user_token_b,
+                user_lp_account: self.user_lp,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: amm::instruction::AddLiquidity { amount_a, amount_b, min_lp_out }.data(),
+        }
+    }
+
+    pub fn remove_liquidity_ix(&self, lp_amount: u64, min_a_out: u64, min_b_out: u64) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts: amm::accounts::RemoveLiquidity {
+                user: self.user,
+                pool: self.address,
+                pool_authority: self.authority,
+                token_a_vault: self.token_a_vault,
+                token_b_vault: self.token_b_vault,
+                lp_mint: self.lp_mint,
+                user_token_a: self.user_token_a,
+                user_token_b: self.user_token_b,
+                user_lp_account: self.user_lp,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: amm::instruction::RemoveLiquidity { lp_amount, min_a_out, min_b_out }.data(),
+        }
+    }
+
+    pub fn swap_ix(&self, amount_in: u64, min_amount_out: u64, a_to_b: bool) -> Instruction {
+        let (user_token_in, user_token_out) = if a_to_b {
+            (self.user_token_a, self.user_token_b)
+        } else {
+            (self.user_token_b, self.user_token_a)
+        };
+        Instruction {
+            program_id: self.program_id,
+            accounts: amm::accounts::Swap {
+                user: self.user,
+                pool: self.address,
+                pool_authority: self.authority,
+                token_a_vault: self.token_a_vault,
+                token_b_vault: self.token_b_vault,
+                user_token_in,
+                user_token_out,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: amm::instruction::Swap { amount_in, min_amount_out, a_to_b }.data(),
+        }
+    }
+}
+
+/// The stake pool and one user's slpSOL account
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StakePoolAccounts {
+    pub config: Pubkey,
+    pub authority: Pubkey,
+    pub reserve_vault: Pubkey,
+    pub slp_mint: Pubkey,
+    pub validator_list: Pubkey,
+    pub user: Pubkey,
+    pub user_slp: Pubkey,
+}
+
+impl StakePoolAccounts {
+    /// Derive the stake pool's addresses, with `user` as its admin
+    pub fn new(slp_mint: Pubkey, user: Pubkey) -> Self {
+        let config = Pubkey::find_program_address(&[POOL_CONFIG_SEED], &stake_pool::ID).0;
+        Self {
+            config,
+            authority: Pubkey::find_program_address(&[POOL_AUTHORITY_SEED, config.as_ref()], &stake_pool::ID).0,
+            reserve_vault: Pubkey::find_program_address(&[RESERVE_VAULT_SEED, config.as_ref()], &stake_pool::ID).0,
+            slp_mint,
+            validator_list: Pubkey::find_program_address(&[VALIDATOR_LIST_SEED, config.as_ref()], &stake_pool::ID).0,
+            user,
+            user_slp: Pubkey::default(),
+        }
+    }
+
+    /// Initialize the pool and its validator list. The slpSOL mint must sign.
+    pub fn initialize_ixs(&self, fee_bps: u16) -> [Instruction; 2] {
+        let initialize = Instruction {
+            program_id: stake_pool::ID,
+            accounts: stake_pool::accounts::InitializePool {
+                admin: self.user,
+                pool_config: self.config,
+                pool_authority: self.authority,
+                reserve_vault: self.reserve_vault,
+                slp_mint: self.slp_mint,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            }
+            .to_account_metas(None),
+            data: stake_pool::instruction::InitializePool { fee_bps }.data(),
+        };
+        let initialize_list = Instruction {
+            program_id: stake_pool::ID,
+            accounts: stake_pool::accounts::InitializeValidatorList {
+                admin: self.user,
+                pool_config: self.config,
+                validator_list: self.validator_list,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: stake_pool::instruction::InitializeValidatorList {}.data(),
+        };
+        [initialize, initialize_list]
+    }
+
+    pub fn deposit_sol_ix(&self, amount_lamports: u64) -> Instruction {
+        Instruction {
+            program_id: stake_pool::ID,
+            accounts: stake_pool::accounts::DepositSol {
+                user: self.user,
+                pool_config: self.config,
+                pool_authority: self.authority,
+                reserve_vault: self.reserve_vault,
+                slp_mint: self.slp_mint,
+                user_slp_account: self.user_slp,
+                fee_recipient: None,
+                referrer: None,
+                user_stake: None,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: stake_pool::instruction::DepositSol { amount_lamports }.data(),
+        }
+    }
+
+    pub fn withdraw_sol_ix(&self, slp_amount: u64) -> Instruction {
+        Instruction {
+            program_id: stake_pool::ID,
+            accounts: stake_pool::accounts::WithdrawSol {
+                user: self.user,
+                pool_config: self.config,
+                reserve_vault: self.reserve_vault,
+                slp_mint: self.slp_mint,
+                user_slp_account: self.user_slp,
+                fee_recipient: None,
+                user_stake: None,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: stake_pool::instruction::WithdrawSol { slp_amount }.data(),
+        }
+    }
+}
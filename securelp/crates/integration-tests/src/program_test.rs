@@ -0,0 +1,132 @@
+//! Fixtures on a `solana-program-test` context, with the payer as the user
+
+use crate::pools::{AmmPoolAccounts, StakePoolAccounts};
+use crate::token::{create_mint_ixs, create_token_account_ixs};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::AnchorDeserialize;
+use anchor_spl::token::spl_token;
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+/// Sign with the payer and `signers` and process the transaction
+pub async fn send(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Simulate `instruction`, signed by the payer, and decode its return data
+pub async fn simulate<T: AnchorDeserialize>(context: &mut ProgramTestContext, instruction: Instruction) -> T {
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+    let simulation = context.banks_client.simulate_transaction(tx).await.unwrap();
+    simulation.result.expect("simulation result").unwrap();
+    let return_data = simulation
+        .simulation_details
+        .and_then(|details| details.return_data)
+        .expect("return data");
+    T::deserialize(&mut return_data.data.as_slice()).unwrap()
+}
+
+/// Assert the transaction failed with the given Anchor error code
+pub fn assert_error(result: Result<(), BanksClientError>, code: u32) {
+    match result.expect_err("transaction should fail").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(actual)) => {
+            assert_eq!(actual, code)
+        }
+        other => panic!("expected custom error {code}, got {other:?}"),
+    }
+}
+
+pub async fn token_balance(context: &mut ProgramTestContext, account: &Pubkey) -> u64 {
+    let account = context.banks_client.get_account(*account).await.unwrap().unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+/// Create a mint with the payer as its mint authority
+pub async fn create_mint(context: &mut ProgramTestContext) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = create_mint_ixs(
+        &context.payer.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+    );
+    send(context, &instructions, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+/// Create a token account for `mint` owned by `owner`, holding `amount`
+/// freshly minted tokens if the payer is the mint authority
+pub async fn create_token_account(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = create_token_account_ixs(
+        &context.payer.pubkey(),
+        &account.pubkey(),
+        mint,
+        owner,
+        amount,
+        rent.minimum_balance(spl_token::state::Account::LEN),
+    );
+    send(context, &instructions, &[&account]).await.unwrap();
+    account.pubkey()
+}
+
+/// Create two mints and an AMM pool between them, and give the payer
+/// `balance` of each token and an empty LP token account. The pool has no
+/// liquidity yet.
+pub async fn create_amm_pool(context: &mut ProgramTestContext, fee_bps: u16, balance: u64) -> AmmPoolAccounts {
+    let payer = context.payer.pubkey();
+    let token_a_mint = create_mint(context).await;
+    let token_b_mint = create_mint(context).await;
+    let lp_mint = Keypair::new();
+    let mut pool = AmmPoolAccounts {
+        user: payer,
+        ..AmmPoolAccounts::new(amm::ID, token_a_mint, token_b_mint, lp_mint.pubkey())
+    };
+    send(context, &[pool.initialize_ix(fee_bps)], &[&lp_mint]).await.unwrap();
+
+    pool.user_token_a = create_token_account(context, &token_a_mint, &payer, balance).await;
+    pool.user_token_b = create_token_account(context, &token_b_mint, &payer, balance).await;
+    pool.user_lp = create_token_account(context, &pool.lp_mint, &payer, 0).await;
+    pool
+}
+
+/// Initialize the stake pool and its validator list with the payer as
+/// admin, and give the payer an empty slpSOL account
+pub async fn create_stake_pool(context: &mut ProgramTestContext, fee_bps: u16) -> StakePoolAccounts {
+    let payer = context.payer.pubkey();
+    let slp_mint = Keypair::new();
+    let mut pool = StakePoolAccounts::new(slp_mint.pubkey(), payer);
+    send(context, &pool.initialize_ixs(fee_bps), &[&slp_mint]).await.unwrap();
+
+    pool.user_slp = create_token_account(context, &pool.slp_mint, &payer, 0).await;
+    pool
+}
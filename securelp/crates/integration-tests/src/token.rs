@@ -0,0 +1,50 @@
+//! SPL token mints and accounts for the fixtures
+
+use anchor_lang::solana_program::{program_pack::Pack, system_instruction};
+use anchor_spl::token::spl_token;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+/// Decimals of every mint the fixtures create
+pub const DECIMALS: u8 = 9;
+
+/// Create `mint` with `payer` as its mint authority. `rent` is the
+/// rent-exempt balance of a mint.
+pub fn create_mint_ixs(payer: &Pubkey, mint: &Pubkey, rent: u64) -> Vec<Instruction> {
+    vec![
+        system_instruction::create_account(
+            payer,
+            mint,
+            rent,
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(&spl_token::ID, mint, payer, None, DECIMALS).unwrap(),
+    ]
+}
+
+/// Create `account` for `mint` owned by `owner`, holding `amount` freshly
+/// minted tokens if `payer` is the mint authority. `rent` is the rent-exempt
+/// balance of a token account.
+pub fn create_token_account_ixs(
+    payer: &Pubkey,
+    account: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    rent: u64,
+) -> Vec<Instruction> {
+    let mut instructions = vec![
+        system_instruction::create_account(
+            payer,
+            account,
+            rent,
+            spl_token::state::Account::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_account3(&spl_token::ID, account, mint, owner).unwrap(),
+    ];
+    if amount > 0 {
+        instructions.push(spl_token::instruction::mint_to(&spl_token::ID, mint, account, payer, &[], amount).unwrap());
+    }
+    instructions
+}
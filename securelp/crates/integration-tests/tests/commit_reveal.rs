@@ -0,0 +1,186 @@
+//! Commit -> reveal -> CPI flows through securelp into stake_pool and amm.
+//! Needs the SBF builds of all three programs: `anchor build`, then
+//! `cargo test -p securelp-integration-tests --features test-sbf`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use amm::errors::AmmError;
+use common::*;
+use securelp::errors::SecureLPError;
+use securelp::state::config::{MAX_DELAY_SECONDS, MIN_DELAY_SECONDS};
+use stake_pool::errors::StakePoolError;
+
+#[tokio::test]
+async fn reveal_and_stake_deposits_through_the_stake_pool() {
+    let mut env = setup().await;
+    let user_slp = env.stake_pool.user_slp;
+    let slp_before = env.token_balance(&user_slp).await;
+
+    let details = details(LAMPORTS_PER_SOL, 0, 1);
+    let commit = env.commit_ix(&details, true);
+    env.send(&[commit]).await.unwrap();
+    env.advance_clock(MIN_DELAY_SECONDS).await;
+    let reveal = env.reveal_and_stake_ix(details);
+    env.send(&[reveal]).await.unwrap();
+
+    assert!(env.token_balance(&user_slp).await > slp_before);
+    assert!(!env.commitment_exists().await);
+}
+
+#[tokio::test]
+async fn reveal_and_unstake_withdraws_through_the_stake_pool() {
+    let mut env = setup().await;
+    let user_slp = env.stake_pool.user_slp;
+    let slp_before = env.token_balance(&user_slp).await;
+
+    let details = details(LAMPORTS_PER_SOL, 0, 1);
+    let commit = env.commit_ix(&details, false);
+    env.send(&[commit]).await.unwrap();
+    env.advance_clock(MIN_DELAY_SECONDS).await;
+    let reveal = env.reveal_and_unstake_ix(details);
+    env.send(&[reveal]).await.unwrap();
+
+    assert_eq!(env.token_balance(&user_slp).await, slp_before - LAMPORTS_PER_SOL);
+    assert!(!env.commitment_exists().await);
+}
+
+#[tokio::test]
+async fn reveal_and_swap_trades_through_the_amm() {
+    let mut env = setup().await;
+    let (user_token_a, user_token_b) = (env.amm_pool.user_token_a, env.amm_pool.user_token_b);
+    let a_before = env.token_balance(&user_token_a).await;
+    let b_before = env.token_balance(&user_token_b).await;
+
+    let min_out = LAMPORTS_PER_SOL * 98 / 100;
+    let details = details(LAMPORTS_PER_SOL, min_out, 1);
+    let commit = env.commit_ix(&details, true);
+    env.send(&[commit]).await.unwrap();
+    env.advance_clock(MIN_DELAY_SECONDS).await;
+    let reveal = env.reveal_and_swap_ix(&env.amm_pool, details);
+    env.send(&[reveal]).await.unwrap();
+
+    assert_eq!(env.token_balance(&user_token_a).await, a_before - LAMPORTS_PER_SOL);
+    assert!(env.token_balance(&user_token_b).await >= b_before + min_out);
+    assert!(!env.commitment_exists().await);
+}
+
+#[tokio::test]
+async fn reveal_before_the_delay_fails() {
+    let mut env = setup().await;
+
+    // Committing and revealing in one transaction sees a single timestamp
+    let details = details(LAMPORTS_PER_SOL, 0, 1);
+    let commit = env.commit_ix(&details, true);
+    let reveal = env.reveal_and_stake_ix(details);
+    assert_error(env.send(&[commit, reveal]).await, SecureLPError::DelayNotMet.into());
+    assert!(!env.commitment_exists().await);
+}
+
+#[tokio::test]
+async fn reveal_with_altered_details_fails() {
+    let mut env = setup().await;
+
+    let committed = details(LAMPORTS_PER_SOL, LAMPORTS_PER_SOL * 98 / 100, 1);
+    let commit = env.commit_ix(&committed, true);
+    env.send(&[commit]).await.unwrap();
+    env.advance_clock(MIN_DELAY_SECONDS).await;
+
+    // A front-runner who loosens the minimum output can't reuse the commitment
+    let loosened = details(LAMPORTS_PER_SOL, 0, 1);
+    let reveal = env.reveal_and_swap_ix(&env.amm_pool, loosened);
+    assert_error(env.send(&[reveal]).await, SecureLPError::HashMismatch.into());
+
+    let reveal = env.reveal_and_swap_ix(&env.amm_pool, committed);
+    env.send(&[reveal]).await.unwrap();
+}
+
+#[tokio::test]
+async fn reveal_against_the_wrong_pool_fails() {
+    let mut env = setup().await;
+
+    let details = details(LAMPORTS_PER_SOL, 0, 1);
+    let commit = env.commit_ix(&details, true);
+    env.send(&[commit]).await.unwrap();
+    env.advance_clock(MIN_DELAY_SECONDS).await;
+
+    // Another pool's state can't be paired with this pool's vaults
+    let wrong_pool = AmmPoolAccounts {
+        address: env.other_amm_pool.address,
+        authority: env.other_amm_pool.authority,
+        ..env.amm_pool
+    };
+    let reveal = env.reveal_and_swap_ix(&wrong_pool, details.clone());
+    assert_error(env.send(&[reveal]).await, SecureLPError::InvalidMint.into());
+
+    // Nor can tokens of this pool be swapped in the other one
+    let mismatched_tokens = AmmPoolAccounts {
+        user_token_a: env.amm_pool.user_token_a,
+        user_token_b: env.amm_pool.user_token_b,
+        ..env.other_amm_pool
+    };
+    let reveal = env.reveal_and_swap_ix(&mismatched_tokens, details);
+    assert!(env.send(&[reveal]).await.is_err());
+    assert!(env.commitment_exists().await);
+}
+
+#[tokio::test]
+async fn reveal_into_a_paused_stake_pool_fails() {
+    let mut env = setup().await;
+
+    let details = details(LAMPORTS_PER_SOL, 0, 1);
+    let commit = env.commit_ix(&details, true);
+    let pause = env.pause_stake_pool_ix();
+    env.send(&[commit, pause]).await.unwrap();
+    env.advance_clock(MIN_DELAY_SECONDS).await;
+
+    let reveal = env.reveal_and_stake_ix(details);
+    assert_error(env.send(&[reveal]).await, StakePoolError::PoolPaused.into());
+    assert!(env.commitment_exists().await);
+}
+
+#[tokio::test]
+async fn reveal_into_a_paused_amm_pool_fails() {
+    let mut env = setup().await;
+
+    let details = details(LAMPORTS_PER_SOL, 0, 1);
+    let commit = env.commit_ix(&details, true);
+    let pause = env.pause_amm_pool_ix(&env.amm_pool);
+    env.send(&[commit, pause]).await.unwrap();
+    env.advance_clock(MIN_DELAY_SECONDS).await;
+
+    let reveal = env.reveal_and_swap_ix(&env.amm_pool, details);
+    assert_error(env.send(&[reveal]).await, AmmError::PoolPaused.into());
+    assert!(env.commitment_exists().await);
+}
+
+#[tokio::test]
+async fn reveal_at_the_end_of_the_window_succeeds() {
+    let mut env = setup().await;
+
+    let details = details(LAMPORTS_PER_SOL, 0, 1);
+    let commit = env.commit_ix(&details, true);
+    env.send(&[commit]).await.unwrap();
+    env.advance_clock(MAX_DELAY_SECONDS).await;
+
+    let reveal = env.reveal_and_stake_ix(details);
+    env.send(&[reveal]).await.unwrap();
+    assert!(!env.commitment_exists().await);
+}
+
+#[tokio::test]
+async fn expired_commitment_can_only_be_cancelled() {
+    let mut env = setup().await;
+
+    let details = details(LAMPORTS_PER_SOL, 0, 1);
+    let commit = env.commit_ix(&details, true);
+    env.send(&[commit]).await.unwrap();
+    env.advance_clock(MAX_DELAY_SECONDS + 1).await;
+
+    let reveal = env.reveal_and_stake_ix(details);
+    assert_error(env.send(&[reveal]).await, SecureLPError::CommitmentExpired.into());
+
+    let cancel = env.cancel_commitment_ix();
+    env.send(&[cancel]).await.unwrap();
+    assert!(!env.commitment_exists().await);
+}
@@ -0,0 +1,228 @@
+//! Shared program-test setup for the commit-reveal tests: a stake pool and two
+//! AMM pools, with the payer as the trader.
+#![allow(dead_code)]
+
+use anchor_lang::prelude::{Clock, Pubkey};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use securelp::state::{Commitment, SwapDetails};
+pub use securelp_integration_tests::{assert_error, AmmPoolAccounts, StakePoolAccounts};
+use securelp_integration_tests::{create_amm_pool, create_stake_pool, send};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{instruction::Instruction, signature::Signer};
+use solana_sdk_ids::system_program;
+use std::sync::Once;
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Tokens of each mint the payer starts with
+pub const TOKEN_BALANCE: u64 = 1_000_000 * LAMPORTS_PER_SOL;
+
+/// Liquidity each AMM pool is seeded with, per side
+pub const POOL_LIQUIDITY: u64 = 10_000 * LAMPORTS_PER_SOL;
+
+pub struct Env {
+    pub context: ProgramTestContext,
+    pub stake_pool: StakePoolAccounts,
+    pub amm_pool: AmmPoolAccounts,
+    /// A second pool over different mints, for wrong-pool reveals
+    pub other_amm_pool: AmmPoolAccounts,
+}
+
+impl Env {
+    pub fn user(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    pub fn commitment(&self) -> Pubkey {
+        Pubkey::find_program_address(&[Commitment::SEED_PREFIX, self.user().as_ref()], &securelp::ID).0
+    }
+
+    pub async fn send(&mut self, instructions: &[Instruction]) -> Result<(), BanksClientError> {
+        send(&mut self.context, instructions, &[]).await
+    }
+
+    /// Move the clock forward without advancing slots
+    pub async fn advance_clock(&mut self, seconds: i64) {
+        let mut clock = self.context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    pub async fn commitment_exists(&mut self) -> bool {
+        let commitment = self.commitment();
+        self.context.banks_client.get_account(commitment).await.unwrap().is_some()
+    }
+
+    pub async fn token_balance(&mut self, account: &Pubkey) -> u64 {
+        securelp_integration_tests::token_balance(&mut self.context, account).await
+    }
+
+    pub fn commit_ix(&self, details: &SwapDetails, is_stake: bool) -> Instruction {
+        Instruction {
+            program_id: securelp::ID,
+            accounts: securelp::accounts::Commit {
+                commitment: self.commitment(),
+                user: self.user(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: securelp::instruction::Commit {
+                hash: details.hash(),
+                amount_lamports: details.amount_in,
+                is_stake,
+            }
+            .data(),
+        }
+    }
+
+    pub fn cancel_commitment_ix(&self) -> Instruction {
+        Instruction {
+            program_id: securelp::ID,
+            accounts: securelp::accounts::CancelCommitment {
+                commitment: self.commitment(),
+                user: self.user(),
+            }
+            .to_account_metas(None),
+            data: securelp::instruction::CancelCommitment {}.data(),
+        }
+    }
+
+    pub fn reveal_and_stake_ix(&self, details: SwapDetails) -> Instruction {
+        Instruction {
+            program_id: securelp::ID,
+            accounts: securelp::accounts::RevealAndStake {
+                commitment: self.commitment(),
+                user: self.user(),
+                stake_pool_program: stake_pool::ID,
+                pool_config: self.stake_pool.config,
+                pool_authority: self.stake_pool.authority,
+                reserve_vault: self.stake_pool.reserve_vault,
+                slp_mint: self.stake_pool.slp_mint,
+                user_slp_account: self.stake_pool.user_slp,
+                fee_recipient: None,
+                referrer: None,
+                user_stake: None,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: securelp::instruction::RevealAndStake { details }.data(),
+        }
+    }
+
+    pub fn reveal_and_unstake_ix(&self, details: SwapDetails) -> Instruction {
+        Instruction {
+            program_id: securelp::ID,
+            accounts: securelp::accounts::RevealAndUnstake {
+                commitment: self.commitment(),
+                user: self.user(),
+                stake_pool_program: stake_pool::ID,
+                pool_config: self.stake_pool.config,
+                reserve_vault: self.stake_pool.reserve_vault,
+                slp_mint: self.stake_pool.slp_mint,
+                user_slp_account: self.stake_pool.user_slp,
+                fee_recipient: None,
+                user_stake: None,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: securelp::instruction::RevealAndUnstake { details }.data(),
+        }
+    }
+
+    /// Reveal a swap of token A for token B on `pool`
+    pub fn reveal_and_swap_ix(&self, pool: &AmmPoolAccounts, details: SwapDetails) -> Instruction {
+        Instruction {
+            program_id: securelp::ID,
+            accounts: securelp::accounts::RevealAndSwap {
+                commitment: self.commitment(),
+                user: self.user(),
+                amm_program: amm::ID,
+                amm_pool: pool.address,
+                amm_authority: pool.authority,
+                token_a_vault: pool.token_a_vault,
+                token_b_vault: pool.token_b_vault,
+                user_token_in: pool.user_token_a,
+                user_token_out: pool.user_token_b,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: securelp::instruction::RevealAndSwap { details, a_to_b: true }.data(),
+        }
+    }
+
+    pub fn pause_stake_pool_ix(&self) -> Instruction {
+        Instruction {
+            program_id: stake_pool::ID,
+            accounts: stake_pool::accounts::AdminAction {
+                admin: self.user(),
+                pool_config: self.stake_pool.config,
+            }
+            .to_account_metas(None),
+            data: stake_pool::instruction::SetPaused { paused: true }.data(),
+        }
+    }
+
+    pub fn pause_amm_pool_ix(&self, pool: &AmmPoolAccounts) -> Instruction {
+        Instruction {
+            program_id: amm::ID,
+            accounts: amm::accounts::AdminAction {
+                authority: self.user(),
+                pool: pool.address,
+            }
+            .to_account_metas(None),
+            data: amm::instruction::SetPaused { paused: true }.data(),
+        }
+    }
+}
+
+/// Swap details with a nonce unique to `seed`, so repeated commitments hash
+/// differently
+pub fn details(amount_in: u64, min_out: u64, seed: u8) -> SwapDetails {
+    SwapDetails {
+        amount_in,
+        min_out,
+        slippage_bps: 50,
+        nonce: [seed; 32],
+    }
+}
+
+/// Create an AMM pool and seed it with liquidity
+async fn setup_amm_pool(context: &mut ProgramTestContext) -> AmmPoolAccounts {
+    let pool = create_amm_pool(context, 30, TOKEN_BALANCE).await;
+    let add_liquidity = pool.add_liquidity_ix(POOL_LIQUIDITY, POOL_LIQUIDITY, 0);
+    send(context, &[add_liquidity], &[]).await.unwrap();
+    pool
+}
+
+/// Start program-test with all three programs, initialize the stake pool with
+/// 100 SOL in its reserve and two seeded AMM pools
+pub async fn setup() -> Env {
+    // Programs come from `anchor build` unless the caller points elsewhere
+    static SBF_OUT_DIR: Once = Once::new();
+    SBF_OUT_DIR.call_once(|| {
+        if std::env::var_os("SBF_OUT_DIR").is_none() {
+            std::env::set_var("SBF_OUT_DIR", concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy"));
+        }
+    });
+
+    let mut program_test = ProgramTest::new("securelp", securelp::ID, None);
+    program_test.add_program("stake_pool", stake_pool::ID, None);
+    program_test.add_program("amm", amm::ID, None);
+    let mut context = program_test.start_with_context().await;
+
+    let stake_pool = create_stake_pool(&mut context, 100).await;
+    send(&mut context, &[stake_pool.deposit_sol_ix(100 * LAMPORTS_PER_SOL)], &[]).await.unwrap();
+    let amm_pool = setup_amm_pool(&mut context).await;
+    let other_amm_pool = setup_amm_pool(&mut context).await;
+
+    Env {
+        context,
+        stake_pool,
+        amm_pool,
+        other_amm_pool,
+    }
+}
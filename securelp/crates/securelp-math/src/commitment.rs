@@ -0,0 +1,32 @@
+//! Commit-reveal hashing of swap details
+
+use sha2::{Digest, Sha256};
+
+/// Serialized length: amount_in (8) + min_out (8) + slippage_bps (2) + nonce (32)
+pub const SWAP_DETAILS_LEN: usize = 50;
+
+/// Swap details in the program's Borsh layout: little-endian integers in field
+/// order, followed by the nonce
+pub fn serialize_swap_details(
+    amount_in: u64,
+    min_out: u64,
+    slippage_bps: u16,
+    nonce: &[u8; 32],
+) -> [u8; SWAP_DETAILS_LEN] {
+    let mut bytes = [0u8; SWAP_DETAILS_LEN];
+    bytes[0..8].copy_from_slice(&amount_in.to_le_bytes());
+    bytes[8..16].copy_from_slice(&min_out.to_le_bytes());
+    bytes[16..18].copy_from_slice(&slippage_bps.to_le_bytes());
+    bytes[18..50].copy_from_slice(nonce);
+    bytes
+}
+
+/// SHA-256 of the serialized swap details, as stored in a commitment
+pub fn hash_swap_details(
+    amount_in: u64,
+    min_out: u64,
+    slippage_bps: u16,
+    nonce: &[u8; 32],
+) -> [u8; 32] {
+    Sha256::digest(serialize_swap_details(amount_in, min_out, slippage_bps, nonce)).into()
+}
@@ -0,0 +1,53 @@
+//! Constant-product (x * y = k) swap math
+
+use crate::BPS_DENOMINATOR;
+
+/// Result of a swap against the curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapOutput {
+    /// Tokens paid out of the output reserve
+    pub amount_out: u64,
+    /// Input tokens taken as the fee; they stay in the input reserve
+    pub fee: u64,
+}
+
+/// The constant product k
+pub fn k(reserve_a: u64, reserve_b: u64) -> u128 {
+    (reserve_a as u128) * (reserve_b as u128)
+}
+
+/// Output for swapping `amount_in` into a pool with the given reserves.
+///
+/// The fee is taken off the input first, rounding in the pool's favour:
+/// amount_out = reserve_out * in_after_fee / (reserve_in + in_after_fee).
+/// `None` if either reserve is empty or `fee_bps` is above 100%.
+pub fn swap_output(
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    fee_bps: u16,
+) -> Option<SwapOutput> {
+    if reserve_in == 0 || reserve_out == 0 {
+        return None;
+    }
+
+    let fee_multiplier = BPS_DENOMINATOR.checked_sub(fee_bps as u64)?;
+    let input_after_fee = (amount_in as u128)
+        .checked_mul(fee_multiplier as u128)?
+        .checked_div(BPS_DENOMINATOR as u128)? as u64;
+    let fee = amount_in.checked_sub(input_after_fee)?;
+
+    let numerator = (reserve_out as u128).checked_mul(input_after_fee as u128)?;
+    let denominator = (reserve_in as u128).checked_add(input_after_fee as u128)?;
+    let amount_out = numerator.checked_div(denominator)? as u64;
+
+    Some(SwapOutput { amount_out, fee })
+}
+
+/// Fee owed on a flash loan of `amount`, rounded up
+pub fn flash_loan_fee(amount: u64, fee_bps: u16) -> Option<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)?
+        .div_ceil(BPS_DENOMINATOR as u128);
+    u64::try_from(fee).ok()
+}
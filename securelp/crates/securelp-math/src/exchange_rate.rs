@@ -0,0 +1,60 @@
+//! Stake pool slpSOL <-> SOL exchange-rate math. `total_sol` is the pool's
+//! staked plus reserve lamports, `supply` the slpSOL in circulation.
+
+/// Fixed-point scale of the exchange rate (9 decimals, like SOL)
+pub const RATE_PRECISION: u64 = 1_000_000_000;
+
+/// Lamports per slpSOL, scaled by RATE_PRECISION; 1:1 for an empty pool
+pub fn exchange_rate(total_sol: u64, supply: u64) -> u64 {
+    if supply == 0 {
+        return RATE_PRECISION;
+    }
+
+    let rate = (total_sol as u128)
+        .checked_mul(RATE_PRECISION as u128)
+        .unwrap_or(0)
+        .checked_div(supply as u128)
+        .unwrap_or(RATE_PRECISION as u128);
+
+    u64::try_from(rate).unwrap_or(RATE_PRECISION)
+}
+
+/// slpSOL minted for depositing `sol_lamports`, rounded down; 1:1 for the
+/// first deposit
+pub fn slp_for_deposit(sol_lamports: u64, total_sol: u64, supply: u64) -> Option<u64> {
+    if supply == 0 {
+        return Some(sol_lamports);
+    }
+
+    let result = (sol_lamports as u128)
+        .checked_mul(supply as u128)?
+        .checked_div(total_sol as u128)?;
+    u64::try_from(result).ok()
+}
+
+/// Lamports owed for burning `slp_amount`, rounded down
+pub fn sol_for_withdrawal(slp_amount: u64, total_sol: u64, supply: u64) -> Option<u64> {
+    if supply == 0 {
+        return Some(0);
+    }
+
+    let result = (slp_amount as u128)
+        .checked_mul(total_sol as u128)?
+        .checked_div(supply as u128)?;
+    u64::try_from(result).ok()
+}
+
+/// slpSOL to mint so that `fee_lamports`, already counted in `total_sol`,
+/// belongs to the fee recipient at the resulting exchange rate:
+/// fee_shares / (supply + fee_shares) = fee_lamports / total_sol
+pub fn fee_shares(fee_lamports: u64, total_sol: u64, supply: u64) -> Option<u64> {
+    let remaining_sol = total_sol.saturating_sub(fee_lamports);
+    if supply == 0 || remaining_sol == 0 {
+        return Some(0);
+    }
+
+    let shares = (fee_lamports as u128)
+        .checked_mul(supply as u128)?
+        .checked_div(remaining_sol as u128)?;
+    u64::try_from(shares).ok()
+}
@@ -0,0 +1,23 @@
+//! Math shared by the on-chain programs and the MEV simulator.
+//!
+//! Every function works on plain integers and returns `None` where the
+//! programs would fail with a math error, so the programs map it to their own
+//! error type and the simulator computes exactly what the chain would.
+
+#![no_std]
+
+pub mod commitment;
+pub mod curve;
+pub mod exchange_rate;
+pub mod lp;
+
+/// Basis points in 100%
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// `bps` basis points of `amount`, rounded down
+pub fn bps_of(amount: u64, bps: u16) -> Option<u64> {
+    let result = (amount as u128)
+        .checked_mul(bps as u128)?
+        .checked_div(BPS_DENOMINATOR as u128)?;
+    u64::try_from(result).ok()
+}
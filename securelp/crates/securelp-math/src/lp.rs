@@ -0,0 +1,66 @@
+//! LP share math
+
+/// LP tokens locked forever on the first deposit to prevent manipulation
+pub const MINIMUM_LIQUIDITY: u64 = 1000;
+
+/// LP tokens for the first deposit: sqrt(amount_a * amount_b) less the
+/// locked MINIMUM_LIQUIDITY. `None` if the deposit doesn't cover it.
+pub fn initial_lp(amount_a: u64, amount_b: u64) -> Option<u64> {
+    let sqrt = integer_sqrt((amount_a as u128) * (amount_b as u128));
+    if sqrt <= MINIMUM_LIQUIDITY as u128 {
+        return None;
+    }
+    u64::try_from(sqrt - MINIMUM_LIQUIDITY as u128).ok()
+}
+
+/// LP tokens for adding to a funded pool, at the less favourable of the two
+/// ratios: min(amount_a * supply / reserve_a, amount_b * supply / reserve_b)
+pub fn lp_for_liquidity(
+    amount_a: u64,
+    amount_b: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    total_lp_supply: u64,
+) -> Option<u64> {
+    let lp_from_a = share(amount_a, total_lp_supply, reserve_a)?;
+    let lp_from_b = share(amount_b, total_lp_supply, reserve_b)?;
+    Some(lp_from_a.min(lp_from_b))
+}
+
+/// Tokens paid out for burning `lp_amount`, pro rata to the reserves
+pub fn tokens_for_lp(
+    lp_amount: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    total_lp_supply: u64,
+) -> Option<(u64, u64)> {
+    Some((
+        share(lp_amount, reserve_a, total_lp_supply)?,
+        share(lp_amount, reserve_b, total_lp_supply)?,
+    ))
+}
+
+/// amount * numerator / denominator, rounded down
+fn share(amount: u64, numerator: u64, denominator: u64) -> Option<u64> {
+    let result = (amount as u128)
+        .checked_mul(numerator as u128)?
+        .checked_div(denominator as u128)?;
+    u64::try_from(result).ok()
+}
+
+/// Integer square root using Newton's method
+pub fn integer_sqrt(n: u128) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+
+    x
+}
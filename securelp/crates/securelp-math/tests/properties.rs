@@ -0,0 +1,139 @@
+//! Invariants of the shared curve, LP share, exchange-rate and commitment math.
+
+use proptest::prelude::*;
+use securelp_math::{commitment, curve, exchange_rate, lp};
+
+const MAX_RESERVE: u64 = 1 << 60;
+
+proptest! {
+    #[test]
+    fn swaps_never_lower_k(
+        reserve_in in 1..MAX_RESERVE,
+        reserve_out in 1..MAX_RESERVE,
+        amount_in in 0..MAX_RESERVE,
+        fee_bps in 0u16..=1000,
+    ) {
+        let out = curve::swap_output(amount_in, reserve_in, reserve_out, fee_bps).unwrap();
+
+        prop_assert!(out.amount_out < reserve_out);
+        prop_assert!(out.fee <= amount_in);
+        let k_after = curve::k(reserve_in + amount_in, reserve_out - out.amount_out);
+        prop_assert!(k_after >= curve::k(reserve_in, reserve_out));
+    }
+
+    #[test]
+    fn swap_fee_rounds_in_the_pools_favour(
+        reserve_in in 1..MAX_RESERVE,
+        reserve_out in 1..MAX_RESERVE,
+        amount_in in 0..MAX_RESERVE,
+        fee_bps in 0u16..=1000,
+    ) {
+        let out = curve::swap_output(amount_in, reserve_in, reserve_out, fee_bps).unwrap();
+        let exact_fee = amount_in as u128 * fee_bps as u128;
+
+        prop_assert!(out.fee as u128 * 10_000 >= exact_fee);
+        prop_assert!((out.fee as u128) * 10_000 < exact_fee + 10_000);
+    }
+
+    #[test]
+    fn removing_liquidity_never_pays_more_than_was_added(
+        reserve_a in 1..MAX_RESERVE,
+        reserve_b in 1..MAX_RESERVE,
+        total_lp_supply in 1..MAX_RESERVE,
+        amount_a in 0..MAX_RESERVE,
+        amount_b in 0..MAX_RESERVE,
+    ) {
+        let Some(minted) = lp::lp_for_liquidity(amount_a, amount_b, reserve_a, reserve_b, total_lp_supply)
+        else {
+            return Ok(());
+        };
+
+        let (out_a, out_b) = lp::tokens_for_lp(
+            minted,
+            reserve_a + amount_a,
+            reserve_b + amount_b,
+            total_lp_supply + minted,
+        )
+        .unwrap();
+        prop_assert!(out_a <= amount_a);
+        prop_assert!(out_b <= amount_b);
+    }
+
+    #[test]
+    fn initial_lp_is_the_geometric_mean_less_the_locked_minimum(
+        amount_a in 0..u64::MAX,
+        amount_b in 0..u64::MAX,
+    ) {
+        let sqrt = lp::integer_sqrt(amount_a as u128 * amount_b as u128);
+        prop_assert!(sqrt * sqrt <= amount_a as u128 * amount_b as u128);
+        prop_assert!((sqrt + 1) * (sqrt + 1) > amount_a as u128 * amount_b as u128);
+
+        match lp::initial_lp(amount_a, amount_b) {
+            Some(minted) => prop_assert_eq!(minted as u128 + lp::MINIMUM_LIQUIDITY as u128, sqrt),
+            None => prop_assert!(sqrt <= lp::MINIMUM_LIQUIDITY as u128),
+        }
+    }
+
+    #[test]
+    fn deposit_then_withdraw_never_profits(
+        total_sol in 1..MAX_RESERVE,
+        supply in 1..MAX_RESERVE,
+        deposit in 0..MAX_RESERVE,
+    ) {
+        let rate_before = exchange_rate::exchange_rate(total_sol, supply);
+        let Some(minted) = exchange_rate::slp_for_deposit(deposit, total_sol, supply) else {
+            return Ok(());
+        };
+        let Some(supply) = supply.checked_add(minted) else {
+            return Ok(());
+        };
+        let total_sol = total_sol + deposit;
+
+        let withdrawn = exchange_rate::sol_for_withdrawal(minted, total_sol, supply).unwrap();
+        prop_assert!(withdrawn <= deposit);
+        prop_assert!(exchange_rate::exchange_rate(total_sol, supply) >= rate_before);
+    }
+
+    #[test]
+    fn fee_shares_are_worth_at_most_the_fee(
+        (total_sol, fee_lamports) in (1..MAX_RESERVE).prop_flat_map(|total_sol| (Just(total_sol), 0..total_sol)),
+        supply in 1..MAX_RESERVE,
+    ) {
+        let Some(shares) = exchange_rate::fee_shares(fee_lamports, total_sol, supply) else {
+            return Ok(());
+        };
+        let Some(supply) = supply.checked_add(shares) else {
+            return Ok(());
+        };
+        let value = exchange_rate::sol_for_withdrawal(shares, total_sol, supply).unwrap();
+        prop_assert!(value <= fee_lamports);
+    }
+
+    #[test]
+    fn swap_details_serialize_field_by_field(
+        amount_in: u64,
+        min_out: u64,
+        slippage_bps: u16,
+        nonce: [u8; 32],
+    ) {
+        let bytes = commitment::serialize_swap_details(amount_in, min_out, slippage_bps, &nonce);
+
+        prop_assert_eq!(u64::from_le_bytes(bytes[0..8].try_into().unwrap()), amount_in);
+        prop_assert_eq!(u64::from_le_bytes(bytes[8..16].try_into().unwrap()), min_out);
+        prop_assert_eq!(u16::from_le_bytes(bytes[16..18].try_into().unwrap()), slippage_bps);
+        prop_assert_eq!(&bytes[18..], &nonce[..]);
+    }
+}
+
+#[test]
+fn empty_reserves_cannot_be_swapped_against() {
+    assert_eq!(curve::swap_output(1_000, 0, 1_000, 30), None);
+    assert_eq!(curve::swap_output(1_000, 1_000, 0, 30), None);
+}
+
+#[test]
+fn commitment_hash_is_sha256_of_the_serialized_details() {
+    // sha256 of 50 zero bytes
+    let hash = commitment::hash_swap_details(0, 0, 0, &[0u8; 32]);
+    assert_eq!(hash[..4], [0xcc, 0x27, 0x86, 0xe1]);
+}
@@ -46,5 +46,44 @@ pub enum AmmError {
 
     #[msg("Same token swap not allowed")]
     SameTokenSwap,
+
+    #[msg("Invalid reward duration")]
+    InvalidRewardDuration,
+
+    #[msg("Insufficient staked LP tokens")]
+    InsufficientStakedLp,
+
+    #[msg("No rewards to claim")]
+    NoRewardsToClaim,
+
+    #[msg("A flash loan is already active on this pool")]
+    FlashLoanActive,
+
+    #[msg("No active flash loan to repay")]
+    FlashLoanNotActive,
+
+    #[msg("Flash loan must be repaid later in the same transaction")]
+    FlashRepayMissing,
+
+    #[msg("Flash loans cannot be taken through CPI")]
+    FlashLoanCpiNotAllowed,
+
+    #[msg("No pending authority transfer")]
+    NoPendingAuthority,
+
+    #[msg("A parameter change is already queued")]
+    ParameterChangePending,
+
+    #[msg("No parameter change queued")]
+    NoPendingParameterChange,
+
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+
+    #[msg("Pool is not paused")]
+    PoolNotPaused,
+
+    #[msg("Pool account is not in the pre-upgrade layout")]
+    PoolAlreadyMigrated,
 }
 
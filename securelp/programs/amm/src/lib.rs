@@ -1,4 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as instructions_sysvar,
+    load_current_index_checked,
+    load_instruction_at_checked,
+};
+use anchor_lang::Discriminator;
 use anchor_spl::token::{self, Mint, MintTo, Burn, Transfer, Token, TokenAccount};
 use anchor_spl::metadata::{
     create_metadata_accounts_v3,
@@ -69,6 +77,7 @@ pub mod amm {
         let pool = &mut ctx.accounts.pool;
         
         require!(!pool.paused, AmmError::PoolPaused);
+        require!(pool.flash_loan_amount == 0, AmmError::FlashLoanActive);
         require!(amount_a > 0 && amount_b > 0, AmmError::InsufficientInput);
 
         // Calculate LP tokens to mint
@@ -162,6 +171,7 @@ pub mod amm {
         let pool = &mut ctx.accounts.pool;
         
         require!(!pool.paused, AmmError::PoolPaused);
+        require!(pool.flash_loan_amount == 0, AmmError::FlashLoanActive);
         require!(lp_amount > 0, AmmError::InvalidLpAmount);
 
         // Calculate tokens to return
@@ -259,6 +269,7 @@ pub mod amm {
         let pool = &mut ctx.accounts.pool;
         
         require!(!pool.paused, AmmError::PoolPaused);
+        require!(pool.flash_loan_amount == 0, AmmError::FlashLoanActive);
         require!(amount_in > 0, AmmError::InsufficientInput);
 
         // Calculate output amount
@@ -373,23 +384,425 @@ pub mod amm {
         Ok(())
     }
 
-    /// Admin: Pause/unpause the pool
-    pub fn set_paused(ctx: Context<AdminAction>, paused: bool) -> Result<()> {
+    /// Flash loan: borrow `amount` from the vault passed in. A matching
+    /// `flash_repay` for this pool must appear later in the same transaction.
+    pub fn flash_borrow(ctx: Context<FlashBorrow>, amount: u64) -> Result<()> {
         let pool = &mut ctx.accounts.pool;
-        pool.paused = paused;
-        
-        msg!("Pool paused: {}", paused);
+
+        require!(!pool.paused, AmmError::PoolPaused);
+        require!(pool.flash_loan_amount == 0, AmmError::FlashLoanActive);
+        require!(amount > 0, AmmError::InsufficientInput);
+
+        // Introspection only sees top-level instructions, so a CPI borrow
+        // could be paired with a repay that belongs to a different loan
+        require!(
+            get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT,
+            AmmError::FlashLoanCpiNotAllowed
+        );
+
+        let is_a = ctx.accounts.vault.key() == pool.token_a_vault;
+        let reserve = if is_a { pool.reserve_a } else { pool.reserve_b };
+        require!(amount <= reserve, AmmError::InsufficientLiquidity);
+
+        // Find the repay instruction for this pool later in the transaction
+        let ixs = ctx.accounts.instructions.to_account_info();
+        let current_index = load_current_index_checked(&ixs)? as usize;
+        let mut index = current_index + 1;
+        let mut repay_found = false;
+        while let Ok(ix) = load_instruction_at_checked(index, &ixs) {
+            if ix.program_id == crate::ID
+                && ix.data.starts_with(instruction::FlashRepay::DISCRIMINATOR)
+                && ix.accounts.get(1).map(|meta| meta.pubkey) == Some(pool.key())
+            {
+                repay_found = true;
+                break;
+            }
+            index += 1;
+        }
+        require!(repay_found, AmmError::FlashRepayMissing);
+
+        let pool_key = pool.key();
+        let seeds = &[
+            AMM_AUTHORITY_SEED,
+            pool_key.as_ref(),
+            &[pool.authority_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        pool.flash_loan_amount = amount;
+        pool.flash_loan_is_a = is_a;
+
+        msg!("Flash borrowed {} {}", amount, if is_a { "A" } else { "B" });
+
+        emit!(FlashLoanBorrowed {
+            user: ctx.accounts.user.key(),
+            pool: pool_key,
+            amount,
+            is_a,
+        });
+
+        Ok(())
+    }
+
+    /// Flash loan: repay the active loan plus fee. The fee is added to the
+    /// reserves, so it accrues to LPs.
+    pub fn flash_repay(ctx: Context<FlashRepay>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+
+        let amount = pool.flash_loan_amount;
+        require!(amount > 0, AmmError::FlashLoanNotActive);
+
+        let expected_vault = if pool.flash_loan_is_a {
+            pool.token_a_vault
+        } else {
+            pool.token_b_vault
+        };
+        require!(ctx.accounts.vault.key() == expected_vault, AmmError::InvalidMint);
+
+        let fee = pool.flash_loan_fee(amount)?;
+        let repay_amount = amount.checked_add(fee)
+            .ok_or(AmmError::MathOverflow)?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+            repay_amount,
+        )?;
+
+        let is_a = pool.flash_loan_is_a;
+        if is_a {
+            pool.reserve_a = pool.reserve_a.checked_add(fee)
+                .ok_or(AmmError::MathOverflow)?;
+            pool.cumulative_fee_a = pool.cumulative_fee_a.checked_add(fee)
+                .ok_or(AmmError::MathOverflow)?;
+        } else {
+            pool.reserve_b = pool.reserve_b.checked_add(fee)
+                .ok_or(AmmError::MathOverflow)?;
+            pool.cumulative_fee_b = pool.cumulative_fee_b.checked_add(fee)
+                .ok_or(AmmError::MathOverflow)?;
+        }
+        pool.flash_loan_amount = 0;
+        pool.flash_loan_is_a = false;
+
+        msg!("Flash loan repaid: {} + {} fee", amount, fee);
+
+        emit!(FlashLoanRepaid {
+            user: ctx.accounts.user.key(),
+            pool: pool.key(),
+            amount,
+            fee,
+            is_a,
+            reserve_a: pool.reserve_a,
+            reserve_b: pool.reserve_b,
+        });
+
+        Ok(())
+    }
+
+    /// View: What swap would pay out for `amount_in`, returned through return
+    /// data so clients can simulate it and programs can CPI it
+    pub fn quote_swap(ctx: Context<QuotePool>, amount_in: u64, a_to_b: bool) -> Result<SwapQuote> {
+        let pool = &ctx.accounts.pool;
+
+        require!(!pool.paused, AmmError::PoolPaused);
+        require!(pool.flash_loan_amount == 0, AmmError::FlashLoanActive);
+        require!(amount_in > 0, AmmError::InsufficientInput);
+
+        let (amount_out, fee_amount) = pool.calculate_swap_output(amount_in, a_to_b)?;
+        require!(amount_out > 0, AmmError::InsufficientOutput);
+
+        Ok(SwapQuote {
+            amount_out,
+            fee_amount,
+        })
+    }
+
+    /// View: LP tokens add_liquidity would mint, returned through return data
+    pub fn quote_add_liquidity(
+        ctx: Context<QuotePool>,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> Result<AddLiquidityQuote> {
+        let pool = &ctx.accounts.pool;
+
+        require!(!pool.paused, AmmError::PoolPaused);
+        require!(pool.flash_loan_amount == 0, AmmError::FlashLoanActive);
+        require!(amount_a > 0 && amount_b > 0, AmmError::InsufficientInput);
+
+        Ok(AddLiquidityQuote {
+            lp_minted: pool.calculate_lp_tokens_for_liquidity(amount_a, amount_b)?,
+        })
+    }
+
+    /// Admin: Set the reserves to the vault balances. Tokens sent straight to
+    /// a vault are added to the reserves, so they accrue to LPs; skim instead
+    /// returns them. Both are admin-only, so nobody can sync a donation into
+    /// the reserves before the admin can skim it.
+    pub fn sync(ctx: Context<Sync>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+
+        require!(pool.flash_loan_amount == 0, AmmError::FlashLoanActive);
+        require!(pool.total_lp_supply > 0, AmmError::ZeroLiquidity);
+
+        let previous_reserve_a = pool.reserve_a;
+        let previous_reserve_b = pool.reserve_b;
+        pool.reserve_a = ctx.accounts.token_a_vault.amount;
+        pool.reserve_b = ctx.accounts.token_b_vault.amount;
+
+        msg!(
+            "Reserves synced: A {} -> {}, B {} -> {}",
+            previous_reserve_a,
+            pool.reserve_a,
+            previous_reserve_b,
+            pool.reserve_b
+        );
+
+        emit!(ReservesSynced {
+            pool: pool.key(),
+            previous_reserve_a,
+            previous_reserve_b,
+            reserve_a: pool.reserve_a,
+            reserve_b: pool.reserve_b,
+        });
+
         Ok(())
     }
 
-    /// Admin: Update fee
+    /// Admin: Send vault tokens above the tracked reserves to the given
+    /// accounts, recovering tokens sent to the pool by mistake
+    pub fn skim(ctx: Context<Skim>) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+
+        require!(pool.flash_loan_amount == 0, AmmError::FlashLoanActive);
+
+        let excess_a = ctx.accounts.token_a_vault.amount.saturating_sub(pool.reserve_a);
+        let excess_b = ctx.accounts.token_b_vault.amount.saturating_sub(pool.reserve_b);
+
+        let pool_key = pool.key();
+        let seeds = &[
+            AMM_AUTHORITY_SEED,
+            pool_key.as_ref(),
+            &[pool.authority_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        for (vault, recipient, excess) in [
+            (&ctx.accounts.token_a_vault, &ctx.accounts.recipient_a, excess_a),
+            (&ctx.accounts.token_b_vault, &ctx.accounts.recipient_b, excess_b),
+        ] {
+            if excess == 0 {
+                continue;
+            }
+            let cpi_accounts = Transfer {
+                from: vault.to_account_info(),
+                to: recipient.to_account_info(),
+                authority: ctx.accounts.pool_authority.to_account_info(),
+            };
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    cpi_accounts,
+                    signer_seeds,
+                ),
+                excess,
+            )?;
+        }
+
+        msg!("Skimmed {} A and {} B", excess_a, excess_b);
+
+        emit!(Skimmed {
+            pool: pool_key,
+            amount_a: excess_a,
+            amount_b: excess_b,
+        });
+
+        Ok(())
+    }
+
+    /// Admin: Pause the pool immediately, or queue an unpause behind the timelock
+    pub fn set_paused(ctx: Context<AdminAction>, paused: bool) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+
+        if paused {
+            pool.paused = true;
+            // A stale queued unpause must not undo an emergency pause
+            if pool.pending_change == ParameterChange::Unpause {
+                pool.pending_change = ParameterChange::None;
+                pool.pending_change_slot = 0;
+            }
+
+            msg!("Pool paused");
+            return Ok(());
+        }
+
+        require!(pool.paused, AmmError::PoolNotPaused);
+        queue_change(pool, ParameterChange::Unpause)
+    }
+
+    /// Admin: Queue a fee update, applied by execute_parameter_change after the timelock
     pub fn update_fee(ctx: Context<AdminAction>, new_fee_bps: u16) -> Result<()> {
         require!(new_fee_bps <= 1000, AmmError::InvalidFee); // Max 10%
-        
+
+        queue_change(&mut ctx.accounts.pool, ParameterChange::Fee { new_fee_bps })
+    }
+
+    /// Admin: Apply the queued parameter change once the timelock has elapsed
+    pub fn execute_parameter_change(ctx: Context<AdminAction>) -> Result<()> {
         let pool = &mut ctx.accounts.pool;
-        pool.fee_bps = new_fee_bps;
-        
-        msg!("Fee updated to {} bps", new_fee_bps);
+        let change = pool.pending_change;
+
+        require!(change != ParameterChange::None, AmmError::NoPendingParameterChange);
+        require!(
+            Clock::get()?.slot >= pool.pending_change_slot,
+            AmmError::TimelockNotElapsed
+        );
+
+        match change {
+            ParameterChange::Fee { new_fee_bps } => {
+                pool.fee_bps = new_fee_bps;
+                msg!("Fee updated to {} bps", new_fee_bps);
+            }
+            ParameterChange::Unpause => {
+                pool.paused = false;
+                msg!("Pool unpaused");
+            }
+            ParameterChange::None => {}
+        }
+        pool.pending_change = ParameterChange::None;
+        pool.pending_change_slot = 0;
+
+        emit!(ParameterChangeExecuted {
+            pool: pool.key(),
+            change,
+        });
+
+        Ok(())
+    }
+
+    /// Admin: Cancel the queued parameter change
+    pub fn cancel_parameter_change(ctx: Context<AdminAction>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        let change = pool.pending_change;
+
+        require!(change != ParameterChange::None, AmmError::NoPendingParameterChange);
+        pool.pending_change = ParameterChange::None;
+        pool.pending_change_slot = 0;
+
+        msg!("Queued parameter change cancelled");
+        emit!(ParameterChangeCancelled {
+            pool: pool.key(),
+            change,
+        });
+
+        Ok(())
+    }
+
+    /// Admin: Propose a new pool authority (step 1 of 2).
+    /// Passing the default pubkey cancels a pending proposal.
+    pub fn propose_authority(ctx: Context<AdminAction>, new_authority: Pubkey) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.pending_authority = new_authority;
+
+        msg!("Authority transfer proposed: {} -> {}", pool.authority, new_authority);
+
+        emit!(AuthorityProposed {
+            pool: pool.key(),
+            current_authority: pool.authority,
+            proposed_authority: new_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Accept a pending authority transfer (step 2 of 2).
+    /// The new authority must sign, so it can be a wallet or a
+    /// multisig PDA signing through CPI.
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+
+        let previous_authority = pool.authority;
+        pool.authority = pool.pending_authority;
+        pool.pending_authority = Pubkey::default();
+
+        msg!("Authority transferred: {} -> {}", previous_authority, pool.authority);
+
+        emit!(AuthorityAccepted {
+            pool: pool.key(),
+            previous_authority,
+            new_authority: pool.authority,
+        });
+
+        Ok(())
+    }
+
+    /// Admin: Rewrite a pool created before the flash loan, authority
+    /// transfer and timelock fields in the current AmmPool layout, growing
+    /// the account and topping up its rent from the authority
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        let pool_info = ctx.accounts.pool.to_account_info();
+        let old = {
+            let data = pool_info.try_borrow_data()?;
+            require!(data.len() == AmmPoolV0::LEN, AmmError::PoolAlreadyMigrated);
+            require!(
+                data.starts_with(AmmPool::DISCRIMINATOR),
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            AmmPoolV0::deserialize(&mut &data[8..])?
+        };
+
+        require_keys_eq!(old.authority, ctx.accounts.authority.key(), AmmError::InvalidAuthority);
+        let expected = Pubkey::create_program_address(
+            &[AMM_POOL_SEED, old.token_a_mint.as_ref(), old.token_b_mint.as_ref(), &[old.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| error!(ErrorCode::ConstraintSeeds))?;
+        require_keys_eq!(expected, pool_info.key(), ErrorCode::ConstraintSeeds);
+
+        let top_up = Rent::get()?
+            .minimum_balance(AmmPool::LEN)
+            .saturating_sub(pool_info.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: pool_info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        pool_info.resize(AmmPool::LEN)?;
+
+        let pool = AmmPool::from(old);
+        pool.try_serialize(&mut &mut pool_info.try_borrow_mut_data()?[..])?;
+
+        msg!("Pool migrated to {} bytes", AmmPool::LEN);
+        emit!(PoolMigrated {
+            pool: pool_info.key(),
+            previous_len: AmmPoolV0::LEN as u32,
+            new_len: AmmPool::LEN as u32,
+        });
+
         Ok(())
     }
 
@@ -443,77 +856,323 @@ pub mod amm {
         msg!("LP token metadata created successfully!");
         Ok(())
     }
-}
 
-// ============================================================================
-// Account Contexts
-// ============================================================================
+    /// Admin: Create the LP farm for this pool
+    pub fn initialize_farm(ctx: Context<InitializeFarm>) -> Result<()> {
+        let farm = &mut ctx.accounts.farm;
+
+        farm.pool = ctx.accounts.pool.key();
+        farm.reward_mint = ctx.accounts.reward_mint.key();
+        farm.lp_vault = ctx.accounts.lp_vault.key();
+        farm.reward_vault = ctx.accounts.reward_vault.key();
+        farm.total_staked = 0;
+        farm.reward_rate = 0;
+        farm.period_finish = 0;
+        farm.last_update_ts = Clock::get()?.unix_timestamp;
+        farm.acc_reward_per_share = 0;
+        farm.unallocated_rewards = 0;
+        farm.bump = ctx.bumps.farm;
+
+        msg!("Farm initialized with reward mint {}", farm.reward_mint);
+
+        emit!(FarmInitialized {
+            pool: farm.pool,
+            farm: farm.key(),
+            reward_mint: farm.reward_mint,
+        });
 
-#[derive(Accounts)]
-pub struct InitializePool<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+        Ok(())
+    }
 
-    /// Token A mint (e.g., wSOL)
-    pub token_a_mint: Account<'info, Mint>,
+    /// Admin: Fund the farm and emit `amount` rewards evenly over `duration` seconds
+    pub fn fund_farm(ctx: Context<FundFarm>, amount: u64, duration: i64) -> Result<()> {
+        require!(amount > 0, AmmError::InsufficientInput);
 
-    /// Token B mint (e.g., slpSOL)
-    pub token_b_mint: Account<'info, Mint>,
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.admin_reward_account.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.admin.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+            amount,
+        )?;
 
-    #[account(
-        init,
-        payer = authority,
-        space = AmmPool::LEN,
-        seeds = [AMM_POOL_SEED, token_a_mint.key().as_ref(), token_b_mint.key().as_ref()],
-        bump
-    )]
-    pub pool: Account<'info, AmmPool>,
+        let farm = &mut ctx.accounts.farm;
+        farm.notify_reward(amount, duration, Clock::get()?.unix_timestamp)?;
 
-    /// CHECK: PDA used as pool authority for signing
-    #[account(
-        seeds = [AMM_AUTHORITY_SEED, pool.key().as_ref()],
-        bump
-    )]
-    pub pool_authority: UncheckedAccount<'info>,
+        msg!(
+            "Farm funded with {} rewards over {} seconds ({} per second)",
+            amount,
+            duration,
+            farm.reward_rate
+        );
 
-    #[account(
-        init,
-        payer = authority,
-        token::mint = token_a_mint,
-        token::authority = pool_authority,
-        seeds = [VAULT_A_SEED, pool.key().as_ref()],
-        bump
-    )]
-    pub token_a_vault: Account<'info, TokenAccount>,
+        emit!(FarmFunded {
+            farm: farm.key(),
+            amount,
+            reward_rate: farm.reward_rate,
+            period_finish: farm.period_finish,
+            acc_reward_per_share: farm.acc_reward_per_share,
+        });
 
-    #[account(
-        init,
-        payer = authority,
-        token::mint = token_b_mint,
-        token::authority = pool_authority,
-        seeds = [VAULT_B_SEED, pool.key().as_ref()],
-        bump
-    )]
-    pub token_b_vault: Account<'info, TokenAccount>,
+        Ok(())
+    }
 
-    #[account(
-        init,
-        payer = authority,
-        mint::decimals = 9,
-        mint::authority = pool_authority,
-        mint::freeze_authority = pool_authority,
-    )]
-    pub lp_mint: Account<'info, Mint>,
+    /// Stake LP tokens into the farm
+    pub fn stake_lp(ctx: Context<StakeLp>, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.pool.paused, AmmError::PoolPaused);
+        require!(amount > 0, AmmError::InvalidLpAmount);
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+        let now = Clock::get()?.unix_timestamp;
+        let farm = &mut ctx.accounts.farm;
+        let position = &mut ctx.accounts.position;
 
-#[derive(Accounts)]
-pub struct AddLiquidity<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+        farm.update_rewards(now)?;
+
+        // First stake: initialize the position
+        if position.owner == Pubkey::default() {
+            position.owner = ctx.accounts.user.key();
+            position.pool = ctx.accounts.pool.key();
+            position.first_deposit_ts = now;
+            position.bump = ctx.bumps.position;
+        }
+        position.settle(farm.acc_reward_per_share)?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_lp_account.to_account_info(),
+            to: ctx.accounts.lp_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+            amount,
+        )?;
+
+        position.lp_tokens = position.lp_tokens.checked_add(amount)
+            .ok_or(AmmError::MathOverflow)?;
+        position.sync_reward_debt(farm.acc_reward_per_share)?;
+        position.last_action_ts = now;
+        farm.total_staked = farm.total_staked.checked_add(amount)
+            .ok_or(AmmError::MathOverflow)?;
+
+        msg!("Staked {} LP, position now {} LP", amount, position.lp_tokens);
+
+        emit!(LpStaked {
+            user: position.owner,
+            farm: farm.key(),
+            amount,
+            position_lp_tokens: position.lp_tokens,
+            pending_rewards: position.pending_rewards,
+            total_staked: farm.total_staked,
+            acc_reward_per_share: farm.acc_reward_per_share,
+        });
+
+        Ok(())
+    }
+
+    /// Unstake LP tokens from the farm (rewards stay pending until claimed)
+    pub fn unstake_lp(ctx: Context<UnstakeLp>, amount: u64) -> Result<()> {
+        require!(amount > 0, AmmError::InvalidLpAmount);
+
+        let now = Clock::get()?.unix_timestamp;
+        let farm = &mut ctx.accounts.farm;
+        let position = &mut ctx.accounts.position;
+
+        require!(position.lp_tokens >= amount, AmmError::InsufficientStakedLp);
+
+        farm.update_rewards(now)?;
+        position.settle(farm.acc_reward_per_share)?;
+
+        let pool_key = ctx.accounts.pool.key();
+        let seeds = &[
+            AMM_AUTHORITY_SEED,
+            pool_key.as_ref(),
+            &[ctx.accounts.pool.authority_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.lp_vault.to_account_info(),
+            to: ctx.accounts.user_lp_account.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        position.lp_tokens = position.lp_tokens.checked_sub(amount)
+            .ok_or(AmmError::MathOverflow)?;
+        position.sync_reward_debt(farm.acc_reward_per_share)?;
+        position.last_action_ts = now;
+        farm.total_staked = farm.total_staked.checked_sub(amount)
+            .ok_or(AmmError::MathOverflow)?;
+
+        msg!("Unstaked {} LP, position now {} LP", amount, position.lp_tokens);
+
+        emit!(LpUnstaked {
+            user: position.owner,
+            farm: farm.key(),
+            amount,
+            position_lp_tokens: position.lp_tokens,
+            pending_rewards: position.pending_rewards,
+            total_staked: farm.total_staked,
+            acc_reward_per_share: farm.acc_reward_per_share,
+        });
+
+        Ok(())
+    }
+
+    /// Claim all pending farm rewards
+    pub fn claim_farm_rewards(ctx: Context<ClaimFarmRewards>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let farm = &mut ctx.accounts.farm;
+        let position = &mut ctx.accounts.position;
+
+        farm.update_rewards(now)?;
+        position.settle(farm.acc_reward_per_share)?;
+        position.sync_reward_debt(farm.acc_reward_per_share)?;
+
+        let amount = position.pending_rewards;
+        require!(amount > 0, AmmError::NoRewardsToClaim);
+
+        let pool_key = ctx.accounts.pool.key();
+        let seeds = &[
+            AMM_AUTHORITY_SEED,
+            pool_key.as_ref(),
+            &[ctx.accounts.pool.authority_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.user_reward_account.to_account_info(),
+            authority: ctx.accounts.pool_authority.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        position.pending_rewards = 0;
+        position.last_action_ts = now;
+
+        msg!("Claimed {} farm rewards", amount);
+
+        emit!(FarmRewardsClaimed {
+            user: position.owner,
+            farm: farm.key(),
+            amount,
+            position_lp_tokens: position.lp_tokens,
+            acc_reward_per_share: farm.acc_reward_per_share,
+        });
+
+        Ok(())
+    }
+}
+
+/// Queue a parameter change that can be executed after PARAMETER_TIMELOCK_SLOTS
+fn queue_change(pool: &mut Account<AmmPool>, change: ParameterChange) -> Result<()> {
+    require!(
+        pool.pending_change == ParameterChange::None,
+        AmmError::ParameterChangePending
+    );
+
+    let activation_slot = Clock::get()?
+        .slot
+        .checked_add(PARAMETER_TIMELOCK_SLOTS)
+        .ok_or(AmmError::MathOverflow)?;
+    pool.pending_change = change;
+    pool.pending_change_slot = activation_slot;
+
+    msg!("Parameter change {:?} queued until slot {}", change, activation_slot);
+    emit!(ParameterChangeQueued {
+        pool: pool.key(),
+        change,
+        activation_slot,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Account Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Token A mint (e.g., wSOL)
+    pub token_a_mint: Account<'info, Mint>,
+
+    /// Token B mint (e.g., slpSOL)
+    pub token_b_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AmmPool::LEN,
+        seeds = [AMM_POOL_SEED, token_a_mint.key().as_ref(), token_b_mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+
+    /// CHECK: PDA used as pool authority for signing
+    #[account(
+        seeds = [AMM_AUTHORITY_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = token_a_mint,
+        token::authority = pool_authority,
+        seeds = [VAULT_A_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub token_a_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = token_b_mint,
+        token::authority = pool_authority,
+        seeds = [VAULT_B_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub token_b_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = 9,
+        mint::authority = pool_authority,
+        mint::freeze_authority = pool_authority,
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
 
     #[account(
         mut,
@@ -675,7 +1334,81 @@ pub struct Swap<'info> {
 }
 
 #[derive(Accounts)]
-pub struct AdminAction<'info> {
+pub struct FlashBorrow<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [AMM_POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+
+    /// CHECK: PDA authority
+    #[account(
+        seeds = [AMM_AUTHORITY_SEED, pool.key().as_ref()],
+        bump = pool.authority_bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Vault to borrow from (token A or token B vault)
+    #[account(
+        mut,
+        constraint = vault.key() == pool.token_a_vault || vault.key() == pool.token_b_vault @ AmmError::InvalidMint
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Receives the borrowed tokens
+    #[account(
+        mut,
+        constraint = user_token_account.mint == vault.mint @ AmmError::InvalidMint
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Instructions sysvar, used to find the matching repay
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct FlashRepay<'info> {
+    pub user: Signer<'info>,
+
+    /// Must stay at index 1: flash_borrow looks for the pool here
+    #[account(
+        mut,
+        seeds = [AMM_POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+
+    /// Vault the loan was taken from
+    #[account(mut)]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Pays back the loan plus fee
+    #[account(
+        mut,
+        constraint = user_token_account.mint == vault.mint @ AmmError::InvalidMint
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct QuotePool<'info> {
+    #[account(
+        seeds = [AMM_POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+}
+
+#[derive(Accounts)]
+pub struct Sync<'info> {
     #[account(
         constraint = authority.key() == pool.authority @ AmmError::InvalidAuthority
     )]
@@ -687,15 +1420,24 @@ pub struct AdminAction<'info> {
         bump = pool.bump
     )]
     pub pool: Account<'info, AmmPool>,
+
+    #[account(
+        constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidMint
+    )]
+    pub token_a_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidMint
+    )]
+    pub token_b_vault: Account<'info, TokenAccount>,
 }
 
 #[derive(Accounts)]
-pub struct CreateLpMetadata<'info> {
+pub struct Skim<'info> {
     #[account(
-        mut,
-        constraint = admin.key() == pool.authority @ AmmError::InvalidAuthority
+        constraint = authority.key() == pool.authority @ AmmError::InvalidAuthority
     )]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
 
     #[account(
         seeds = [AMM_POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
@@ -703,7 +1445,7 @@ pub struct CreateLpMetadata<'info> {
     )]
     pub pool: Account<'info, AmmPool>,
 
-    /// CHECK: Pool authority PDA - mint authority for lp_mint
+    /// CHECK: PDA authority
     #[account(
         seeds = [AMM_AUTHORITY_SEED, pool.key().as_ref()],
         bump = pool.authority_bump
@@ -711,35 +1453,368 @@ pub struct CreateLpMetadata<'info> {
     pub pool_authority: UncheckedAccount<'info>,
 
     #[account(
-        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidMint
+        mut,
+        constraint = token_a_vault.key() == pool.token_a_vault @ AmmError::InvalidMint
     )]
-    pub lp_mint: Account<'info, Mint>,
+    pub token_a_vault: Account<'info, TokenAccount>,
 
-    /// CHECK: Metadata account to be created
-    #[account(mut)]
-    pub metadata: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = token_b_vault.key() == pool.token_b_vault @ AmmError::InvalidMint
+    )]
+    pub token_b_vault: Account<'info, TokenAccount>,
 
-    pub token_metadata_program: Program<'info, Metadata>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    /// Receives the excess token A
+    #[account(
+        mut,
+        constraint = recipient_a.mint == pool.token_a_mint @ AmmError::InvalidMint
+    )]
+    pub recipient_a: Account<'info, TokenAccount>,
 
-// ============================================================================
-// Events
-// ============================================================================
+    /// Receives the excess token B
+    #[account(
+        mut,
+        constraint = recipient_b.mint == pool.token_b_mint @ AmmError::InvalidMint
+    )]
+    pub recipient_b: Account<'info, TokenAccount>,
 
-#[event]
-pub struct PoolInitialized {
-    pub pool: Pubkey,
-    pub token_a_mint: Pubkey,
-    pub token_b_mint: Pubkey,
-    pub fee_bps: u16,
+    pub token_program: Program<'info, Token>,
 }
 
-#[event]
-pub struct LiquidityAdded {
-    pub user: Pubkey,
-    pub amount_a: u64,
+#[derive(Accounts)]
+pub struct AdminAction<'info> {
+    #[account(
+        constraint = authority.key() == pool.authority @ AmmError::InvalidAuthority
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [AMM_POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        constraint = pool.pending_authority != Pubkey::default() @ AmmError::NoPendingAuthority,
+        constraint = new_authority.key() == pool.pending_authority @ AmmError::InvalidAuthority
+    )]
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [AMM_POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+}
+
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Still in the AmmPoolV0 layout, so it can't be loaded as an
+    /// AmmPool; the discriminator, authority and PDA are checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub pool: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateLpMetadata<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == pool.authority @ AmmError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [AMM_POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+
+    /// CHECK: Pool authority PDA - mint authority for lp_mint
+    #[account(
+        seeds = [AMM_AUTHORITY_SEED, pool.key().as_ref()],
+        bump = pool.authority_bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidMint
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// CHECK: Metadata account to be created
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFarm<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == pool.authority @ AmmError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [AMM_POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+
+    /// CHECK: PDA authority, owns the farm vaults
+    #[account(
+        seeds = [AMM_AUTHORITY_SEED, pool.key().as_ref()],
+        bump = pool.authority_bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Farm::LEN,
+        seeds = [FARM_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        constraint = lp_mint.key() == pool.lp_mint @ AmmError::InvalidMint
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    /// Reward token mint
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        token::mint = lp_mint,
+        token::authority = pool_authority,
+        seeds = [FARM_LP_VAULT_SEED, farm.key().as_ref()],
+        bump
+    )]
+    pub lp_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        token::mint = reward_mint,
+        token::authority = pool_authority,
+        seeds = [FARM_REWARD_VAULT_SEED, farm.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct FundFarm<'info> {
+    #[account(
+        constraint = admin.key() == pool.authority @ AmmError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [AMM_POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED, pool.key().as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        constraint = reward_vault.key() == farm.reward_vault @ AmmError::InvalidMint
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = admin_reward_account.mint == farm.reward_mint @ AmmError::InvalidMint,
+        constraint = admin_reward_account.owner == admin.key()
+    )]
+    pub admin_reward_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct StakeLp<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [AMM_POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED, pool.key().as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserLpPosition::LEN,
+        seeds = [USER_LP_POSITION_SEED, pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, UserLpPosition>,
+
+    #[account(
+        mut,
+        constraint = lp_vault.key() == farm.lp_vault @ AmmError::InvalidMint
+    )]
+    pub lp_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_lp_account.mint == pool.lp_mint @ AmmError::InvalidMint,
+        constraint = user_lp_account.owner == user.key()
+    )]
+    pub user_lp_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeLp<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [AMM_POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+
+    /// CHECK: PDA authority
+    #[account(
+        seeds = [AMM_AUTHORITY_SEED, pool.key().as_ref()],
+        bump = pool.authority_bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED, pool.key().as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        seeds = [USER_LP_POSITION_SEED, pool.key().as_ref(), user.key().as_ref()],
+        bump = position.bump,
+        constraint = position.owner == user.key() @ AmmError::InvalidAuthority
+    )]
+    pub position: Account<'info, UserLpPosition>,
+
+    #[account(
+        mut,
+        constraint = lp_vault.key() == farm.lp_vault @ AmmError::InvalidMint
+    )]
+    pub lp_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_lp_account.mint == pool.lp_mint @ AmmError::InvalidMint,
+        constraint = user_lp_account.owner == user.key()
+    )]
+    pub user_lp_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFarmRewards<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [AMM_POOL_SEED, pool.token_a_mint.as_ref(), pool.token_b_mint.as_ref()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+
+    /// CHECK: PDA authority
+    #[account(
+        seeds = [AMM_AUTHORITY_SEED, pool.key().as_ref()],
+        bump = pool.authority_bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED, pool.key().as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        seeds = [USER_LP_POSITION_SEED, pool.key().as_ref(), user.key().as_ref()],
+        bump = position.bump,
+        constraint = position.owner == user.key() @ AmmError::InvalidAuthority
+    )]
+    pub position: Account<'info, UserLpPosition>,
+
+    #[account(
+        mut,
+        constraint = reward_vault.key() == farm.reward_vault @ AmmError::InvalidMint
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_reward_account.mint == farm.reward_mint @ AmmError::InvalidMint,
+        constraint = user_reward_account.owner == user.key()
+    )]
+    pub user_reward_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[event]
+pub struct PoolInitialized {
+    pub pool: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub fee_bps: u16,
+}
+
+#[event]
+pub struct LiquidityAdded {
+    pub user: Pubkey,
+    pub amount_a: u64,
     pub amount_b: u64,
     pub lp_minted: u64,
     pub reserve_a: u64,
@@ -767,3 +1842,124 @@ pub struct Swapped {
     pub reserve_b: u64,
 }
 
+#[event]
+pub struct ReservesSynced {
+    pub pool: Pubkey,
+    pub previous_reserve_a: u64,
+    pub previous_reserve_b: u64,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+}
+
+#[event]
+pub struct Skimmed {
+    pub pool: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+#[event]
+pub struct ParameterChangeQueued {
+    pub pool: Pubkey,
+    pub change: ParameterChange,
+    pub activation_slot: u64,
+}
+
+#[event]
+pub struct ParameterChangeExecuted {
+    pub pool: Pubkey,
+    pub change: ParameterChange,
+}
+
+#[event]
+pub struct ParameterChangeCancelled {
+    pub pool: Pubkey,
+    pub change: ParameterChange,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub pool: Pubkey,
+    pub current_authority: Pubkey,
+    pub proposed_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityAccepted {
+    pub pool: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct PoolMigrated {
+    pub pool: Pubkey,
+    pub previous_len: u32,
+    pub new_len: u32,
+}
+
+#[event]
+pub struct FlashLoanBorrowed {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub is_a: bool,
+}
+
+#[event]
+pub struct FlashLoanRepaid {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub is_a: bool,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+}
+
+#[event]
+pub struct FarmInitialized {
+    pub pool: Pubkey,
+    pub farm: Pubkey,
+    pub reward_mint: Pubkey,
+}
+
+#[event]
+pub struct FarmFunded {
+    pub farm: Pubkey,
+    pub amount: u64,
+    pub reward_rate: u64,
+    pub period_finish: i64,
+    pub acc_reward_per_share: u128,
+}
+
+#[event]
+pub struct LpStaked {
+    pub user: Pubkey,
+    pub farm: Pubkey,
+    pub amount: u64,
+    pub position_lp_tokens: u64,
+    pub pending_rewards: u64,
+    pub total_staked: u64,
+    pub acc_reward_per_share: u128,
+}
+
+#[event]
+pub struct LpUnstaked {
+    pub user: Pubkey,
+    pub farm: Pubkey,
+    pub amount: u64,
+    pub position_lp_tokens: u64,
+    pub pending_rewards: u64,
+    pub total_staked: u64,
+    pub acc_reward_per_share: u128,
+}
+
+#[event]
+pub struct FarmRewardsClaimed {
+    pub user: Pubkey,
+    pub farm: Pubkey,
+    pub amount: u64,
+    pub position_lp_tokens: u64,
+    pub acc_reward_per_share: u128,
+}
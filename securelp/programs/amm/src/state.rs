@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use securelp_math::{curve, lp};
 
 /// Seed for AMM pool config PDA
 pub const AMM_POOL_SEED: &[u8] = b"amm_pool";
@@ -13,11 +14,60 @@ pub const VAULT_A_SEED: &[u8] = b"vault_a";
 pub const VAULT_B_SEED: &[u8] = b"vault_b";
 
 /// Minimum liquidity locked forever to prevent manipulation
-pub const MINIMUM_LIQUIDITY: u64 = 1000;
+pub const MINIMUM_LIQUIDITY: u64 = lp::MINIMUM_LIQUIDITY;
 
 /// Default swap fee (0.3% = 30 bps)
 pub const DEFAULT_FEE_BPS: u16 = 30;
 
+/// Seed for LP farm PDA
+pub const FARM_SEED: &[u8] = b"farm";
+
+/// Seed for the farm's staked LP token vault
+pub const FARM_LP_VAULT_SEED: &[u8] = b"farm_lp_vault";
+
+/// Seed for the farm's reward token vault
+pub const FARM_REWARD_VAULT_SEED: &[u8] = b"farm_reward_vault";
+
+/// Seed for user LP position PDA
+pub const USER_LP_POSITION_SEED: &[u8] = b"lp_position";
+
+/// Scaling factor for the reward-per-share accumulator
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Flash loan fee (0.09% = 9 bps), paid into the reserves for LPs
+pub const FLASH_LOAN_FEE_BPS: u16 = 9;
+
+/// Delay before a queued parameter change can be executed (~1 day of slots)
+pub const PARAMETER_TIMELOCK_SLOTS: u64 = 216_000;
+
+/// Parameter change waiting out the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParameterChange {
+    /// Nothing queued
+    #[default]
+    None,
+    /// Set the fee to a new value
+    Fee { new_fee_bps: u16 },
+    /// Lift an emergency pause
+    Unpause,
+}
+
+/// Result of a swap, as returned by quote_swap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapQuote {
+    /// Tokens the user receives
+    pub amount_out: u64,
+    /// Input tokens taken as the swap fee
+    pub fee_amount: u64,
+}
+
+/// Result of adding liquidity, as returned by quote_add_liquidity
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AddLiquidityQuote {
+    /// LP tokens minted to the user
+    pub lp_minted: u64,
+}
+
 /// AMM Pool configuration
 #[account]
 pub struct AmmPool {
@@ -66,8 +116,23 @@ pub struct AmmPool {
     /// Bump for authority PDA
     pub authority_bump: u8,
 
+    /// Amount currently out on flash loan (0 = no active loan)
+    pub flash_loan_amount: u64,
+
+    /// Whether the active flash loan is in token A (false = token B)
+    pub flash_loan_is_a: bool,
+
+    /// Proposed new authority, awaiting acceptance (default = none)
+    pub pending_authority: Pubkey,
+
+    /// Queued parameter change
+    pub pending_change: ParameterChange,
+
+    /// Slot at which the queued change can be executed
+    pub pending_change_slot: u64,
+
     /// Reserved for future use
-    pub _reserved: [u8; 32],
+    pub _reserved: [u8; 12],
 }
 
 impl Default for AmmPool {
@@ -88,12 +153,40 @@ impl Default for AmmPool {
             cumulative_fee_b: 0,
             bump: 0,
             authority_bump: 0,
-            _reserved: [0u8; 32],
+            flash_loan_amount: 0,
+            flash_loan_is_a: false,
+            pending_authority: Pubkey::default(),
+            pending_change: ParameterChange::None,
+            pending_change_slot: 0,
+            _reserved: [0u8; 12],
         }
     }
 }
 
-impl AmmPool {
+/// AmmPool as laid out before the flash loan, authority transfer and
+/// timelock fields. Pools created then are read in this layout by
+/// migrate_pool and rewritten in the current one.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct AmmPoolV0 {
+    pub authority: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub token_a_vault: Pubkey,
+    pub token_b_vault: Pubkey,
+    pub lp_mint: Pubkey,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    pub total_lp_supply: u64,
+    pub fee_bps: u16,
+    pub paused: bool,
+    pub cumulative_fee_a: u64,
+    pub cumulative_fee_b: u64,
+    pub bump: u8,
+    pub authority_bump: u8,
+    pub _reserved: [u8; 32],
+}
+
+impl AmmPoolV0 {
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
         32 + // token_a_mint
@@ -111,12 +204,58 @@ impl AmmPool {
         1 +  // bump
         1 +  // authority_bump
         32;  // reserved
+}
+
+impl From<AmmPoolV0> for AmmPool {
+    fn from(old: AmmPoolV0) -> Self {
+        Self {
+            authority: old.authority,
+            token_a_mint: old.token_a_mint,
+            token_b_mint: old.token_b_mint,
+            token_a_vault: old.token_a_vault,
+            token_b_vault: old.token_b_vault,
+            lp_mint: old.lp_mint,
+            reserve_a: old.reserve_a,
+            reserve_b: old.reserve_b,
+            total_lp_supply: old.total_lp_supply,
+            fee_bps: old.fee_bps,
+            paused: old.paused,
+            cumulative_fee_a: old.cumulative_fee_a,
+            cumulative_fee_b: old.cumulative_fee_b,
+            bump: old.bump,
+            authority_bump: old.authority_bump,
+            ..Self::default()
+        }
+    }
+}
+
+impl AmmPool {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        32 + // token_a_mint
+        32 + // token_b_mint
+        32 + // token_a_vault
+        32 + // token_b_vault
+        32 + // lp_mint
+        8 +  // reserve_a
+        8 +  // reserve_b
+        8 +  // total_lp_supply
+        2 +  // fee_bps
+        1 +  // paused
+        8 +  // cumulative_fee_a
+        8 +  // cumulative_fee_b
+        1 +  // bump
+        1 +  // authority_bump
+        8 +  // flash_loan_amount
+        1 +  // flash_loan_is_a
+        32 + // pending_authority
+        3 +  // pending_change (tag + u16)
+        8 +  // pending_change_slot
+        12;  // reserved
 
     /// Calculate the constant product K
     pub fn k(&self) -> u128 {
-        (self.reserve_a as u128)
-            .checked_mul(self.reserve_b as u128)
-            .unwrap_or(0)
+        curve::k(self.reserve_a, self.reserve_b)
     }
 
     /// Calculate output amount for a swap using constant product formula
@@ -137,49 +276,17 @@ impl AmmPool {
 
         require!(input_reserve > 0 && output_reserve > 0, super::errors::AmmError::ZeroLiquidity);
 
-        // Apply fee: input_after_fee = input * (10000 - fee_bps) / 10000
-        let fee_multiplier = 10000u64.checked_sub(self.fee_bps as u64)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))?;
-        
-        let input_after_fee = (input_amount as u128)
-            .checked_mul(fee_multiplier as u128)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))?
-            .checked_div(10000)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))? as u64;
-
-        let fee_amount = input_amount.checked_sub(input_after_fee)
+        let output = curve::swap_output(input_amount, input_reserve, output_reserve, self.fee_bps)
             .ok_or(error!(super::errors::AmmError::MathOverflow))?;
 
-        // Calculate output: output = output_reserve * input_after_fee / (input_reserve + input_after_fee)
-        let numerator = (output_reserve as u128)
-            .checked_mul(input_after_fee as u128)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))?;
-
-        let denominator = (input_reserve as u128)
-            .checked_add(input_after_fee as u128)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))?;
-
-        let output_amount = numerator
-            .checked_div(denominator)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))? as u64;
-
-        Ok((output_amount, fee_amount))
+        Ok((output.amount_out, output.fee))
     }
 
     /// Calculate LP tokens to mint for initial liquidity
     pub fn calculate_initial_lp(&self, amount_a: u64, amount_b: u64) -> Result<u64> {
         // Initial LP = sqrt(amount_a * amount_b) - MINIMUM_LIQUIDITY
-        let product = (amount_a as u128)
-            .checked_mul(amount_b as u128)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))?;
-
-        let sqrt = integer_sqrt(product);
-        
-        if sqrt <= MINIMUM_LIQUIDITY as u128 {
-            return Err(error!(super::errors::AmmError::MinimumLiquidityNotMet));
-        }
-
-        Ok((sqrt - MINIMUM_LIQUIDITY as u128) as u64)
+        lp::initial_lp(amount_a, amount_b)
+            .ok_or(error!(super::errors::AmmError::MinimumLiquidityNotMet))
     }
 
     /// Calculate LP tokens to mint for adding liquidity
@@ -193,38 +300,28 @@ impl AmmPool {
         }
 
         // LP tokens = min(amount_a * total_lp / reserve_a, amount_b * total_lp / reserve_b)
-        let lp_from_a = (amount_a as u128)
-            .checked_mul(self.total_lp_supply as u128)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))?
-            .checked_div(self.reserve_a as u128)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))? as u64;
-
-        let lp_from_b = (amount_b as u128)
-            .checked_mul(self.total_lp_supply as u128)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))?
-            .checked_div(self.reserve_b as u128)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))? as u64;
-
-        Ok(lp_from_a.min(lp_from_b))
+        lp::lp_for_liquidity(
+            amount_a,
+            amount_b,
+            self.reserve_a,
+            self.reserve_b,
+            self.total_lp_supply,
+        )
+        .ok_or(error!(super::errors::AmmError::MathOverflow))
     }
 
     /// Calculate tokens to return when removing liquidity
     pub fn calculate_tokens_for_lp(&self, lp_amount: u64) -> Result<(u64, u64)> {
         require!(self.total_lp_supply > 0, super::errors::AmmError::ZeroLiquidity);
 
-        let amount_a = (lp_amount as u128)
-            .checked_mul(self.reserve_a as u128)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))?
-            .checked_div(self.total_lp_supply as u128)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))? as u64;
-
-        let amount_b = (lp_amount as u128)
-            .checked_mul(self.reserve_b as u128)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))?
-            .checked_div(self.total_lp_supply as u128)
-            .ok_or(error!(super::errors::AmmError::MathOverflow))? as u64;
+        lp::tokens_for_lp(lp_amount, self.reserve_a, self.reserve_b, self.total_lp_supply)
+            .ok_or(error!(super::errors::AmmError::MathOverflow))
+    }
 
-        Ok((amount_a, amount_b))
+    /// Fee owed on a flash loan of `amount`, rounded up
+    pub fn flash_loan_fee(&self, amount: u64) -> Result<u64> {
+        curve::flash_loan_fee(amount, FLASH_LOAN_FEE_BPS)
+            .ok_or(error!(super::errors::AmmError::MathOverflow))
     }
 
     /// Get current price of token A in terms of token B
@@ -254,25 +351,9 @@ impl AmmPool {
     }
 }
 
-/// Integer square root using Newton's method
-fn integer_sqrt(n: u128) -> u128 {
-    if n == 0 {
-        return 0;
-    }
-    
-    let mut x = n;
-    let mut y = (x + 1) / 2;
-    
-    while y < x {
-        x = y;
-        y = (x + n / x) / 2;
-    }
-    
-    x
-}
-
-/// User's LP position (optional tracking)
+/// User's LP position staked in the pool's farm
 #[account]
+#[derive(Default)]
 pub struct UserLpPosition {
     /// User's wallet
     pub owner: Pubkey,
@@ -280,7 +361,7 @@ pub struct UserLpPosition {
     /// Pool this position is for
     pub pool: Pubkey,
 
-    /// LP tokens owned
+    /// LP tokens staked in the farm
     pub lp_tokens: u64,
 
     /// Timestamp of first deposit
@@ -289,6 +370,12 @@ pub struct UserLpPosition {
     /// Timestamp of last action
     pub last_action_ts: i64,
 
+    /// Rewards already accounted for (lp_tokens * acc_reward_per_share)
+    pub reward_debt: u128,
+
+    /// Rewards settled but not yet claimed
+    pub pending_rewards: u64,
+
     /// Bump seed
     pub bump: u8,
 
@@ -296,20 +383,6 @@ pub struct UserLpPosition {
     pub _reserved: [u8; 16],
 }
 
-impl Default for UserLpPosition {
-    fn default() -> Self {
-        Self {
-            owner: Pubkey::default(),
-            pool: Pubkey::default(),
-            lp_tokens: 0,
-            first_deposit_ts: 0,
-            last_action_ts: 0,
-            bump: 0,
-            _reserved: [0u8; 16],
-        }
-    }
-}
-
 impl UserLpPosition {
     pub const LEN: usize = 8 + // discriminator
         32 + // owner
@@ -317,7 +390,166 @@ impl UserLpPosition {
         8 +  // lp_tokens
         8 +  // first_deposit_ts
         8 +  // last_action_ts
+        16 + // reward_debt
+        8 +  // pending_rewards
         1 +  // bump
         16;  // reserved
+
+    /// Rewards earned by the current stake at the given accumulator value
+    fn accrued(&self, acc_reward_per_share: u128) -> Result<u128> {
+        (self.lp_tokens as u128)
+            .checked_mul(acc_reward_per_share)
+            .ok_or(error!(super::errors::AmmError::MathOverflow))?
+            .checked_div(REWARD_PRECISION)
+            .ok_or(error!(super::errors::AmmError::MathOverflow))
+    }
+
+    /// Move rewards earned since the last action into pending_rewards
+    pub fn settle(&mut self, acc_reward_per_share: u128) -> Result<()> {
+        let earned = self.accrued(acc_reward_per_share)?
+            .checked_sub(self.reward_debt)
+            .ok_or(error!(super::errors::AmmError::MathOverflow))?;
+
+        self.pending_rewards = self.pending_rewards
+            .checked_add(u64::try_from(earned).map_err(|_| error!(super::errors::AmmError::MathOverflow))?)
+            .ok_or(error!(super::errors::AmmError::MathOverflow))?;
+
+        Ok(())
+    }
+
+    /// Reset reward debt after lp_tokens changes
+    pub fn sync_reward_debt(&mut self, acc_reward_per_share: u128) -> Result<()> {
+        self.reward_debt = self.accrued(acc_reward_per_share)?;
+        Ok(())
+    }
 }
 
+/// LP farm: distributes admin-funded rewards to staked LP tokens
+#[account]
+#[derive(Default)]
+pub struct Farm {
+    /// AMM pool whose LP tokens are staked
+    pub pool: Pubkey,
+
+    /// Reward token mint
+    pub reward_mint: Pubkey,
+
+    /// Vault holding staked LP tokens (PDA-owned)
+    pub lp_vault: Pubkey,
+
+    /// Vault holding undistributed rewards (PDA-owned)
+    pub reward_vault: Pubkey,
+
+    /// Total LP tokens staked in the farm
+    pub total_staked: u64,
+
+    /// Rewards emitted per second during the current period
+    pub reward_rate: u64,
+
+    /// Timestamp when the current reward period ends
+    pub period_finish: i64,
+
+    /// Timestamp the accumulator was last updated
+    pub last_update_ts: i64,
+
+    /// Accumulated rewards per staked LP token (scaled by REWARD_PRECISION)
+    pub acc_reward_per_share: u128,
+
+    /// Bump for this PDA
+    pub bump: u8,
+
+    /// Rewards emitted while nothing was staked, added to the next period
+    pub unallocated_rewards: u64,
+
+    /// Reserved for future use
+    pub _reserved: [u8; 24],
+}
+
+impl Farm {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // reward_mint
+        32 + // lp_vault
+        32 + // reward_vault
+        8 +  // total_staked
+        8 +  // reward_rate
+        8 +  // period_finish
+        8 +  // last_update_ts
+        16 + // acc_reward_per_share
+        1 +  // bump
+        8 +  // unallocated_rewards
+        24;  // reserved
+
+    /// Accrue emissions since last update into the accumulator.
+    /// Emissions while nothing is staked are set aside in
+    /// unallocated_rewards for the next period.
+    pub fn update_rewards(&mut self, now: i64) -> Result<()> {
+        let applicable = now.min(self.period_finish);
+        if applicable <= self.last_update_ts {
+            return Ok(());
+        }
+
+        let elapsed = applicable
+            .checked_sub(self.last_update_ts)
+            .ok_or(error!(super::errors::AmmError::MathOverflow))? as u128;
+        let emitted = elapsed
+            .checked_mul(self.reward_rate as u128)
+            .ok_or(error!(super::errors::AmmError::MathOverflow))?;
+
+        if self.total_staked > 0 {
+            let increment = emitted
+                .checked_mul(REWARD_PRECISION)
+                .ok_or(error!(super::errors::AmmError::MathOverflow))?
+                .checked_div(self.total_staked as u128)
+                .ok_or(error!(super::errors::AmmError::MathOverflow))?;
+
+            self.acc_reward_per_share = self.acc_reward_per_share
+                .checked_add(increment)
+                .ok_or(error!(super::errors::AmmError::MathOverflow))?;
+        } else {
+            self.unallocated_rewards = u64::try_from(emitted)
+                .ok()
+                .and_then(|emitted| self.unallocated_rewards.checked_add(emitted))
+                .ok_or(error!(super::errors::AmmError::MathOverflow))?;
+        }
+
+        self.last_update_ts = applicable;
+        Ok(())
+    }
+
+    /// Start a new reward period, rolling over what the current period has
+    /// left to emit and what it emitted while nothing was staked
+    pub fn notify_reward(&mut self, amount: u64, duration: i64, now: i64) -> Result<()> {
+        require!(duration > 0, super::errors::AmmError::InvalidRewardDuration);
+        self.update_rewards(now)?;
+
+        let leftover = if now < self.period_finish {
+            (self.period_finish
+                .checked_sub(now)
+                .ok_or(error!(super::errors::AmmError::MathOverflow))? as u128)
+                .checked_mul(self.reward_rate as u128)
+                .ok_or(error!(super::errors::AmmError::MathOverflow))?
+        } else {
+            0
+        };
+
+        let rate = (amount as u128)
+            .checked_add(leftover)
+            .ok_or(error!(super::errors::AmmError::MathOverflow))?
+            .checked_add(self.unallocated_rewards as u128)
+            .ok_or(error!(super::errors::AmmError::MathOverflow))?
+            .checked_div(duration as u128)
+            .ok_or(error!(super::errors::AmmError::MathOverflow))?;
+        require!(rate > 0, super::errors::AmmError::InvalidRewardDuration);
+
+        self.reward_rate = u64::try_from(rate)
+            .map_err(|_| error!(super::errors::AmmError::MathOverflow))?;
+        self.unallocated_rewards = 0;
+        self.last_update_ts = now;
+        self.period_finish = now
+            .checked_add(duration)
+            .ok_or(error!(super::errors::AmmError::MathOverflow))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+//! AmmPool's size against the pre-upgrade AmmPoolV0 layout: the flash loan
+//! and timelock fields came out of _reserved, so only pending_authority
+//! grows the account that migrate_pool resizes.
+
+use amm::state::*;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountSerialize, AnchorSerialize};
+
+#[test]
+fn pool_grows_only_by_the_pending_authority() {
+    assert_eq!(AmmPoolV0::LEN, 277);
+    assert_eq!(AmmPool::LEN, AmmPoolV0::LEN + 32);
+}
+
+#[test]
+fn len_matches_the_serialized_accounts() {
+    let old = AmmPoolV0::default().try_to_vec().unwrap();
+    assert_eq!(8 + old.len(), AmmPoolV0::LEN);
+
+    // Fee is the largest ParameterChange variant
+    let pool = AmmPool {
+        pending_authority: Pubkey::new_unique(),
+        pending_change: ParameterChange::Fee { new_fee_bps: u16::MAX },
+        pending_change_slot: u64::MAX,
+        ..Default::default()
+    };
+    let mut data = Vec::new();
+    pool.try_serialize(&mut data).unwrap();
+    assert_eq!(data.len(), AmmPool::LEN);
+}
@@ -0,0 +1,112 @@
+//! Two-step authority transfer for an AMM pool: propose_authority, then
+//! accept_authority signed by the proposed authority.
+//! Needs the SBF build of the program: `cargo test-sbf -p amm`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use amm::errors::AmmError;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+};
+
+const BALANCE: u64 = 1_000_000_000_000;
+
+fn propose_authority_ix(pool: &AmmPoolAccounts, authority: &Pubkey, new_authority: Pubkey) -> Instruction {
+    Instruction {
+        program_id: amm::ID,
+        accounts: amm::accounts::AdminAction {
+            authority: *authority,
+            pool: pool.address,
+        }
+        .to_account_metas(None),
+        data: amm::instruction::ProposeAuthority { new_authority }.data(),
+    }
+}
+
+fn accept_authority_ix(pool: &AmmPoolAccounts, new_authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: amm::ID,
+        accounts: amm::accounts::AcceptAuthority {
+            new_authority: *new_authority,
+            pool: pool.address,
+        }
+        .to_account_metas(None),
+        data: amm::instruction::AcceptAuthority {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn proposed_authority_takes_over_on_accepting() {
+    let (mut context, pool) = setup(30, BALANCE).await;
+    let new_authority = Keypair::new();
+
+    let propose = propose_authority_ix(&pool, &pool.user, new_authority.pubkey());
+    send(&mut context, &[propose], &[]).await.unwrap();
+    let state = pool_state(&mut context, &pool).await;
+    assert_eq!(state.authority, pool.user);
+    assert_eq!(state.pending_authority, new_authority.pubkey());
+
+    let accept = accept_authority_ix(&pool, &new_authority.pubkey());
+    send(&mut context, &[accept], &[&new_authority]).await.unwrap();
+    let state = pool_state(&mut context, &pool).await;
+    assert_eq!(state.authority, new_authority.pubkey());
+    assert_eq!(state.pending_authority, Pubkey::default());
+
+    // Only the new authority can act for the pool now
+    let propose = propose_authority_ix(&pool, &pool.user, pool.user);
+    assert_error(send(&mut context, &[propose], &[]).await, AmmError::InvalidAuthority.into());
+    let propose = propose_authority_ix(&pool, &new_authority.pubkey(), pool.user);
+    send(&mut context, &[propose], &[&new_authority]).await.unwrap();
+}
+
+#[tokio::test]
+async fn only_the_proposed_authority_can_accept() {
+    let (mut context, pool) = setup(30, BALANCE).await;
+    let new_authority = Keypair::new();
+    let impostor = Keypair::new();
+
+    let propose = propose_authority_ix(&pool, &pool.user, new_authority.pubkey());
+    send(&mut context, &[propose], &[]).await.unwrap();
+
+    let accept = accept_authority_ix(&pool, &impostor.pubkey());
+    assert_error(
+        send(&mut context, &[accept], &[&impostor]).await,
+        AmmError::InvalidAuthority.into(),
+    );
+    assert_eq!(pool_state(&mut context, &pool).await.authority, pool.user);
+}
+
+#[tokio::test]
+async fn accepting_with_nothing_proposed_fails() {
+    let (mut context, pool) = setup(30, BALANCE).await;
+    let new_authority = Keypair::new();
+
+    let accept = accept_authority_ix(&pool, &new_authority.pubkey());
+    assert_error(
+        send(&mut context, &[accept], &[&new_authority]).await,
+        AmmError::NoPendingAuthority.into(),
+    );
+}
+
+#[tokio::test]
+async fn proposing_the_default_pubkey_cancels_a_proposal() {
+    let (mut context, pool) = setup(30, BALANCE).await;
+    let new_authority = Keypair::new();
+
+    let propose = propose_authority_ix(&pool, &pool.user, new_authority.pubkey());
+    let cancel = propose_authority_ix(&pool, &pool.user, Pubkey::default());
+    send(&mut context, &[propose, cancel], &[]).await.unwrap();
+    assert_eq!(pool_state(&mut context, &pool).await.pending_authority, Pubkey::default());
+
+    let accept = accept_authority_ix(&pool, &new_authority.pubkey());
+    assert_error(
+        send(&mut context, &[accept], &[&new_authority]).await,
+        AmmError::NoPendingAuthority.into(),
+    );
+    assert_eq!(pool_state(&mut context, &pool).await.authority, pool.user);
+}
@@ -0,0 +1,302 @@
+//! Shared program-test setup for the amm SBF tests.
+#![allow(dead_code, unused_imports)]
+
+use amm::state::*;
+use anchor_lang::prelude::{Clock, Pubkey};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+pub use securelp_integration_tests::{
+    assert_error, create_mint, create_token_account, send, simulate, token_balance, AmmPoolAccounts,
+};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::instruction::Instruction;
+use solana_sdk_ids::{system_program, sysvar};
+
+pub async fn pool_state(context: &mut ProgramTestContext, pool: &AmmPoolAccounts) -> AmmPool {
+    let account = context.banks_client.get_account(pool.address).await.unwrap().unwrap();
+    AmmPool::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+pub async fn farm_state(context: &mut ProgramTestContext, farm: &FarmAccounts) -> Farm {
+    let account = context.banks_client.get_account(farm.address).await.unwrap().unwrap();
+    Farm::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+/// Move the clock forward and fetch a fresh blockhash, so a repeated
+/// instruction isn't rejected as an already processed transaction
+pub async fn advance_clock(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+    context.get_new_latest_blockhash().await.unwrap();
+}
+
+/// Start program-test, create both mints and an AMM pool between them, and
+/// give the payer `balance` of each token to trade and provide liquidity with
+pub async fn setup(fee_bps: u16, balance: u64) -> (ProgramTestContext, AmmPoolAccounts) {
+    start(ProgramTest::new("amm", amm::ID, None), fee_bps, balance).await
+}
+
+/// Like [`setup`], on a program-test that already has amm and whatever else
+/// the test needs
+pub async fn start(program_test: ProgramTest, fee_bps: u16, balance: u64) -> (ProgramTestContext, AmmPoolAccounts) {
+    let mut context = program_test.start_with_context().await;
+    let pool = securelp_integration_tests::create_amm_pool(&mut context, fee_bps, balance).await;
+    (context, pool)
+}
+
+/// Admin instruction on the pool, signed by the user as pool authority
+pub fn admin_ix(pool: &AmmPoolAccounts, data: Vec<u8>) -> Instruction {
+    Instruction {
+        program_id: amm::ID,
+        accounts: amm::accounts::AdminAction {
+            authority: pool.user,
+            pool: pool.address,
+        }
+        .to_account_metas(None),
+        data,
+    }
+}
+
+/// Warp past PARAMETER_TIMELOCK_SLOTS so a queued change can be executed
+pub async fn wait_out_the_timelock(context: &mut ProgramTestContext) {
+    let slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(slot + PARAMETER_TIMELOCK_SLOTS + 1).unwrap();
+}
+
+/// Borrow `amount` of token A (or B) into the user's account
+pub fn flash_borrow_ix(pool: &AmmPoolAccounts, amount: u64, token_a: bool) -> Instruction {
+    let (vault, user_token_account) = if token_a {
+        (pool.token_a_vault, pool.user_token_a)
+    } else {
+        (pool.token_b_vault, pool.user_token_b)
+    };
+    Instruction {
+        program_id: amm::ID,
+        accounts: amm::accounts::FlashBorrow {
+            user: pool.user,
+            pool: pool.address,
+            pool_authority: pool.authority,
+            vault,
+            user_token_account,
+            instructions: sysvar::instructions::ID,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: amm::instruction::FlashBorrow { amount }.data(),
+    }
+}
+
+/// Repay the active loan in token A (or B) from the user's account
+pub fn flash_repay_ix(pool: &AmmPoolAccounts, token_a: bool) -> Instruction {
+    let (vault, user_token_account) = if token_a {
+        (pool.token_a_vault, pool.user_token_a)
+    } else {
+        (pool.token_b_vault, pool.user_token_b)
+    };
+    Instruction {
+        program_id: amm::ID,
+        accounts: amm::accounts::FlashRepay {
+            user: pool.user,
+            pool: pool.address,
+            vault,
+            user_token_account,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: amm::instruction::FlashRepay {}.data(),
+    }
+}
+
+pub fn quote_swap_ix(pool: &AmmPoolAccounts, amount_in: u64, a_to_b: bool) -> Instruction {
+    Instruction {
+        program_id: amm::ID,
+        accounts: amm::accounts::QuotePool { pool: pool.address }.to_account_metas(None),
+        data: amm::instruction::QuoteSwap { amount_in, a_to_b }.data(),
+    }
+}
+
+pub fn quote_add_liquidity_ix(pool: &AmmPoolAccounts, amount_a: u64, amount_b: u64) -> Instruction {
+    Instruction {
+        program_id: amm::ID,
+        accounts: amm::accounts::QuotePool { pool: pool.address }.to_account_metas(None),
+        data: amm::instruction::QuoteAddLiquidity { amount_a, amount_b }.data(),
+    }
+}
+
+/// Set the reserves to the vault balances, signed by `authority`
+pub fn sync_ix(pool: &AmmPoolAccounts, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: amm::ID,
+        accounts: amm::accounts::Sync {
+            authority: *authority,
+            pool: pool.address,
+            token_a_vault: pool.token_a_vault,
+            token_b_vault: pool.token_b_vault,
+        }
+        .to_account_metas(None),
+        data: amm::instruction::Sync {}.data(),
+    }
+}
+
+/// Send the vaults' excess over the reserves back to the user's accounts
+pub fn skim_ix(pool: &AmmPoolAccounts) -> Instruction {
+    Instruction {
+        program_id: amm::ID,
+        accounts: amm::accounts::Skim {
+            authority: pool.user,
+            pool: pool.address,
+            pool_authority: pool.authority,
+            token_a_vault: pool.token_a_vault,
+            token_b_vault: pool.token_b_vault,
+            recipient_a: pool.user_token_a,
+            recipient_b: pool.user_token_b,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: amm::instruction::Skim {}.data(),
+    }
+}
+
+/// Transfer `amount` of token A straight into the pool's vault
+pub fn donate_ix(pool: &AmmPoolAccounts, amount: u64) -> Instruction {
+    spl_token::instruction::transfer(
+        &spl_token::ID,
+        &pool.user_token_a,
+        &pool.token_a_vault,
+        &pool.user,
+        &[],
+        amount,
+    )
+    .unwrap()
+}
+
+/// A pool's LP farm, with the payer as both admin and staker
+#[derive(Clone, Copy, Debug)]
+pub struct FarmAccounts {
+    pub address: Pubkey,
+    pub reward_mint: Pubkey,
+    pub lp_vault: Pubkey,
+    pub reward_vault: Pubkey,
+    pub position: Pubkey,
+    pub user_rewards: Pubkey,
+}
+
+impl FarmAccounts {
+    pub fn new(pool: &AmmPoolAccounts, reward_mint: Pubkey, user_rewards: Pubkey) -> Self {
+        let address = Pubkey::find_program_address(&[FARM_SEED, pool.address.as_ref()], &amm::ID).0;
+        Self {
+            address,
+            reward_mint,
+            lp_vault: Pubkey::find_program_address(&[FARM_LP_VAULT_SEED, address.as_ref()], &amm::ID).0,
+            reward_vault: Pubkey::find_program_address(&[FARM_REWARD_VAULT_SEED, address.as_ref()], &amm::ID).0,
+            position: Pubkey::find_program_address(
+                &[USER_LP_POSITION_SEED, pool.address.as_ref(), pool.user.as_ref()],
+                &amm::ID,
+            )
+            .0,
+            user_rewards,
+        }
+    }
+
+    pub fn initialize_ix(&self, pool: &AmmPoolAccounts) -> Instruction {
+        Instruction {
+            program_id: amm::ID,
+            accounts: amm::accounts::InitializeFarm {
+                admin: pool.user,
+                pool: pool.address,
+                pool_authority: pool.authority,
+                farm: self.address,
+                lp_mint: pool.lp_mint,
+                reward_mint: self.reward_mint,
+                lp_vault: self.lp_vault,
+                reward_vault: self.reward_vault,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+                rent: sysvar::rent::ID,
+            }
+            .to_account_metas(None),
+            data: amm::instruction::InitializeFarm {}.data(),
+        }
+    }
+
+    pub fn fund_ix(&self, pool: &AmmPoolAccounts, amount: u64, duration: i64) -> Instruction {
+        Instruction {
+            program_id: amm::ID,
+            accounts: amm::accounts::FundFarm {
+                admin: pool.user,
+                pool: pool.address,
+                farm: self.address,
+                reward_vault: self.reward_vault,
+                admin_reward_account: self.user_rewards,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: amm::instruction::FundFarm { amount, duration }.data(),
+        }
+    }
+
+    pub fn stake_ix(&self, pool: &AmmPoolAccounts, amount: u64) -> Instruction {
+        Instruction {
+            program_id: amm::ID,
+            accounts: amm::accounts::StakeLp {
+                user: pool.user,
+                pool: pool.address,
+                farm: self.address,
+                position: self.position,
+                lp_vault: self.lp_vault,
+                user_lp_account: pool.user_lp,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: amm::instruction::StakeLp { amount }.data(),
+        }
+    }
+
+    pub fn unstake_ix(&self, pool: &AmmPoolAccounts, amount: u64) -> Instruction {
+        Instruction {
+            program_id: amm::ID,
+            accounts: amm::accounts::UnstakeLp {
+                user: pool.user,
+                pool: pool.address,
+                pool_authority: pool.authority,
+                farm: self.address,
+                position: self.position,
+                lp_vault: self.lp_vault,
+                user_lp_account: pool.user_lp,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: amm::instruction::UnstakeLp { amount }.data(),
+        }
+    }
+
+    pub fn claim_ix(&self, pool: &AmmPoolAccounts) -> Instruction {
+        Instruction {
+            program_id: amm::ID,
+            accounts: amm::accounts::ClaimFarmRewards {
+                user: pool.user,
+                pool: pool.address,
+                pool_authority: pool.authority,
+                farm: self.address,
+                position: self.position,
+                reward_vault: self.reward_vault,
+                user_reward_account: self.user_rewards,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: amm::instruction::ClaimFarmRewards {}.data(),
+        }
+    }
+}
+
+/// Create a reward mint, give the payer `rewards` of it and initialize the
+/// pool's farm
+pub async fn create_farm(context: &mut ProgramTestContext, pool: &AmmPoolAccounts, rewards: u64) -> FarmAccounts {
+    let reward_mint = create_mint(context).await;
+    let user_rewards = create_token_account(context, &reward_mint, &pool.user, rewards).await;
+    let farm = FarmAccounts::new(pool, reward_mint, user_rewards);
+    send(context, &[farm.initialize_ix(pool)], &[]).await.unwrap();
+    farm
+}
@@ -0,0 +1,113 @@
+//! LP farming in program-test: staking, unstaking and claiming against a
+//! funded reward period, and funding a farm that is already running.
+//! Needs the SBF build of the program: `cargo test-sbf -p amm`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use amm::errors::AmmError;
+use common::*;
+use solana_program_test::ProgramTestContext;
+
+const BALANCE: u64 = 1_000_000_000_000;
+const LIQUIDITY: u64 = 100_000_000_000;
+const STAKE: u64 = 50_000_000_000;
+const REWARDS: u64 = 10_000_000;
+const DURATION: i64 = 1_000;
+
+/// Each accrual rounds down by less than one reward token
+const ROUNDING: u64 = 5;
+
+async fn farm_setup() -> (ProgramTestContext, AmmPoolAccounts, FarmAccounts) {
+    let (mut context, pool) = setup(30, BALANCE).await;
+    send(&mut context, &[pool.add_liquidity_ix(LIQUIDITY, LIQUIDITY, 0)], &[]).await.unwrap();
+    let farm = create_farm(&mut context, &pool, REWARDS).await;
+    (context, pool, farm)
+}
+
+/// Claim and return how much the claim paid out
+async fn claim(context: &mut ProgramTestContext, pool: &AmmPoolAccounts, farm: &FarmAccounts) -> u64 {
+    let before = token_balance(context, &farm.user_rewards).await;
+    send(context, &[farm.claim_ix(pool)], &[]).await.unwrap();
+    token_balance(context, &farm.user_rewards).await - before
+}
+
+fn assert_paid_out(claimed: u64, funded: u64) {
+    assert!(claimed <= funded, "claimed {claimed} of {funded}");
+    assert!(claimed + ROUNDING >= funded, "claimed {claimed} of {funded}");
+}
+
+#[tokio::test]
+async fn stakers_earn_the_funded_rewards_over_the_period() {
+    let (mut context, pool, farm) = farm_setup().await;
+    let lp_before = token_balance(&mut context, &pool.user_lp).await;
+
+    send(&mut context, &[farm.stake_ix(&pool, STAKE)], &[]).await.unwrap();
+    send(&mut context, &[farm.fund_ix(&pool, 1_000_000, DURATION)], &[]).await.unwrap();
+    assert_eq!(farm_state(&mut context, &farm).await.reward_rate, 1_000);
+    assert_eq!(token_balance(&mut context, &pool.user_lp).await, lp_before - STAKE);
+
+    // Part way through the period the staker has earned its share so far
+    advance_clock(&mut context, DURATION * 2 / 5).await;
+    let first = claim(&mut context, &pool, &farm).await;
+    assert!((400_000 - ROUNDING..=410_000).contains(&first), "claimed {first}");
+
+    // A smaller stake keeps earning the whole emission as the only staker,
+    // and nothing accrues past the end of the period
+    send(&mut context, &[farm.unstake_ix(&pool, STAKE / 2)], &[]).await.unwrap();
+    advance_clock(&mut context, DURATION * 2).await;
+    let second = claim(&mut context, &pool, &farm).await;
+    assert_paid_out(first + second, 1_000_000);
+
+    advance_clock(&mut context, DURATION).await;
+    assert_error(
+        send(&mut context, &[farm.claim_ix(&pool)], &[]).await,
+        AmmError::NoRewardsToClaim.into(),
+    );
+
+    assert_error(
+        send(&mut context, &[farm.unstake_ix(&pool, STAKE)], &[]).await,
+        AmmError::InsufficientStakedLp.into(),
+    );
+    send(&mut context, &[farm.unstake_ix(&pool, STAKE / 2)], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &pool.user_lp).await, lp_before);
+    assert_eq!(farm_state(&mut context, &farm).await.total_staked, 0);
+}
+
+#[tokio::test]
+async fn rewards_left_to_emit_roll_into_a_new_funding() {
+    let (mut context, pool, farm) = farm_setup().await;
+
+    send(&mut context, &[farm.stake_ix(&pool, STAKE)], &[]).await.unwrap();
+    send(&mut context, &[farm.fund_ix(&pool, 1_000_000, DURATION)], &[]).await.unwrap();
+    advance_clock(&mut context, DURATION * 2 / 5).await;
+
+    // Funding again restarts the period with what the first one had left
+    send(&mut context, &[farm.fund_ix(&pool, 600_000, DURATION)], &[]).await.unwrap();
+    let state = farm_state(&mut context, &farm).await;
+    assert!((1_190..=1_200).contains(&state.reward_rate), "rate {}", state.reward_rate);
+
+    advance_clock(&mut context, DURATION * 2).await;
+    let claimed = claim(&mut context, &pool, &farm).await;
+    assert_paid_out(claimed, 1_600_000);
+    assert!(token_balance(&mut context, &farm.reward_vault).await <= ROUNDING);
+}
+
+#[tokio::test]
+async fn rewards_emitted_with_nothing_staked_roll_into_the_next_period() {
+    let (mut context, pool, farm) = farm_setup().await;
+
+    send(&mut context, &[farm.fund_ix(&pool, 1_000_000, DURATION)], &[]).await.unwrap();
+    advance_clock(&mut context, DURATION * 2).await;
+    send(&mut context, &[farm.stake_ix(&pool, STAKE)], &[]).await.unwrap();
+    assert_eq!(farm_state(&mut context, &farm).await.unallocated_rewards, 1_000_000);
+
+    send(&mut context, &[farm.fund_ix(&pool, 100_000, DURATION)], &[]).await.unwrap();
+    let state = farm_state(&mut context, &farm).await;
+    assert_eq!(state.reward_rate, 1_100);
+    assert_eq!(state.unallocated_rewards, 0);
+
+    advance_clock(&mut context, DURATION * 2).await;
+    let claimed = claim(&mut context, &pool, &farm).await;
+    assert_paid_out(claimed, 1_100_000);
+}
@@ -0,0 +1,146 @@
+//! flash_borrow and flash_repay in program-test: a loan must be repaid to the
+//! same pool later in the same top-level transaction, the pool is locked
+//! while it is out, and the fee is added to the reserves.
+//! Needs the SBF build of the program: `cargo test-sbf -p amm`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use amm::errors::AmmError;
+use anchor_lang::solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::AccountMeta,
+    program::invoke,
+};
+use common::*;
+use solana_program_test::{processor, ProgramTest};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+const BALANCE: u64 = 1_000_000_000_000;
+const LIQUIDITY: u64 = 100_000_000_000;
+const LOAN: u64 = 10_000_000_000;
+
+async fn seeded_pool() -> (solana_program_test::ProgramTestContext, AmmPoolAccounts) {
+    let (mut context, pool) = setup(30, BALANCE).await;
+    send(&mut context, &[pool.add_liquidity_ix(LIQUIDITY, LIQUIDITY, 0)], &[]).await.unwrap();
+    (context, pool)
+}
+
+/// Calls the program in its first account with the remaining accounts and
+/// its own instruction data
+fn forward(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (program, accounts) = accounts.split_first().unwrap();
+    let instruction = Instruction {
+        program_id: *program.key,
+        accounts: accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: data.to_vec(),
+    };
+    invoke(&instruction, accounts)
+}
+
+#[tokio::test]
+async fn repaying_a_loan_adds_the_fee_to_the_reserves() {
+    let (mut context, pool) = seeded_pool().await;
+    let before = pool_state(&mut context, &pool).await;
+    let user_before = token_balance(&mut context, &pool.user_token_a).await;
+
+    let borrow = flash_borrow_ix(&pool, LOAN, true);
+    let repay = flash_repay_ix(&pool, true);
+    send(&mut context, &[borrow, repay], &[]).await.unwrap();
+
+    let fee = before.flash_loan_fee(LOAN).unwrap();
+    assert!(fee > 0);
+    let after = pool_state(&mut context, &pool).await;
+    assert_eq!(after.reserve_a, before.reserve_a + fee);
+    assert_eq!(after.reserve_b, before.reserve_b);
+    assert_eq!(after.cumulative_fee_a, before.cumulative_fee_a + fee);
+    assert_eq!(after.flash_loan_amount, 0);
+    assert_eq!(token_balance(&mut context, &pool.token_a_vault).await, after.reserve_a);
+    assert_eq!(token_balance(&mut context, &pool.user_token_a).await, user_before - fee);
+
+    // Token B loans pay their fee into reserve_b
+    let borrow = flash_borrow_ix(&pool, LOAN, false);
+    let repay = flash_repay_ix(&pool, false);
+    send(&mut context, &[borrow, repay], &[]).await.unwrap();
+    let after_b = pool_state(&mut context, &pool).await;
+    assert_eq!(after_b.reserve_a, after.reserve_a);
+    assert_eq!(after_b.reserve_b, after.reserve_b + fee);
+    assert_eq!(token_balance(&mut context, &pool.token_b_vault).await, after_b.reserve_b);
+}
+
+#[tokio::test]
+async fn a_borrow_without_a_repay_fails() {
+    let (mut context, pool) = seeded_pool().await;
+
+    let borrow = flash_borrow_ix(&pool, LOAN, true);
+    assert_error(send(&mut context, &[borrow], &[]).await, AmmError::FlashRepayMissing.into());
+}
+
+#[tokio::test]
+async fn a_repay_to_another_pool_does_not_count() {
+    let (mut context, pool) = seeded_pool().await;
+    let other = securelp_integration_tests::create_amm_pool(&mut context, 30, BALANCE).await;
+    send(&mut context, &[other.add_liquidity_ix(LIQUIDITY, LIQUIDITY, 0)], &[]).await.unwrap();
+
+    let borrow = flash_borrow_ix(&pool, LOAN, true);
+    let repay = flash_repay_ix(&other, true);
+    assert_error(send(&mut context, &[borrow, repay], &[]).await, AmmError::FlashRepayMissing.into());
+}
+
+#[tokio::test]
+async fn a_borrow_through_cpi_fails() {
+    let forwarder = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new("amm", amm::ID, None);
+    program_test.prefer_bpf(false);
+    program_test.add_program("forwarder", forwarder, processor!(forward));
+    let (mut context, pool) = start(program_test, 30, BALANCE).await;
+    send(&mut context, &[pool.add_liquidity_ix(LIQUIDITY, LIQUIDITY, 0)], &[]).await.unwrap();
+
+    let borrow = flash_borrow_ix(&pool, LOAN, true);
+    let mut accounts = vec![AccountMeta::new_readonly(amm::ID, false)];
+    accounts.extend(borrow.accounts);
+    let forwarded = Instruction {
+        program_id: forwarder,
+        accounts,
+        data: borrow.data,
+    };
+    let repay = flash_repay_ix(&pool, true);
+    assert_error(
+        send(&mut context, &[forwarded, repay], &[]).await,
+        AmmError::FlashLoanCpiNotAllowed.into(),
+    );
+}
+
+#[tokio::test]
+async fn the_pool_is_locked_while_a_loan_is_out() {
+    let (mut context, pool) = seeded_pool().await;
+
+    let lp_balance = token_balance(&mut context, &pool.user_lp).await;
+    for locked in [
+        pool.swap_ix(1_000_000, 0, true),
+        pool.add_liquidity_ix(1_000_000, 1_000_000, 0),
+        pool.remove_liquidity_ix(lp_balance / 10, 0, 0),
+        flash_borrow_ix(&pool, LOAN, false),
+    ] {
+        context.get_new_latest_blockhash().await.unwrap();
+        let borrow = flash_borrow_ix(&pool, LOAN, true);
+        let repay = flash_repay_ix(&pool, true);
+        assert_error(
+            send(&mut context, &[borrow, locked, repay], &[]).await,
+            AmmError::FlashLoanActive.into(),
+        );
+    }
+
+    // None of it landed
+    let state = pool_state(&mut context, &pool).await;
+    assert_eq!((state.reserve_a, state.reserve_b), (LIQUIDITY, LIQUIDITY));
+    assert_eq!(state.flash_loan_amount, 0);
+}
@@ -0,0 +1,104 @@
+//! Property tests running random swap and liquidity sequences through the amm
+//! handlers in program-test. Needs the SBF build of the program:
+//! `cargo test-sbf -p amm`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use amm::state::MINIMUM_LIQUIDITY;
+use common::*;
+use proptest::prelude::*;
+use solana_program_test::ProgramTestContext;
+
+const BALANCE: u64 = 1_000_000_000_000_000;
+
+#[derive(Debug, Clone)]
+enum Action {
+    Swap { amount_in: u64, a_to_b: bool },
+    AddLiquidity { amount_a: u64, amount_b: u64 },
+    /// Burn this share (in bps) of the payer's LP tokens
+    RemoveLiquidity { lp_bps: u16 },
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        (1..BALANCE / 1_000, any::<bool>())
+            .prop_map(|(amount_in, a_to_b)| Action::Swap { amount_in, a_to_b }),
+        (1..BALANCE / 1_000, 1..BALANCE / 1_000)
+            .prop_map(|(amount_a, amount_b)| Action::AddLiquidity { amount_a, amount_b }),
+        (1u16..=10_000).prop_map(|lp_bps| Action::RemoveLiquidity { lp_bps }),
+    ]
+}
+
+/// The pool's booked reserves must match what its vaults hold
+async fn assert_reserves_match_vaults(context: &mut ProgramTestContext, pool: &AmmPoolAccounts) {
+    let state = pool_state(context, pool).await;
+    assert_eq!(state.reserve_a, token_balance(context, &pool.token_a_vault).await);
+    assert_eq!(state.reserve_b, token_balance(context, &pool.token_b_vault).await);
+}
+
+async fn run(initial_a: u64, initial_b: u64, actions: Vec<Action>) {
+    let (mut context, pool) = setup(30, BALANCE).await;
+    let add = pool.add_liquidity_ix(initial_a, initial_b, 0);
+    send(&mut context, &[add], &[]).await.unwrap();
+
+    for action in actions {
+        let before = pool_state(&mut context, &pool).await;
+        let lp_before = token_balance(&mut context, &pool.user_lp).await;
+        let a_before = token_balance(&mut context, &pool.user_token_a).await;
+        let b_before = token_balance(&mut context, &pool.user_token_b).await;
+
+        let instruction = match action {
+            Action::Swap { amount_in, a_to_b } => pool.swap_ix(amount_in, 0, a_to_b),
+            Action::AddLiquidity { amount_a, amount_b } => {
+                pool.add_liquidity_ix(amount_a, amount_b, 0)
+            }
+            Action::RemoveLiquidity { lp_bps } => {
+                let lp_amount = (lp_before as u128 * lp_bps as u128 / 10_000) as u64;
+                pool.remove_liquidity_ix(lp_amount, 0, 0)
+            }
+        };
+        // Rejected actions (zero output, empty burns) must leave the pool as it was
+        context.get_new_latest_blockhash().await.unwrap();
+        if send(&mut context, &[instruction], &[]).await.is_err() {
+            assert_eq!(pool_state(&mut context, &pool).await.k(), before.k());
+            continue;
+        }
+
+        let after = pool_state(&mut context, &pool).await;
+        assert_reserves_match_vaults(&mut context, &pool).await;
+        assert!(after.total_lp_supply >= MINIMUM_LIQUIDITY);
+
+        if let Action::Swap { .. } = action {
+            assert!(after.k() >= before.k(), "{action:?} lowered k");
+        }
+        if let Action::AddLiquidity { amount_a, amount_b } = action {
+            // Burning what was just minted never pays out more than was added
+            let minted = token_balance(&mut context, &pool.user_lp).await - lp_before;
+            let (out_a, out_b) = after.calculate_tokens_for_lp(minted).unwrap();
+            assert!(out_a <= amount_a && out_b <= amount_b, "{action:?} round trip profits");
+        }
+        if let Action::RemoveLiquidity { .. } = action {
+            let a_out = token_balance(&mut context, &pool.user_token_a).await - a_before;
+            let b_out = token_balance(&mut context, &pool.user_token_b).await - b_before;
+            assert_eq!((a_out, b_out), (before.reserve_a - after.reserve_a, before.reserve_b - after.reserve_b));
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn handlers_keep_the_pool_solvent(
+        initial_a in 1_000_000..BALANCE / 100,
+        initial_b in 1_000_000..BALANCE / 100,
+        actions in prop::collection::vec(action(), 1..12),
+    ) {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(run(initial_a, initial_b, actions));
+    }
+}
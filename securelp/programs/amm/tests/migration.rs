@@ -0,0 +1,134 @@
+//! Upgrading pools created before the flash loan, authority transfer and
+//! timelock fields: migrate_pool rewrites an AmmPoolV0 account in the
+//! current AmmPool layout.
+//! Needs the SBF build of the program: `cargo test-sbf -p amm`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use amm::errors::AmmError;
+use amm::state::{AmmPool, AmmPoolV0, ParameterChange};
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::{Pubkey, Rent};
+use anchor_lang::{AnchorSerialize, Discriminator, InstructionData, ToAccountMetas};
+use common::*;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+};
+use solana_sdk_ids::system_program;
+
+const BALANCE: u64 = 1_000_000_000_000;
+
+fn migrate_pool_ix(pool: &AmmPoolAccounts, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: amm::ID,
+        accounts: amm::accounts::MigratePool {
+            authority: *authority,
+            pool: pool.address,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: amm::instruction::MigratePool {}.data(),
+    }
+}
+
+/// Rewrite the pool account as a pool created before the upgrade would be
+/// laid out, rent-exempt at the old size
+async fn downgrade(context: &mut ProgramTestContext, pool: &AmmPoolAccounts) -> AmmPool {
+    let state = pool_state(context, pool).await;
+    let old = AmmPoolV0 {
+        authority: state.authority,
+        token_a_mint: state.token_a_mint,
+        token_b_mint: state.token_b_mint,
+        token_a_vault: state.token_a_vault,
+        token_b_vault: state.token_b_vault,
+        lp_mint: state.lp_mint,
+        reserve_a: state.reserve_a,
+        reserve_b: state.reserve_b,
+        total_lp_supply: state.total_lp_supply,
+        fee_bps: state.fee_bps,
+        paused: state.paused,
+        cumulative_fee_a: state.cumulative_fee_a,
+        cumulative_fee_b: state.cumulative_fee_b,
+        bump: state.bump,
+        authority_bump: state.authority_bump,
+        _reserved: [0; 32],
+    };
+
+    let mut account = context.banks_client.get_account(pool.address).await.unwrap().unwrap();
+    account.data = AmmPool::DISCRIMINATOR.to_vec();
+    old.serialize(&mut account.data).unwrap();
+    assert_eq!(account.data.len(), AmmPoolV0::LEN);
+    account.lamports = Rent::default().minimum_balance(AmmPoolV0::LEN);
+    context.set_account(&pool.address, &account.into());
+
+    state
+}
+
+#[tokio::test]
+async fn old_pools_are_migrated_to_the_current_layout() {
+    let (mut context, pool) = setup(30, BALANCE).await;
+    send(&mut context, &[pool.add_liquidity_ix(BALANCE / 10, BALANCE / 10, 0)], &[]).await.unwrap();
+    send(&mut context, &[pool.swap_ix(1_000_000_000, 0, true)], &[]).await.unwrap();
+    let before = downgrade(&mut context, &pool).await;
+
+    // The old layout can't be loaded until it is migrated
+    assert_error(
+        send(&mut context, &[pool.swap_ix(2_000_000_000, 0, true)], &[]).await,
+        ErrorCode::AccountDidNotDeserialize.into(),
+    );
+
+    send(&mut context, &[migrate_pool_ix(&pool, &pool.user)], &[]).await.unwrap();
+
+    let account = context.banks_client.get_account(pool.address).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), AmmPool::LEN);
+    assert!(account.lamports >= Rent::default().minimum_balance(AmmPool::LEN));
+
+    let state = pool_state(&mut context, &pool).await;
+    assert_eq!(state.authority, before.authority);
+    assert_eq!(state.lp_mint, before.lp_mint);
+    assert_eq!((state.reserve_a, state.reserve_b), (before.reserve_a, before.reserve_b));
+    assert_eq!(state.total_lp_supply, before.total_lp_supply);
+    assert_eq!(state.fee_bps, 30);
+    assert_eq!(state.cumulative_fee_a, before.cumulative_fee_a);
+    assert_eq!(state.flash_loan_amount, 0);
+    assert_eq!(state.pending_authority, Pubkey::default());
+    assert_eq!(state.pending_change, ParameterChange::None);
+
+    // Trading and the new admin flows work on the migrated pool
+    send(&mut context, &[pool.swap_ix(3_000_000_000, 0, true)], &[]).await.unwrap();
+    let update_fee = admin_ix(&pool, amm::instruction::UpdateFee { new_fee_bps: 50 }.data());
+    send(&mut context, &[update_fee], &[]).await.unwrap();
+    assert_eq!(
+        pool_state(&mut context, &pool).await.pending_change,
+        ParameterChange::Fee { new_fee_bps: 50 }
+    );
+}
+
+#[tokio::test]
+async fn only_the_authority_can_migrate_and_only_once() {
+    let (mut context, pool) = setup(30, BALANCE).await;
+
+    // A pool already in the current layout has nothing to migrate
+    assert_error(
+        send(&mut context, &[migrate_pool_ix(&pool, &pool.user)], &[]).await,
+        AmmError::PoolAlreadyMigrated.into(),
+    );
+
+    downgrade(&mut context, &pool).await;
+    context.get_new_latest_blockhash().await.unwrap();
+    let stranger = Keypair::new();
+    assert_error(
+        send(&mut context, &[migrate_pool_ix(&pool, &stranger.pubkey())], &[&stranger]).await,
+        AmmError::InvalidAuthority.into(),
+    );
+
+    send(&mut context, &[migrate_pool_ix(&pool, &pool.user)], &[]).await.unwrap();
+    context.get_new_latest_blockhash().await.unwrap();
+    assert_error(
+        send(&mut context, &[migrate_pool_ix(&pool, &pool.user)], &[]).await,
+        AmmError::PoolAlreadyMigrated.into(),
+    );
+}
@@ -0,0 +1,203 @@
+//! Property tests for AmmPool and Farm state math: the pool updates the
+//! handlers make must never lower k or let LPs take out more than they put in,
+//! and no input may panic.
+
+use amm::state::*;
+use proptest::prelude::*;
+
+const MAX_RESERVE: u64 = 1 << 60;
+
+#[derive(Debug, Clone)]
+enum Action {
+    Swap { amount_in: u64, a_to_b: bool },
+    AddLiquidity { amount_a: u64, amount_b: u64 },
+    RemoveLiquidity { lp_amount: u64 },
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        (1..MAX_RESERVE >> 4, any::<bool>())
+            .prop_map(|(amount_in, a_to_b)| Action::Swap { amount_in, a_to_b }),
+        (1..MAX_RESERVE >> 4, 1..MAX_RESERVE >> 4)
+            .prop_map(|(amount_a, amount_b)| Action::AddLiquidity { amount_a, amount_b }),
+        (1..MAX_RESERVE >> 4).prop_map(|lp_amount| Action::RemoveLiquidity { lp_amount }),
+    ]
+}
+
+/// Pool after a first deposit, as initialize_pool and add_liquidity leave it
+fn seeded_pool(amount_a: u64, amount_b: u64, fee_bps: u16) -> Option<AmmPool> {
+    let mut pool = AmmPool {
+        fee_bps,
+        ..Default::default()
+    };
+    let lp = pool.calculate_lp_tokens_for_liquidity(amount_a, amount_b).ok()?;
+    pool.reserve_a = amount_a;
+    pool.reserve_b = amount_b;
+    pool.total_lp_supply = MINIMUM_LIQUIDITY + lp;
+    Some(pool)
+}
+
+/// Apply an action the way its handler updates the pool; false if the
+/// handler would reject it
+fn apply(pool: &mut AmmPool, action: &Action) -> bool {
+    match *action {
+        Action::Swap { amount_in, a_to_b } => {
+            let Ok((amount_out, _)) = pool.calculate_swap_output(amount_in, a_to_b) else {
+                return false;
+            };
+            if amount_out == 0 {
+                return false;
+            }
+            let (reserve_in, reserve_out) = if a_to_b {
+                (&mut pool.reserve_a, &mut pool.reserve_b)
+            } else {
+                (&mut pool.reserve_b, &mut pool.reserve_a)
+            };
+            *reserve_in += amount_in;
+            *reserve_out -= amount_out;
+        }
+        Action::AddLiquidity { amount_a, amount_b } => {
+            let Ok(lp) = pool.calculate_lp_tokens_for_liquidity(amount_a, amount_b) else {
+                return false;
+            };
+            pool.reserve_a += amount_a;
+            pool.reserve_b += amount_b;
+            pool.total_lp_supply += lp;
+        }
+        Action::RemoveLiquidity { lp_amount } => {
+            // The locked minimum can never be burned
+            if lp_amount > pool.total_lp_supply - MINIMUM_LIQUIDITY {
+                return false;
+            }
+            let (amount_a, amount_b) = pool.calculate_tokens_for_lp(lp_amount).unwrap();
+            pool.reserve_a -= amount_a;
+            pool.reserve_b -= amount_b;
+            pool.total_lp_supply -= lp_amount;
+        }
+    }
+    true
+}
+
+proptest! {
+    #[test]
+    fn k_never_decreases_across_swaps(
+        reserve_a in MINIMUM_LIQUIDITY..MAX_RESERVE,
+        reserve_b in MINIMUM_LIQUIDITY..MAX_RESERVE,
+        fee_bps in 0u16..=1000,
+        swaps in prop::collection::vec((1..MAX_RESERVE >> 4, any::<bool>()), 1..32),
+    ) {
+        let Some(mut pool) = seeded_pool(reserve_a, reserve_b, fee_bps) else {
+            return Ok(());
+        };
+
+        for (amount_in, a_to_b) in swaps {
+            let k_before = pool.k();
+            apply(&mut pool, &Action::Swap { amount_in, a_to_b });
+            prop_assert!(pool.k() >= k_before);
+            prop_assert!(pool.reserve_a > 0 && pool.reserve_b > 0);
+        }
+    }
+
+    #[test]
+    fn lp_round_trip_never_profits(
+        reserve_a in MINIMUM_LIQUIDITY..MAX_RESERVE,
+        reserve_b in MINIMUM_LIQUIDITY..MAX_RESERVE,
+        actions in prop::collection::vec(action(), 0..16),
+        amount_a in 1..MAX_RESERVE >> 4,
+        amount_b in 1..MAX_RESERVE >> 4,
+    ) {
+        let Some(mut pool) = seeded_pool(reserve_a, reserve_b, DEFAULT_FEE_BPS) else {
+            return Ok(());
+        };
+        for action in &actions {
+            apply(&mut pool, action);
+        }
+
+        let Ok(lp) = pool.calculate_lp_tokens_for_liquidity(amount_a, amount_b) else {
+            return Ok(());
+        };
+        apply(&mut pool, &Action::AddLiquidity { amount_a, amount_b });
+        let (out_a, out_b) = pool.calculate_tokens_for_lp(lp).unwrap();
+
+        prop_assert!(out_a <= amount_a);
+        prop_assert!(out_b <= amount_b);
+    }
+
+    #[test]
+    fn first_depositor_cannot_withdraw_the_locked_minimum(
+        amount_a in 1..MAX_RESERVE,
+        amount_b in 1..MAX_RESERVE,
+    ) {
+        let Some(pool) = seeded_pool(amount_a, amount_b, DEFAULT_FEE_BPS) else {
+            return Ok(());
+        };
+
+        let (out_a, out_b) = pool
+            .calculate_tokens_for_lp(pool.total_lp_supply - MINIMUM_LIQUIDITY)
+            .unwrap();
+        prop_assert!(out_a < amount_a || out_b < amount_b);
+    }
+
+    #[test]
+    fn pool_math_never_panics(
+        reserve_a: u64,
+        reserve_b: u64,
+        total_lp_supply: u64,
+        fee_bps: u16,
+        amount_a: u64,
+        amount_b: u64,
+        a_to_b: bool,
+    ) {
+        let pool = AmmPool {
+            reserve_a,
+            reserve_b,
+            total_lp_supply,
+            fee_bps,
+            ..Default::default()
+        };
+
+        let _ = pool.k();
+        let _ = pool.price_a_in_b();
+        let _ = pool.price_b_in_a();
+        let _ = pool.calculate_swap_output(amount_a, a_to_b);
+        let _ = pool.calculate_initial_lp(amount_a, amount_b);
+        let _ = pool.calculate_lp_tokens_for_liquidity(amount_a, amount_b);
+        let _ = pool.calculate_tokens_for_lp(amount_a);
+        let _ = pool.flash_loan_fee(amount_a);
+    }
+
+    #[test]
+    fn farm_math_never_panics(
+        total_staked: u64,
+        reward_rate: u64,
+        period_finish: i64,
+        last_update_ts: i64,
+        acc_reward_per_share: u128,
+        unallocated_rewards: u64,
+        now: i64,
+        amount: u64,
+        duration: i64,
+        lp_tokens: u64,
+        reward_debt: u128,
+    ) {
+        let mut farm = Farm {
+            total_staked,
+            reward_rate,
+            period_finish,
+            last_update_ts,
+            acc_reward_per_share,
+            unallocated_rewards,
+            ..Default::default()
+        };
+        let _ = farm.update_rewards(now);
+        let _ = farm.notify_reward(amount, duration, now);
+
+        let mut position = UserLpPosition {
+            lp_tokens,
+            reward_debt,
+            ..Default::default()
+        };
+        let _ = position.settle(acc_reward_per_share);
+        let _ = position.sync_reward_debt(acc_reward_per_share);
+    }
+}
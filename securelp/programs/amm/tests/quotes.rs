@@ -0,0 +1,65 @@
+//! quote_swap and quote_add_liquidity in program-test: simulating a quote
+//! returns exactly what the instruction it quotes then pays out.
+//! Needs the SBF build of the program: `cargo test-sbf -p amm`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use amm::state::{AddLiquidityQuote, SwapQuote, MINIMUM_LIQUIDITY};
+use common::*;
+use solana_program_test::ProgramTestContext;
+
+const BALANCE: u64 = 1_000_000_000_000;
+const LIQUIDITY: u64 = 100_000_000_000;
+
+async fn seeded_pool() -> (ProgramTestContext, AmmPoolAccounts) {
+    let (mut context, pool) = setup(30, BALANCE).await;
+    send(&mut context, &[pool.add_liquidity_ix(LIQUIDITY, LIQUIDITY, 0)], &[]).await.unwrap();
+    (context, pool)
+}
+
+#[tokio::test]
+async fn first_deposit_quote_matches_the_minted_lp() {
+    let (mut context, pool) = setup(30, BALANCE).await;
+
+    let quote_ix = quote_add_liquidity_ix(&pool, LIQUIDITY, 4 * LIQUIDITY);
+    let quote: AddLiquidityQuote = simulate(&mut context, quote_ix).await;
+    send(&mut context, &[pool.add_liquidity_ix(LIQUIDITY, 4 * LIQUIDITY, 0)], &[]).await.unwrap();
+
+    // sqrt(a * b) less the locked minimum
+    assert_eq!(quote.lp_minted, 2 * LIQUIDITY - MINIMUM_LIQUIDITY);
+    assert_eq!(token_balance(&mut context, &pool.user_lp).await, quote.lp_minted);
+}
+
+#[tokio::test]
+async fn add_liquidity_quote_matches_the_minted_lp() {
+    let (mut context, pool) = seeded_pool().await;
+    let lp_before = token_balance(&mut context, &pool.user_lp).await;
+
+    // Unbalanced amounts mint for the smaller side
+    let (amount_a, amount_b) = (LIQUIDITY / 3, LIQUIDITY / 2);
+    let quote_ix = quote_add_liquidity_ix(&pool, amount_a, amount_b);
+    let quote: AddLiquidityQuote = simulate(&mut context, quote_ix).await;
+    send(&mut context, &[pool.add_liquidity_ix(amount_a, amount_b, 0)], &[]).await.unwrap();
+
+    assert!(quote.lp_minted > 0);
+    assert_eq!(token_balance(&mut context, &pool.user_lp).await - lp_before, quote.lp_minted);
+}
+
+#[tokio::test]
+async fn swap_quote_matches_the_swap() {
+    let (mut context, pool) = seeded_pool().await;
+
+    for (amount_in, a_to_b) in [(LIQUIDITY / 10, true), (LIQUIDITY / 7, false), (1_000, true)] {
+        let out_account = if a_to_b { pool.user_token_b } else { pool.user_token_a };
+        let out_before = token_balance(&mut context, &out_account).await;
+        let before = pool_state(&mut context, &pool).await;
+
+        let quote: SwapQuote = simulate(&mut context, quote_swap_ix(&pool, amount_in, a_to_b)).await;
+        send(&mut context, &[pool.swap_ix(amount_in, quote.amount_out, a_to_b)], &[]).await.unwrap();
+
+        assert_eq!(token_balance(&mut context, &out_account).await - out_before, quote.amount_out);
+        let expected = before.calculate_swap_output(amount_in, a_to_b).unwrap();
+        assert_eq!(expected, (quote.amount_out, quote.fee_amount));
+    }
+}
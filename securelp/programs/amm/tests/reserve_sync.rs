@@ -0,0 +1,84 @@
+//! sync and skim in program-test: tokens sent straight to a vault are either
+//! folded into the reserves or returned, only by the admin, and never while
+//! a flash loan is out.
+//! Needs the SBF build of the program: `cargo test-sbf -p amm`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use amm::errors::AmmError;
+use common::*;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+const BALANCE: u64 = 1_000_000_000_000;
+const LIQUIDITY: u64 = 100_000_000_000;
+const DONATION: u64 = 1_000_000_000;
+
+async fn seeded_pool() -> (ProgramTestContext, AmmPoolAccounts) {
+    let (mut context, pool) = setup(30, BALANCE).await;
+    send(&mut context, &[pool.add_liquidity_ix(LIQUIDITY, LIQUIDITY, 0)], &[]).await.unwrap();
+    (context, pool)
+}
+
+#[tokio::test]
+async fn sync_adds_a_donation_to_the_reserves() {
+    let (mut context, pool) = seeded_pool().await;
+
+    let sync = sync_ix(&pool, &pool.user);
+    send(&mut context, &[donate_ix(&pool, DONATION), sync], &[]).await.unwrap();
+
+    let state = pool_state(&mut context, &pool).await;
+    assert_eq!(state.reserve_a, LIQUIDITY + DONATION);
+    assert_eq!(state.reserve_b, LIQUIDITY);
+    assert_eq!(token_balance(&mut context, &pool.token_a_vault).await, state.reserve_a);
+}
+
+#[tokio::test]
+async fn skim_returns_a_donation() {
+    let (mut context, pool) = seeded_pool().await;
+    let before = token_balance(&mut context, &pool.user_token_a).await;
+
+    send(&mut context, &[donate_ix(&pool, DONATION), skim_ix(&pool)], &[]).await.unwrap();
+
+    assert_eq!(token_balance(&mut context, &pool.user_token_a).await, before);
+    assert_eq!(token_balance(&mut context, &pool.token_a_vault).await, LIQUIDITY);
+    assert_eq!(pool_state(&mut context, &pool).await.reserve_a, LIQUIDITY);
+}
+
+#[tokio::test]
+async fn only_the_admin_can_sync() {
+    let (mut context, pool) = seeded_pool().await;
+    send(&mut context, &[donate_ix(&pool, DONATION)], &[]).await.unwrap();
+
+    let outsider = Keypair::new();
+    let sync = sync_ix(&pool, &outsider.pubkey());
+    assert_error(
+        send(&mut context, &[sync], &[&outsider]).await,
+        AmmError::InvalidAuthority.into(),
+    );
+    assert_eq!(pool_state(&mut context, &pool).await.reserve_a, LIQUIDITY);
+
+    // The admin can still return the donation
+    send(&mut context, &[skim_ix(&pool)], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &pool.token_a_vault).await, LIQUIDITY);
+}
+
+#[tokio::test]
+async fn sync_and_skim_are_blocked_while_a_loan_is_out() {
+    let (mut context, pool) = seeded_pool().await;
+
+    // The lent tokens would otherwise show up as a shortfall
+    let borrow = flash_borrow_ix(&pool, DONATION, true);
+    let repay = flash_repay_ix(&pool, true);
+    let sync = sync_ix(&pool, &pool.user);
+    assert_error(
+        send(&mut context, &[borrow.clone(), sync, repay.clone()], &[]).await,
+        AmmError::FlashLoanActive.into(),
+    );
+    assert_error(
+        send(&mut context, &[borrow, skim_ix(&pool), repay], &[]).await,
+        AmmError::FlashLoanActive.into(),
+    );
+    assert_eq!(pool_state(&mut context, &pool).await.reserve_a, LIQUIDITY);
+}
@@ -0,0 +1,71 @@
+//! AmmPool's swap and LP math is the shared securelp-math curve, the same
+//! code the MEV simulator prices swaps with.
+
+use amm::state::*;
+use proptest::prelude::*;
+use securelp_math::{curve, lp};
+
+const MAX_RESERVE: u64 = 1 << 60;
+
+fn pool(reserve_a: u64, reserve_b: u64, fee_bps: u16, total_lp_supply: u64) -> AmmPool {
+    AmmPool {
+        reserve_a,
+        reserve_b,
+        fee_bps,
+        total_lp_supply,
+        ..Default::default()
+    }
+}
+
+proptest! {
+    #[test]
+    fn swap_output_matches_the_shared_curve(
+        reserve_a in 1..MAX_RESERVE,
+        reserve_b in 1..MAX_RESERVE,
+        amount_in in 0..MAX_RESERVE,
+        fee_bps in 0u16..=1000,
+        a_to_b: bool,
+    ) {
+        let pool = pool(reserve_a, reserve_b, fee_bps, 0);
+        let (reserve_in, reserve_out) = if a_to_b {
+            (reserve_a, reserve_b)
+        } else {
+            (reserve_b, reserve_a)
+        };
+
+        let expected = curve::swap_output(amount_in, reserve_in, reserve_out, fee_bps).unwrap();
+        prop_assert_eq!(
+            pool.calculate_swap_output(amount_in, a_to_b).unwrap(),
+            (expected.amount_out, expected.fee)
+        );
+    }
+
+    #[test]
+    fn lp_math_matches_the_shared_curve(
+        reserve_a in 1..MAX_RESERVE,
+        reserve_b in 1..MAX_RESERVE,
+        total_lp_supply in 1..MAX_RESERVE,
+        amount_a in 0..MAX_RESERVE,
+        amount_b in 0..MAX_RESERVE,
+    ) {
+        let pool = pool(reserve_a, reserve_b, DEFAULT_FEE_BPS, total_lp_supply);
+
+        prop_assert_eq!(
+            pool.calculate_lp_tokens_for_liquidity(amount_a, amount_b).ok(),
+            lp::lp_for_liquidity(amount_a, amount_b, reserve_a, reserve_b, total_lp_supply)
+        );
+        prop_assert_eq!(
+            pool.calculate_tokens_for_lp(amount_a).ok(),
+            lp::tokens_for_lp(amount_a, reserve_a, reserve_b, total_lp_supply)
+        );
+        prop_assert_eq!(
+            pool.flash_loan_fee(amount_a).unwrap(),
+            curve::flash_loan_fee(amount_a, FLASH_LOAN_FEE_BPS).unwrap()
+        );
+    }
+}
+
+#[test]
+fn empty_pool_has_no_liquidity_to_swap_against() {
+    assert!(pool(0, 1_000, DEFAULT_FEE_BPS, 0).calculate_swap_output(1_000, true).is_err());
+}
@@ -0,0 +1,113 @@
+//! Timelocked parameter changes for an AMM pool: update_fee and unpausing
+//! queue a change that execute_parameter_change applies once
+//! PARAMETER_TIMELOCK_SLOTS have passed.
+//! Needs the SBF build of the program: `cargo test-sbf -p amm`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use amm::errors::AmmError;
+use amm::state::ParameterChange;
+use anchor_lang::InstructionData;
+use common::*;
+use solana_sdk::instruction::Instruction;
+
+const BALANCE: u64 = 1_000_000_000_000;
+
+fn update_fee_ix(pool: &AmmPoolAccounts, new_fee_bps: u16) -> Instruction {
+    admin_ix(pool, amm::instruction::UpdateFee { new_fee_bps }.data())
+}
+
+fn set_paused_ix(pool: &AmmPoolAccounts, paused: bool) -> Instruction {
+    admin_ix(pool, amm::instruction::SetPaused { paused }.data())
+}
+
+fn execute_ix(pool: &AmmPoolAccounts) -> Instruction {
+    admin_ix(pool, amm::instruction::ExecuteParameterChange {}.data())
+}
+
+fn cancel_ix(pool: &AmmPoolAccounts) -> Instruction {
+    admin_ix(pool, amm::instruction::CancelParameterChange {}.data())
+}
+
+#[tokio::test]
+async fn fee_change_waits_for_the_timelock() {
+    let (mut context, pool) = setup(30, BALANCE).await;
+
+    send(&mut context, &[update_fee_ix(&pool, 50)], &[]).await.unwrap();
+    let state = pool_state(&mut context, &pool).await;
+    assert_eq!(state.pending_change, ParameterChange::Fee { new_fee_bps: 50 });
+
+    assert_error(
+        send(&mut context, &[execute_ix(&pool)], &[]).await,
+        AmmError::TimelockNotElapsed.into(),
+    );
+    assert_eq!(pool_state(&mut context, &pool).await.fee_bps, 30);
+
+    wait_out_the_timelock(&mut context).await;
+    send(&mut context, &[execute_ix(&pool)], &[]).await.unwrap();
+
+    let state = pool_state(&mut context, &pool).await;
+    assert_eq!(state.fee_bps, 50);
+    assert_eq!(state.pending_change, ParameterChange::None);
+    assert_eq!(state.pending_change_slot, 0);
+}
+
+#[tokio::test]
+async fn only_one_change_can_be_queued() {
+    let (mut context, pool) = setup(30, BALANCE).await;
+
+    send(&mut context, &[update_fee_ix(&pool, 50)], &[]).await.unwrap();
+    assert_error(
+        send(&mut context, &[update_fee_ix(&pool, 100)], &[]).await,
+        AmmError::ParameterChangePending.into(),
+    );
+
+    // Pausing is immediate, but queueing the unpause has to wait its turn
+    send(&mut context, &[set_paused_ix(&pool, true)], &[]).await.unwrap();
+    assert_error(
+        send(&mut context, &[set_paused_ix(&pool, false)], &[]).await,
+        AmmError::ParameterChangePending.into(),
+    );
+}
+
+#[tokio::test]
+async fn queued_change_can_be_cancelled() {
+    let (mut context, pool) = setup(30, BALANCE).await;
+
+    send(&mut context, &[update_fee_ix(&pool, 50), cancel_ix(&pool)], &[]).await.unwrap();
+    assert_eq!(pool_state(&mut context, &pool).await.pending_change, ParameterChange::None);
+
+    wait_out_the_timelock(&mut context).await;
+    assert_error(
+        send(&mut context, &[execute_ix(&pool)], &[]).await,
+        AmmError::NoPendingParameterChange.into(),
+    );
+    assert_error(
+        send(&mut context, &[cancel_ix(&pool)], &[]).await,
+        AmmError::NoPendingParameterChange.into(),
+    );
+    assert_eq!(pool_state(&mut context, &pool).await.fee_bps, 30);
+}
+
+#[tokio::test]
+async fn emergency_pause_clears_a_queued_unpause() {
+    let (mut context, pool) = setup(30, BALANCE).await;
+
+    let queue_unpause = [set_paused_ix(&pool, true), set_paused_ix(&pool, false)];
+    send(&mut context, &queue_unpause, &[]).await.unwrap();
+    let state = pool_state(&mut context, &pool).await;
+    assert!(state.paused);
+    assert_eq!(state.pending_change, ParameterChange::Unpause);
+
+    send(&mut context, &[set_paused_ix(&pool, true)], &[]).await.unwrap();
+    assert_eq!(pool_state(&mut context, &pool).await.pending_change, ParameterChange::None);
+
+    // The old unpause can't be executed once its timelock would have passed
+    wait_out_the_timelock(&mut context).await;
+    assert_error(
+        send(&mut context, &[execute_ix(&pool)], &[]).await,
+        AmmError::NoPendingParameterChange.into(),
+    );
+    assert!(pool_state(&mut context, &pool).await.paused);
+}
@@ -38,4 +38,12 @@ pub enum SecureLPError {
     /// Insufficient balance for the operation
     #[msg("Insufficient balance for this operation.")]
     InsufficientBalance,
+
+    /// Output below the committed minimum
+    #[msg("Output below the committed minimum.")]
+    MinOutputNotMet,
+
+    /// Commitment is older than the reveal window (5 minutes)
+    #[msg("Commitment expired. Cancel it and commit again.")]
+    CommitmentExpired,
 }
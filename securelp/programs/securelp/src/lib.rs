@@ -1,6 +1,5 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Mint};
-use sha2::{Sha256, Digest};
 
 pub mod errors;
 pub mod state;
@@ -9,10 +8,13 @@ use errors::SecureLPError;
 use state::{config, Commitment, SwapDetails};
 
 // Import CPI modules from stake_pool and amm
-use stake_pool::cpi::accounts::{DepositSol, WithdrawSol};
-use stake_pool::cpi::{deposit_sol, withdraw_sol};
+use stake_pool::cpi::accounts::{DepositSol, RequestWithdrawal, WithdrawSol};
+use stake_pool::cpi::{deposit_sol, request_withdrawal, withdraw_sol};
 use stake_pool::program::StakePool;
-use stake_pool::state::{PoolConfig, POOL_CONFIG_SEED, POOL_AUTHORITY_SEED, RESERVE_VAULT_SEED};
+use stake_pool::state::{
+    PoolConfig, WithdrawalTicket, POOL_CONFIG_SEED, POOL_AUTHORITY_SEED, RESERVE_VAULT_SEED,
+    WITHDRAWAL_TICKET_SEED,
+};
 
 use amm::cpi::accounts::Swap as AmmSwapAccounts;
 use amm::cpi::swap as amm_swap;
@@ -68,7 +70,8 @@ pub mod securelp {
     /// Reveal and Stake: Verify commitment and execute SOL -> slpSOL deposit
     /// 
     /// This instruction:
-    /// 1. Verifies the minimum delay has passed since commit
+    /// 1. Verifies the minimum delay has passed since commit, and the
+    ///    commitment hasn't expired
     /// 2. Verifies the hash matches the provided SwapDetails
     /// 3. Executes stake_pool deposit via CPI
     /// 4. Closes the commitment PDA (returns rent to user)
@@ -79,19 +82,12 @@ pub mod securelp {
         let commitment = &ctx.accounts.commitment;
         let clock = Clock::get()?;
 
-        // Step 1: Verify minimum delay has passed
-        require!(
-            clock.unix_timestamp >= commitment.timestamp + config::MIN_DELAY_SECONDS,
-            SecureLPError::DelayNotMet
-        );
+        // Step 1: Verify minimum delay has passed and the commitment hasn't expired
+        check_reveal_window(commitment, &clock)?;
 
         // Step 2: Verify hash matches
-        let serialized = details.try_to_vec().map_err(|_| SecureLPError::HashMismatch)?;
-        let mut hasher = Sha256::new();
-        hasher.update(&serialized);
-        let computed_hash: [u8; 32] = hasher.finalize().into();
         require!(
-            computed_hash == commitment.hash,
+            details.hash() == commitment.hash,
             SecureLPError::HashMismatch
         );
 
@@ -110,6 +106,9 @@ pub mod securelp {
             reserve_vault: ctx.accounts.reserve_vault.to_account_info(),
             slp_mint: ctx.accounts.slp_mint.to_account_info(),
             user_slp_account: ctx.accounts.user_slp_account.to_account_info(),
+            fee_recipient: ctx.accounts.fee_recipient.as_ref().map(|a| a.to_account_info()),
+            referrer: ctx.accounts.referrer.as_ref().map(|a| a.to_account_info()),
+            user_stake: ctx.accounts.user_stake.as_ref().map(|a| a.to_account_info()),
             token_program: ctx.accounts.token_program.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
         };
@@ -136,7 +135,8 @@ pub mod securelp {
     /// Reveal and Unstake: Verify commitment and execute slpSOL -> SOL withdrawal
     /// 
     /// This instruction:
-    /// 1. Verifies the minimum delay has passed since commit
+    /// 1. Verifies the minimum delay has passed since commit, and the
+    ///    commitment hasn't expired
     /// 2. Verifies the hash matches the provided SwapDetails
     /// 3. Executes stake_pool withdrawal via CPI
     /// 4. Closes the commitment PDA (returns rent to user)
@@ -147,19 +147,12 @@ pub mod securelp {
         let commitment = &ctx.accounts.commitment;
         let clock = Clock::get()?;
 
-        // Step 1: Verify minimum delay has passed
-        require!(
-            clock.unix_timestamp >= commitment.timestamp + config::MIN_DELAY_SECONDS,
-            SecureLPError::DelayNotMet
-        );
+        // Step 1: Verify minimum delay has passed and the commitment hasn't expired
+        check_reveal_window(commitment, &clock)?;
 
         // Step 2: Verify hash matches
-        let serialized = details.try_to_vec().map_err(|_| SecureLPError::HashMismatch)?;
-        let mut hasher = Sha256::new();
-        hasher.update(&serialized);
-        let computed_hash: [u8; 32] = hasher.finalize().into();
         require!(
-            computed_hash == commitment.hash,
+            details.hash() == commitment.hash,
             SecureLPError::HashMismatch
         );
 
@@ -177,6 +170,8 @@ pub mod securelp {
             reserve_vault: ctx.accounts.reserve_vault.to_account_info(),
             slp_mint: ctx.accounts.slp_mint.to_account_info(),
             user_slp_account: ctx.accounts.user_slp_account.to_account_info(),
+            fee_recipient: ctx.accounts.fee_recipient.as_ref().map(|a| a.to_account_info()),
+            user_stake: ctx.accounts.user_stake.as_ref().map(|a| a.to_account_info()),
             token_program: ctx.accounts.token_program.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
         };
@@ -200,10 +195,85 @@ pub mod securelp {
         Ok(())
     }
 
+    /// Reveal and Request Withdrawal: Verify commitment and open a delayed-unstake ticket
+    /// 
+    /// This instruction:
+    /// 1. Verifies the minimum delay has passed since commit, and the
+    ///    commitment hasn't expired
+    /// 2. Verifies the hash matches the provided SwapDetails
+    /// 3. Executes stake_pool request_withdrawal via CPI
+    /// 4. Checks the ticket is worth at least min_out lamports
+    /// 5. Closes the commitment PDA (returns rent to user)
+    pub fn reveal_and_request_withdrawal(
+        ctx: Context<RevealAndRequestWithdrawal>,
+        details: SwapDetails,
+        ticket_id: u64,
+    ) -> Result<()> {
+        let commitment = &ctx.accounts.commitment;
+        let clock = Clock::get()?;
+
+        // Step 1: Verify minimum delay has passed and the commitment hasn't expired
+        check_reveal_window(commitment, &clock)?;
+
+        // Step 2: Verify hash matches
+        require!(
+            details.hash() == commitment.hash,
+            SecureLPError::HashMismatch
+        );
+
+        // Step 3: Validate slippage
+        require!(
+            details.slippage_bps <= SwapDetails::MAX_SLIPPAGE_BPS,
+            SecureLPError::SlippageTooHigh
+        );
+
+        // Step 4: Execute stake_pool request_withdrawal via CPI
+        let cpi_program = ctx.accounts.stake_pool_program.to_account_info();
+        let cpi_accounts = RequestWithdrawal {
+            user: ctx.accounts.user.to_account_info(),
+            pool_config: ctx.accounts.pool_config.to_account_info(),
+            withdrawal_ticket: ctx.accounts.withdrawal_ticket.to_account_info(),
+            slp_mint: ctx.accounts.slp_mint.to_account_info(),
+            user_slp_account: ctx.accounts.user_slp_account.to_account_info(),
+            fee_recipient: ctx.accounts.fee_recipient.as_ref().map(|a| a.to_account_info()),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        request_withdrawal(cpi_ctx, details.amount_in, ticket_id)?;
+
+        // Step 5: The ticket's SOL is fixed now, so enforce min_out
+        let ticket_data = ctx.accounts.withdrawal_ticket.try_borrow_data()?;
+        let ticket = WithdrawalTicket::try_deserialize(&mut &ticket_data[..])?;
+        require!(
+            ticket.lamports >= details.min_out,
+            SecureLPError::MinOutputNotMet
+        );
+
+        msg!(
+            "Withdrawal requested: user={}, slp_amount={}, ticket={}",
+            ctx.accounts.user.key(),
+            details.amount_in,
+            ctx.accounts.withdrawal_ticket.key()
+        );
+
+        // Emit event for indexing
+        emit!(WithdrawalRequestEvent {
+            user: ctx.accounts.user.key(),
+            ticket: ctx.accounts.withdrawal_ticket.key(),
+            amount_in: details.amount_in,
+            min_out: details.min_out,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
     /// Reveal and Swap: Verify commitment and execute AMM swap
     /// 
     /// This instruction:
-    /// 1. Verifies the minimum delay has passed since commit
+    /// 1. Verifies the minimum delay has passed since commit, and the
+    ///    commitment hasn't expired
     /// 2. Verifies the hash matches the provided SwapDetails
     /// 3. Executes AMM swap via CPI
     /// 4. Closes the commitment PDA (returns rent to user)
@@ -215,19 +285,12 @@ pub mod securelp {
         let commitment = &ctx.accounts.commitment;
         let clock = Clock::get()?;
 
-        // Step 1: Verify minimum delay has passed
-        require!(
-            clock.unix_timestamp >= commitment.timestamp + config::MIN_DELAY_SECONDS,
-            SecureLPError::DelayNotMet
-        );
+        // Step 1: Verify minimum delay has passed and the commitment hasn't expired
+        check_reveal_window(commitment, &clock)?;
 
         // Step 2: Verify hash matches
-        let serialized = details.try_to_vec().map_err(|_| SecureLPError::HashMismatch)?;
-        let mut hasher = Sha256::new();
-        hasher.update(&serialized);
-        let computed_hash: [u8; 32] = hasher.finalize().into();
         require!(
-            computed_hash == commitment.hash,
+            details.hash() == commitment.hash,
             SecureLPError::HashMismatch
         );
 
@@ -275,6 +338,7 @@ pub mod securelp {
     /// Cancel Commitment: Allow user to cancel their commitment and reclaim rent
     /// 
     /// This can only be called by the original user who created the commitment.
+    /// Expired commitments can only be cancelled.
     pub fn cancel_commitment(ctx: Context<CancelCommitment>) -> Result<()> {
         msg!(
             "Commitment cancelled: user={}",
@@ -284,6 +348,24 @@ pub mod securelp {
     }
 }
 
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// A commitment can be revealed from MIN_DELAY_SECONDS after it was made
+/// until MAX_DELAY_SECONDS after it was made
+fn check_reveal_window(commitment: &Commitment, clock: &Clock) -> Result<()> {
+    require!(
+        clock.unix_timestamp >= commitment.timestamp + config::MIN_DELAY_SECONDS,
+        SecureLPError::DelayNotMet
+    );
+    require!(
+        clock.unix_timestamp <= commitment.timestamp + config::MAX_DELAY_SECONDS,
+        SecureLPError::CommitmentExpired
+    );
+    Ok(())
+}
+
 // ============================================================================
 // ACCOUNT STRUCTS
 // ============================================================================
@@ -373,6 +455,21 @@ pub struct RevealAndStake<'info> {
     )]
     pub user_slp_account: Account<'info, TokenAccount>,
 
+    /// Pool's slpSOL fee account (validated by stake_pool)
+    /// CHECK: Must match pool_config.fee_recipient; checked in the CPI
+    #[account(mut)]
+    pub fee_recipient: Option<UncheckedAccount<'info>>,
+
+    /// Referring partner's slpSOL account (validated by stake_pool)
+    /// CHECK: Must be an slpSOL token account; checked in the CPI
+    #[account(mut)]
+    pub referrer: Option<UncheckedAccount<'info>>,
+
+    /// User's stake position (validated by stake_pool)
+    /// CHECK: Must be the user's UserStake PDA; checked in the CPI
+    #[account(mut)]
+    pub user_stake: Option<UncheckedAccount<'info>>,
+
     /// Token program
     pub token_program: Program<'info, Token>,
 
@@ -436,6 +533,90 @@ pub struct RevealAndUnstake<'info> {
     )]
     pub user_slp_account: Account<'info, TokenAccount>,
 
+    /// Pool's slpSOL fee account (validated by stake_pool)
+    /// CHECK: Must match pool_config.fee_recipient; checked in the CPI
+    #[account(mut)]
+    pub fee_recipient: Option<UncheckedAccount<'info>>,
+
+    /// User's stake position (validated by stake_pool)
+    /// CHECK: Must be the user's UserStake PDA; checked in the CPI
+    #[account(mut)]
+    pub user_stake: Option<UncheckedAccount<'info>>,
+
+    /// Token program
+    pub token_program: Program<'info, Token>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for the reveal_and_request_withdrawal instruction
+#[derive(Accounts)]
+#[instruction(details: SwapDetails, ticket_id: u64)]
+pub struct RevealAndRequestWithdrawal<'info> {
+    /// The commitment PDA to verify and close
+    #[account(
+        mut,
+        seeds = [Commitment::SEED_PREFIX, user.key().as_ref()],
+        bump = commitment.bump,
+        constraint = commitment.user == user.key() @ SecureLPError::CommitmentNotFound,
+        constraint = !commitment.is_stake @ SecureLPError::CommitmentNotFound,
+        close = user
+    )]
+    pub commitment: Account<'info, Commitment>,
+
+    /// The user executing the reveal (must match commitment creator)
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    // === Stake Pool accounts ===
+    
+    /// Stake pool program
+    pub stake_pool_program: Program<'info, StakePool>,
+
+    /// Pool config PDA
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED],
+        bump,
+        seeds::program = stake_pool_program.key()
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    /// CHECK: Withdrawal ticket PDA, created by the stake_pool CPI
+    #[account(
+        mut,
+        seeds = [
+            WITHDRAWAL_TICKET_SEED,
+            pool_config.key().as_ref(),
+            user.key().as_ref(),
+            &ticket_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = stake_pool_program.key()
+    )]
+    pub withdrawal_ticket: UncheckedAccount<'info>,
+
+    /// slpSOL mint
+    #[account(
+        mut,
+        constraint = slp_mint.key() == pool_config.slp_mint @ SecureLPError::InvalidMint
+    )]
+    pub slp_mint: Account<'info, Mint>,
+
+    /// User's slpSOL token account
+    #[account(
+        mut,
+        constraint = user_slp_account.mint == slp_mint.key(),
+        constraint = user_slp_account.owner == user.key()
+    )]
+    pub user_slp_account: Account<'info, TokenAccount>,
+
+    /// Pool's slpSOL fee account (validated by stake_pool)
+    /// CHECK: Must match pool_config.fee_recipient; checked in the CPI
+    #[account(mut)]
+    pub fee_recipient: Option<UncheckedAccount<'info>>,
+
     /// Token program
     pub token_program: Program<'info, Token>,
 
@@ -554,6 +735,21 @@ pub struct UnstakeEvent {
     pub timestamp: i64,
 }
 
+/// Event emitted when a delayed-unstake ticket is opened
+#[event]
+pub struct WithdrawalRequestEvent {
+    /// User who requested the withdrawal
+    pub user: Pubkey,
+    /// Withdrawal ticket created in stake_pool
+    pub ticket: Pubkey,
+    /// Amount of slpSOL burned
+    pub amount_in: u64,
+    /// Minimum SOL expected
+    pub min_out: u64,
+    /// Timestamp of the request
+    pub timestamp: i64,
+}
+
 /// Event emitted when an AMM swap is completed
 #[event]
 pub struct SwapEvent {
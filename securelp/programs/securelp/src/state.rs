@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use securelp_math::commitment;
 
 /// Commitment PDA - stores the blinded swap intent
 /// Seeds: ["commit", user_pubkey]
@@ -56,10 +57,19 @@ impl SwapDetails {
     
     /// Minimum amount (0.001 SOL = 1,000,000 lamports)
     pub const MIN_AMOUNT: u64 = 1_000_000;
+
+    /// SHA-256 of the Borsh-serialized details, as committed to
+    pub fn hash(&self) -> [u8; 32] {
+        commitment::hash_swap_details(self.amount_in, self.min_out, self.slippage_bps, &self.nonce)
+    }
 }
 
 /// Configuration constants
 pub mod config {
     /// Minimum delay in seconds between commit and reveal
     pub const MIN_DELAY_SECONDS: i64 = 1;
+
+    /// Maximum age in seconds of a commitment that can still be revealed, so
+    /// a stale intent can't be executed at a much later price
+    pub const MAX_DELAY_SECONDS: i64 = 300;
 }
@@ -0,0 +1,28 @@
+//! The commitment hash checked on reveal is the shared securelp-math hash of
+//! the Borsh-serialized SwapDetails, the same one the MEV simulator commits.
+
+use anchor_lang::AnchorSerialize;
+use proptest::prelude::*;
+use securelp::state::SwapDetails;
+use securelp_math::commitment;
+
+proptest! {
+    #[test]
+    fn shared_serialization_is_the_borsh_layout(
+        amount_in: u64,
+        min_out: u64,
+        slippage_bps: u16,
+        nonce: [u8; 32],
+    ) {
+        let details = SwapDetails { amount_in, min_out, slippage_bps, nonce };
+
+        prop_assert_eq!(
+            details.try_to_vec().unwrap(),
+            commitment::serialize_swap_details(amount_in, min_out, slippage_bps, &nonce).to_vec()
+        );
+        prop_assert_eq!(
+            details.hash(),
+            commitment::hash_swap_details(amount_in, min_out, slippage_bps, &nonce)
+        );
+    }
+}
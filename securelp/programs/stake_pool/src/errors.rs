@@ -49,5 +49,80 @@ pub enum StakePoolError {
 
     #[msg("Reserve ratio exceeded")]
     ReserveRatioExceeded,
+
+    #[msg("No pending authority transfer")]
+    NoPendingAuthority,
+
+    #[msg("A parameter change is already queued")]
+    ParameterChangePending,
+
+    #[msg("No parameter change queued")]
+    NoPendingParameterChange,
+
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+
+    #[msg("Pool is not paused")]
+    PoolNotPaused,
+
+    #[msg("Not enough delegated stake to cover the withdrawal")]
+    InsufficientStake,
+
+    #[msg("No SOL owed to withdrawal tickets is left to unstake")]
+    NoUnstakeNeeded,
+
+    #[msg("Withdrawal ticket is not claimable yet")]
+    TicketNotClaimable,
+
+    #[msg("Validator stake is already at or below target")]
+    NoRebalanceNeeded,
+
+    #[msg("Validator must be deactivated first")]
+    ValidatorStillActive,
+
+    #[msg("Validator still holds stake")]
+    ValidatorNotEmpty,
+
+    #[msg("Commission and uptime must be at most 10000 bps")]
+    InvalidValidatorScore,
+
+    #[msg("Validator is already at its delegation target")]
+    NoDelegationNeeded,
+
+    #[msg("Validator list account is malformed")]
+    InvalidValidatorList,
+
+    #[msg("Validator list is full, grow it with realloc_validator_list")]
+    ValidatorListFull,
+
+    #[msg("Validator list can only grow, by at most 10KiB and up to MAX_VALIDATORS")]
+    InvalidValidatorListSize,
+
+    #[msg("Validator balances must be updated this epoch")]
+    ValidatorListOutdated,
+
+    #[msg("Fee exceeds the maximum for its fee type")]
+    InvalidFee,
+
+    #[msg("Fee recipient must be the pool's slpSOL fee account")]
+    InvalidFeeRecipient,
+
+    #[msg("Referrer must be an slpSOL token account")]
+    InvalidReferrer,
+
+    #[msg("Stake account must be active, unlocked and delegated to a pool validator")]
+    InvalidDepositStake,
+
+    #[msg("First deposit must exceed the locked minimum slpSOL supply")]
+    DepositBelowDeadShares,
+
+    #[msg("Exchange rate must not decrease on deposits and withdrawals")]
+    ExchangeRateDecreased,
+
+    #[msg("Reserve vault holds less SOL than the pool accounts for")]
+    ReserveOutOfSync,
+
+    #[msg("Pool config is not in the pre-upgrade layout")]
+    PoolConfigAlreadyMigrated,
 }
 
@@ -1,7 +1,16 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
-use anchor_spl::token::{self, Mint, MintTo, Burn, Token, TokenAccount};
+use anchor_lang::solana_program::{
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock, stake_history},
+};
+use anchor_spl::token::{self, Mint, MintTo, Burn, Token, TokenAccount, Transfer};
+use solana_stake_interface::{
+    instruction as stake_instruction,
+    state::{Authorized, Lockup, StakeAuthorize, StakeStateV2},
+    tools::get_minimum_delegation,
+};
 use anchor_spl::metadata::{
     create_metadata_accounts_v3,
     CreateMetadataAccountsV3,
@@ -23,6 +32,7 @@ pub mod stake_pool {
 
     /// Initialize the staking pool with slpSOL mint
     pub fn initialize_pool(ctx: Context<InitializePool>, fee_bps: u16) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, StakePoolError::InvalidFee);
         let pool = &mut ctx.accounts.pool_config;
 
         pool.admin = ctx.accounts.admin.key();
@@ -36,6 +46,9 @@ pub mod stake_pool {
         pool.validator_count = 0;
         pool.bump = ctx.bumps.pool_config;
         pool.authority_bump = ctx.bumps.pool_authority;
+        pool.pending_admin = Pubkey::default();
+        pool.score_oracle = pool.admin;
+        pool.referral_fee_bps = DEFAULT_REFERRAL_FEE_BPS;
 
         msg!("Pool initialized with fee: {} bps", fee_bps);
         emit!(PoolInitialized {
@@ -47,30 +60,227 @@ pub mod stake_pool {
         Ok(())
     }
 
+    /// Admin: Create the validator list with room for
+    /// INITIAL_VALIDATOR_LIST_CAPACITY validators
+    pub fn initialize_validator_list(ctx: Context<InitializeValidatorList>) -> Result<()> {
+        let mut list = ctx.accounts.validator_list.load_init()?;
+        list.pool = ctx.accounts.pool_config.key();
+        list.max_validators = INITIAL_VALIDATOR_LIST_CAPACITY;
+        list.validator_count = 0;
+        list.bump = ctx.bumps.validator_list;
+
+        msg!("Validator list created with {} slots", list.max_validators);
+        emit!(ValidatorListResized {
+            max_validators: list.max_validators,
+        });
+
+        Ok(())
+    }
+
+    /// Admin: Grow the validator list to hold `max_validators` validators.
+    /// The runtime caps growth at 10KiB per instruction, so large lists take
+    /// several calls.
+    pub fn realloc_validator_list(
+        ctx: Context<ReallocValidatorList>,
+        max_validators: u32,
+    ) -> Result<()> {
+        let mut list = ctx.accounts.validator_list.load_mut()?;
+
+        let growth = ValidatorList::space(max_validators)
+            .saturating_sub(ValidatorList::space(list.max_validators));
+        require!(
+            max_validators > list.max_validators
+                && max_validators <= MAX_VALIDATORS
+                && growth <= MAX_LIST_REALLOC_BYTES,
+            StakePoolError::InvalidValidatorListSize
+        );
+        list.max_validators = max_validators;
+
+        msg!("Validator list grown to {} slots", max_validators);
+        emit!(ValidatorListResized { max_validators });
+
+        Ok(())
+    }
+
     /// Add a validator to the pool's delegation list
     pub fn add_validator(ctx: Context<AddValidator>) -> Result<()> {
         let pool = &mut ctx.accounts.pool_config;
         
         require!(!pool.paused, StakePoolError::PoolPaused);
         require!(
-            pool.validator_count < MAX_VALIDATORS as u8,
+            pool.validator_count < MAX_VALIDATORS,
             StakePoolError::MaxValidatorsReached
         );
 
-        let validator = &mut ctx.accounts.validator_entry;
-        validator.vote_account = ctx.accounts.vote_account.key();
-        validator.stake_account = Pubkey::default(); // Set when stake is created
-        validator.staked_lamports = 0;
-        validator.last_update_epoch = Clock::get()?.epoch;
-        validator.active = true;
-        validator.index = pool.validator_count;
+        let vote_account = ctx.accounts.vote_account.key();
+        let list_info = ctx.accounts.validator_list.to_account_info();
+        let mut list_data = list_info.try_borrow_mut_data()?;
+        require!(
+            !ValidatorList::validators(&list_data)?
+                .iter()
+                .any(|validator| validator.vote_account == vote_account),
+            StakePoolError::ValidatorAlreadyExists
+        );
+
+        let index = ValidatorList::push(
+            &mut list_data,
+            ValidatorEntry {
+                vote_account,
+                stake_account: Pubkey::default(), // Set when stake is created
+                last_update_epoch: Clock::get()?.epoch,
+                target_weight: DEFAULT_VALIDATOR_WEIGHT,
+                active: 1,
+                ..Default::default()
+            },
+        )?;
 
         pool.validator_count += 1;
+        pool.total_validator_weight = pool.total_validator_weight
+            .checked_add(DEFAULT_VALIDATOR_WEIGHT as u64)
+            .ok_or(StakePoolError::MathOverflow)?;
 
-        msg!("Validator added: {}", validator.vote_account);
+        msg!("Validator added: {}", vote_account);
         emit!(ValidatorAdded {
+            vote_account,
+            index,
+        });
+
+        Ok(())
+    }
+
+    /// Admin: Set a validator's target weight for delegation and rebalancing
+    pub fn set_validator_weight(ctx: Context<ValidatorAdminAction>, weight: u16) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        let list_info = ctx.accounts.validator_list.to_account_info();
+        let mut list_data = list_info.try_borrow_mut_data()?;
+        let validator = ValidatorList::find_mut(&mut list_data, ctx.accounts.vote_account.key)?;
+
+        require!(validator.is_active(), StakePoolError::InvalidValidator);
+
+        pool.total_validator_weight = pool.total_validator_weight
+            .checked_sub(validator.target_weight as u64)
+            .ok_or(StakePoolError::MathOverflow)?
+            .checked_add(weight as u64)
+            .ok_or(StakePoolError::MathOverflow)?;
+        validator.target_weight = weight;
+
+        msg!("Validator {} weight set to {}", validator.vote_account, weight);
+        emit!(ValidatorWeightUpdated {
+            vote_account: validator.vote_account,
+            weight,
+            total_weight: pool.total_validator_weight,
+        });
+
+        Ok(())
+    }
+
+    /// Admin: Set the oracle allowed to push validator scores
+    pub fn set_score_oracle(ctx: Context<AdminAction>, score_oracle: Pubkey) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        pool.score_oracle = score_oracle;
+
+        msg!("Score oracle set to {}", score_oracle);
+        emit!(ScoreOracleUpdated { score_oracle });
+
+        Ok(())
+    }
+
+    /// Oracle: Push a validator's commission and uptime. Its target weight is
+    /// recomputed from the score, replacing any weight set by the admin.
+    pub fn update_validator_score(
+        ctx: Context<UpdateValidatorScore>,
+        commission_bps: u16,
+        uptime_bps: u16,
+    ) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        let list_info = ctx.accounts.validator_list.to_account_info();
+        let mut list_data = list_info.try_borrow_mut_data()?;
+        let validator = ValidatorList::find_mut(&mut list_data, ctx.accounts.vote_account.key)?;
+
+        require!(validator.is_active(), StakePoolError::InvalidValidator);
+        require!(
+            commission_bps <= SCORE_BPS_DENOMINATOR && uptime_bps <= SCORE_BPS_DENOMINATOR,
+            StakePoolError::InvalidValidatorScore
+        );
+
+        let weight = ValidatorEntry::score_weight(commission_bps, uptime_bps);
+        pool.total_validator_weight = pool.total_validator_weight
+            .checked_sub(validator.target_weight as u64)
+            .ok_or(StakePoolError::MathOverflow)?
+            .checked_add(weight as u64)
+            .ok_or(StakePoolError::MathOverflow)?;
+        validator.target_weight = weight;
+        validator.commission_bps = commission_bps;
+        validator.uptime_bps = uptime_bps;
+        validator.score_epoch = Clock::get()?.epoch;
+
+        msg!(
+            "Validator {} scored: commission {} bps, uptime {} bps, weight {}",
+            validator.vote_account,
+            commission_bps,
+            uptime_bps,
+            weight
+        );
+        emit!(ValidatorScoreUpdated {
+            vote_account: validator.vote_account,
+            commission_bps,
+            uptime_bps,
+            weight,
+            total_weight: pool.total_validator_weight,
+            epoch: validator.score_epoch,
+        });
+
+        Ok(())
+    }
+
+    /// Admin: Stop delegating to a validator. Its stake is drained by
+    /// rebalance, after which it can be removed.
+    pub fn deactivate_validator(ctx: Context<ValidatorAdminAction>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        let list_info = ctx.accounts.validator_list.to_account_info();
+        let mut list_data = list_info.try_borrow_mut_data()?;
+        let validator = ValidatorList::find_mut(&mut list_data, ctx.accounts.vote_account.key)?;
+
+        require!(validator.is_active(), StakePoolError::InvalidValidator);
+
+        pool.total_validator_weight = pool.total_validator_weight
+            .checked_sub(validator.target_weight as u64)
+            .ok_or(StakePoolError::MathOverflow)?;
+        validator.active = 0;
+        validator.target_weight = 0;
+
+        msg!("Validator deactivated: {}", validator.vote_account);
+        emit!(ValidatorDeactivated {
             vote_account: validator.vote_account,
-            index: validator.index,
+            staked_lamports: validator.staked_lamports,
+        });
+
+        Ok(())
+    }
+
+    /// Admin: Remove a deactivated validator that no longer holds any stake
+    pub fn remove_validator(ctx: Context<ValidatorAdminAction>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        let list_info = ctx.accounts.validator_list.to_account_info();
+        let mut list_data = list_info.try_borrow_mut_data()?;
+        let validator = ValidatorList::find_mut(&mut list_data, ctx.accounts.vote_account.key)?;
+
+        require!(!validator.is_active(), StakePoolError::ValidatorStillActive);
+        require!(
+            validator.staked_lamports == 0
+                && validator.transient_stake_lamports == 0
+                && validator.deactivating_lamports == 0,
+            StakePoolError::ValidatorNotEmpty
+        );
+
+        let removed = ValidatorList::swap_remove(&mut list_data, ctx.accounts.vote_account.key)?;
+        pool.validator_count = pool.validator_count
+            .checked_sub(1)
+            .ok_or(StakePoolError::MathOverflow)?;
+
+        msg!("Validator removed: {}", removed.vote_account);
+        emit!(ValidatorRemoved {
+            vote_account: removed.vote_account,
         });
 
         Ok(())
@@ -86,9 +296,22 @@ pub mod stake_pool {
             StakePoolError::BelowMinimumStake
         );
 
-        // Calculate slpSOL to mint
-        let slp_to_mint = pool.calculate_slp_for_deposit(amount_lamports)?;
-        require!(slp_to_mint > 0, StakePoolError::MathOverflow);
+        require_fee_recipient(pool, &ctx.accounts.fee_recipient)?;
+
+        // Calculate slpSOL to mint, part of which is the deposit fee and, on
+        // the first deposit, the locked dead shares. A referrer, if given,
+        // takes its share of the deposit fee.
+        let rate_before = pool.exchange_rate();
+        let DepositQuote {
+            slp_minted: slp_to_mint,
+            user_slp,
+            deposit_fee,
+            referral_fee,
+            dead_shares,
+        } = pool.quote_deposit(amount_lamports, ctx.accounts.referrer.is_some())?;
+        let protocol_fee = deposit_fee
+            .checked_sub(referral_fee)
+            .ok_or(StakePoolError::MathOverflow)?;
 
         // Transfer SOL from user to reserve vault
         let cpi_context = CpiContext::new(
@@ -100,19 +323,14 @@ pub mod stake_pool {
         );
         system_program::transfer(cpi_context, amount_lamports)?;
 
-        // Calculate reserve vs stake amounts
-        let reserve_amount = pool.calculate_reserve_amount(amount_lamports);
-        let stake_amount = amount_lamports.checked_sub(reserve_amount).unwrap_or(0);
-
-        // Update pool state
+        // Deposits land in the reserve; delegate_stake moves the excess to validators
         pool.reserve_lamports = pool.reserve_lamports
-            .checked_add(reserve_amount)
-            .ok_or(StakePoolError::MathOverflow)?;
-        pool.total_staked_lamports = pool.total_staked_lamports
-            .checked_add(stake_amount)
+            .checked_add(amount_lamports)
             .ok_or(StakePoolError::MathOverflow)?;
         pool.total_slp_supply = pool.total_slp_supply
-            .checked_add(slp_to_mint)
+            .checked_add(user_slp)
+            .ok_or(StakePoolError::MathOverflow)?
+            .checked_add(dead_shares)
             .ok_or(StakePoolError::MathOverflow)?;
 
         // Mint slpSOL to user
@@ -134,19 +352,202 @@ pub mod stake_pool {
             cpi_accounts,
             signer_seeds,
         );
-        token::mint_to(cpi_ctx, slp_to_mint)?;
+        token::mint_to(cpi_ctx, user_slp)?;
+
+        mint_protocol_fee(
+            pool,
+            &ctx.accounts.slp_mint,
+            &ctx.accounts.fee_recipient,
+            &ctx.accounts.pool_authority,
+            &ctx.accounts.token_program,
+            protocol_fee,
+        )?;
+        mint_protocol_fee(
+            pool,
+            &ctx.accounts.slp_mint,
+            &ctx.accounts.referrer,
+            &ctx.accounts.pool_authority,
+            &ctx.accounts.token_program,
+            referral_fee,
+        )?;
 
         msg!(
-            "Deposited {} lamports, minted {} slpSOL",
+            "Deposited {} lamports, minted {} slpSOL ({} deposit fee, {} to referrer)",
             amount_lamports,
-            slp_to_mint
+            slp_to_mint,
+            deposit_fee,
+            referral_fee
         );
         emit!(Deposited {
             user: ctx.accounts.user.key(),
             sol_amount: amount_lamports,
-            slp_minted: slp_to_mint,
+            slp_minted: user_slp,
+            deposit_fee,
+            referrer: ctx.accounts.referrer.as_ref().map(|r| r.key()),
+            referral_fee,
+            exchange_rate: pool.exchange_rate(),
+        });
+        pool.check_exchange_rate(rate_before)?;
+
+        if let Some(user_stake) = ctx.accounts.user_stake.as_mut() {
+            user_stake.record_deposit(amount_lamports, user_slp, Clock::get()?.unix_timestamp)?;
+        }
+
+        Ok(())
+    }
+
+    /// Deposit an active stake account delegated to a pool validator and
+    /// receive slpSOL. The account is handed to the pool authority and merged
+    /// into the validator's stake account; its full balance counts as stake.
+    pub fn deposit_stake(ctx: Context<DepositStake>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        let clock = Clock::get()?;
+        let vote_key = ctx.accounts.vote_account.key();
+        let user_key = ctx.accounts.user.key();
+        let list_info = ctx.accounts.validator_list.to_account_info();
+        let mut list_data = list_info.try_borrow_mut_data()?;
+
+        require!(!pool.paused, StakePoolError::PoolPaused);
+        require!(
+            ValidatorList::is_up_to_date(&list_data, clock.epoch)?,
+            StakePoolError::ValidatorListOutdated
+        );
+        let validator = ValidatorList::find_mut(&mut list_data, &vote_key)?;
+        require!(validator.is_active(), StakePoolError::InvalidValidator);
+        require_keys_eq!(
+            validator.stake_account,
+            ctx.accounts.validator_stake.key(),
+            StakePoolError::InvalidStakeState
+        );
+        require_fee_recipient(pool, &ctx.accounts.fee_recipient)?;
+
+        // Only fully active, unlocked stake the user controls can be merged
+        let StakeStateV2::Stake(meta, stake, _) =
+            StakeStateV2::deserialize(&mut &ctx.accounts.deposit_stake.try_borrow_data()?[..])?
+        else {
+            return err!(StakePoolError::InvalidDepositStake);
+        };
+        require_keys_eq!(meta.authorized.staker, user_key, StakePoolError::InvalidAuthority);
+        require_keys_eq!(meta.authorized.withdrawer, user_key, StakePoolError::InvalidAuthority);
+        require!(
+            stake.delegation.voter_pubkey == vote_key
+                && stake.delegation.activation_epoch < clock.epoch
+                && stake.delegation.deactivation_epoch == u64::MAX
+                && !meta.lockup.is_in_force(&clock, None),
+            StakePoolError::InvalidDepositStake
+        );
+
+        let deposit_lamports = ctx.accounts.deposit_stake.lamports();
+        require!(
+            deposit_lamports >= MIN_DEPOSIT_LAMPORTS,
+            StakePoolError::BelowMinimumStake
+        );
+
+        // Priced before the stake joins the pool, as for deposit_sol
+        let rate_before = pool.exchange_rate();
+        let slp_to_mint = pool.calculate_slp_for_deposit(deposit_lamports)?;
+        require!(slp_to_mint > 0, StakePoolError::MathOverflow);
+        let deposit_fee = pool.protocol_fee(slp_to_mint, pool.deposit_fee_bps)?;
+        let dead_shares = pool.dead_shares();
+        let user_slp = slp_to_mint
+            .checked_sub(deposit_fee)
+            .ok_or(StakePoolError::MathOverflow)?
+            .checked_sub(dead_shares)
+            .ok_or(StakePoolError::DepositBelowDeadShares)?;
+
+        // Hand both authorities to the pool, then merge into the validator stake
+        for stake_authorize in [StakeAuthorize::Staker, StakeAuthorize::Withdrawer] {
+            invoke(
+                &stake_instruction::authorize(
+                    &ctx.accounts.deposit_stake.key(),
+                    &user_key,
+                    &ctx.accounts.pool_authority.key(),
+                    stake_authorize,
+                    None,
+                ),
+                &[
+                    ctx.accounts.deposit_stake.to_account_info(),
+                    ctx.accounts.clock.to_account_info(),
+                    ctx.accounts.user.to_account_info(),
+                ],
+            )?;
+        }
+
+        let pool_key = pool.key();
+        let authority_seeds: &[&[u8]] = &[
+            POOL_AUTHORITY_SEED,
+            pool_key.as_ref(),
+            &[pool.authority_bump],
+        ];
+        for ix in stake_instruction::merge(
+            &ctx.accounts.validator_stake.key(),
+            &ctx.accounts.deposit_stake.key(),
+            &ctx.accounts.pool_authority.key(),
+        ) {
+            invoke_signed(
+                &ix,
+                &[
+                    ctx.accounts.validator_stake.to_account_info(),
+                    ctx.accounts.deposit_stake.to_account_info(),
+                    ctx.accounts.clock.to_account_info(),
+                    ctx.accounts.stake_history.to_account_info(),
+                    ctx.accounts.pool_authority.to_account_info(),
+                ],
+                &[authority_seeds],
+            )?;
+        }
+
+        validator.staked_lamports = validator.staked_lamports
+            .checked_add(deposit_lamports)
+            .ok_or(StakePoolError::MathOverflow)?;
+        pool.total_staked_lamports = pool.total_staked_lamports
+            .checked_add(deposit_lamports)
+            .ok_or(StakePoolError::MathOverflow)?;
+        pool.total_slp_supply = pool.total_slp_supply
+            .checked_add(user_slp)
+            .ok_or(StakePoolError::MathOverflow)?
+            .checked_add(dead_shares)
+            .ok_or(StakePoolError::MathOverflow)?;
+
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.slp_mint.to_account_info(),
+                    to: ctx.accounts.user_slp_account.to_account_info(),
+                    authority: ctx.accounts.pool_authority.to_account_info(),
+                },
+                &[authority_seeds],
+            ),
+            user_slp,
+        )?;
+        mint_protocol_fee(
+            pool,
+            &ctx.accounts.slp_mint,
+            &ctx.accounts.fee_recipient,
+            &ctx.accounts.pool_authority,
+            &ctx.accounts.token_program,
+            deposit_fee,
+        )?;
+
+        msg!(
+            "Deposited stake account {} with {} lamports for validator {}, minted {} slpSOL ({} deposit fee)",
+            ctx.accounts.deposit_stake.key(),
+            deposit_lamports,
+            vote_key,
+            slp_to_mint,
+            deposit_fee
+        );
+        emit!(StakeDeposited {
+            user: user_key,
+            stake_account: ctx.accounts.deposit_stake.key(),
+            validator: vote_key,
+            lamports: deposit_lamports,
+            slp_minted: user_slp,
+            deposit_fee,
             exchange_rate: pool.exchange_rate(),
         });
+        pool.check_exchange_rate(rate_before)?;
 
         Ok(())
     }
@@ -157,9 +558,17 @@ pub mod stake_pool {
         
         require!(!pool.paused, StakePoolError::PoolPaused);
         require!(slp_amount > 0, StakePoolError::InsufficientSlpSol);
-
-        // Calculate SOL to return
-        let sol_to_return = pool.calculate_sol_for_withdrawal(slp_amount)?;
+        require_fee_recipient(pool, &ctx.accounts.fee_recipient)?;
+
+        // The withdrawal fee is paid in slpSOL; only the rest is redeemed, less
+        // the instant-unstake fee that stays in the pool
+        let rate_before = pool.exchange_rate();
+        let WithdrawQuote {
+            withdrawal_fee,
+            slp_burned: slp_to_burn,
+            sol_out: sol_to_return,
+            liquidity_fee,
+        } = pool.quote_withdraw(slp_amount)?;
         require!(sol_to_return > 0, StakePoolError::MathOverflow);
 
         // Check if we have enough in reserve for instant unstake
@@ -168,6 +577,14 @@ pub mod stake_pool {
             StakePoolError::InsufficientReserve
         );
 
+        transfer_withdrawal_fee(
+            &ctx.accounts.user_slp_account,
+            &ctx.accounts.fee_recipient,
+            &ctx.accounts.user,
+            &ctx.accounts.token_program,
+            withdrawal_fee,
+        )?;
+
         // Burn slpSOL from user
         let cpi_accounts = Burn {
             mint: ctx.accounts.slp_mint.to_account_info(),
@@ -178,7 +595,7 @@ pub mod stake_pool {
             ctx.accounts.token_program.to_account_info(),
             cpi_accounts,
         );
-        token::burn(cpi_ctx, slp_amount)?;
+        token::burn(cpi_ctx, slp_to_burn)?;
 
         // Transfer SOL from reserve vault PDA to user using invoke_signed
         // This is required because the reserve vault is owned by the system program
@@ -210,238 +627,2253 @@ pub mod stake_pool {
             .checked_sub(sol_to_return)
             .ok_or(StakePoolError::MathOverflow)?;
         pool.total_slp_supply = pool.total_slp_supply
-            .checked_sub(slp_amount)
+            .checked_sub(slp_to_burn)
             .ok_or(StakePoolError::MathOverflow)?;
 
         msg!(
-            "Withdrew {} lamports, burned {} slpSOL",
+            "Withdrew {} lamports ({} liquidity fee), burned {} slpSOL ({} withdrawal fee)",
             sol_to_return,
-            slp_amount
+            liquidity_fee,
+            slp_to_burn,
+            withdrawal_fee
         );
         emit!(Withdrawn {
             user: ctx.accounts.user.key(),
             sol_amount: sol_to_return,
-            slp_burned: slp_amount,
+            slp_burned: slp_to_burn,
+            withdrawal_fee,
+            liquidity_fee,
             exchange_rate: pool.exchange_rate(),
         });
+        pool.check_exchange_rate(rate_before)?;
+        pool.check_reserve(ctx.accounts.reserve_vault.lamports())?;
+
+        if let Some(user_stake) = ctx.accounts.user_stake.as_mut() {
+            user_stake.record_withdrawal(slp_amount, sol_to_return, Clock::get()?.unix_timestamp)?;
+        }
 
         Ok(())
     }
 
-    /// Crank: Move SOL from reserve to validators
-    /// This is called periodically to actually stake the deposited SOL
-    pub fn delegate_stake(ctx: Context<DelegateStake>, amount_lamports: u64) -> Result<()> {
+    /// Burn slpSOL for a slice of a validator's stake, split into a new stake
+    /// account owned by the user. Exits at the exchange rate without touching
+    /// the reserve; the user funds the new account's rent.
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>, slp_amount: u64) -> Result<()> {
         let pool = &mut ctx.accounts.pool_config;
-        
+        let clock = Clock::get()?;
+        let vote_key = ctx.accounts.vote_account.key();
+        let user_key = ctx.accounts.user.key();
+        let list_info = ctx.accounts.validator_list.to_account_info();
+        let mut list_data = list_info.try_borrow_mut_data()?;
+
         require!(!pool.paused, StakePoolError::PoolPaused);
-        require!(pool.validator_count > 0, StakePoolError::ValidatorNotFound);
+        require!(slp_amount > 0, StakePoolError::InsufficientSlpSol);
+        require!(
+            ValidatorList::is_up_to_date(&list_data, clock.epoch)?,
+            StakePoolError::ValidatorListOutdated
+        );
+        let validator = ValidatorList::find_mut(&mut list_data, &vote_key)?;
+        require_keys_eq!(
+            validator.stake_account,
+            ctx.accounts.validator_stake.key(),
+            StakePoolError::InvalidStakeState
+        );
+        require_fee_recipient(pool, &ctx.accounts.fee_recipient)?;
 
-        // For devnet simplicity, we just track the amount as "staked"
-        // without actually creating stake accounts (which requires rent-exempt minimum)
-        // In production, this would create actual stake accounts
-        
-        let validator = &mut ctx.accounts.validator_entry;
-        require!(validator.active, StakePoolError::InvalidValidator);
+        let rate_before = pool.exchange_rate();
+        let withdrawal_fee = pool.protocol_fee(slp_amount, pool.withdrawal_fee_bps)?;
+        let slp_to_burn = slp_amount
+            .checked_sub(withdrawal_fee)
+            .ok_or(StakePoolError::MathOverflow)?;
+        let stake_lamports = pool.calculate_sol_for_withdrawal(slp_to_burn)?;
 
-        // Move from reserve to staked tracking
-        // Note: In a full implementation, we'd create stake accounts here
-        let transfer_amount = amount_lamports.min(pool.reserve_lamports);
-        
-        pool.reserve_lamports = pool.reserve_lamports
-            .checked_sub(transfer_amount)
+        // Both the split and what stays behind must hold the minimum delegation
+        let stake_rent = Rent::get()?.minimum_balance(StakeStateV2::size_of());
+        let min_delegation = get_minimum_delegation()?;
+        require!(
+            stake_lamports >= min_delegation,
+            StakePoolError::BelowMinimumStake
+        );
+        let remaining = ctx.accounts.validator_stake.lamports()
+            .checked_sub(stake_lamports)
+            .ok_or(StakePoolError::InsufficientStake)?;
+        require!(
+            remaining >= stake_rent
+                .checked_add(min_delegation)
+                .ok_or(StakePoolError::MathOverflow)?
+                && validator.staked_lamports >= stake_lamports
+                && pool.total_staked_lamports >= stake_lamports,
+            StakePoolError::InsufficientStake
+        );
+
+        transfer_withdrawal_fee(
+            &ctx.accounts.user_slp_account,
+            &ctx.accounts.fee_recipient,
+            &ctx.accounts.user,
+            &ctx.accounts.token_program,
+            withdrawal_fee,
+        )?;
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.slp_mint.to_account_info(),
+                    from: ctx.accounts.user_slp_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            slp_to_burn,
+        )?;
+
+        // Split destinations must already be rent exempt
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: ctx.accounts.split_stake.to_account_info(),
+                },
+            ),
+            stake_rent,
+        )?;
+
+        let pool_key = pool.key();
+        let authority_seeds: &[&[u8]] = &[
+            POOL_AUTHORITY_SEED,
+            pool_key.as_ref(),
+            &[pool.authority_bump],
+        ];
+        for ix in stake_instruction::split(
+            &ctx.accounts.validator_stake.key(),
+            &ctx.accounts.pool_authority.key(),
+            stake_lamports,
+            &ctx.accounts.split_stake.key(),
+        ) {
+            invoke_signed(
+                &ix,
+                &[
+                    ctx.accounts.validator_stake.to_account_info(),
+                    ctx.accounts.split_stake.to_account_info(),
+                    ctx.accounts.pool_authority.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+                &[authority_seeds],
+            )?;
+        }
+
+        // Hand the new stake account over to the user
+        for stake_authorize in [StakeAuthorize::Staker, StakeAuthorize::Withdrawer] {
+            invoke_signed(
+                &stake_instruction::authorize(
+                    &ctx.accounts.split_stake.key(),
+                    &ctx.accounts.pool_authority.key(),
+                    &user_key,
+                    stake_authorize,
+                    None,
+                ),
+                &[
+                    ctx.accounts.split_stake.to_account_info(),
+                    ctx.accounts.clock.to_account_info(),
+                    ctx.accounts.pool_authority.to_account_info(),
+                ],
+                &[authority_seeds],
+            )?;
+        }
+
+        validator.staked_lamports = validator.staked_lamports
+            .checked_sub(stake_lamports)
             .ok_or(StakePoolError::MathOverflow)?;
         pool.total_staked_lamports = pool.total_staked_lamports
-            .checked_add(transfer_amount)
+            .checked_sub(stake_lamports)
             .ok_or(StakePoolError::MathOverflow)?;
-        
-        validator.staked_lamports = validator.staked_lamports
-            .checked_add(transfer_amount)
+        pool.total_slp_supply = pool.total_slp_supply
+            .checked_sub(slp_to_burn)
             .ok_or(StakePoolError::MathOverflow)?;
-        validator.last_update_epoch = Clock::get()?.epoch;
 
         msg!(
-            "Delegated {} lamports to validator {}",
-            transfer_amount,
-            validator.vote_account
+            "Withdrew {} lamports of stake from validator {} into {}, burned {} slpSOL ({} withdrawal fee)",
+            stake_lamports,
+            vote_key,
+            ctx.accounts.split_stake.key(),
+            slp_to_burn,
+            withdrawal_fee
         );
-        emit!(StakeDelegated {
-            validator: validator.vote_account,
-            amount: transfer_amount,
-            epoch: validator.last_update_epoch,
+        emit!(StakeWithdrawn {
+            user: user_key,
+            stake_account: ctx.accounts.split_stake.key(),
+            validator: vote_key,
+            lamports: stake_lamports,
+            slp_burned: slp_to_burn,
+            withdrawal_fee,
+            exchange_rate: pool.exchange_rate(),
         });
+        pool.check_exchange_rate(rate_before)?;
 
         Ok(())
     }
 
-    /// Crank: Simulate harvesting epoch rewards
-    /// On devnet, we simulate rewards based on ~7% APY
-    pub fn harvest_rewards(ctx: Context<HarvestRewards>) -> Result<()> {
+    /// Delayed unstake: burn slpSOL for a ticket on SOL that is still delegated.
+    /// The SOL owed is fixed at the current rate and can be claimed with
+    /// claim_withdrawal once cranks have deactivated and withdrawn the stake.
+    pub fn request_withdrawal(
+        ctx: Context<RequestWithdrawal>,
+        slp_amount: u64,
+        ticket_id: u64,
+    ) -> Result<()> {
         let pool = &mut ctx.accounts.pool_config;
         let clock = Clock::get()?;
 
         require!(!pool.paused, StakePoolError::PoolPaused);
-        require!(
-            clock.epoch > pool.last_harvest_epoch,
-            StakePoolError::EpochNotChanged
-        );
-
-        // Calculate simulated rewards
-        // ~7% APY = ~0.019% per epoch (assuming ~365 epochs/year)
-        // rewards = total_staked * 0.00019 per epoch
-        let epochs_passed = clock.epoch
-            .checked_sub(pool.last_harvest_epoch)
-            .unwrap_or(1)
-            .max(1);
+        require!(slp_amount > 0, StakePoolError::InsufficientSlpSol);
+        require_fee_recipient(pool, &ctx.accounts.fee_recipient)?;
 
-        // 19 basis points per epoch (0.019%)
-        let reward_rate_per_epoch: u64 = 19;
-        let rewards_lamports = pool.total_staked_lamports
-            .checked_mul(reward_rate_per_epoch)
-            .ok_or(StakePoolError::MathOverflow)?
-            .checked_mul(epochs_passed)
-            .ok_or(StakePoolError::MathOverflow)?
-            .checked_div(100_000) // Divide by 100_000 to get the rate
+        let rate_before = pool.exchange_rate();
+        let withdrawal_fee = pool.protocol_fee(slp_amount, pool.withdrawal_fee_bps)?;
+        let slp_to_burn = slp_amount
+            .checked_sub(withdrawal_fee)
             .ok_or(StakePoolError::MathOverflow)?;
 
-        if rewards_lamports == 0 {
-            msg!("No rewards to harvest");
-            return Ok(());
-        }
+        let sol_owed = pool.calculate_sol_for_withdrawal(slp_to_burn)?;
+        require!(sol_owed > 0, StakePoolError::MathOverflow);
+        require!(
+            pool.total_staked_lamports >= sol_owed,
+            StakePoolError::InsufficientStake
+        );
 
-        // Deduct protocol fee
-        let protocol_fee = rewards_lamports
-            .checked_mul(pool.fee_bps as u64)
-            .ok_or(StakePoolError::MathOverflow)?
-            .checked_div(10_000)
-            .ok_or(StakePoolError::MathOverflow)?;
+        transfer_withdrawal_fee(
+            &ctx.accounts.user_slp_account,
+            &ctx.accounts.fee_recipient,
+            &ctx.accounts.user,
+            &ctx.accounts.token_program,
+            withdrawal_fee,
+        )?;
 
-        let net_rewards = rewards_lamports
-            .checked_sub(protocol_fee)
-            .ok_or(StakePoolError::MathOverflow)?;
+        // Burn slpSOL from user
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.slp_mint.to_account_info(),
+            from: ctx.accounts.user_slp_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+        );
+        token::burn(cpi_ctx, slp_to_burn)?;
 
-        // Add net rewards to total staked (increases exchange rate)
+        // Move the SOL from pool assets to ticket liabilities
         pool.total_staked_lamports = pool.total_staked_lamports
-            .checked_add(net_rewards)
+            .checked_sub(sol_owed)
+            .ok_or(StakePoolError::MathOverflow)?;
+        pool.ticket_lamports_owed = pool.ticket_lamports_owed
+            .checked_add(sol_owed)
+            .ok_or(StakePoolError::MathOverflow)?;
+        pool.ticket_lamports_requested = pool.ticket_lamports_requested
+            .checked_add(sol_owed)
+            .ok_or(StakePoolError::MathOverflow)?;
+        pool.total_slp_supply = pool.total_slp_supply
+            .checked_sub(slp_to_burn)
             .ok_or(StakePoolError::MathOverflow)?;
 
-        pool.last_harvest_epoch = clock.epoch;
+        // Deactivation takes effect at the next epoch boundary
+        let ticket = &mut ctx.accounts.withdrawal_ticket;
+        ticket.owner = ctx.accounts.user.key();
+        ticket.ticket_id = ticket_id;
+        ticket.lamports = sol_owed;
+        ticket.slp_burned = slp_to_burn;
+        ticket.created_epoch = clock.epoch;
+        ticket.claimable_epoch = clock.epoch
+            .checked_add(1)
+            .ok_or(StakePoolError::MathOverflow)?;
+        ticket.bump = ctx.bumps.withdrawal_ticket;
+        ticket.queue_position = pool.ticket_lamports_requested;
 
         msg!(
-            "Harvested {} lamports ({} net after {} fee) over {} epochs",
-            rewards_lamports,
-            net_rewards,
-            protocol_fee,
-            epochs_passed
+            "Withdrawal ticket {} for {} lamports, burned {} slpSOL ({} withdrawal fee)",
+            ticket_id,
+            sol_owed,
+            slp_to_burn,
+            withdrawal_fee
         );
-        emit!(RewardsHarvested {
-            gross_rewards: rewards_lamports,
-            protocol_fee,
-            net_rewards,
-            new_exchange_rate: pool.exchange_rate(),
-            epoch: clock.epoch,
+        emit!(WithdrawalRequested {
+            user: ticket.owner,
+            ticket: ticket.key(),
+            sol_amount: sol_owed,
+            slp_burned: slp_to_burn,
+            withdrawal_fee,
+            claimable_epoch: ticket.claimable_epoch,
+            exchange_rate: pool.exchange_rate(),
         });
+        pool.check_exchange_rate(rate_before)?;
 
         Ok(())
     }
 
-    /// Admin: Pause/unpause the pool
-    pub fn set_paused(ctx: Context<AdminAction>, paused: bool) -> Result<()> {
+    /// Claim the SOL owed by a withdrawal ticket and close it
+    pub fn claim_withdrawal(ctx: Context<ClaimWithdrawal>) -> Result<()> {
         let pool = &mut ctx.accounts.pool_config;
-        pool.paused = paused;
-        
-        msg!("Pool paused: {}", paused);
-        Ok(())
+        let ticket = &ctx.accounts.withdrawal_ticket;
+
+        require!(
+            Clock::get()?.epoch >= ticket.claimable_epoch,
+            StakePoolError::TicketNotClaimable
+        );
+        // Tickets are paid in request order, so a later ticket can't take SOL
+        // that was unstaked for an earlier one
+        require!(
+            pool.ticket_lamports_funded >= ticket.queue_position,
+            StakePoolError::TicketNotClaimable
+        );
+        require!(
+            pool.claimable_lamports >= ticket.lamports,
+            StakePoolError::TicketNotClaimable
+        );
+
+        let pool_key = pool.key();
+        let seeds: &[&[u8]] = &[
+            RESERVE_VAULT_SEED,
+            pool_key.as_ref(),
+            &[ctx.bumps.reserve_vault],
+        ];
+        let signer_seeds = &[seeds];
+
+        invoke_signed(
+            &system_instruction::transfer(
+                &ctx.accounts.reserve_vault.key(),
+                &ctx.accounts.user.key(),
+                ticket.lamports,
+            ),
+            &[
+                ctx.accounts.reserve_vault.to_account_info(),
+                ctx.accounts.user.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+
+        pool.claimable_lamports = pool.claimable_lamports
+            .checked_sub(ticket.lamports)
+            .ok_or(StakePoolError::MathOverflow)?;
+        pool.ticket_lamports_owed = pool.ticket_lamports_owed
+            .checked_sub(ticket.lamports)
+            .ok_or(StakePoolError::MathOverflow)?;
+
+        msg!("Claimed withdrawal ticket {} for {} lamports", ticket.ticket_id, ticket.lamports);
+        emit!(WithdrawalClaimed {
+            user: ticket.owner,
+            ticket: ticket.key(),
+            sol_amount: ticket.lamports,
+        });
+        pool.check_reserve(ctx.accounts.reserve_vault.lamports())?;
+
+        Ok(())
+    }
+
+    /// View: What deposit_sol would mint for `amount_lamports`, returned
+    /// through return data so clients can simulate it and programs can CPI it
+    pub fn quote_deposit(
+        ctx: Context<QuotePool>,
+        amount_lamports: u64,
+        with_referrer: bool,
+    ) -> Result<DepositQuote> {
+        let pool = &ctx.accounts.pool_config;
+
+        require!(!pool.paused, StakePoolError::PoolPaused);
+        require!(
+            amount_lamports >= MIN_DEPOSIT_LAMPORTS,
+            StakePoolError::BelowMinimumStake
+        );
+
+        pool.quote_deposit(amount_lamports, with_referrer)
+    }
+
+    /// View: What withdraw_sol would pay out for `slp_amount`, returned
+    /// through return data
+    pub fn quote_withdraw(ctx: Context<QuotePool>, slp_amount: u64) -> Result<WithdrawQuote> {
+        let pool = &ctx.accounts.pool_config;
+
+        require!(!pool.paused, StakePoolError::PoolPaused);
+        require!(slp_amount > 0, StakePoolError::InsufficientSlpSol);
+
+        let quote = pool.quote_withdraw(slp_amount)?;
+        require!(quote.sol_out > 0, StakePoolError::MathOverflow);
+        require!(
+            pool.reserve_lamports >= quote.sol_out,
+            StakePoolError::InsufficientReserve
+        );
+
+        Ok(quote)
+    }
+
+    /// Open a stake position account that deposit_sol and withdraw_sol keep
+    /// up to date when it is passed in
+    pub fn initialize_user_stake(ctx: Context<InitializeUserStake>) -> Result<()> {
+        let user_stake = &mut ctx.accounts.user_stake;
+        user_stake.owner = ctx.accounts.user.key();
+        user_stake.bump = ctx.bumps.user_stake;
+
+        msg!("Opened stake position for {}", user_stake.owner);
+
+        Ok(())
+    }
+
+    /// Close a stake position account and return its rent to the owner
+    pub fn close_user_stake(ctx: Context<CloseUserStake>) -> Result<()> {
+        msg!("Closed stake position for {}", ctx.accounts.user_stake.owner);
+
+        Ok(())
     }
 
-    /// Admin: Update fee
-    pub fn update_fee(ctx: Context<AdminAction>, new_fee_bps: u16) -> Result<()> {
+    /// Crank: Move SOL from reserve to validators, split by target weight
+    /// The first delegation creates the validator stake account; later ones go
+    /// through a transient stake account that merge_transient_stake folds in.
+    pub fn delegate_stake(ctx: Context<DelegateStake>) -> Result<()> {
         let pool = &mut ctx.accounts.pool_config;
-        
-        require!(new_fee_bps <= 1000, StakePoolError::InvalidAuthority); // Max 10%
-        pool.fee_bps = new_fee_bps;
-        
-        msg!("Fee updated to {} bps", new_fee_bps);
+        let epoch = Clock::get()?.epoch;
+        let vote_key = ctx.accounts.vote_account.key();
+        let list_info = ctx.accounts.validator_list.to_account_info();
+        let mut list_data = list_info.try_borrow_mut_data()?;
+
+        require!(!pool.paused, StakePoolError::PoolPaused);
+        require!(
+            ValidatorList::is_up_to_date(&list_data, epoch)?,
+            StakePoolError::ValidatorListOutdated
+        );
+        let validator = ValidatorList::find_mut(&mut list_data, &vote_key)?;
+        require!(validator.is_active(), StakePoolError::InvalidValidator);
+
+        // The pool decides the amount: the validator's weighted share of the
+        // reserve excess, so crankers cannot concentrate stake
+        let amount_lamports = pool.delegation_amount(validator)?;
+        require!(amount_lamports > 0, StakePoolError::NoDelegationNeeded);
+
+        // The stake account must be rent exempt and hold the minimum delegation
+        let stake_space = StakeStateV2::size_of();
+        let stake_rent = Rent::get()?.minimum_balance(stake_space);
+        let min_delegation = get_minimum_delegation()?;
+        require!(
+            amount_lamports >= stake_rent
+                .checked_add(min_delegation)
+                .ok_or(StakePoolError::MathOverflow)?,
+            StakePoolError::BelowMinimumStake
+        );
+
+        // Fresh validators get their main stake account, otherwise use the transient one
+        let is_first_delegation = validator.stake_account == Pubkey::default();
+        let (stake_account, stake_seed, stake_bump) = if is_first_delegation {
+            (
+                ctx.accounts.validator_stake.to_account_info(),
+                VALIDATOR_STAKE_SEED,
+                ctx.bumps.validator_stake,
+            )
+        } else {
+            require!(
+                validator.transient_stake_lamports == 0,
+                StakePoolError::InvalidStakeState
+            );
+            (
+                ctx.accounts.transient_stake.to_account_info(),
+                TRANSIENT_STAKE_SEED,
+                ctx.bumps.transient_stake,
+            )
+        };
+
+        let pool_key = pool.key();
+        let reserve_seeds: &[&[u8]] = &[
+            RESERVE_VAULT_SEED,
+            pool_key.as_ref(),
+            &[ctx.bumps.reserve_vault],
+        ];
+        let stake_seeds: &[&[u8]] = &[
+            stake_seed,
+            pool_key.as_ref(),
+            vote_key.as_ref(),
+            &[stake_bump],
+        ];
+        let authority_seeds: &[&[u8]] = &[
+            POOL_AUTHORITY_SEED,
+            pool_key.as_ref(),
+            &[pool.authority_bump],
+        ];
+
+        // Fund the stake account straight from the reserve vault
+        invoke_signed(
+            &system_instruction::create_account(
+                &ctx.accounts.reserve_vault.key(),
+                &stake_account.key(),
+                amount_lamports,
+                stake_space as u64,
+                &ctx.accounts.stake_program.key(),
+            ),
+            &[
+                ctx.accounts.reserve_vault.to_account_info(),
+                stake_account.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[reserve_seeds, stake_seeds],
+        )?;
+
+        // Pool authority is both staker and withdrawer
+        let pool_authority = ctx.accounts.pool_authority.key();
+        invoke(
+            &stake_instruction::initialize(
+                &stake_account.key(),
+                &Authorized {
+                    staker: pool_authority,
+                    withdrawer: pool_authority,
+                },
+                &Lockup::default(),
+            ),
+            &[stake_account.clone(), ctx.accounts.rent.to_account_info()],
+        )?;
+
+        invoke_signed(
+            &stake_instruction::delegate_stake(
+                &stake_account.key(),
+                &pool_authority,
+                &ctx.accounts.vote_account.key(),
+            ),
+            &[
+                stake_account.clone(),
+                ctx.accounts.vote_account.to_account_info(),
+                ctx.accounts.clock.to_account_info(),
+                ctx.accounts.stake_history.to_account_info(),
+                ctx.accounts.stake_config.to_account_info(),
+                ctx.accounts.pool_authority.to_account_info(),
+            ],
+            &[authority_seeds],
+        )?;
+
+        // Move from reserve to staked tracking
+        pool.reserve_lamports = pool.reserve_lamports
+            .checked_sub(amount_lamports)
+            .ok_or(StakePoolError::MathOverflow)?;
+        pool.total_staked_lamports = pool.total_staked_lamports
+            .checked_add(amount_lamports)
+            .ok_or(StakePoolError::MathOverflow)?;
+
+        if is_first_delegation {
+            validator.stake_account = stake_account.key();
+        } else {
+            validator.transient_stake_lamports = amount_lamports;
+        }
+        validator.staked_lamports = validator.staked_lamports
+            .checked_add(amount_lamports)
+            .ok_or(StakePoolError::MathOverflow)?;
+
+        msg!(
+            "Delegated {} lamports to validator {} via {}",
+            amount_lamports,
+            validator.vote_account,
+            stake_account.key()
+        );
+        emit!(StakeDelegated {
+            validator: validator.vote_account,
+            amount: amount_lamports,
+            epoch,
+        });
+
         Ok(())
     }
 
-    /// Admin: Create token metadata for secuSOL
-    pub fn create_token_metadata(
-        ctx: Context<CreateTokenMetadata>,
-        name: String,
-        symbol: String,
-        uri: String,
-    ) -> Result<()> {
+    /// Crank: Merge the transient stake account into the validator stake account
+    /// Succeeds once both accounts are in a mergeable state (e.g. both active)
+    pub fn merge_transient_stake(ctx: Context<MergeTransientStake>) -> Result<()> {
         let pool = &ctx.accounts.pool_config;
+        let list_info = ctx.accounts.validator_list.to_account_info();
+        let mut list_data = list_info.try_borrow_mut_data()?;
+        let validator = ValidatorList::find_mut(&mut list_data, ctx.accounts.vote_account.key)?;
+
+        require!(
+            validator.transient_stake_lamports > 0,
+            StakePoolError::InvalidStakeState
+        );
 
-        // Create signer seeds for pool authority
         let pool_key = pool.key();
-        let seeds = &[
+        let authority_seeds: &[&[u8]] = &[
             POOL_AUTHORITY_SEED,
             pool_key.as_ref(),
             &[pool.authority_bump],
         ];
-        let signer_seeds = &[&seeds[..]];
 
-        // Create metadata
-        let data_v2 = DataV2 {
-            name,
-            symbol,
-            uri,
-            seller_fee_basis_points: 0,
-            creators: None,
-            collection: None,
-            uses: None,
-        };
+        for ix in stake_instruction::merge(
+            &ctx.accounts.validator_stake.key(),
+            &ctx.accounts.transient_stake.key(),
+            &ctx.accounts.pool_authority.key(),
+        ) {
+            invoke_signed(
+                &ix,
+                &[
+                    ctx.accounts.validator_stake.to_account_info(),
+                    ctx.accounts.transient_stake.to_account_info(),
+                    ctx.accounts.clock.to_account_info(),
+                    ctx.accounts.stake_history.to_account_info(),
+                    ctx.accounts.pool_authority.to_account_info(),
+                ],
+                &[authority_seeds],
+            )?;
+        }
 
-        let cpi_accounts = CreateMetadataAccountsV3 {
-            metadata: ctx.accounts.metadata.to_account_info(),
-            mint: ctx.accounts.slp_mint.to_account_info(),
-            mint_authority: ctx.accounts.pool_authority.to_account_info(),
-            payer: ctx.accounts.admin.to_account_info(),
-            update_authority: ctx.accounts.pool_authority.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-            rent: ctx.accounts.rent.to_account_info(),
-        };
+        let merged = validator.transient_stake_lamports;
+        validator.transient_stake_lamports = 0;
 
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_metadata_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
+        msg!(
+            "Merged {} lamports of transient stake for validator {}",
+            merged,
+            validator.vote_account
         );
+        emit!(TransientStakeMerged {
+            validator: validator.vote_account,
+            amount: merged,
+            epoch: Clock::get()?.epoch,
+        });
 
-        create_metadata_accounts_v3(cpi_ctx, data_v2, true, true, None)?;
+        Ok(())
+    }
+
+    /// Crank: Split stake owed to withdrawal tickets off a validator's stake
+    /// account and deactivate it. Only one deactivating account per validator
+    /// can be in flight; withdraw_deactivated_stake empties it after cooldown.
+    pub fn deactivate_stake(ctx: Context<DeactivateStake>) -> Result<()> {
+        let pool = &ctx.accounts.pool_config;
+
+        // The pool decides the amount: what tickets still need, so crankers
+        // cannot tie up the validator's deactivating account with dust
+        let tickets_uncovered = pool.ticket_lamports_owed
+            .saturating_sub(pool.claimable_lamports)
+            .saturating_sub(pool.deactivating_lamports);
+        require!(tickets_uncovered > 0, StakePoolError::NoUnstakeNeeded);
+
+        // What stays behind must be rent exempt and hold the minimum delegation
+        let list_info = ctx.accounts.validator_list.to_account_info();
+        let staked_lamports = ValidatorList::find_mut(
+            &mut list_info.try_borrow_mut_data()?,
+            ctx.accounts.vote_account.key,
+        )?
+        .staked_lamports;
+        let stake_minimum = Rent::get()?
+            .minimum_balance(StakeStateV2::size_of())
+            .checked_add(get_minimum_delegation()?)
+            .ok_or(StakePoolError::MathOverflow)?;
+        let stake_above_minimum = staked_lamports
+            .min(ctx.accounts.validator_stake.lamports())
+            .saturating_sub(stake_minimum);
+
+        let amount_lamports = tickets_uncovered.min(stake_above_minimum);
+        require!(amount_lamports > 0, StakePoolError::InsufficientStake);
+
+        split_and_deactivate(ctx.accounts, &ctx.bumps, amount_lamports, amount_lamports)
+    }
+
+    /// Crank: Deactivate a validator's stake above its target weight, or all of
+    /// it once the validator is deactivated. The SOL returns to the reserve via
+    /// withdraw_deactivated_stake, and delegate_stake moves it to validators
+    /// below target.
+    pub fn rebalance(ctx: Context<DeactivateStake>) -> Result<()> {
+        let pool = &ctx.accounts.pool_config;
+        let epoch = Clock::get()?.epoch;
+        let list_info = ctx.accounts.validator_list.to_account_info();
+        let mut list_data = list_info.try_borrow_mut_data()?;
+
+        // Targets come from pool totals, which are only current once every
+        // validator's balance has been synced this epoch
+        require!(
+            ValidatorList::is_up_to_date(&list_data, epoch)?,
+            StakePoolError::ValidatorListOutdated
+        );
+        let validator = ValidatorList::find_mut(&mut list_data, ctx.accounts.vote_account.key)?;
+        require!(
+            validator.transient_stake_lamports == 0,
+            StakePoolError::InvalidStakeState
+        );
+
+        let target_lamports = pool.target_validator_lamports(validator)?;
+        let amount_lamports = validator.staked_lamports.saturating_sub(target_lamports);
+        require!(amount_lamports > 0, StakePoolError::NoRebalanceNeeded);
+
+        msg!(
+            "Rebalancing validator {}: {} staked, {} target",
+            validator.vote_account,
+            validator.staked_lamports,
+            target_lamports
+        );
+        emit!(StakeRebalanced {
+            validator: validator.vote_account,
+            amount: amount_lamports,
+            target_lamports,
+            epoch,
+        });
+        drop(list_data);
+
+        split_and_deactivate(ctx.accounts, &ctx.bumps, amount_lamports, 0)
+    }
+
+    /// Crank: Withdraw a fully deactivated stake account into the reserve vault,
+    /// setting the ticket share aside for claims
+    pub fn withdraw_deactivated_stake(ctx: Context<WithdrawDeactivatedStake>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        let list_info = ctx.accounts.validator_list.to_account_info();
+        let mut list_data = list_info.try_borrow_mut_data()?;
+        let validator = ValidatorList::find_mut(&mut list_data, ctx.accounts.vote_account.key)?;
+
+        let deactivating = validator.deactivating_lamports;
+        require!(deactivating > 0, StakePoolError::InvalidStakeState);
+
+        let withdrawn = ctx.accounts.deactivating_stake.lamports();
+        let pool_key = pool.key();
+        let authority_seeds: &[&[u8]] = &[
+            POOL_AUTHORITY_SEED,
+            pool_key.as_ref(),
+            &[pool.authority_bump],
+        ];
+
+        // Fails in the stake program until the cooldown has passed
+        invoke_signed(
+            &stake_instruction::withdraw(
+                &ctx.accounts.deactivating_stake.key(),
+                &ctx.accounts.pool_authority.key(),
+                &ctx.accounts.reserve_vault.key(),
+                withdrawn,
+                None,
+            ),
+            &[
+                ctx.accounts.deactivating_stake.to_account_info(),
+                ctx.accounts.reserve_vault.to_account_info(),
+                ctx.accounts.clock.to_account_info(),
+                ctx.accounts.stake_history.to_account_info(),
+                ctx.accounts.pool_authority.to_account_info(),
+            ],
+            &[authority_seeds],
+        )?;
+
+        // The ticket share is set aside for claims; rent, rebalanced stake and
+        // any late rewards refill the reserve
+        let ticket_share = validator.deactivating_ticket_lamports.min(withdrawn);
+        let to_reserve = withdrawn
+            .checked_sub(ticket_share)
+            .ok_or(StakePoolError::MathOverflow)?;
+
+        pool.deactivating_lamports = pool.deactivating_lamports
+            .checked_sub(deactivating)
+            .ok_or(StakePoolError::MathOverflow)?;
+        pool.claimable_lamports = pool.claimable_lamports
+            .checked_add(ticket_share)
+            .ok_or(StakePoolError::MathOverflow)?;
+        pool.ticket_lamports_funded = pool.ticket_lamports_funded
+            .checked_add(ticket_share)
+            .ok_or(StakePoolError::MathOverflow)?;
+        pool.total_staked_lamports = pool.total_staked_lamports
+            .saturating_sub(deactivating.saturating_sub(ticket_share));
+        pool.reserve_lamports = pool.reserve_lamports
+            .checked_add(to_reserve)
+            .ok_or(StakePoolError::MathOverflow)?;
+        validator.deactivating_lamports = 0;
+        validator.deactivating_ticket_lamports = 0;
+
+        msg!(
+            "Withdrew {} deactivated lamports from validator {} ({} for tickets)",
+            withdrawn,
+            validator.vote_account,
+            ticket_share
+        );
+        emit!(DeactivatedStakeWithdrawn {
+            validator: validator.vote_account,
+            amount: withdrawn,
+            claimable: ticket_share,
+        });
 
-        msg!("Token metadata created successfully!");
         Ok(())
     }
-}
 
-// ============================================================================
-// Account Contexts
-// ============================================================================
+    /// Crank: Sync validator stake with the lamports actually held by their
+    /// stake accounts, walking the validator list from `start_index`.
+    /// remaining_accounts holds a (validator stake, transient stake) pair per
+    /// validator; validators already updated this epoch are skipped. Growth
+    /// since the last update is staking rewards, shrinkage is slashing; both
+    /// flow into the exchange rate.
+    pub fn update_validator_list_balance(
+        ctx: Context<UpdateValidatorListBalance>,
+        start_index: u32,
+    ) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        let pool_key = pool.key();
+        let clock = Clock::get()?;
+        require_fee_recipient(pool, &ctx.accounts.fee_recipient)?;
+
+        let list_info = ctx.accounts.validator_list.to_account_info();
+        let mut list_data = list_info.try_borrow_mut_data()?;
+        let validators = ValidatorList::validators_mut(&mut list_data)?
+            .get_mut(start_index as usize..)
+            .ok_or(StakePoolError::ValidatorNotFound)?;
+
+        let mut total_rewards: u64 = 0;
+        let mut total_slashed: u64 = 0;
+        for (validator, stake_accounts) in validators
+            .iter_mut()
+            .zip(ctx.remaining_accounts.chunks_exact(2))
+        {
+            if validator.last_update_epoch >= clock.epoch {
+                continue;
+            }
+
+            let current_lamports = if validator.stake_account == Pubkey::default() {
+                0
+            } else {
+                let (validator_stake, transient_stake) = (&stake_accounts[0], &stake_accounts[1]);
+                require_keys_eq!(
+                    validator_stake.key(),
+                    validator.stake_account,
+                    StakePoolError::InvalidStakeState
+                );
+                let transient_lamports = if validator.transient_stake_lamports > 0 {
+                    let (transient_key, _) = Pubkey::find_program_address(
+                        &[TRANSIENT_STAKE_SEED, pool_key.as_ref(), validator.vote_account.as_ref()],
+                        &crate::ID,
+                    );
+                    require_keys_eq!(
+                        transient_stake.key(),
+                        transient_key,
+                        StakePoolError::InvalidStakeState
+                    );
+                    transient_stake.lamports()
+                } else {
+                    0
+                };
+                validator_stake.lamports()
+                    .checked_add(transient_lamports)
+                    .ok_or(StakePoolError::MathOverflow)?
+            };
+            let previous_lamports = validator.staked_lamports;
+
+            // Losses count in full; rewards above the epoch cap wait for later updates
+            let earned = current_lamports.saturating_sub(previous_lamports);
+            let rewards = earned.min(PoolConfig::max_epoch_rewards(previous_lamports));
+            let deferred_rewards = earned - rewards;
+            let slashed = previous_lamports.saturating_sub(current_lamports);
+            total_rewards = total_rewards.saturating_add(rewards);
+            total_slashed = total_slashed.saturating_add(slashed);
+
+            pool.total_staked_lamports = pool.total_staked_lamports
+                .checked_add(rewards)
+                .ok_or(StakePoolError::MathOverflow)?
+                .checked_sub(slashed)
+                .ok_or(StakePoolError::MathOverflow)?;
+
+            validator.staked_lamports = current_lamports
+                .checked_sub(deferred_rewards)
+                .ok_or(StakePoolError::MathOverflow)?;
+            validator.last_update_epoch = clock.epoch;
+
+            msg!(
+                "Validator {} balance {} -> {} lamports ({} rewards, {} deferred, {} slashed)",
+                validator.vote_account,
+                previous_lamports,
+                current_lamports,
+                rewards,
+                deferred_rewards,
+                slashed
+            );
+            emit!(ValidatorBalanceUpdated {
+                validator: validator.vote_account,
+                previous_lamports,
+                current_lamports,
+                rewards,
+                deferred_rewards,
+                slashed,
+                new_exchange_rate: pool.exchange_rate(),
+                epoch: clock.epoch,
+            });
+        }
 
-#[derive(Accounts)]
-pub struct InitializePool<'info> {
-    #[account(mut)]
-    pub admin: Signer<'info>,
+        // The protocol's cut of this batch's net rewards is minted as slpSOL
+        let protocol_fee = pool.protocol_fee(total_rewards.saturating_sub(total_slashed), pool.fee_bps)?;
+        let fee_slp_minted = pool.calculate_fee_shares(protocol_fee)?;
+        if fee_slp_minted > 0 {
+            mint_protocol_fee(
+                pool,
+                &ctx.accounts.slp_mint,
+                &ctx.accounts.fee_recipient,
+                &ctx.accounts.pool_authority,
+                &ctx.accounts.token_program,
+                fee_slp_minted,
+            )?;
+
+            msg!("Minted {} slpSOL for a {} lamport reward fee", fee_slp_minted, protocol_fee);
+            emit!(RewardFeeMinted {
+                protocol_fee,
+                fee_slp_minted,
+                new_exchange_rate: pool.exchange_rate(),
+                epoch: clock.epoch,
+            });
+        }
 
-    #[account(
-        init,
-        payer = admin,
-        space = PoolConfig::LEN,
+        Ok(())
+    }
+
+    /// Crank: Reconcile reserve_lamports with the reserve vault's real balance.
+    /// SOL sent straight to the vault is recognised as rewards, at most once
+    /// per epoch and within the epoch reward cap; a shortfall is written off
+    /// the reserve in full.
+    pub fn update_pool_balance(ctx: Context<UpdatePoolBalance>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        let epoch = Clock::get()?.epoch;
+        require_fee_recipient(pool, &ctx.accounts.fee_recipient)?;
+
+        let vault_lamports = ctx.accounts.reserve_vault.lamports();
+        let booked_lamports = pool.reserve_lamports
+            .checked_add(pool.claimable_lamports)
+            .ok_or(StakePoolError::MathOverflow)?;
+        let donation = vault_lamports.saturating_sub(booked_lamports);
+        let shortfall = booked_lamports.saturating_sub(vault_lamports);
+
+        let rewards = if epoch > pool.last_balance_update_epoch {
+            let total_sol = pool.total_staked_lamports
+                .checked_add(pool.reserve_lamports)
+                .ok_or(StakePoolError::MathOverflow)?;
+            donation.min(PoolConfig::max_epoch_rewards(total_sol))
+        } else {
+            0
+        };
+        if rewards > 0 {
+            pool.last_balance_update_epoch = epoch;
+        }
+
+        pool.reserve_lamports = pool.reserve_lamports
+            .checked_add(rewards)
+            .ok_or(StakePoolError::MathOverflow)?
+            .checked_sub(shortfall)
+            .ok_or(StakePoolError::ReserveOutOfSync)?;
+
+        let protocol_fee = pool.protocol_fee(rewards, pool.fee_bps)?;
+        let fee_slp_minted = pool.calculate_fee_shares(protocol_fee)?;
+        mint_protocol_fee(
+            pool,
+            &ctx.accounts.slp_mint,
+            &ctx.accounts.fee_recipient,
+            &ctx.accounts.pool_authority,
+            &ctx.accounts.token_program,
+            fee_slp_minted,
+        )?;
+
+        msg!(
+            "Reserve vault holds {} lamports against {} booked ({} recognised as rewards, {} written off)",
+            vault_lamports,
+            booked_lamports,
+            rewards,
+            shortfall
+        );
+        emit!(BalanceReconciled {
+            vault_lamports,
+            booked_lamports,
+            rewards,
+            deferred_rewards: donation - rewards,
+            shortfall,
+            fee_slp_minted,
+            new_exchange_rate: pool.exchange_rate(),
+            epoch,
+        });
+
+        Ok(())
+    }
+
+    /// Crank: Simulate harvesting epoch rewards
+    /// Devnet only: simulates rewards based on ~7% APY instead of reading
+    /// stake account balances (see update_validator_list_balance)
+    #[cfg(feature = "devnet")]
+    pub fn harvest_rewards(ctx: Context<HarvestRewards>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        let clock = Clock::get()?;
+
+        require!(!pool.paused, StakePoolError::PoolPaused);
+        require!(
+            clock.epoch > pool.last_harvest_epoch,
+            StakePoolError::EpochNotChanged
+        );
+
+        // Calculate simulated rewards
+        // ~7% APY = ~0.019% per epoch (assuming ~365 epochs/year)
+        // rewards = total_staked * 0.00019 per epoch
+        let epochs_passed = clock.epoch
+            .checked_sub(pool.last_harvest_epoch)
+            .unwrap_or(1)
+            .max(1);
+
+        // 19 basis points per epoch (0.019%)
+        let reward_rate_per_epoch: u64 = 19;
+        let rewards_lamports = pool.total_staked_lamports
+            .checked_mul(reward_rate_per_epoch)
+            .ok_or(StakePoolError::MathOverflow)?
+            .checked_mul(epochs_passed)
+            .ok_or(StakePoolError::MathOverflow)?
+            .checked_div(100_000) // Divide by 100_000 to get the rate
+            .ok_or(StakePoolError::MathOverflow)?
+            .min(
+                PoolConfig::max_epoch_rewards(pool.total_staked_lamports)
+                    .saturating_mul(epochs_passed),
+            );
+
+        if rewards_lamports == 0 {
+            msg!("No rewards to harvest");
+            return Ok(());
+        }
+
+        require_fee_recipient(pool, &ctx.accounts.fee_recipient)?;
+
+        // Rewards raise the exchange rate; the protocol's cut is minted as slpSOL
+        let protocol_fee = pool.protocol_fee(rewards_lamports, pool.fee_bps)?;
+        let net_rewards = rewards_lamports
+            .checked_sub(protocol_fee)
+            .ok_or(StakePoolError::MathOverflow)?;
+
+        pool.total_staked_lamports = pool.total_staked_lamports
+            .checked_add(rewards_lamports)
+            .ok_or(StakePoolError::MathOverflow)?;
+        let fee_slp_minted = pool.calculate_fee_shares(protocol_fee)?;
+        mint_protocol_fee(
+            pool,
+            &ctx.accounts.slp_mint,
+            &ctx.accounts.fee_recipient,
+            &ctx.accounts.pool_authority,
+            &ctx.accounts.token_program,
+            fee_slp_minted,
+        )?;
+
+        pool.last_harvest_epoch = clock.epoch;
+
+        msg!(
+            "Harvested {} lamports ({} net after {} fee) over {} epochs",
+            rewards_lamports,
+            net_rewards,
+            protocol_fee,
+            epochs_passed
+        );
+        emit!(RewardsHarvested {
+            gross_rewards: rewards_lamports,
+            protocol_fee,
+            fee_slp_minted,
+            net_rewards,
+            new_exchange_rate: pool.exchange_rate(),
+            epoch: clock.epoch,
+        });
+
+        Ok(())
+    }
+
+    /// Admin: Pause the pool immediately, or queue an unpause behind the timelock
+    pub fn set_paused(ctx: Context<AdminAction>, paused: bool) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+
+        if paused {
+            pool.paused = true;
+            // A stale queued unpause must not undo an emergency pause
+            if pool.pending_change == ParameterChange::Unpause {
+                pool.pending_change = ParameterChange::None;
+                pool.pending_change_slot = 0;
+            }
+
+            msg!("Pool paused");
+            return Ok(());
+        }
+
+        require!(pool.paused, StakePoolError::PoolNotPaused);
+        queue_change(pool, ParameterChange::Unpause)
+    }
+
+    /// Admin: Queue a fee update, applied by execute_parameter_change after the timelock
+    pub fn update_fee(ctx: Context<AdminAction>, fee_type: FeeType, new_fee_bps: u16) -> Result<()> {
+        let max_fee_bps = match fee_type {
+            FeeType::Referral => MAX_REFERRAL_FEE_BPS,
+            _ => MAX_FEE_BPS,
+        };
+        require!(new_fee_bps <= max_fee_bps, StakePoolError::InvalidFee);
+
+        queue_change(
+            &mut ctx.accounts.pool_config,
+            ParameterChange::Fee { fee_type, new_fee_bps },
+        )
+    }
+
+    /// Admin: Queue a new slpSOL token account to receive protocol fees,
+    /// applied by execute_parameter_change after the timelock
+    pub fn set_fee_recipient(ctx: Context<SetFeeRecipient>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        queue_change(pool, ParameterChange::FeeRecipient)?;
+        pool.pending_fee_recipient = ctx.accounts.fee_recipient.key();
+
+        msg!("Fee recipient {} queued", pool.pending_fee_recipient);
+
+        Ok(())
+    }
+
+    /// Admin: Apply the queued parameter change once the timelock has elapsed
+    pub fn execute_parameter_change(ctx: Context<AdminAction>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        let change = pool.pending_change;
+
+        require!(change != ParameterChange::None, StakePoolError::NoPendingParameterChange);
+        require!(
+            Clock::get()?.slot >= pool.pending_change_slot,
+            StakePoolError::TimelockNotElapsed
+        );
+
+        match change {
+            ParameterChange::Fee { fee_type, new_fee_bps } => {
+                match fee_type {
+                    FeeType::Deposit => pool.deposit_fee_bps = new_fee_bps,
+                    FeeType::Withdrawal => pool.withdrawal_fee_bps = new_fee_bps,
+                    FeeType::Reward => pool.fee_bps = new_fee_bps,
+                    FeeType::Referral => pool.referral_fee_bps = new_fee_bps,
+                }
+                msg!("{:?} fee updated to {} bps", fee_type, new_fee_bps);
+            }
+            ParameterChange::Unpause => {
+                pool.paused = false;
+                msg!("Pool unpaused");
+            }
+            ParameterChange::FeeRecipient => {
+                pool.fee_recipient = pool.pending_fee_recipient;
+                pool.pending_fee_recipient = Pubkey::default();
+                msg!("Fee recipient set to {}", pool.fee_recipient);
+                emit!(FeeRecipientUpdated {
+                    fee_recipient: pool.fee_recipient,
+                });
+            }
+            ParameterChange::None => {}
+        }
+        pool.pending_change = ParameterChange::None;
+        pool.pending_change_slot = 0;
+
+        emit!(ParameterChangeExecuted { change });
+
+        Ok(())
+    }
+
+    /// Admin: Cancel the queued parameter change
+    pub fn cancel_parameter_change(ctx: Context<AdminAction>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        let change = pool.pending_change;
+
+        require!(change != ParameterChange::None, StakePoolError::NoPendingParameterChange);
+        pool.pending_change = ParameterChange::None;
+        pool.pending_change_slot = 0;
+        pool.pending_fee_recipient = Pubkey::default();
+
+        msg!("Queued parameter change cancelled");
+        emit!(ParameterChangeCancelled { change });
+
+        Ok(())
+    }
+
+    /// Admin: Propose a new pool admin (step 1 of 2).
+    /// Passing the default pubkey cancels a pending proposal.
+    pub fn propose_authority(ctx: Context<AdminAction>, new_admin: Pubkey) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+        pool.pending_admin = new_admin;
+
+        msg!("Admin transfer proposed: {} -> {}", pool.admin, new_admin);
+        emit!(AuthorityProposed {
+            current_admin: pool.admin,
+            proposed_admin: new_admin,
+        });
+
+        Ok(())
+    }
+
+    /// Accept a pending admin transfer (step 2 of 2).
+    /// The new admin must sign, so it can be a wallet or a
+    /// multisig PDA signing through CPI.
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool_config;
+
+        let previous_admin = pool.admin;
+        pool.admin = pool.pending_admin;
+        pool.pending_admin = Pubkey::default();
+
+        msg!("Admin transferred: {} -> {}", previous_admin, pool.admin);
+        emit!(AuthorityAccepted {
+            previous_admin,
+            new_admin: pool.admin,
+        });
+
+        Ok(())
+    }
+
+    /// Admin: Rewrite a pool config created before the authority transfer,
+    /// timelock, ticket, validator weight and fee fields in the current
+    /// PoolConfig layout, growing the account and topping up its rent from
+    /// the admin
+    pub fn migrate_pool_config(ctx: Context<MigratePoolConfig>) -> Result<()> {
+        let config_info = ctx.accounts.pool_config.to_account_info();
+        let old = {
+            let data = config_info.try_borrow_data()?;
+            require!(
+                data.len() == PoolConfigV0::LEN,
+                StakePoolError::PoolConfigAlreadyMigrated
+            );
+            require!(
+                data.starts_with(PoolConfig::DISCRIMINATOR),
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            PoolConfigV0::deserialize(&mut &data[8..])?
+        };
+        require_keys_eq!(old.admin, ctx.accounts.admin.key(), StakePoolError::InvalidAuthority);
+
+        let top_up = Rent::get()?
+            .minimum_balance(PoolConfig::LEN)
+            .saturating_sub(config_info.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.admin.to_account_info(),
+                        to: config_info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        config_info.resize(PoolConfig::LEN)?;
+
+        let config = PoolConfig::from(old);
+        config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+        msg!("Pool config migrated to {} bytes", PoolConfig::LEN);
+        emit!(PoolConfigMigrated {
+            previous_len: PoolConfigV0::LEN as u32,
+            new_len: PoolConfig::LEN as u32,
+        });
+
+        Ok(())
+    }
+
+    /// Admin: Create token metadata for secuSOL
+    pub fn create_token_metadata(
+        ctx: Context<CreateTokenMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        let pool = &ctx.accounts.pool_config;
+
+        // Create signer seeds for pool authority
+        let pool_key = pool.key();
+        let seeds = &[
+            POOL_AUTHORITY_SEED,
+            pool_key.as_ref(),
+            &[pool.authority_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Create metadata
+        let data_v2 = DataV2 {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        };
+
+        let cpi_accounts = CreateMetadataAccountsV3 {
+            metadata: ctx.accounts.metadata.to_account_info(),
+            mint: ctx.accounts.slp_mint.to_account_info(),
+            mint_authority: ctx.accounts.pool_authority.to_account_info(),
+            payer: ctx.accounts.admin.to_account_info(),
+            update_authority: ctx.accounts.pool_authority.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        create_metadata_accounts_v3(cpi_ctx, data_v2, true, true, None)?;
+
+        msg!("Token metadata created successfully!");
+        Ok(())
+    }
+}
+
+/// Split `amount_lamports` off the validator stake account into its
+/// deactivating stake account and deactivate it. `ticket_lamports` of it are
+/// set aside for withdrawal tickets when withdraw_deactivated_stake runs.
+fn split_and_deactivate(
+    accounts: &mut DeactivateStake,
+    bumps: &DeactivateStakeBumps,
+    amount_lamports: u64,
+    ticket_lamports: u64,
+) -> Result<()> {
+    let pool = &mut accounts.pool_config;
+    let vote_key = accounts.vote_account.key();
+    let list_info = accounts.validator_list.to_account_info();
+    let mut list_data = list_info.try_borrow_mut_data()?;
+    let validator = ValidatorList::find_mut(&mut list_data, &vote_key)?;
+
+    require!(
+        validator.deactivating_lamports == 0,
+        StakePoolError::InvalidStakeState
+    );
+    require!(
+        amount_lamports <= validator.staked_lamports,
+        StakePoolError::InsufficientStake
+    );
+
+    let stake_rent = Rent::get()?.minimum_balance(StakeStateV2::size_of());
+    require!(
+        pool.reserve_lamports >= stake_rent,
+        StakePoolError::InsufficientReserve
+    );
+
+    let pool_key = pool.key();
+    let reserve_seeds: &[&[u8]] = &[
+        RESERVE_VAULT_SEED,
+        pool_key.as_ref(),
+        &[bumps.reserve_vault],
+    ];
+    let deactivating_seeds: &[&[u8]] = &[
+        DEACTIVATING_STAKE_SEED,
+        pool_key.as_ref(),
+        vote_key.as_ref(),
+        &[bumps.deactivating_stake],
+    ];
+    let authority_seeds: &[&[u8]] = &[
+        POOL_AUTHORITY_SEED,
+        pool_key.as_ref(),
+        &[pool.authority_bump],
+    ];
+
+    // Split destinations must already be rent exempt
+    invoke_signed(
+        &system_instruction::transfer(
+            &accounts.reserve_vault.key(),
+            &accounts.deactivating_stake.key(),
+            stake_rent,
+        ),
+        &[
+            accounts.reserve_vault.to_account_info(),
+            accounts.deactivating_stake.to_account_info(),
+            accounts.system_program.to_account_info(),
+        ],
+        &[reserve_seeds],
+    )?;
+
+    for ix in stake_instruction::split(
+        &accounts.validator_stake.key(),
+        &accounts.pool_authority.key(),
+        amount_lamports,
+        &accounts.deactivating_stake.key(),
+    ) {
+        invoke_signed(
+            &ix,
+            &[
+                accounts.validator_stake.to_account_info(),
+                accounts.deactivating_stake.to_account_info(),
+                accounts.pool_authority.to_account_info(),
+                accounts.system_program.to_account_info(),
+            ],
+            &[deactivating_seeds, authority_seeds],
+        )?;
+    }
+
+    invoke_signed(
+        &stake_instruction::deactivate_stake(
+            &accounts.deactivating_stake.key(),
+            &accounts.pool_authority.key(),
+        ),
+        &[
+            accounts.deactivating_stake.to_account_info(),
+            accounts.clock.to_account_info(),
+            accounts.pool_authority.to_account_info(),
+        ],
+        &[authority_seeds],
+    )?;
+
+    // The rent moves from reserve to the pool's stake, and comes back on withdraw
+    let deactivating = amount_lamports
+        .checked_add(stake_rent)
+        .ok_or(StakePoolError::MathOverflow)?;
+    pool.reserve_lamports = pool.reserve_lamports
+        .checked_sub(stake_rent)
+        .ok_or(StakePoolError::MathOverflow)?;
+    pool.total_staked_lamports = pool.total_staked_lamports
+        .checked_add(stake_rent)
+        .ok_or(StakePoolError::MathOverflow)?;
+    pool.deactivating_lamports = pool.deactivating_lamports
+        .checked_add(deactivating)
+        .ok_or(StakePoolError::MathOverflow)?;
+    validator.staked_lamports = validator.staked_lamports
+        .checked_sub(amount_lamports)
+        .ok_or(StakePoolError::MathOverflow)?;
+    validator.deactivating_lamports = deactivating;
+    validator.deactivating_ticket_lamports = ticket_lamports;
+
+    // A full split empties the validator stake account; the next delegation recreates it
+    if validator.staked_lamports == 0 {
+        validator.stake_account = Pubkey::default();
+    }
+
+    msg!(
+        "Deactivating {} lamports from validator {}",
+        amount_lamports,
+        validator.vote_account
+    );
+    emit!(StakeDeactivated {
+        validator: validator.vote_account,
+        amount: amount_lamports,
+        epoch: Clock::get()?.epoch,
+    });
+
+    Ok(())
+}
+
+/// Queue a parameter change that can be executed after PARAMETER_TIMELOCK_SLOTS
+fn queue_change(pool: &mut Account<PoolConfig>, change: ParameterChange) -> Result<()> {
+    require!(
+        pool.pending_change == ParameterChange::None,
+        StakePoolError::ParameterChangePending
+    );
+
+    let activation_slot = Clock::get()?
+        .slot
+        .checked_add(PARAMETER_TIMELOCK_SLOTS)
+        .ok_or(StakePoolError::MathOverflow)?;
+    pool.pending_change = change;
+    pool.pending_change_slot = activation_slot;
+
+    msg!("Parameter change {:?} queued until slot {}", change, activation_slot);
+    emit!(ParameterChangeQueued {
+        change,
+        activation_slot,
+    });
+
+    Ok(())
+}
+
+/// Once the pool has a fee recipient, fee-charging instructions must be
+/// given it, so fees cannot be skipped by leaving the account out
+fn require_fee_recipient(
+    pool: &PoolConfig,
+    fee_recipient: &Option<Account<TokenAccount>>,
+) -> Result<()> {
+    require!(
+        fee_recipient.is_some() || pool.fee_recipient == Pubkey::default(),
+        StakePoolError::InvalidFeeRecipient
+    );
+    Ok(())
+}
+
+/// Mint fee slpSOL to the fee recipient or referrer and count it in the supply
+fn mint_protocol_fee<'info>(
+    pool: &mut Account<'info, PoolConfig>,
+    slp_mint: &Account<'info, Mint>,
+    fee_recipient: &Option<Account<'info, TokenAccount>>,
+    pool_authority: &UncheckedAccount<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let Some(fee_recipient) = fee_recipient else {
+        return Ok(());
+    };
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let pool_key = pool.key();
+    let authority_seeds: &[&[u8]] = &[
+        POOL_AUTHORITY_SEED,
+        pool_key.as_ref(),
+        &[pool.authority_bump],
+    ];
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: slp_mint.to_account_info(),
+                to: fee_recipient.to_account_info(),
+                authority: pool_authority.to_account_info(),
+            },
+            &[authority_seeds],
+        ),
+        amount,
+    )?;
+
+    pool.total_slp_supply = pool.total_slp_supply
+        .checked_add(amount)
+        .ok_or(StakePoolError::MathOverflow)?;
+
+    Ok(())
+}
+
+/// Move the withdrawal fee's slpSOL from the user to the fee recipient
+fn transfer_withdrawal_fee<'info>(
+    user_slp_account: &Account<'info, TokenAccount>,
+    fee_recipient: &Option<Account<'info, TokenAccount>>,
+    user: &Signer<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let Some(fee_recipient) = fee_recipient else {
+        return Ok(());
+    };
+    if amount == 0 {
+        return Ok(());
+    }
+
+    token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: user_slp_account.to_account_info(),
+                to: fee_recipient.to_account_info(),
+                authority: user.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+// ============================================================================
+// Account Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = PoolConfig::LEN,
+        seeds = [POOL_CONFIG_SEED],
+        bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    /// CHECK: PDA used as mint/stake authority
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_config.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: PDA used as reserve vault
+    #[account(
+        mut,
+        seeds = [RESERVE_VAULT_SEED, pool_config.key().as_ref()],
+        bump
+    )]
+    pub reserve_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        mint::decimals = 9,
+        mint::authority = pool_authority,
+        mint::freeze_authority = pool_authority,
+    )]
+    pub slp_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeValidatorList<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == pool_config.admin @ StakePoolError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ValidatorList::space(INITIAL_VALIDATOR_LIST_CAPACITY),
+        seeds = [VALIDATOR_LIST_SEED, pool_config.key().as_ref()],
+        bump
+    )]
+    pub validator_list: AccountLoader<'info, ValidatorList>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(max_validators: u32)]
+pub struct ReallocValidatorList<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == pool_config.admin @ StakePoolError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATOR_LIST_SEED, pool_config.key().as_ref()],
+        bump = validator_list.load()?.bump,
+        realloc = ValidatorList::space(max_validators),
+        realloc::payer = admin,
+        realloc::zero = false
+    )]
+    pub validator_list: AccountLoader<'info, ValidatorList>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddValidator<'info> {
+    #[account(
+        constraint = admin.key() == pool_config.admin @ StakePoolError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATOR_LIST_SEED, pool_config.key().as_ref()],
+        bump = validator_list.load()?.bump
+    )]
+    pub validator_list: AccountLoader<'info, ValidatorList>,
+
+    /// CHECK: Validated by the stake program when stake is delegated to it
+    pub vote_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ValidatorAdminAction<'info> {
+    #[account(
+        constraint = admin.key() == pool_config.admin @ StakePoolError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATOR_LIST_SEED, pool_config.key().as_ref()],
+        bump = validator_list.load()?.bump
+    )]
+    pub validator_list: AccountLoader<'info, ValidatorList>,
+
+    /// CHECK: Looked up in the validator list
+    pub vote_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateValidatorScore<'info> {
+    #[account(
+        constraint = oracle.key() == pool_config.score_oracle @ StakePoolError::InvalidAuthority
+    )]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATOR_LIST_SEED, pool_config.key().as_ref()],
+        bump = validator_list.load()?.bump
+    )]
+    pub validator_list: AccountLoader<'info, ValidatorList>,
+
+    /// CHECK: Looked up in the validator list
+    pub vote_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    /// CHECK: PDA authority for minting
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_config.key().as_ref()],
+        bump = pool_config.authority_bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: PDA vault for SOL reserve
+    #[account(
+        mut,
+        seeds = [RESERVE_VAULT_SEED, pool_config.key().as_ref()],
+        bump
+    )]
+    pub reserve_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = slp_mint.key() == pool_config.slp_mint @ StakePoolError::InvalidMintAuthority
+    )]
+    pub slp_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_slp_account.mint == slp_mint.key(),
+        constraint = user_slp_account.owner == user.key()
+    )]
+    pub user_slp_account: Account<'info, TokenAccount>,
+
+    /// Pool's slpSOL fee account; required once a fee recipient is set
+    #[account(
+        mut,
+        address = pool_config.fee_recipient @ StakePoolError::InvalidFeeRecipient
+    )]
+    pub fee_recipient: Option<Account<'info, TokenAccount>>,
+
+    /// Referring partner's slpSOL account, paid a share of the deposit fee
+    #[account(
+        mut,
+        constraint = referrer.mint == pool_config.slp_mint @ StakePoolError::InvalidReferrer
+    )]
+    pub referrer: Option<Account<'info, TokenAccount>>,
+
+    /// Depositor's stake position, updated when passed in
+    #[account(
+        mut,
+        seeds = [USER_STAKE_SEED, pool_config.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump
+    )]
+    pub user_stake: Option<Account<'info, UserStake>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositStake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    /// CHECK: PDA mint authority and new staker/withdrawer of the deposited stake
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_config.key().as_ref()],
+        bump = pool_config.authority_bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATOR_LIST_SEED, pool_config.key().as_ref()],
+        bump = validator_list.load()?.bump
+    )]
+    pub validator_list: AccountLoader<'info, ValidatorList>,
+
+    /// CHECK: Vote account the deposited stake is delegated to, looked up in
+    /// the validator list
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// CHECK: PDA stake account for this validator, the merge destination
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STAKE_SEED, pool_config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub validator_stake: UncheckedAccount<'info>,
+
+    /// CHECK: User's stake account, deserialized in the instruction and
+    /// closed by the merge
+    #[account(
+        mut,
+        owner = solana_stake_interface::program::ID @ StakePoolError::InvalidDepositStake
+    )]
+    pub deposit_stake: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = slp_mint.key() == pool_config.slp_mint @ StakePoolError::InvalidMintAuthority
+    )]
+    pub slp_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_slp_account.mint == slp_mint.key(),
+        constraint = user_slp_account.owner == user.key()
+    )]
+    pub user_slp_account: Account<'info, TokenAccount>,
+
+    /// Pool's slpSOL fee account; required once a fee recipient is set
+    #[account(
+        mut,
+        address = pool_config.fee_recipient @ StakePoolError::InvalidFeeRecipient
+    )]
+    pub fee_recipient: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Clock sysvar
+    #[account(address = clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+
+    /// CHECK: Stake history sysvar
+    #[account(address = stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: Native stake program
+    #[account(address = solana_stake_interface::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSol<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    /// CHECK: PDA vault for SOL reserve
+    #[account(
+        mut,
+        seeds = [RESERVE_VAULT_SEED, pool_config.key().as_ref()],
+        bump
+    )]
+    pub reserve_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = slp_mint.key() == pool_config.slp_mint @ StakePoolError::InvalidMintAuthority
+    )]
+    pub slp_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_slp_account.mint == slp_mint.key(),
+        constraint = user_slp_account.owner == user.key()
+    )]
+    pub user_slp_account: Account<'info, TokenAccount>,
+
+    /// Pool's slpSOL fee account; required once a fee recipient is set
+    #[account(
+        mut,
+        address = pool_config.fee_recipient @ StakePoolError::InvalidFeeRecipient
+    )]
+    pub fee_recipient: Option<Account<'info, TokenAccount>>,
+
+    /// Depositor's stake position, updated when passed in
+    #[account(
+        mut,
+        seeds = [USER_STAKE_SEED, pool_config.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump
+    )]
+    pub user_stake: Option<Account<'info, UserStake>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    /// CHECK: PDA staker/withdrawer authority for pool stake accounts
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_config.key().as_ref()],
+        bump = pool_config.authority_bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATOR_LIST_SEED, pool_config.key().as_ref()],
+        bump = validator_list.load()?.bump
+    )]
+    pub validator_list: AccountLoader<'info, ValidatorList>,
+
+    /// CHECK: Looked up in the validator list
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// CHECK: PDA stake account for this validator, the split source
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STAKE_SEED, pool_config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub validator_stake: UncheckedAccount<'info>,
+
+    /// New stake account for the user, allocated by the split
+    #[account(mut)]
+    pub split_stake: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = slp_mint.key() == pool_config.slp_mint @ StakePoolError::InvalidMintAuthority
+    )]
+    pub slp_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_slp_account.mint == slp_mint.key(),
+        constraint = user_slp_account.owner == user.key()
+    )]
+    pub user_slp_account: Account<'info, TokenAccount>,
+
+    /// Pool's slpSOL fee account; required once a fee recipient is set
+    #[account(
+        mut,
+        address = pool_config.fee_recipient @ StakePoolError::InvalidFeeRecipient
+    )]
+    pub fee_recipient: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Clock sysvar
+    #[account(address = clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+
+    /// CHECK: Native stake program
+    #[account(address = solana_stake_interface::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(slp_amount: u64, ticket_id: u64)]
+pub struct RequestWithdrawal<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        init,
+        payer = user,
+        space = WithdrawalTicket::LEN,
+        seeds = [
+            WITHDRAWAL_TICKET_SEED,
+            pool_config.key().as_ref(),
+            user.key().as_ref(),
+            &ticket_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub withdrawal_ticket: Account<'info, WithdrawalTicket>,
+
+    #[account(
+        mut,
+        constraint = slp_mint.key() == pool_config.slp_mint @ StakePoolError::InvalidMintAuthority
+    )]
+    pub slp_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_slp_account.mint == slp_mint.key(),
+        constraint = user_slp_account.owner == user.key()
+    )]
+    pub user_slp_account: Account<'info, TokenAccount>,
+
+    /// Pool's slpSOL fee account; required once a fee recipient is set
+    #[account(
+        mut,
+        address = pool_config.fee_recipient @ StakePoolError::InvalidFeeRecipient
+    )]
+    pub fee_recipient: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimWithdrawal<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            WITHDRAWAL_TICKET_SEED,
+            pool_config.key().as_ref(),
+            user.key().as_ref(),
+            &withdrawal_ticket.ticket_id.to_le_bytes()
+        ],
+        bump = withdrawal_ticket.bump,
+        constraint = withdrawal_ticket.owner == user.key() @ StakePoolError::InvalidAuthority,
+        close = user
+    )]
+    pub withdrawal_ticket: Account<'info, WithdrawalTicket>,
+
+    /// CHECK: PDA vault for SOL reserve
+    #[account(
+        mut,
+        seeds = [RESERVE_VAULT_SEED, pool_config.key().as_ref()],
+        bump
+    )]
+    pub reserve_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct QuotePool<'info> {
+    #[account(
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeUserStake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        init,
+        payer = user,
+        space = UserStake::LEN,
+        seeds = [USER_STAKE_SEED, pool_config.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseUserStake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [USER_STAKE_SEED, pool_config.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump,
+        constraint = user_stake.owner == user.key() @ StakePoolError::InvalidAuthority,
+        close = user
+    )]
+    pub user_stake: Account<'info, UserStake>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateStake<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    /// CHECK: PDA staker/withdrawer authority for pool stake accounts
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_config.key().as_ref()],
+        bump = pool_config.authority_bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: PDA vault for SOL reserve, funds the new stake account
+    #[account(
+        mut,
+        seeds = [RESERVE_VAULT_SEED, pool_config.key().as_ref()],
+        bump
+    )]
+    pub reserve_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATOR_LIST_SEED, pool_config.key().as_ref()],
+        bump = validator_list.load()?.bump
+    )]
+    pub validator_list: AccountLoader<'info, ValidatorList>,
+
+    /// CHECK: Vote account the stake is delegated to, looked up in the validator
+    /// list and validated by the stake program
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// CHECK: PDA stake account for this validator, created on first delegation
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STAKE_SEED, pool_config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub validator_stake: UncheckedAccount<'info>,
+
+    /// CHECK: PDA transient stake account, created on later delegations
+    #[account(
+        mut,
+        seeds = [TRANSIENT_STAKE_SEED, pool_config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub transient_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Clock sysvar
+    #[account(address = clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+
+    /// CHECK: Stake history sysvar
+    #[account(address = stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: Stake config account, still required by the delegate instruction
+    #[account(address = solana_stake_interface::config::ID)]
+    pub stake_config: UncheckedAccount<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Native stake program
+    #[account(address = solana_stake_interface::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MergeTransientStake<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    /// CHECK: PDA staker authority for pool stake accounts
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_config.key().as_ref()],
+        bump = pool_config.authority_bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATOR_LIST_SEED, pool_config.key().as_ref()],
+        bump = validator_list.load()?.bump
+    )]
+    pub validator_list: AccountLoader<'info, ValidatorList>,
+
+    /// CHECK: Looked up in the validator list
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// CHECK: PDA stake account for this validator
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STAKE_SEED, pool_config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub validator_stake: UncheckedAccount<'info>,
+
+    /// CHECK: PDA transient stake account, closed by the merge
+    #[account(
+        mut,
+        seeds = [TRANSIENT_STAKE_SEED, pool_config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub transient_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Clock sysvar
+    #[account(address = clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+
+    /// CHECK: Stake history sysvar
+    #[account(address = stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: Native stake program
+    #[account(address = solana_stake_interface::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateStake<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
         seeds = [POOL_CONFIG_SEED],
-        bump
+        bump = pool_config.bump
     )]
     pub pool_config: Account<'info, PoolConfig>,
 
-    /// CHECK: PDA used as mint/stake authority
+    /// CHECK: PDA staker authority for pool stake accounts
     #[account(
         seeds = [POOL_AUTHORITY_SEED, pool_config.key().as_ref()],
-        bump
+        bump = pool_config.authority_bump
     )]
     pub pool_authority: UncheckedAccount<'info>,
 
-    /// CHECK: PDA used as reserve vault
+    /// CHECK: PDA vault for SOL reserve, pays the split account's rent
     #[account(
         mut,
         seeds = [RESERVE_VAULT_SEED, pool_config.key().as_ref()],
@@ -450,53 +2882,45 @@ pub struct InitializePool<'info> {
     pub reserve_vault: UncheckedAccount<'info>,
 
     #[account(
-        init,
-        payer = admin,
-        mint::decimals = 9,
-        mint::authority = pool_authority,
-        mint::freeze_authority = pool_authority,
+        mut,
+        seeds = [VALIDATOR_LIST_SEED, pool_config.key().as_ref()],
+        bump = validator_list.load()?.bump
     )]
-    pub slp_mint: Account<'info, Mint>,
+    pub validator_list: AccountLoader<'info, ValidatorList>,
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    /// CHECK: Looked up in the validator list
+    pub vote_account: UncheckedAccount<'info>,
 
-#[derive(Accounts)]
-pub struct AddValidator<'info> {
+    /// CHECK: PDA stake account for this validator
     #[account(
         mut,
-        constraint = admin.key() == pool_config.admin @ StakePoolError::InvalidAuthority
+        seeds = [VALIDATOR_STAKE_SEED, pool_config.key().as_ref(), vote_account.key().as_ref()],
+        bump
     )]
-    pub admin: Signer<'info>,
+    pub validator_stake: UncheckedAccount<'info>,
 
+    /// CHECK: PDA deactivating stake account, created by the split
     #[account(
         mut,
-        seeds = [POOL_CONFIG_SEED],
-        bump = pool_config.bump
+        seeds = [DEACTIVATING_STAKE_SEED, pool_config.key().as_ref(), vote_account.key().as_ref()],
+        bump
     )]
-    pub pool_config: Account<'info, PoolConfig>,
+    pub deactivating_stake: UncheckedAccount<'info>,
 
-    /// CHECK: Validated by checking it's a valid vote account
-    pub vote_account: UncheckedAccount<'info>,
+    /// CHECK: Clock sysvar
+    #[account(address = clock::ID)]
+    pub clock: UncheckedAccount<'info>,
 
-    #[account(
-        init,
-        payer = admin,
-        space = ValidatorEntry::LEN,
-        seeds = [VALIDATOR_STAKE_SEED, pool_config.key().as_ref(), vote_account.key().as_ref()],
-        bump
-    )]
-    pub validator_entry: Account<'info, ValidatorEntry>,
+    /// CHECK: Native stake program
+    #[account(address = solana_stake_interface::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct DepositSol<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+pub struct WithdrawDeactivatedStake<'info> {
+    pub cranker: Signer<'info>,
 
     #[account(
         mut,
@@ -505,14 +2929,14 @@ pub struct DepositSol<'info> {
     )]
     pub pool_config: Account<'info, PoolConfig>,
 
-    /// CHECK: PDA authority for minting
+    /// CHECK: PDA withdrawer authority for pool stake accounts
     #[account(
         seeds = [POOL_AUTHORITY_SEED, pool_config.key().as_ref()],
         bump = pool_config.authority_bump
     )]
     pub pool_authority: UncheckedAccount<'info>,
 
-    /// CHECK: PDA vault for SOL reserve
+    /// CHECK: PDA vault for SOL reserve, receives the withdrawn stake
     #[account(
         mut,
         seeds = [RESERVE_VAULT_SEED, pool_config.key().as_ref()],
@@ -520,27 +2944,81 @@ pub struct DepositSol<'info> {
     )]
     pub reserve_vault: UncheckedAccount<'info>,
 
+    #[account(
+        mut,
+        seeds = [VALIDATOR_LIST_SEED, pool_config.key().as_ref()],
+        bump = validator_list.load()?.bump
+    )]
+    pub validator_list: AccountLoader<'info, ValidatorList>,
+
+    /// CHECK: Looked up in the validator list
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// CHECK: PDA deactivating stake account, emptied by the withdraw
+    #[account(
+        mut,
+        seeds = [DEACTIVATING_STAKE_SEED, pool_config.key().as_ref(), vote_account.key().as_ref()],
+        bump
+    )]
+    pub deactivating_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Clock sysvar
+    #[account(address = clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+
+    /// CHECK: Stake history sysvar
+    #[account(address = stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: Native stake program
+    #[account(address = solana_stake_interface::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateValidatorListBalance<'info> {
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [VALIDATOR_LIST_SEED, pool_config.key().as_ref()],
+        bump = validator_list.load()?.bump
+    )]
+    pub validator_list: AccountLoader<'info, ValidatorList>,
+
+    /// CHECK: PDA authority for minting
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_config.key().as_ref()],
+        bump = pool_config.authority_bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
     #[account(
         mut,
         constraint = slp_mint.key() == pool_config.slp_mint @ StakePoolError::InvalidMintAuthority
     )]
     pub slp_mint: Account<'info, Mint>,
 
+    /// Pool's slpSOL fee account; required once a fee recipient is set
     #[account(
         mut,
-        constraint = user_slp_account.mint == slp_mint.key(),
-        constraint = user_slp_account.owner == user.key()
+        address = pool_config.fee_recipient @ StakePoolError::InvalidFeeRecipient
     )]
-    pub user_slp_account: Account<'info, TokenAccount>,
+    pub fee_recipient: Option<Account<'info, TokenAccount>>,
 
     pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct WithdrawSol<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+pub struct UpdatePoolBalance<'info> {
+    pub cranker: Signer<'info>,
 
     #[account(
         mut,
@@ -549,33 +3027,39 @@ pub struct WithdrawSol<'info> {
     )]
     pub pool_config: Account<'info, PoolConfig>,
 
-    /// CHECK: PDA vault for SOL reserve
+    /// CHECK: PDA vault for SOL reserve, only its balance is read
     #[account(
-        mut,
         seeds = [RESERVE_VAULT_SEED, pool_config.key().as_ref()],
         bump
     )]
     pub reserve_vault: UncheckedAccount<'info>,
 
+    /// CHECK: PDA authority for minting
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_config.key().as_ref()],
+        bump = pool_config.authority_bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
     #[account(
         mut,
         constraint = slp_mint.key() == pool_config.slp_mint @ StakePoolError::InvalidMintAuthority
     )]
     pub slp_mint: Account<'info, Mint>,
 
+    /// Pool's slpSOL fee account; required once a fee recipient is set
     #[account(
         mut,
-        constraint = user_slp_account.mint == slp_mint.key(),
-        constraint = user_slp_account.owner == user.key()
+        address = pool_config.fee_recipient @ StakePoolError::InvalidFeeRecipient
     )]
-    pub user_slp_account: Account<'info, TokenAccount>,
+    pub fee_recipient: Option<Account<'info, TokenAccount>>,
 
     pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
 }
 
+#[cfg(feature = "devnet")]
 #[derive(Accounts)]
-pub struct DelegateStake<'info> {
+pub struct HarvestRewards<'info> {
     pub cranker: Signer<'info>,
 
     #[account(
@@ -585,19 +3069,35 @@ pub struct DelegateStake<'info> {
     )]
     pub pool_config: Account<'info, PoolConfig>,
 
+    /// CHECK: PDA authority for minting
+    #[account(
+        seeds = [POOL_AUTHORITY_SEED, pool_config.key().as_ref()],
+        bump = pool_config.authority_bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
     #[account(
         mut,
-        seeds = [VALIDATOR_STAKE_SEED, pool_config.key().as_ref(), validator_entry.vote_account.as_ref()],
-        bump
+        constraint = slp_mint.key() == pool_config.slp_mint @ StakePoolError::InvalidMintAuthority
+    )]
+    pub slp_mint: Account<'info, Mint>,
+
+    /// Pool's slpSOL fee account; required once a fee recipient is set
+    #[account(
+        mut,
+        address = pool_config.fee_recipient @ StakePoolError::InvalidFeeRecipient
     )]
-    pub validator_entry: Account<'info, ValidatorEntry>,
+    pub fee_recipient: Option<Account<'info, TokenAccount>>,
 
-    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct HarvestRewards<'info> {
-    pub cranker: Signer<'info>,
+pub struct SetFeeRecipient<'info> {
+    #[account(
+        constraint = admin.key() == pool_config.admin @ StakePoolError::InvalidAuthority
+    )]
+    pub admin: Signer<'info>,
 
     #[account(
         mut,
@@ -605,6 +3105,11 @@ pub struct HarvestRewards<'info> {
         bump = pool_config.bump
     )]
     pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        constraint = fee_recipient.mint == pool_config.slp_mint @ StakePoolError::InvalidFeeRecipient
+    )]
+    pub fee_recipient: Account<'info, TokenAccount>,
 }
 
 #[derive(Accounts)]
@@ -622,6 +3127,35 @@ pub struct AdminAction<'info> {
     pub pool_config: Account<'info, PoolConfig>,
 }
 
+#[derive(Accounts)]
+pub struct MigratePoolConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Still in the PoolConfigV0 layout, so it can't be loaded as a
+    /// PoolConfig; the discriminator and admin are checked in the handler
+    #[account(mut, seeds = [POOL_CONFIG_SEED], bump, owner = crate::ID)]
+    pub pool_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        constraint = pool_config.pending_admin != Pubkey::default() @ StakePoolError::NoPendingAuthority,
+        constraint = new_admin.key() == pool_config.pending_admin @ StakePoolError::InvalidAuthority
+    )]
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [POOL_CONFIG_SEED],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+}
+
 #[derive(Accounts)]
 pub struct CreateTokenMetadata<'info> {
     #[account(
@@ -671,7 +3205,45 @@ pub struct PoolInitialized {
 #[event]
 pub struct ValidatorAdded {
     pub vote_account: Pubkey,
-    pub index: u8,
+    pub index: u32,
+}
+
+#[event]
+pub struct ValidatorListResized {
+    pub max_validators: u32,
+}
+
+#[event]
+pub struct ValidatorWeightUpdated {
+    pub vote_account: Pubkey,
+    pub weight: u16,
+    pub total_weight: u64,
+}
+
+#[event]
+pub struct ScoreOracleUpdated {
+    pub score_oracle: Pubkey,
+}
+
+#[event]
+pub struct ValidatorScoreUpdated {
+    pub vote_account: Pubkey,
+    pub commission_bps: u16,
+    pub uptime_bps: u16,
+    pub weight: u16,
+    pub total_weight: u64,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct ValidatorDeactivated {
+    pub vote_account: Pubkey,
+    pub staked_lamports: u64,
+}
+
+#[event]
+pub struct ValidatorRemoved {
+    pub vote_account: Pubkey,
 }
 
 #[event]
@@ -679,6 +3251,20 @@ pub struct Deposited {
     pub user: Pubkey,
     pub sol_amount: u64,
     pub slp_minted: u64,
+    pub deposit_fee: u64,
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct StakeDeposited {
+    pub user: Pubkey,
+    pub stake_account: Pubkey,
+    pub validator: Pubkey,
+    pub lamports: u64,
+    pub slp_minted: u64,
+    pub deposit_fee: u64,
     pub exchange_rate: u64,
 }
 
@@ -687,9 +3273,62 @@ pub struct Withdrawn {
     pub user: Pubkey,
     pub sol_amount: u64,
     pub slp_burned: u64,
+    pub withdrawal_fee: u64,
+    pub liquidity_fee: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct StakeWithdrawn {
+    pub user: Pubkey,
+    pub stake_account: Pubkey,
+    pub validator: Pubkey,
+    pub lamports: u64,
+    pub slp_burned: u64,
+    pub withdrawal_fee: u64,
+    pub exchange_rate: u64,
+}
+
+#[event]
+pub struct WithdrawalRequested {
+    pub user: Pubkey,
+    pub ticket: Pubkey,
+    pub sol_amount: u64,
+    pub slp_burned: u64,
+    pub withdrawal_fee: u64,
+    pub claimable_epoch: u64,
     pub exchange_rate: u64,
 }
 
+#[event]
+pub struct WithdrawalClaimed {
+    pub user: Pubkey,
+    pub ticket: Pubkey,
+    pub sol_amount: u64,
+}
+
+#[event]
+pub struct StakeRebalanced {
+    pub validator: Pubkey,
+    pub amount: u64,
+    pub target_lamports: u64,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct StakeDeactivated {
+    pub validator: Pubkey,
+    pub amount: u64,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct DeactivatedStakeWithdrawn {
+    pub validator: Pubkey,
+    pub amount: u64,
+    pub claimable: u64,
+}
+
 #[event]
 pub struct StakeDelegated {
     pub validator: Pubkey,
@@ -697,12 +3336,90 @@ pub struct StakeDelegated {
     pub epoch: u64,
 }
 
+#[event]
+pub struct ValidatorBalanceUpdated {
+    pub validator: Pubkey,
+    pub previous_lamports: u64,
+    pub current_lamports: u64,
+    pub rewards: u64,
+    pub deferred_rewards: u64,
+    pub slashed: u64,
+    pub new_exchange_rate: u64,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct TransientStakeMerged {
+    pub validator: Pubkey,
+    pub amount: u64,
+    pub epoch: u64,
+}
+
 #[event]
 pub struct RewardsHarvested {
     pub gross_rewards: u64,
     pub protocol_fee: u64,
+    pub fee_slp_minted: u64,
     pub net_rewards: u64,
     pub new_exchange_rate: u64,
     pub epoch: u64,
 }
 
+#[event]
+pub struct BalanceReconciled {
+    pub vault_lamports: u64,
+    pub booked_lamports: u64,
+    pub rewards: u64,
+    pub deferred_rewards: u64,
+    pub shortfall: u64,
+    pub fee_slp_minted: u64,
+    pub new_exchange_rate: u64,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct RewardFeeMinted {
+    pub protocol_fee: u64,
+    pub fee_slp_minted: u64,
+    pub new_exchange_rate: u64,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct FeeRecipientUpdated {
+    pub fee_recipient: Pubkey,
+}
+
+#[event]
+pub struct ParameterChangeQueued {
+    pub change: ParameterChange,
+    pub activation_slot: u64,
+}
+
+#[event]
+pub struct ParameterChangeExecuted {
+    pub change: ParameterChange,
+}
+
+#[event]
+pub struct ParameterChangeCancelled {
+    pub change: ParameterChange,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub current_admin: Pubkey,
+    pub proposed_admin: Pubkey,
+}
+
+#[event]
+pub struct PoolConfigMigrated {
+    pub previous_len: u32,
+    pub new_len: u32,
+}
+
+#[event]
+pub struct AuthorityAccepted {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
@@ -1,15 +1,42 @@
 use anchor_lang::prelude::*;
+use securelp_math::{bps_of, exchange_rate};
 
 /// Maximum number of validators the pool can delegate to
-pub const MAX_VALIDATORS: usize = 10;
+pub const MAX_VALIDATORS: u32 = 1_000;
+
+/// Validator slots allocated when the validator list is created
+pub const INITIAL_VALIDATOR_LIST_CAPACITY: u32 = 16;
+
+/// Largest growth of an account in one instruction (runtime limit)
+pub const MAX_LIST_REALLOC_BYTES: usize = 10_240;
 
 /// Minimum deposit amount (0.01 SOL = 10_000_000 lamports)
 pub const MIN_DEPOSIT_LAMPORTS: u64 = 10_000_000;
 
+/// slpSOL locked forever on the first deposit (0.001 slpSOL), so the share
+/// price cannot be inflated from a near-empty supply
+pub const MINIMUM_SLP_SUPPLY: u64 = 1_000_000;
+
+/// Largest exchange-rate rise one epoch's rewards can cause (0.5%); rewards
+/// above it are recognised in later epochs
+pub const MAX_EPOCH_REWARD_BPS: u16 = 50;
+
 /// Reserve ratio in basis points (10% = 1000 bps)
 /// Keeps 10% of deposits liquid for instant unstakes
 pub const RESERVE_RATIO_BPS: u16 = 1000;
 
+/// Target weight given to newly added validators
+pub const DEFAULT_VALIDATOR_WEIGHT: u16 = 100;
+
+/// Validator score inputs (commission, uptime) are in basis points of this
+pub const SCORE_BPS_DENOMINATOR: u16 = 10_000;
+
+/// Instant-unstake fee while the reserve stays at or above its target (0.3%)
+pub const UNSTAKE_FEE_MIN_BPS: u16 = 30;
+
+/// Instant-unstake fee for a withdrawal that empties the reserve (3%)
+pub const UNSTAKE_FEE_MAX_BPS: u16 = 300;
+
 /// Seed for pool config PDA
 pub const POOL_CONFIG_SEED: &[u8] = b"pool_config";
 
@@ -19,11 +46,94 @@ pub const POOL_AUTHORITY_SEED: &[u8] = b"pool_authority";
 /// Seed for reserve vault PDA
 pub const RESERVE_VAULT_SEED: &[u8] = b"reserve_vault";
 
+/// Seed for validator list PDA
+pub const VALIDATOR_LIST_SEED: &[u8] = b"validator_list";
+
 /// Seed for validator stake account PDA
 pub const VALIDATOR_STAKE_SEED: &[u8] = b"validator_stake";
 
+/// Seed for transient stake account PDA (activating stake waiting to be merged)
+pub const TRANSIENT_STAKE_SEED: &[u8] = b"transient_stake";
+
+/// Seed for deactivating stake account PDA (stake split off for withdrawal tickets)
+pub const DEACTIVATING_STAKE_SEED: &[u8] = b"deactivating_stake";
+
+/// Seed for withdrawal ticket PDA
+pub const WITHDRAWAL_TICKET_SEED: &[u8] = b"withdrawal_ticket";
+
+/// Seed for a user's stake position PDA
+pub const USER_STAKE_SEED: &[u8] = b"user_stake";
+
+/// Delay before a queued parameter change can be executed (~1 day of slots)
+pub const PARAMETER_TIMELOCK_SLOTS: u64 = 216_000;
+
+/// Highest fee the admin can set, for any fee type (10%)
+pub const MAX_FEE_BPS: u16 = 1000;
+
+/// Referrer's share of the deposit fee at pool creation (50%)
+pub const DEFAULT_REFERRAL_FEE_BPS: u16 = 5_000;
+
+/// Referral share is a fraction of the deposit fee, so it can go up to 100%
+pub const MAX_REFERRAL_FEE_BPS: u16 = 10_000;
+
+/// Which protocol fee a fee update applies to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeType {
+    /// Share of slpSOL minted on deposit
+    Deposit,
+    /// Share of slpSOL withdrawn, on instant and delayed unstakes
+    Withdrawal,
+    /// Share of staking rewards
+    Reward,
+    /// Referrer's share of the deposit fee
+    Referral,
+}
+
+/// Breakdown of a deposit_sol, as returned by quote_deposit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DepositQuote {
+    /// slpSOL issued in total: the user's, the fees and the dead shares
+    pub slp_minted: u64,
+    /// slpSOL the depositor receives
+    pub user_slp: u64,
+    /// slpSOL taken as the deposit fee
+    pub deposit_fee: u64,
+    /// Referrer's share of the deposit fee
+    pub referral_fee: u64,
+    /// slpSOL booked to supply but never minted, on the first deposit only
+    pub dead_shares: u64,
+}
+
+/// Breakdown of a withdraw_sol, as returned by quote_withdraw
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WithdrawQuote {
+    /// slpSOL sent to the fee recipient
+    pub withdrawal_fee: u64,
+    /// slpSOL burned
+    pub slp_burned: u64,
+    /// Lamports paid to the user
+    pub sol_out: u64,
+    /// Instant-unstake fee kept by the pool, in lamports
+    pub liquidity_fee: u64,
+}
+
+/// Parameter change waiting out the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParameterChange {
+    /// Nothing queued
+    #[default]
+    None,
+    /// Set one of the fees to a new value
+    Fee { fee_type: FeeType, new_fee_bps: u16 },
+    /// Lift an emergency pause
+    Unpause,
+    /// Send protocol fees to pending_fee_recipient
+    FeeRecipient,
+}
+
 /// Main pool configuration account
 #[account]
+#[derive(Default)]
 pub struct PoolConfig {
     /// Admin who can update pool settings
     pub admin: Pubkey,
@@ -40,7 +150,7 @@ pub struct PoolConfig {
     /// SOL held in reserve for instant unstakes
     pub reserve_lamports: u64,
 
-    /// Protocol fee in basis points (e.g., 100 = 1%)
+    /// Protocol fee on staking rewards in basis points (e.g., 100 = 1%)
     pub fee_bps: u16,
 
     /// Whether the pool is paused
@@ -50,7 +160,7 @@ pub struct PoolConfig {
     pub last_harvest_epoch: u64,
 
     /// Number of validators in the pool
-    pub validator_count: u8,
+    pub validator_count: u32,
 
     /// Bump seed for this PDA
     pub bump: u8,
@@ -58,25 +168,110 @@ pub struct PoolConfig {
     /// Bump seed for pool authority PDA
     pub authority_bump: u8,
 
+    /// Proposed new admin, awaiting acceptance (default = none)
+    pub pending_admin: Pubkey,
+
+    /// Queued parameter change
+    pub pending_change: ParameterChange,
+
+    /// Slot at which the queued change can be executed
+    pub pending_change_slot: u64,
+
+    /// SOL owed to open withdrawal tickets (already removed from total_staked_lamports)
+    pub ticket_lamports_owed: u64,
+
+    /// Lamports in deactivating stake accounts, incl. their rent
+    pub deactivating_lamports: u64,
+
+    /// SOL back in the reserve vault, set aside for ticket claims
+    pub claimable_lamports: u64,
+
+    /// Sum of target weights over active validators
+    pub total_validator_weight: u64,
+
+    /// Oracle allowed to push validator scores
+    pub score_oracle: Pubkey,
+
+    /// slpSOL token account receiving protocol fees (default = fees off)
+    pub fee_recipient: Pubkey,
+
+    /// Fee recipient queued behind the timelock (default = none)
+    pub pending_fee_recipient: Pubkey,
+
+    /// Protocol fee on deposits, in bps of the slpSOL minted
+    pub deposit_fee_bps: u16,
+
+    /// Protocol fee on withdrawals, in bps of the slpSOL withdrawn
+    pub withdrawal_fee_bps: u16,
+
+    /// Referrer's share of the deposit fee, in bps of the fee
+    pub referral_fee_bps: u16,
+
+    /// Last epoch update_pool_balance recognised reserve donations as rewards
+    pub last_balance_update_epoch: u64,
+
+    /// SOL ever owed to withdrawal tickets, in request order
+    pub ticket_lamports_requested: u64,
+
+    /// SOL ever set aside for ticket claims; tickets are paid in request order
+    /// up to this amount
+    pub ticket_lamports_funded: u64,
+
     /// Reserved for future use
+    pub _reserved: [u8; 4],
+}
+
+/// PoolConfig as laid out before the authority transfer, timelock, ticket,
+/// validator weight and fee fields, with an 8-bit validator_count. A config
+/// created then is read in this layout by migrate_pool_config and rewritten
+/// in the current one.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct PoolConfigV0 {
+    pub admin: Pubkey,
+    pub slp_mint: Pubkey,
+    pub total_staked_lamports: u64,
+    pub total_slp_supply: u64,
+    pub reserve_lamports: u64,
+    pub fee_bps: u16,
+    pub paused: bool,
+    pub last_harvest_epoch: u64,
+    pub validator_count: u8,
+    pub bump: u8,
+    pub authority_bump: u8,
     pub _reserved: [u8; 32],
 }
 
-impl Default for PoolConfig {
-    fn default() -> Self {
+impl PoolConfigV0 {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin
+        32 + // slp_mint
+        8 +  // total_staked_lamports
+        8 +  // total_slp_supply
+        8 +  // reserve_lamports
+        2 +  // fee_bps
+        1 +  // paused
+        8 +  // last_harvest_epoch
+        1 +  // validator_count
+        1 +  // bump
+        1 +  // authority_bump
+        32;  // reserved
+}
+
+impl From<PoolConfigV0> for PoolConfig {
+    fn from(old: PoolConfigV0) -> Self {
         Self {
-            admin: Pubkey::default(),
-            slp_mint: Pubkey::default(),
-            total_staked_lamports: 0,
-            total_slp_supply: 0,
-            reserve_lamports: 0,
-            fee_bps: 0,
-            paused: false,
-            last_harvest_epoch: 0,
-            validator_count: 0,
-            bump: 0,
-            authority_bump: 0,
-            _reserved: [0u8; 32],
+            admin: old.admin,
+            slp_mint: old.slp_mint,
+            total_staked_lamports: old.total_staked_lamports,
+            total_slp_supply: old.total_slp_supply,
+            reserve_lamports: old.reserve_lamports,
+            fee_bps: old.fee_bps,
+            paused: old.paused,
+            last_harvest_epoch: old.last_harvest_epoch,
+            validator_count: old.validator_count as u32,
+            bump: old.bump,
+            authority_bump: old.authority_bump,
+            ..Self::default()
         }
     }
 }
@@ -91,79 +286,123 @@ impl PoolConfig {
         2 +  // fee_bps
         1 +  // paused
         8 +  // last_harvest_epoch
-        1 +  // validator_count
+        4 +  // validator_count
         1 +  // bump
         1 +  // authority_bump
-        32;  // reserved
+        32 + // pending_admin
+        4 +  // pending_change (tag + fee type + u16)
+        8 +  // pending_change_slot
+        8 +  // ticket_lamports_owed
+        8 +  // deactivating_lamports
+        8 +  // claimable_lamports
+        8 +  // total_validator_weight
+        32 + // score_oracle
+        32 + // fee_recipient
+        32 + // pending_fee_recipient
+        2 +  // deposit_fee_bps
+        2 +  // withdrawal_fee_bps
+        2 +  // referral_fee_bps
+        8 +  // last_balance_update_epoch
+        8 +  // ticket_lamports_requested
+        8 +  // ticket_lamports_funded
+        4;   // reserved
 
     /// Calculate exchange rate: how much SOL per slpSOL
     /// Returns rate in lamports per slpSOL (with 9 decimal precision)
     pub fn exchange_rate(&self) -> u64 {
-        if self.total_slp_supply == 0 {
-            // 1:1 rate initially
-            1_000_000_000 // 1 SOL in lamports
-        } else {
-            // Total SOL (staked + reserve) / total slpSOL supply
-            // Use u128 to avoid overflow when scaling up
-            let total_sol = self.total_staked_lamports
-                .checked_add(self.reserve_lamports)
-                .unwrap_or(0) as u128;
-            
-            // Scale up to avoid precision loss
-            let rate = total_sol
-                .checked_mul(1_000_000_000)
-                .unwrap_or(0)
-                .checked_div(self.total_slp_supply as u128)
-                .unwrap_or(1_000_000_000);
-            
-            // Safe to unwrap since rate should fit in u64
-            u64::try_from(rate).unwrap_or(1_000_000_000)
-        }
+        // Total SOL (staked + reserve) / total slpSOL supply, 1:1 initially
+        let total_sol = self.total_staked_lamports
+            .checked_add(self.reserve_lamports)
+            .unwrap_or(0);
+        exchange_rate::exchange_rate(total_sol, self.total_slp_supply)
+    }
+
+    /// Total SOL backing slpSOL: staked plus reserve
+    fn total_sol(&self) -> Result<u64> {
+        self.total_staked_lamports
+            .checked_add(self.reserve_lamports)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))
     }
 
     /// Calculate slpSOL to mint for given SOL deposit
     pub fn calculate_slp_for_deposit(&self, sol_lamports: u64) -> Result<u64> {
+        // slp_to_mint = sol_deposited * total_slp_supply / total_sol, 1:1 for
+        // the first deposit
+        exchange_rate::slp_for_deposit(sol_lamports, self.total_sol()?, self.total_slp_supply)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))
+    }
+
+    /// slpSOL to lock out of the first deposit, zero afterwards
+    pub fn dead_shares(&self) -> u64 {
         if self.total_slp_supply == 0 {
-            // First deposit: 1:1 ratio
-            Ok(sol_lamports)
+            MINIMUM_SLP_SUPPLY
         } else {
-            // slp_to_mint = sol_deposited * total_slp_supply / total_sol
-            // Use u128 for intermediate calculation to avoid overflow
-            let total_sol = self.total_staked_lamports
-                .checked_add(self.reserve_lamports)
-                .ok_or(error!(super::errors::StakePoolError::MathOverflow))?;
-            
-            let result = (sol_lamports as u128)
-                .checked_mul(self.total_slp_supply as u128)
-                .ok_or(error!(super::errors::StakePoolError::MathOverflow))?
-                .checked_div(total_sol as u128)
-                .ok_or(error!(super::errors::StakePoolError::MathOverflow))?;
-            
-            // Convert back to u64, should fit since result <= sol_lamports
-            u64::try_from(result)
-                .map_err(|_| error!(super::errors::StakePoolError::MathOverflow))
+            0
         }
     }
 
+    /// Most rewards a stake balance can recognise in one epoch
+    pub fn max_epoch_rewards(lamports: u64) -> u64 {
+        // Can't overflow: the cap is below 100%
+        bps_of(lamports, MAX_EPOCH_REWARD_BPS).unwrap_or(lamports)
+    }
+
+    /// Deposits and withdrawals round in the pool's favour, so they must never
+    /// lower the exchange rate
+    pub fn check_exchange_rate(&self, rate_before: u64) -> Result<()> {
+        require!(
+            self.exchange_rate() >= rate_before,
+            super::errors::StakePoolError::ExchangeRateDecreased
+        );
+        Ok(())
+    }
+
+    /// The reserve vault must hold at least the SOL booked to it; donations
+    /// may push it above
+    pub fn check_reserve(&self, vault_lamports: u64) -> Result<()> {
+        let booked = self.reserve_lamports
+            .checked_add(self.claimable_lamports)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))?;
+        require!(
+            vault_lamports >= booked,
+            super::errors::StakePoolError::ReserveOutOfSync
+        );
+        Ok(())
+    }
+
     /// Calculate SOL to return for given slpSOL burn
     pub fn calculate_sol_for_withdrawal(&self, slp_amount: u64) -> Result<u64> {
-        if self.total_slp_supply == 0 {
+        // sol_to_return = slp_burned * total_sol / total_slp_supply
+        exchange_rate::sol_for_withdrawal(slp_amount, self.total_sol()?, self.total_slp_supply)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))
+    }
+
+    /// slpSOL to mint so that `fee_lamports`, already counted in the pool's
+    /// SOL, belongs to the fee recipient at the resulting exchange rate
+    pub fn calculate_fee_shares(&self, fee_lamports: u64) -> Result<u64> {
+        exchange_rate::fee_shares(fee_lamports, self.total_sol()?, self.total_slp_supply)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))
+    }
+
+    /// Protocol fee of `fee_bps` on `amount`; zero while no fee recipient is set
+    pub fn protocol_fee(&self, amount: u64, fee_bps: u16) -> Result<u64> {
+        if self.fee_recipient == Pubkey::default() {
             return Ok(0);
         }
 
-        // sol_to_return = slp_burned * total_sol / total_slp_supply
-        // Use u128 for intermediate calculation to avoid overflow
-        let total_sol = self.total_staked_lamports
-            .checked_add(self.reserve_lamports)
-            .ok_or(error!(super::errors::StakePoolError::MathOverflow))?;
+        bps_of(amount, fee_bps)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))
+    }
 
-        let result = (slp_amount as u128)
-            .checked_mul(total_sol as u128)
+    /// Referrer's cut of a deposit fee
+    pub fn referral_fee(&self, deposit_fee: u64) -> Result<u64> {
+        let fee = (deposit_fee as u128)
+            .checked_mul(self.referral_fee_bps as u128)
             .ok_or(error!(super::errors::StakePoolError::MathOverflow))?
-            .checked_div(self.total_slp_supply as u128)
+            .checked_div(MAX_REFERRAL_FEE_BPS as u128)
             .ok_or(error!(super::errors::StakePoolError::MathOverflow))?;
-        
-        u64::try_from(result)
+
+        u64::try_from(fee)
             .map_err(|_| error!(super::errors::StakePoolError::MathOverflow))
     }
 
@@ -176,10 +415,252 @@ impl PoolConfig {
             .checked_div(10000)
             .unwrap_or(0)
     }
+
+    /// Reserve the pool aims to keep liquid for instant unstakes
+    pub fn target_reserve_lamports(&self) -> u64 {
+        self.calculate_reserve_amount(
+            self.total_staked_lamports.saturating_add(self.reserve_lamports),
+        )
+    }
+
+    /// Instant-unstake fee for taking `sol_amount` out of the reserve.
+    /// Flat UNSTAKE_FEE_MIN_BPS while the reserve left behind is at or above
+    /// target, rising linearly to UNSTAKE_FEE_MAX_BPS as it approaches zero.
+    pub fn instant_unstake_fee_bps(&self, sol_amount: u64) -> u16 {
+        let target = self.target_reserve_lamports();
+        let remaining = self.reserve_lamports.saturating_sub(sol_amount);
+        if target == 0 || remaining >= target {
+            return UNSTAKE_FEE_MIN_BPS;
+        }
+
+        let span = (UNSTAKE_FEE_MAX_BPS - UNSTAKE_FEE_MIN_BPS) as u128;
+        let discount = span * remaining as u128 / target as u128;
+        UNSTAKE_FEE_MAX_BPS - discount as u16
+    }
+
+    /// Reserve above its target ratio, free to be delegated
+    pub fn reserve_excess_lamports(&self) -> u64 {
+        self.reserve_lamports.saturating_sub(self.target_reserve_lamports())
+    }
+
+    /// Stake a validator should hold given its share of the total weight.
+    /// Deactivated validators target zero; without any weights set every
+    /// validator is considered on target.
+    pub fn target_validator_lamports(&self, validator: &ValidatorEntry) -> Result<u64> {
+        if !validator.is_active() {
+            return Ok(0);
+        }
+        if self.total_validator_weight == 0 {
+            return Ok(validator.staked_lamports);
+        }
+
+        self.weighted_share(self.total_staked_lamports, validator)
+    }
+
+    /// Stake delegate_stake sends to a validator: its weighted share of the
+    /// staked SOL plus the reserve excess, less what it already holds, capped
+    /// by the reserve excess. Cranking every validator spreads the excess
+    /// proportionally to weight, whatever order they are cranked in.
+    pub fn delegation_amount(&self, validator: &ValidatorEntry) -> Result<u64> {
+        if !validator.is_active() || self.total_validator_weight == 0 {
+            return Ok(0);
+        }
+
+        let reserve_excess = self.reserve_excess_lamports();
+        let stakeable = self.total_staked_lamports
+            .checked_add(reserve_excess)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))?;
+        let target = self.weighted_share(stakeable, validator)?;
+
+        Ok(target.saturating_sub(validator.staked_lamports).min(reserve_excess))
+    }
+
+    fn weighted_share(&self, lamports: u64, validator: &ValidatorEntry) -> Result<u64> {
+        let share = (lamports as u128)
+            .checked_mul(validator.target_weight as u128)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))?
+            .checked_div(self.total_validator_weight as u128)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))?;
+
+        u64::try_from(share)
+            .map_err(|_| error!(super::errors::StakePoolError::MathOverflow))
+    }
+
+    /// Quote deposit_sol for `sol_lamports`, with or without a referrer
+    pub fn quote_deposit(&self, sol_lamports: u64, with_referrer: bool) -> Result<DepositQuote> {
+        let slp_minted = self.calculate_slp_for_deposit(sol_lamports)?;
+        require!(slp_minted > 0, super::errors::StakePoolError::MathOverflow);
+
+        let deposit_fee = self.protocol_fee(slp_minted, self.deposit_fee_bps)?;
+        let dead_shares = self.dead_shares();
+        let user_slp = slp_minted
+            .checked_sub(deposit_fee)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))?
+            .checked_sub(dead_shares)
+            .ok_or(error!(super::errors::StakePoolError::DepositBelowDeadShares))?;
+        let referral_fee = if with_referrer {
+            self.referral_fee(deposit_fee)?
+        } else {
+            0
+        };
+
+        Ok(DepositQuote {
+            slp_minted,
+            user_slp,
+            deposit_fee,
+            referral_fee,
+            dead_shares,
+        })
+    }
+
+    /// Quote withdraw_sol for `slp_amount`, the withdrawal fee included
+    pub fn quote_withdraw(&self, slp_amount: u64) -> Result<WithdrawQuote> {
+        let withdrawal_fee = self.protocol_fee(slp_amount, self.withdrawal_fee_bps)?;
+        let slp_burned = slp_amount
+            .checked_sub(withdrawal_fee)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))?;
+        let (sol_out, liquidity_fee) = self.quote_withdraw_sol(slp_burned)?;
+
+        Ok(WithdrawQuote {
+            withdrawal_fee,
+            slp_burned,
+            sol_out,
+            liquidity_fee,
+        })
+    }
+
+    /// Quote withdraw_sol for `slp_amount`: (SOL paid to the user, fee kept by the pool)
+    pub fn quote_withdraw_sol(&self, slp_amount: u64) -> Result<(u64, u64)> {
+        let gross = self.calculate_sol_for_withdrawal(slp_amount)?;
+        let fee_bps = self.instant_unstake_fee_bps(gross);
+
+        let fee = (gross as u128)
+            .checked_mul(fee_bps as u128)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))?
+            .checked_div(10_000)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))? as u64;
+        let sol_out = gross
+            .checked_sub(fee)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))?;
+
+        Ok((sol_out, fee))
+    }
 }
 
-/// Validator entry in the pool
-#[account]
+/// Growable list of the pool's validators. Zero-copy: the header is followed
+/// by `max_validators` ValidatorEntry slots, the first `validator_count` in use.
+/// Seeds: ["validator_list", pool_config]
+#[account(zero_copy)]
+pub struct ValidatorList {
+    /// Pool this list belongs to
+    pub pool: Pubkey,
+
+    /// Slots allocated in the account
+    pub max_validators: u32,
+
+    /// Slots in use
+    pub validator_count: u32,
+
+    /// Bump seed for this PDA
+    pub bump: u8,
+
+    /// Padding to an 8-byte boundary
+    pub _padding: [u8; 7],
+}
+
+impl ValidatorList {
+    /// Discriminator and header; records start here
+    pub const HEADER_LEN: usize = 8 + std::mem::size_of::<ValidatorList>();
+
+    /// Account size for a list with room for `max_validators` validators
+    pub fn space(max_validators: u32) -> usize {
+        Self::HEADER_LEN + max_validators as usize * ValidatorEntry::LEN
+    }
+
+    /// Header and all allocated slots of a list account's data
+    fn split(data: &[u8]) -> Result<(&ValidatorList, &[ValidatorEntry])> {
+        let (header, records) = data.split_at(Self::HEADER_LEN);
+        let header: &ValidatorList = bytemuck::try_from_bytes(&header[8..])
+            .map_err(|_| error!(super::errors::StakePoolError::InvalidValidatorList))?;
+        let capacity = header.max_validators as usize * ValidatorEntry::LEN;
+        let records = records
+            .get(..capacity)
+            .and_then(|bytes| bytemuck::try_cast_slice(bytes).ok())
+            .ok_or(error!(super::errors::StakePoolError::InvalidValidatorList))?;
+        Ok((header, records))
+    }
+
+    fn split_mut(data: &mut [u8]) -> Result<(&mut ValidatorList, &mut [ValidatorEntry])> {
+        let (header, records) = data.split_at_mut(Self::HEADER_LEN);
+        let header: &mut ValidatorList = bytemuck::try_from_bytes_mut(&mut header[8..])
+            .map_err(|_| error!(super::errors::StakePoolError::InvalidValidatorList))?;
+        let capacity = header.max_validators as usize * ValidatorEntry::LEN;
+        let records = records
+            .get_mut(..capacity)
+            .and_then(|bytes| bytemuck::try_cast_slice_mut(bytes).ok())
+            .ok_or(error!(super::errors::StakePoolError::InvalidValidatorList))?;
+        Ok((header, records))
+    }
+
+    /// Validators in use, in list order
+    pub fn validators(data: &[u8]) -> Result<&[ValidatorEntry]> {
+        let (header, records) = Self::split(data)?;
+        Ok(&records[..header.validator_count as usize])
+    }
+
+    /// Whether every validator's balance has been updated in `epoch`, so
+    /// pool-wide targets are computed from current stake
+    pub fn is_up_to_date(data: &[u8], epoch: u64) -> Result<bool> {
+        Ok(Self::validators(data)?
+            .iter()
+            .all(|validator| validator.last_update_epoch >= epoch))
+    }
+
+    pub fn validators_mut(data: &mut [u8]) -> Result<&mut [ValidatorEntry]> {
+        let (header, records) = Self::split_mut(data)?;
+        Ok(&mut records[..header.validator_count as usize])
+    }
+
+    /// Validator delegating to `vote_account`
+    pub fn find_mut<'a>(data: &'a mut [u8], vote_account: &Pubkey) -> Result<&'a mut ValidatorEntry> {
+        Self::validators_mut(data)?
+            .iter_mut()
+            .find(|validator| validator.vote_account == *vote_account)
+            .ok_or(error!(super::errors::StakePoolError::ValidatorNotFound))
+    }
+
+    /// Append a validator, returning its index
+    pub fn push(data: &mut [u8], validator: ValidatorEntry) -> Result<u32> {
+        let (header, records) = Self::split_mut(data)?;
+        let index = header.validator_count;
+        let slot = records
+            .get_mut(index as usize)
+            .ok_or(error!(super::errors::StakePoolError::ValidatorListFull))?;
+        *slot = validator;
+        header.validator_count += 1;
+        Ok(index)
+    }
+
+    /// Remove a validator, moving the last one into its slot
+    pub fn swap_remove(data: &mut [u8], vote_account: &Pubkey) -> Result<ValidatorEntry> {
+        let (header, records) = Self::split_mut(data)?;
+        let count = header.validator_count as usize;
+        let index = records[..count]
+            .iter()
+            .position(|validator| validator.vote_account == *vote_account)
+            .ok_or(error!(super::errors::StakePoolError::ValidatorNotFound))?;
+
+        let removed = records[index];
+        records[index] = records[count - 1];
+        records[count - 1] = ValidatorEntry::default();
+        header.validator_count -= 1;
+        Ok(removed)
+    }
+}
+
+/// Validator record in the validator list
+#[zero_copy]
+#[derive(Default, Debug)]
 pub struct ValidatorEntry {
     /// The validator's vote account
     pub vote_account: Pubkey,
@@ -193,48 +674,69 @@ pub struct ValidatorEntry {
     /// Last epoch this validator's stake was updated
     pub last_update_epoch: u64,
 
-    /// Whether this validator is active
-    pub active: bool,
+    /// Lamports in the transient stake account, waiting to be merged
+    pub transient_stake_lamports: u64,
 
-    /// Bump seed for stake account PDA
-    pub stake_bump: u8,
+    /// Lamports in the deactivating stake account, incl. its rent
+    pub deactivating_lamports: u64,
 
-    /// Index in validator list
-    pub index: u8,
+    /// Part of the deactivating stake owed to withdrawal tickets
+    pub deactivating_ticket_lamports: u64,
 
-    /// Reserved for future use
-    pub _reserved: [u8; 16],
-}
+    /// Epoch of the last score update
+    pub score_epoch: u64,
 
-impl Default for ValidatorEntry {
-    fn default() -> Self {
-        Self {
-            vote_account: Pubkey::default(),
-            stake_account: Pubkey::default(),
-            staked_lamports: 0,
-            last_update_epoch: 0,
-            active: false,
-            stake_bump: 0,
-            index: 0,
-            _reserved: [0u8; 16],
-        }
-    }
+    /// Admin-set weight for this validator's share of pool stake
+    pub target_weight: u16,
+
+    /// Commission reported by the score oracle, in bps
+    pub commission_bps: u16,
+
+    /// Uptime reported by the score oracle, in bps
+    pub uptime_bps: u16,
+
+    /// Whether this validator is active (1) or being drained (0)
+    pub active: u8,
+
+    /// Reserved for future use
+    pub _reserved: [u8; 17],
 }
 
 impl ValidatorEntry {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // vote_account
+    pub const LEN: usize = 32 + // vote_account
         32 + // stake_account
         8 +  // staked_lamports
         8 +  // last_update_epoch
+        8 +  // transient_stake_lamports
+        8 +  // deactivating_lamports
+        8 +  // deactivating_ticket_lamports
+        8 +  // score_epoch
+        2 +  // target_weight
+        2 +  // commission_bps
+        2 +  // uptime_bps
         1 +  // active
-        1 +  // stake_bump
-        1 +  // index
-        16;  // reserved
+        17;  // reserved
+
+    pub fn is_active(&self) -> bool {
+        self.active != 0
+    }
+
+    /// Target weight for a validator's score. A validator with no commission
+    /// and full uptime weighs DEFAULT_VALIDATOR_WEIGHT; commission and missed
+    /// uptime scale it down linearly.
+    pub fn score_weight(commission_bps: u16, uptime_bps: u16) -> u16 {
+        let denominator = SCORE_BPS_DENOMINATOR as u64;
+        let kept = denominator.saturating_sub(commission_bps as u64);
+        let weight = DEFAULT_VALIDATOR_WEIGHT as u64 * kept * uptime_bps as u64
+            / (denominator * denominator);
+        weight as u16
+    }
 }
 
 /// User's staking position (optional, for tracking)
+/// Seeds: ["user_stake", pool_config, owner]
 #[account]
+#[derive(Default)]
 pub struct UserStake {
     /// User's wallet
     pub owner: Pubkey,
@@ -254,24 +756,16 @@ pub struct UserStake {
     /// Bump seed
     pub bump: u8,
 
+    /// Total slpSOL the user has given up on withdrawals, incl. fees
+    pub total_slp_redeemed: u64,
+
+    /// Total SOL the user has withdrawn
+    pub total_sol_withdrawn: u64,
+
     /// Reserved
     pub _reserved: [u8; 16],
 }
 
-impl Default for UserStake {
-    fn default() -> Self {
-        Self {
-            owner: Pubkey::default(),
-            total_slp_received: 0,
-            total_sol_deposited: 0,
-            first_deposit_ts: 0,
-            last_action_ts: 0,
-            bump: 0,
-            _reserved: [0u8; 16],
-        }
-    }
-}
-
 impl UserStake {
     pub const LEN: usize = 8 + // discriminator
         32 + // owner
@@ -280,6 +774,81 @@ impl UserStake {
         8 +  // first_deposit_ts
         8 +  // last_action_ts
         1 +  // bump
+        8 +  // total_slp_redeemed
+        8 +  // total_sol_withdrawn
         16;  // reserved
+
+    /// Record a deposit of `sol_deposited` that paid out `slp_received`
+    pub fn record_deposit(&mut self, sol_deposited: u64, slp_received: u64, now: i64) -> Result<()> {
+        self.total_sol_deposited = self.total_sol_deposited
+            .checked_add(sol_deposited)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))?;
+        self.total_slp_received = self.total_slp_received
+            .checked_add(slp_received)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))?;
+        if self.first_deposit_ts == 0 {
+            self.first_deposit_ts = now;
+        }
+        self.last_action_ts = now;
+        Ok(())
+    }
+
+    /// Record a withdrawal of `sol_withdrawn` for `slp_redeemed`
+    pub fn record_withdrawal(&mut self, slp_redeemed: u64, sol_withdrawn: u64, now: i64) -> Result<()> {
+        self.total_slp_redeemed = self.total_slp_redeemed
+            .checked_add(slp_redeemed)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))?;
+        self.total_sol_withdrawn = self.total_sol_withdrawn
+            .checked_add(sol_withdrawn)
+            .ok_or(error!(super::errors::StakePoolError::MathOverflow))?;
+        self.last_action_ts = now;
+        Ok(())
+    }
 }
 
+/// Delayed unstake claim: SOL owed to a user who burned slpSOL
+/// Seeds: ["withdrawal_ticket", pool_config, owner, ticket_id]
+#[account]
+#[derive(Default)]
+pub struct WithdrawalTicket {
+    /// User who can claim the SOL
+    pub owner: Pubkey,
+
+    /// Caller-chosen id, lets a user hold several tickets
+    pub ticket_id: u64,
+
+    /// SOL owed, fixed at the exchange rate when the ticket was created
+    pub lamports: u64,
+
+    /// slpSOL burned for this ticket
+    pub slp_burned: u64,
+
+    /// Epoch the ticket was created
+    pub created_epoch: u64,
+
+    /// First epoch the ticket can be claimed (after stake cooldown)
+    pub claimable_epoch: u64,
+
+    /// Bump seed
+    pub bump: u8,
+
+    /// Pool's ticket_lamports_requested including this ticket; claimable once
+    /// ticket_lamports_funded reaches it
+    pub queue_position: u64,
+
+    /// Reserved
+    pub _reserved: [u8; 8],
+}
+
+impl WithdrawalTicket {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        8 +  // ticket_id
+        8 +  // lamports
+        8 +  // slp_burned
+        8 +  // created_epoch
+        8 +  // claimable_epoch
+        1 +  // bump
+        8 +  // queue_position
+        8;   // reserved
+}
@@ -0,0 +1,43 @@
+//! PoolConfig's size against the pre-upgrade PoolConfigV0 layout that
+//! migrate_pool_config resizes from.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountSerialize, AnchorSerialize};
+use stake_pool::state::*;
+
+#[test]
+fn timelock_fields_come_out_of_reserved() {
+    assert_eq!(PoolConfigV0::LEN, 142);
+
+    let widened_validator_count = 4 - 1;
+    let pending_admin = 32;
+    let tickets = 5 * 8; // owed, deactivating, claimable, requested, funded
+    let weights_and_oracle = 8 + 32;
+    let fees = 2 * 32 + 3 * 2 + 8; // recipient and pending recipient, three fee rates, last balance update
+    let timelock = 4 + 8; // pending_change, pending_change_slot
+    let reserved_given_up = 32 - 4;
+
+    assert!(timelock <= reserved_given_up);
+    assert_eq!(
+        PoolConfig::LEN,
+        PoolConfigV0::LEN + widened_validator_count + pending_admin + tickets + weights_and_oracle + fees + timelock
+            - reserved_given_up
+    );
+}
+
+#[test]
+fn len_matches_the_serialized_accounts() {
+    let old = PoolConfigV0::default().try_to_vec().unwrap();
+    assert_eq!(8 + old.len(), PoolConfigV0::LEN);
+
+    // Fee is the largest ParameterChange variant
+    let config = PoolConfig {
+        pending_admin: Pubkey::new_unique(),
+        pending_change: ParameterChange::Fee { fee_type: FeeType::Withdrawal, new_fee_bps: u16::MAX },
+        pending_change_slot: u64::MAX,
+        ..Default::default()
+    };
+    let mut data = Vec::new();
+    config.try_serialize(&mut data).unwrap();
+    assert_eq!(data.len(), PoolConfig::LEN);
+}
@@ -0,0 +1,112 @@
+//! Two-step admin transfer for stake_pool: propose_authority, then
+//! accept_authority signed by the proposed admin.
+//! Needs the SBF build of the program: `cargo test-sbf -p stake_pool`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+};
+use stake_pool::errors::StakePoolError;
+
+fn propose_authority_ix(pool: &Pool, admin: &Pubkey, new_admin: Pubkey) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::AdminAction {
+            admin: *admin,
+            pool_config: pool.config,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::ProposeAuthority { new_admin }.data(),
+    }
+}
+
+fn accept_authority_ix(pool: &Pool, new_admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::AcceptAuthority {
+            new_admin: *new_admin,
+            pool_config: pool.config,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::AcceptAuthority {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn proposed_admin_takes_over_on_accepting() {
+    let (mut context, pool) = setup(LAMPORTS_PER_SOL).await;
+    let old_admin = context.payer.pubkey();
+    let new_admin = Keypair::new();
+
+    let propose = propose_authority_ix(&pool, &old_admin, new_admin.pubkey());
+    send(&mut context, &[propose], &[]).await.unwrap();
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.admin, old_admin);
+    assert_eq!(config.pending_admin, new_admin.pubkey());
+
+    let accept = accept_authority_ix(&pool, &new_admin.pubkey());
+    send(&mut context, &[accept], &[&new_admin]).await.unwrap();
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.pending_admin, Pubkey::default());
+
+    // Only the new admin can act for the pool now
+    let propose = propose_authority_ix(&pool, &old_admin, old_admin);
+    assert_error(send(&mut context, &[propose], &[]).await, StakePoolError::InvalidAuthority.into());
+    let propose = propose_authority_ix(&pool, &new_admin.pubkey(), old_admin);
+    send(&mut context, &[propose], &[&new_admin]).await.unwrap();
+}
+
+#[tokio::test]
+async fn only_the_proposed_admin_can_accept() {
+    let (mut context, pool) = setup(LAMPORTS_PER_SOL).await;
+    let new_admin = Keypair::new();
+    let impostor = Keypair::new();
+
+    let propose = propose_authority_ix(&pool, &context.payer.pubkey(), new_admin.pubkey());
+    send(&mut context, &[propose], &[]).await.unwrap();
+
+    let accept = accept_authority_ix(&pool, &impostor.pubkey());
+    assert_error(
+        send(&mut context, &[accept], &[&impostor]).await,
+        StakePoolError::InvalidAuthority.into(),
+    );
+    assert_eq!(pool_config(&mut context, &pool).await.admin, context.payer.pubkey());
+}
+
+#[tokio::test]
+async fn accepting_with_nothing_proposed_fails() {
+    let (mut context, pool) = setup(LAMPORTS_PER_SOL).await;
+    let new_admin = Keypair::new();
+
+    let accept = accept_authority_ix(&pool, &new_admin.pubkey());
+    assert_error(
+        send(&mut context, &[accept], &[&new_admin]).await,
+        StakePoolError::NoPendingAuthority.into(),
+    );
+}
+
+#[tokio::test]
+async fn proposing_the_default_pubkey_cancels_a_proposal() {
+    let (mut context, pool) = setup(LAMPORTS_PER_SOL).await;
+    let admin = context.payer.pubkey();
+    let new_admin = Keypair::new();
+
+    let propose = propose_authority_ix(&pool, &admin, new_admin.pubkey());
+    let cancel = propose_authority_ix(&pool, &admin, Pubkey::default());
+    send(&mut context, &[propose, cancel], &[]).await.unwrap();
+    assert_eq!(pool_config(&mut context, &pool).await.pending_admin, Pubkey::default());
+
+    let accept = accept_authority_ix(&pool, &new_admin.pubkey());
+    assert_error(
+        send(&mut context, &[accept], &[&new_admin]).await,
+        StakePoolError::NoPendingAuthority.into(),
+    );
+    assert_eq!(pool_config(&mut context, &pool).await.admin, admin);
+}
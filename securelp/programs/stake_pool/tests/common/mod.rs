@@ -0,0 +1,289 @@
+//! Shared program-test setup for the stake_pool SBF tests.
+#![allow(dead_code, unused_imports)]
+
+use anchor_lang::prelude::{Clock, Pubkey};
+use anchor_lang::{AnchorDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use securelp_integration_tests::create_stake_pool;
+pub use securelp_integration_tests::{
+    assert_error, create_token_account, send, token_balance, StakePoolAccounts,
+};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    signature::Signer,
+};
+use solana_sdk_ids::{stake, system_program, sysvar, vote};
+use stake_pool::state::*;
+use std::ops::Deref;
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// The pool under test, with the bootstrap validator registered
+pub struct Pool {
+    pub accounts: StakePoolAccounts,
+    pub vote_account: Pubkey,
+}
+
+impl Deref for Pool {
+    type Target = StakePoolAccounts;
+
+    fn deref(&self) -> &StakePoolAccounts {
+        &self.accounts
+    }
+}
+
+impl Pool {
+    pub fn validator_stake(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[VALIDATOR_STAKE_SEED, self.config.as_ref(), self.vote_account.as_ref()],
+            &stake_pool::ID,
+        )
+        .0
+    }
+
+    pub fn transient_stake(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[TRANSIENT_STAKE_SEED, self.config.as_ref(), self.vote_account.as_ref()],
+            &stake_pool::ID,
+        )
+        .0
+    }
+
+    pub fn deactivating_stake(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[DEACTIVATING_STAKE_SEED, self.config.as_ref(), self.vote_account.as_ref()],
+            &stake_pool::ID,
+        )
+        .0
+    }
+
+    pub fn withdrawal_ticket(&self, owner: &Pubkey, ticket_id: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                WITHDRAWAL_TICKET_SEED,
+                self.config.as_ref(),
+                owner.as_ref(),
+                &ticket_id.to_le_bytes(),
+            ],
+            &stake_pool::ID,
+        )
+        .0
+    }
+}
+
+pub async fn current_epoch(context: &mut ProgramTestContext) -> u64 {
+    context.banks_client.get_sysvar::<Clock>().await.unwrap().epoch
+}
+
+pub async fn pool_config(context: &mut ProgramTestContext, pool: &Pool) -> PoolConfig {
+    let account = context.banks_client.get_account(pool.config).await.unwrap().unwrap();
+    PoolConfig::deserialize(&mut &account.data[8..]).unwrap()
+}
+
+pub async fn validator_list(context: &mut ProgramTestContext, pool: &Pool) -> (ValidatorList, Vec<ValidatorEntry>) {
+    let account = context
+        .banks_client
+        .get_account(pool.validator_list)
+        .await
+        .unwrap()
+        .unwrap();
+    let header: ValidatorList = bytemuck::pod_read_unaligned(&account.data[8..ValidatorList::HEADER_LEN]);
+    let validators = account.data[ValidatorList::HEADER_LEN..]
+        .chunks_exact(ValidatorEntry::LEN)
+        .take(header.validator_count as usize)
+        .map(bytemuck::pod_read_unaligned)
+        .collect();
+    (header, validators)
+}
+
+/// The bootstrap validator's record
+pub async fn validator_entry(context: &mut ProgramTestContext, pool: &Pool) -> ValidatorEntry {
+    let (_, validators) = validator_list(context, pool).await;
+    *validators
+        .iter()
+        .find(|validator| validator.vote_account == pool.vote_account)
+        .expect("validator in list")
+}
+
+/// Start program-test, initialize the pool, register the bootstrap validator
+/// and deposit `deposit` lamports into the reserve.
+pub async fn setup(deposit: u64) -> (ProgramTestContext, Pool) {
+    let program_test = ProgramTest::new("stake_pool", stake_pool::ID, None);
+    let mut context = program_test.start_with_context().await;
+
+    let vote_account = *context
+        .genesis_config()
+        .accounts
+        .iter()
+        .find(|(_, account)| account.owner == vote::ID)
+        .expect("bootstrap vote account")
+        .0;
+
+    let accounts = create_stake_pool(&mut context, 100).await;
+    let pool = Pool { accounts, vote_account };
+    let add_validator = add_validator_ix(&context, &pool, &pool.vote_account);
+    send(&mut context, &[add_validator, pool.deposit_sol_ix(deposit)], &[]).await.unwrap();
+
+    (context, pool)
+}
+
+/// Admin instruction on the pool config, signed by the payer as admin
+pub fn admin_ix(context: &ProgramTestContext, pool: &Pool, data: Vec<u8>) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::AdminAction {
+            admin: context.payer.pubkey(),
+            pool_config: pool.config,
+        }
+        .to_account_metas(None),
+        data,
+    }
+}
+
+/// Warp past PARAMETER_TIMELOCK_SLOTS so a queued change can be executed
+pub async fn wait_out_the_timelock(context: &mut ProgramTestContext) {
+    let slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(slot + PARAMETER_TIMELOCK_SLOTS + 1).unwrap();
+}
+
+/// Queue `fee_recipient` as the protocol fee recipient, signed by the payer as admin
+pub fn set_fee_recipient_ix(context: &ProgramTestContext, pool: &Pool, fee_recipient: Pubkey) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::SetFeeRecipient {
+            admin: context.payer.pubkey(),
+            pool_config: pool.config,
+            fee_recipient,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::SetFeeRecipient {}.data(),
+    }
+}
+
+pub fn add_validator_ix(context: &ProgramTestContext, pool: &Pool, vote_account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::AddValidator {
+            admin: context.payer.pubkey(),
+            pool_config: pool.config,
+            validator_list: pool.validator_list,
+            vote_account: *vote_account,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::AddValidator {}.data(),
+    }
+}
+
+pub fn delegate_stake_ix(context: &ProgramTestContext, pool: &Pool) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::DelegateStake {
+            cranker: context.payer.pubkey(),
+            pool_config: pool.config,
+            pool_authority: pool.authority,
+            reserve_vault: pool.reserve_vault,
+            validator_list: pool.validator_list,
+            vote_account: pool.vote_account,
+            validator_stake: pool.validator_stake(),
+            transient_stake: pool.transient_stake(),
+            clock: sysvar::clock::ID,
+            stake_history: sysvar::stake_history::ID,
+            stake_config: solana_stake_interface::config::ID,
+            rent: sysvar::rent::ID,
+            stake_program: stake::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::DelegateStake {}.data(),
+    }
+}
+
+pub fn merge_transient_stake_ix(context: &ProgramTestContext, pool: &Pool) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::MergeTransientStake {
+            cranker: context.payer.pubkey(),
+            pool_config: pool.config,
+            pool_authority: pool.authority,
+            validator_list: pool.validator_list,
+            vote_account: pool.vote_account,
+            validator_stake: pool.validator_stake(),
+            transient_stake: pool.transient_stake(),
+            clock: sysvar::clock::ID,
+            stake_history: sysvar::stake_history::ID,
+            stake_program: stake::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::MergeTransientStake {}.data(),
+    }
+}
+
+pub fn deactivate_stake_ix(context: &ProgramTestContext, pool: &Pool) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::DeactivateStake {
+            cranker: context.payer.pubkey(),
+            pool_config: pool.config,
+            pool_authority: pool.authority,
+            reserve_vault: pool.reserve_vault,
+            validator_list: pool.validator_list,
+            vote_account: pool.vote_account,
+            validator_stake: pool.validator_stake(),
+            deactivating_stake: pool.deactivating_stake(),
+            clock: sysvar::clock::ID,
+            stake_program: stake::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::DeactivateStake {}.data(),
+    }
+}
+
+pub fn rebalance_ix(context: &ProgramTestContext, pool: &Pool) -> Instruction {
+    let mut ix = deactivate_stake_ix(context, pool);
+    ix.data = stake_pool::instruction::Rebalance {}.data();
+    ix
+}
+
+pub fn withdraw_deactivated_stake_ix(context: &ProgramTestContext, pool: &Pool) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::WithdrawDeactivatedStake {
+            cranker: context.payer.pubkey(),
+            pool_config: pool.config,
+            pool_authority: pool.authority,
+            reserve_vault: pool.reserve_vault,
+            validator_list: pool.validator_list,
+            vote_account: pool.vote_account,
+            deactivating_stake: pool.deactivating_stake(),
+            clock: sysvar::clock::ID,
+            stake_history: sysvar::stake_history::ID,
+            stake_program: stake::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::WithdrawDeactivatedStake {}.data(),
+    }
+}
+
+/// Sync the bootstrap validator's balance with its stake accounts
+pub fn update_validator_list_balance_ix(context: &ProgramTestContext, pool: &Pool) -> Instruction {
+    let mut accounts = stake_pool::accounts::UpdateValidatorListBalance {
+        cranker: context.payer.pubkey(),
+        pool_config: pool.config,
+        validator_list: pool.validator_list,
+        pool_authority: pool.authority,
+        slp_mint: pool.slp_mint,
+        fee_recipient: None,
+        token_program: spl_token::ID,
+    }
+    .to_account_metas(None);
+    accounts.push(AccountMeta::new_readonly(pool.validator_stake(), false));
+    accounts.push(AccountMeta::new_readonly(pool.transient_stake(), false));
+
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts,
+        data: stake_pool::instruction::UpdateValidatorListBalance { start_index: 0 }.data(),
+    }
+}
@@ -0,0 +1,117 @@
+//! Delegation tests for stake_pool, run against the native stake program in program-test.
+//! Needs the SBF build of the program: `cargo test-sbf -p stake_pool`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::AnchorDeserialize;
+use common::*;
+use solana_sdk_ids::stake;
+use solana_stake_interface::state::StakeStateV2;
+
+#[tokio::test]
+async fn delegate_creates_and_delegates_validator_stake_account() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+
+    // Everything above the 10% reserve target goes to the only validator
+    let amount = 9 * LAMPORTS_PER_SOL;
+    let ix = delegate_stake_ix(&context, &pool);
+    send(&mut context, &[ix], &[]).await.unwrap();
+
+    let stake_account = context
+        .banks_client
+        .get_account(pool.validator_stake())
+        .await
+        .unwrap()
+        .expect("stake account created");
+    assert_eq!(stake_account.owner, stake::ID);
+    assert_eq!(stake_account.lamports, amount);
+
+    match StakeStateV2::deserialize(&mut stake_account.data.as_slice()).unwrap() {
+        StakeStateV2::Stake(meta, stake, _) => {
+            assert_eq!(meta.authorized.staker, pool.authority);
+            assert_eq!(meta.authorized.withdrawer, pool.authority);
+            assert_eq!(stake.delegation.voter_pubkey, pool.vote_account);
+            assert_eq!(stake.delegation.stake, amount - meta.rent_exempt_reserve);
+        }
+        other => panic!("stake account not delegated: {other:?}"),
+    }
+
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.total_staked_lamports, amount);
+    assert_eq!(config.reserve_lamports, LAMPORTS_PER_SOL);
+
+    let validator = validator_entry(&mut context, &pool).await;
+    assert_eq!(validator.stake_account, pool.validator_stake());
+    assert_eq!(validator.staked_lamports, amount);
+    assert_eq!(validator.transient_stake_lamports, 0);
+}
+
+#[tokio::test]
+async fn later_delegations_go_through_transient_stake_and_merge() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+
+    let first = delegate_stake_ix(&context, &pool);
+    send(&mut context, &[first], &[]).await.unwrap();
+
+    // A new deposit refills the reserve excess: 11 SOL reserve against a 2 SOL target
+    let deposit = pool.deposit_sol_ix(10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[deposit], &[]).await.unwrap();
+    let second = delegate_stake_ix(&context, &pool);
+    send(&mut context, &[second], &[]).await.unwrap();
+
+    let transient = context
+        .banks_client
+        .get_account(pool.transient_stake())
+        .await
+        .unwrap()
+        .expect("transient stake account created");
+    assert_eq!(transient.owner, stake::ID);
+    assert_eq!(transient.lamports, 9 * LAMPORTS_PER_SOL);
+
+    // Only one transient account can be in flight
+    let deposit = pool.deposit_sol_ix(10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[deposit], &[]).await.unwrap();
+    let third = delegate_stake_ix(&context, &pool);
+    assert!(send(&mut context, &[third], &[]).await.is_err());
+
+    // Let both stakes activate, then merge
+    let epoch = current_epoch(&mut context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+    let merge = merge_transient_stake_ix(&context, &pool);
+    send(&mut context, &[merge], &[]).await.unwrap();
+
+    let main = context
+        .banks_client
+        .get_account(pool.validator_stake())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(main.lamports, 18 * LAMPORTS_PER_SOL);
+    assert!(context
+        .banks_client
+        .get_account(pool.transient_stake())
+        .await
+        .unwrap()
+        .is_none());
+
+    let validator = validator_entry(&mut context, &pool).await;
+    assert_eq!(validator.staked_lamports, 18 * LAMPORTS_PER_SOL);
+    assert_eq!(validator.transient_stake_lamports, 0);
+}
+
+#[tokio::test]
+async fn delegate_leaves_reserve_at_target_ratio() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+
+    let delegate = delegate_stake_ix(&context, &pool);
+    send(&mut context, &[delegate], &[]).await.unwrap();
+
+    // 10% of the pool stays liquid, so there is nothing left to delegate
+    let again = delegate_stake_ix(&context, &pool);
+    assert!(send(&mut context, &[again], &[]).await.is_err());
+
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.total_staked_lamports, 9 * LAMPORTS_PER_SOL);
+    assert_eq!(config.reserve_lamports, LAMPORTS_PER_SOL);
+}
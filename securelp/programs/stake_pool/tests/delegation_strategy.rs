@@ -0,0 +1,70 @@
+//! Score-weighted delegation targets on PoolConfig and ValidatorEntry.
+
+use stake_pool::state::*;
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+fn validator(target_weight: u16, staked_lamports: u64) -> ValidatorEntry {
+    ValidatorEntry {
+        active: 1,
+        target_weight,
+        staked_lamports,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn score_weight_scales_with_commission_and_uptime() {
+    assert_eq!(ValidatorEntry::score_weight(0, 10_000), DEFAULT_VALIDATOR_WEIGHT);
+    assert_eq!(ValidatorEntry::score_weight(1_000, 10_000), 90);
+    assert_eq!(ValidatorEntry::score_weight(1_000, 5_000), 45);
+    assert_eq!(ValidatorEntry::score_weight(10_000, 10_000), 0);
+    assert_eq!(ValidatorEntry::score_weight(0, 0), 0);
+}
+
+#[test]
+fn reserve_excess_is_split_by_weight_in_any_order() {
+    // 100 SOL in the reserve, 90 SOL above the 10% target
+    let mut pool = PoolConfig {
+        reserve_lamports: 100 * LAMPORTS_PER_SOL,
+        total_validator_weight: 300,
+        ..Default::default()
+    };
+    let mut heavy = validator(200, 0);
+    let mut light = validator(100, 0);
+
+    // Crank the light validator first; it still only gets its third
+    for v in [&mut light, &mut heavy] {
+        let amount = pool.delegation_amount(v).unwrap();
+        v.staked_lamports += amount;
+        pool.reserve_lamports -= amount;
+        pool.total_staked_lamports += amount;
+    }
+
+    assert_eq!(light.staked_lamports, 30 * LAMPORTS_PER_SOL);
+    assert_eq!(heavy.staked_lamports, 60 * LAMPORTS_PER_SOL);
+    assert_eq!(pool.reserve_excess_lamports(), 0);
+    assert_eq!(pool.delegation_amount(&heavy).unwrap(), 0);
+}
+
+#[test]
+fn overweight_and_inactive_validators_get_nothing() {
+    let pool = PoolConfig {
+        total_staked_lamports: 80 * LAMPORTS_PER_SOL,
+        reserve_lamports: 20 * LAMPORTS_PER_SOL,
+        total_validator_weight: 200,
+        ..Default::default()
+    };
+
+    // Already above its half of the 90 SOL stakeable
+    assert_eq!(pool.delegation_amount(&validator(100, 60 * LAMPORTS_PER_SOL)).unwrap(), 0);
+    // The under-weight validator is capped by the 10 SOL excess
+    assert_eq!(
+        pool.delegation_amount(&validator(100, 20 * LAMPORTS_PER_SOL)).unwrap(),
+        10 * LAMPORTS_PER_SOL
+    );
+
+    let mut inactive = validator(100, 0);
+    inactive.active = 0;
+    assert_eq!(pool.delegation_amount(&inactive).unwrap(), 0);
+}
@@ -0,0 +1,158 @@
+//! Deposit fee and referral tests for stake_pool.
+//! Needs the SBF build of the program: `cargo test-sbf -p stake_pool`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use common::*;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{instruction::Instruction, signature::Signer};
+use solana_sdk_ids::system_program;
+use stake_pool::errors::StakePoolError;
+use stake_pool::state::{FeeType, ParameterChange, DEFAULT_REFERRAL_FEE_BPS};
+
+fn deposit_with_fees_ix(
+    context: &ProgramTestContext,
+    pool: &Pool,
+    fee_recipient: Pubkey,
+    referrer: Option<Pubkey>,
+    amount_lamports: u64,
+) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::DepositSol {
+            user: context.payer.pubkey(),
+            pool_config: pool.config,
+            pool_authority: pool.authority,
+            reserve_vault: pool.reserve_vault,
+            slp_mint: pool.slp_mint,
+            user_slp_account: pool.user_slp,
+            fee_recipient: Some(fee_recipient),
+            referrer,
+            user_stake: None,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::DepositSol { amount_lamports }.data(),
+    }
+}
+
+fn execute_ix(context: &ProgramTestContext, pool: &Pool) -> Instruction {
+    admin_ix(context, pool, stake_pool::instruction::ExecuteParameterChange {}.data())
+}
+
+/// Queue a 1% deposit fee and wait it out
+async fn set_deposit_fee(context: &mut ProgramTestContext, pool: &Pool) {
+    let update_fee = admin_ix(
+        context,
+        pool,
+        stake_pool::instruction::UpdateFee {
+            fee_type: FeeType::Deposit,
+            new_fee_bps: 100,
+        }
+        .data(),
+    );
+    send(context, &[update_fee], &[]).await.unwrap();
+    wait_out_the_timelock(context).await;
+    let execute = execute_ix(context, pool);
+    send(context, &[execute], &[]).await.unwrap();
+}
+
+/// Set a 1% deposit fee and point fees at a fresh treasury account
+async fn enable_deposit_fee(context: &mut ProgramTestContext, pool: &Pool) -> Pubkey {
+    let admin = context.payer.pubkey();
+    let treasury = create_token_account(context, &pool.slp_mint, &admin, 0).await;
+    set_deposit_fee(context, pool).await;
+
+    let set_fee_recipient = set_fee_recipient_ix(context, pool, treasury);
+    send(context, &[set_fee_recipient], &[]).await.unwrap();
+    wait_out_the_timelock(context).await;
+    let execute = execute_ix(context, pool);
+    send(context, &[execute], &[]).await.unwrap();
+
+    treasury
+}
+
+#[tokio::test]
+async fn deposit_fee_is_minted_to_the_fee_recipient() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    let treasury = enable_deposit_fee(&mut context, &pool).await;
+    let user_before = token_balance(&mut context, &pool.user_slp).await;
+
+    let deposit = deposit_with_fees_ix(&context, &pool, treasury, None, 10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[deposit], &[]).await.unwrap();
+
+    assert_eq!(token_balance(&mut context, &treasury).await, LAMPORTS_PER_SOL / 10);
+    assert_eq!(
+        token_balance(&mut context, &pool.user_slp).await - user_before,
+        10 * LAMPORTS_PER_SOL - LAMPORTS_PER_SOL / 10
+    );
+    assert_eq!(pool_config(&mut context, &pool).await.total_slp_supply, 20 * LAMPORTS_PER_SOL);
+}
+
+#[tokio::test]
+async fn referrer_shares_the_deposit_fee() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    let treasury = enable_deposit_fee(&mut context, &pool).await;
+    let referrer = create_token_account(&mut context, &pool.slp_mint, &Pubkey::new_unique(), 0).await;
+
+    let deposit = deposit_with_fees_ix(&context, &pool, treasury, Some(referrer), 10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[deposit], &[]).await.unwrap();
+
+    let deposit_fee = LAMPORTS_PER_SOL / 10;
+    let referral_fee = deposit_fee * DEFAULT_REFERRAL_FEE_BPS as u64 / 10_000;
+    assert_eq!(token_balance(&mut context, &referrer).await, referral_fee);
+    assert_eq!(token_balance(&mut context, &treasury).await, deposit_fee - referral_fee);
+}
+
+#[tokio::test]
+async fn fee_recipient_cannot_be_skipped() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    enable_deposit_fee(&mut context, &pool).await;
+
+    let deposit = pool.deposit_sol_ix(LAMPORTS_PER_SOL);
+    assert!(send(&mut context, &[deposit], &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn no_fee_is_charged_until_the_fee_recipient_timelock_ends() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    let admin = context.payer.pubkey();
+    let treasury = create_token_account(&mut context, &pool.slp_mint, &admin, 0).await;
+    set_deposit_fee(&mut context, &pool).await;
+
+    let set_fee_recipient = set_fee_recipient_ix(&context, &pool, treasury);
+    send(&mut context, &[set_fee_recipient], &[]).await.unwrap();
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.fee_recipient, Pubkey::default());
+    assert_eq!(config.pending_fee_recipient, treasury);
+    assert_eq!(config.pending_change, ParameterChange::FeeRecipient);
+
+    // Fees stay off, and the queued recipient isn't accepted yet
+    let user_before = token_balance(&mut context, &pool.user_slp).await;
+    send(&mut context, &[pool.deposit_sol_ix(10 * LAMPORTS_PER_SOL)], &[]).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &pool.user_slp).await - user_before,
+        10 * LAMPORTS_PER_SOL
+    );
+    let deposit = deposit_with_fees_ix(&context, &pool, treasury, None, LAMPORTS_PER_SOL);
+    assert_error(send(&mut context, &[deposit], &[]).await, StakePoolError::InvalidFeeRecipient.into());
+    let execute = execute_ix(&context, &pool);
+    assert_error(send(&mut context, &[execute], &[]).await, StakePoolError::TimelockNotElapsed.into());
+    assert_eq!(token_balance(&mut context, &treasury).await, 0);
+
+    wait_out_the_timelock(&mut context).await;
+    let execute = execute_ix(&context, &pool);
+    send(&mut context, &[execute], &[]).await.unwrap();
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.fee_recipient, treasury);
+    assert_eq!(config.pending_fee_recipient, Pubkey::default());
+
+    let deposit = deposit_with_fees_ix(&context, &pool, treasury, None, 10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[deposit], &[]).await.unwrap();
+    assert!(token_balance(&mut context, &treasury).await > 0);
+}
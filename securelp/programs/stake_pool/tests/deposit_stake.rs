@@ -0,0 +1,132 @@
+//! deposit_stake tests for stake_pool, run against the native stake program in program-test.
+//! Needs the SBF build of the program: `cargo test-sbf -p stake_pool`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use common::*;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+};
+use solana_sdk_ids::{stake, sysvar};
+use solana_stake_interface::{
+    instruction as stake_instruction,
+    state::{Authorized, Lockup},
+};
+
+fn deposit_stake_ix(context: &ProgramTestContext, pool: &Pool, deposit_stake: Pubkey) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::DepositStake {
+            user: context.payer.pubkey(),
+            pool_config: pool.config,
+            pool_authority: pool.authority,
+            validator_list: pool.validator_list,
+            vote_account: pool.vote_account,
+            validator_stake: pool.validator_stake(),
+            deposit_stake,
+            slp_mint: pool.slp_mint,
+            user_slp_account: pool.user_slp,
+            fee_recipient: None,
+            clock: sysvar::clock::ID,
+            stake_history: sysvar::stake_history::ID,
+            stake_program: stake::ID,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::DepositStake {}.data(),
+    }
+}
+
+/// Delegate `lamports` from the payer to the bootstrap validator
+async fn create_user_stake(context: &mut ProgramTestContext, pool: &Pool, lamports: u64) -> Pubkey {
+    let user = context.payer.pubkey();
+    let stake_account = Keypair::new();
+    let instructions = stake_instruction::create_account_and_delegate_stake(
+        &user,
+        &stake_account.pubkey(),
+        &pool.vote_account,
+        &Authorized::auto(&user),
+        &Lockup::default(),
+        lamports,
+    );
+    send(context, &instructions, &[&stake_account]).await.unwrap();
+    stake_account.pubkey()
+}
+
+/// Pool with delegated validator stake and an active 5 SOL user stake account
+async fn setup_with_user_stake() -> (ProgramTestContext, Pool, Pubkey) {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    let delegate = delegate_stake_ix(&context, &pool);
+    send(&mut context, &[delegate], &[]).await.unwrap();
+    let user_stake = create_user_stake(&mut context, &pool, 5 * LAMPORTS_PER_SOL).await;
+
+    let epoch = current_epoch(&mut context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+    let update = update_validator_list_balance_ix(&context, &pool);
+    send(&mut context, &[update], &[]).await.unwrap();
+
+    (context, pool, user_stake)
+}
+
+#[tokio::test]
+async fn deposit_stake_merges_into_validator_stake() {
+    let (mut context, pool, user_stake) = setup_with_user_stake().await;
+    let before = pool_config(&mut context, &pool).await;
+    let validator_before = validator_entry(&mut context, &pool).await;
+    let slp_before = token_balance(&mut context, &pool.user_slp).await;
+
+    let deposit = deposit_stake_ix(&context, &pool, user_stake);
+    send(&mut context, &[deposit], &[]).await.unwrap();
+
+    // The user's account is merged away and its full balance backs the new slpSOL
+    assert!(context.banks_client.get_account(user_stake).await.unwrap().is_none());
+    let validator_stake = context
+        .banks_client
+        .get_account(pool.validator_stake())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        validator_stake.lamports,
+        validator_before.staked_lamports + 5 * LAMPORTS_PER_SOL
+    );
+
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(
+        config.total_staked_lamports,
+        before.total_staked_lamports + 5 * LAMPORTS_PER_SOL
+    );
+    let minted = token_balance(&mut context, &pool.user_slp).await - slp_before;
+    assert_eq!(minted, before.calculate_slp_for_deposit(5 * LAMPORTS_PER_SOL).unwrap());
+    assert_eq!(config.total_slp_supply, before.total_slp_supply + minted);
+    assert!(config.exchange_rate() >= before.exchange_rate());
+
+    let validator = validator_entry(&mut context, &pool).await;
+    assert_eq!(validator.staked_lamports, validator_stake.lamports);
+}
+
+#[tokio::test]
+async fn activating_stake_is_rejected() {
+    let (mut context, pool, _) = setup_with_user_stake().await;
+
+    // Delegated this epoch, so still warming up
+    let user_stake = create_user_stake(&mut context, &pool, 5 * LAMPORTS_PER_SOL).await;
+    let deposit = deposit_stake_ix(&context, &pool, user_stake);
+    assert!(send(&mut context, &[deposit], &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn deposit_stake_requires_an_updated_validator_list() {
+    let (mut context, pool, user_stake) = setup_with_user_stake().await;
+
+    let epoch = current_epoch(&mut context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+    let deposit = deposit_stake_ix(&context, &pool, user_stake);
+    assert!(send(&mut context, &[deposit], &[]).await.is_err());
+}
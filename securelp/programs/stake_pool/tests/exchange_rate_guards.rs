@@ -0,0 +1,66 @@
+//! Share-inflation and rate-change guards on PoolConfig.
+
+use stake_pool::state::*;
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+#[test]
+fn first_deposit_locks_dead_shares() {
+    let mut pool = PoolConfig::default();
+    assert_eq!(pool.dead_shares(), MINIMUM_SLP_SUPPLY);
+
+    pool.total_slp_supply = MINIMUM_SLP_SUPPLY;
+    assert_eq!(pool.dead_shares(), 0);
+}
+
+#[test]
+fn donation_cannot_round_later_deposits_to_zero() {
+    // Attacker makes the smallest first deposit, then donates 100 SOL
+    let pool = PoolConfig {
+        reserve_lamports: MIN_DEPOSIT_LAMPORTS + 100 * LAMPORTS_PER_SOL,
+        total_slp_supply: MIN_DEPOSIT_LAMPORTS,
+        ..Default::default()
+    };
+
+    // The dead shares keep the victim's deposit from rounding to nothing
+    let victim = pool.calculate_slp_for_deposit(LAMPORTS_PER_SOL).unwrap();
+    assert!(victim > 0);
+    let attacker = MIN_DEPOSIT_LAMPORTS - MINIMUM_SLP_SUPPLY;
+    let attacker_value = pool.calculate_sol_for_withdrawal(attacker).unwrap();
+    assert!(attacker_value < MIN_DEPOSIT_LAMPORTS + 100 * LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn epoch_rewards_are_capped() {
+    let balance = 100 * LAMPORTS_PER_SOL;
+    assert_eq!(
+        PoolConfig::max_epoch_rewards(balance),
+        balance * MAX_EPOCH_REWARD_BPS as u64 / 10_000
+    );
+    assert_eq!(PoolConfig::max_epoch_rewards(0), 0);
+}
+
+#[test]
+fn exchange_rate_must_not_fall() {
+    let pool = PoolConfig {
+        total_staked_lamports: 90 * LAMPORTS_PER_SOL,
+        reserve_lamports: 10 * LAMPORTS_PER_SOL,
+        total_slp_supply: 100 * LAMPORTS_PER_SOL,
+        ..Default::default()
+    };
+    let rate = pool.exchange_rate();
+    assert!(pool.check_exchange_rate(rate).is_ok());
+    assert!(pool.check_exchange_rate(rate + 1).is_err());
+}
+
+#[test]
+fn reserve_vault_must_cover_booked_sol() {
+    let pool = PoolConfig {
+        reserve_lamports: 10 * LAMPORTS_PER_SOL,
+        claimable_lamports: LAMPORTS_PER_SOL,
+        ..Default::default()
+    };
+    assert!(pool.check_reserve(11 * LAMPORTS_PER_SOL).is_ok());
+    assert!(pool.check_reserve(12 * LAMPORTS_PER_SOL).is_ok());
+    assert!(pool.check_reserve(11 * LAMPORTS_PER_SOL - 1).is_err());
+}
@@ -0,0 +1,44 @@
+//! Instant-unstake fee curve on PoolConfig.
+
+use stake_pool::state::*;
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// 100 SOL pool with a 10 SOL reserve target
+fn pool(reserve_lamports: u64) -> PoolConfig {
+    PoolConfig {
+        total_staked_lamports: 100 * LAMPORTS_PER_SOL - reserve_lamports,
+        reserve_lamports,
+        total_slp_supply: 100 * LAMPORTS_PER_SOL,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn fee_is_minimal_while_reserve_stays_above_target() {
+    let pool = pool(20 * LAMPORTS_PER_SOL);
+    assert_eq!(pool.target_reserve_lamports(), 10 * LAMPORTS_PER_SOL);
+    assert_eq!(pool.instant_unstake_fee_bps(10 * LAMPORTS_PER_SOL), UNSTAKE_FEE_MIN_BPS);
+}
+
+#[test]
+fn fee_grows_as_reserve_falls_below_target() {
+    let pool = pool(10 * LAMPORTS_PER_SOL);
+
+    let small = pool.instant_unstake_fee_bps(LAMPORTS_PER_SOL);
+    let half = pool.instant_unstake_fee_bps(5 * LAMPORTS_PER_SOL);
+    let drain = pool.instant_unstake_fee_bps(10 * LAMPORTS_PER_SOL);
+
+    assert!(UNSTAKE_FEE_MIN_BPS < small && small < half && half < drain);
+    assert_eq!(half, (UNSTAKE_FEE_MIN_BPS + UNSTAKE_FEE_MAX_BPS) / 2);
+    assert_eq!(drain, UNSTAKE_FEE_MAX_BPS);
+}
+
+#[test]
+fn quote_keeps_fee_in_pool() {
+    let pool = pool(10 * LAMPORTS_PER_SOL);
+    let (sol_out, fee) = pool.quote_withdraw_sol(5 * LAMPORTS_PER_SOL).unwrap();
+
+    assert_eq!(sol_out + fee, 5 * LAMPORTS_PER_SOL);
+    assert_eq!(fee, 5 * LAMPORTS_PER_SOL * 165 / 10_000);
+}
@@ -0,0 +1,76 @@
+//! Property tests running random deposit and withdrawal sequences through the
+//! stake_pool handlers in program-test.
+//! Needs the SBF build of the program: `cargo test-sbf -p stake_pool`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token;
+use common::*;
+use proptest::prelude::*;
+use stake_pool::state::{MINIMUM_SLP_SUPPLY, MIN_DEPOSIT_LAMPORTS};
+
+#[derive(Debug, Clone)]
+enum Action {
+    Deposit { lamports: u64 },
+    /// Withdraw this share (in bps) of the payer's slpSOL
+    Withdraw { slp_bps: u16 },
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        (MIN_DEPOSIT_LAMPORTS..100 * LAMPORTS_PER_SOL).prop_map(|lamports| Action::Deposit { lamports }),
+        (1u16..=10_000).prop_map(|slp_bps| Action::Withdraw { slp_bps }),
+    ]
+}
+
+async fn run(first_deposit: u64, actions: Vec<Action>) {
+    let (mut context, pool) = setup(first_deposit).await;
+
+    for action in actions {
+        let before = pool_config(&mut context, &pool).await;
+        let instruction = match action {
+            Action::Deposit { lamports } => pool.deposit_sol_ix(lamports),
+            Action::Withdraw { slp_bps } => {
+                let balance = token_balance(&mut context, &pool.user_slp).await;
+                pool.withdraw_sol_ix((balance as u128 * slp_bps as u128 / 10_000) as u64)
+            }
+        };
+        // Rejected actions (empty or reserve-draining withdrawals) must leave
+        // the pool as it was
+        context.get_new_latest_blockhash().await.unwrap();
+        if send(&mut context, &[instruction], &[]).await.is_err() {
+            let after = pool_config(&mut context, &pool).await;
+            assert_eq!(after.total_slp_supply, before.total_slp_supply);
+            continue;
+        }
+
+        let after = pool_config(&mut context, &pool).await;
+        assert!(after.exchange_rate() >= before.exchange_rate(), "{action:?} lowered the rate");
+
+        // Supply booked by the pool is what the mint has issued plus the dead
+        // shares, and the vault covers the reserve it books
+        let mint = context.banks_client.get_account(pool.slp_mint).await.unwrap().unwrap();
+        let mint = spl_token::state::Mint::unpack(&mint.data).unwrap();
+        assert_eq!(after.total_slp_supply, mint.supply + MINIMUM_SLP_SUPPLY);
+        let vault = context.banks_client.get_balance(pool.reserve_vault).await.unwrap();
+        assert!(after.check_reserve(vault).is_ok());
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn handlers_never_lower_the_exchange_rate(
+        first_deposit in LAMPORTS_PER_SOL..100 * LAMPORTS_PER_SOL,
+        actions in prop::collection::vec(action(), 1..12),
+    ) {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(run(first_deposit, actions));
+    }
+}
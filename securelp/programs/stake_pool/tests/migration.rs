@@ -0,0 +1,139 @@
+//! Upgrading a pool config created before the authority transfer, timelock,
+//! ticket, validator weight and fee fields: migrate_pool_config rewrites a
+//! PoolConfigV0 account in the current PoolConfig layout.
+//! Needs the SBF build of the program: `cargo test-sbf -p stake_pool`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::{Pubkey, Rent};
+use anchor_lang::{AnchorSerialize, Discriminator, InstructionData, ToAccountMetas};
+use common::*;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+};
+use solana_sdk_ids::system_program;
+use stake_pool::errors::StakePoolError;
+use stake_pool::state::{FeeType, ParameterChange, PoolConfig, PoolConfigV0};
+
+fn migrate_pool_config_ix(pool: &Pool, admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::MigratePoolConfig {
+            admin: *admin,
+            pool_config: pool.config,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::MigratePoolConfig {}.data(),
+    }
+}
+
+/// Rewrite the pool config as one created before the upgrade would be laid
+/// out, rent-exempt at the old size
+async fn downgrade(context: &mut ProgramTestContext, pool: &Pool) -> PoolConfig {
+    let config = pool_config(context, pool).await;
+    let old = PoolConfigV0 {
+        admin: config.admin,
+        slp_mint: config.slp_mint,
+        total_staked_lamports: config.total_staked_lamports,
+        total_slp_supply: config.total_slp_supply,
+        reserve_lamports: config.reserve_lamports,
+        fee_bps: config.fee_bps,
+        paused: config.paused,
+        last_harvest_epoch: config.last_harvest_epoch,
+        validator_count: config.validator_count as u8,
+        bump: config.bump,
+        authority_bump: config.authority_bump,
+        _reserved: [0; 32],
+    };
+
+    let mut account = context.banks_client.get_account(pool.config).await.unwrap().unwrap();
+    account.data = PoolConfig::DISCRIMINATOR.to_vec();
+    old.serialize(&mut account.data).unwrap();
+    assert_eq!(account.data.len(), PoolConfigV0::LEN);
+    account.lamports = Rent::default().minimum_balance(PoolConfigV0::LEN);
+    context.set_account(&pool.config, &account.into());
+
+    config
+}
+
+#[tokio::test]
+async fn old_pool_config_is_migrated_to_the_current_layout() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    let admin = context.payer.pubkey();
+    let before = downgrade(&mut context, &pool).await;
+
+    // The old layout can't be loaded until it is migrated
+    assert_error(
+        send(&mut context, &[pool.deposit_sol_ix(LAMPORTS_PER_SOL)], &[]).await,
+        ErrorCode::AccountDidNotDeserialize.into(),
+    );
+
+    send(&mut context, &[migrate_pool_config_ix(&pool, &admin)], &[]).await.unwrap();
+
+    let account = context.banks_client.get_account(pool.config).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), PoolConfig::LEN);
+    assert!(account.lamports >= Rent::default().minimum_balance(PoolConfig::LEN));
+
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.admin, before.admin);
+    assert_eq!(config.slp_mint, before.slp_mint);
+    assert_eq!(config.total_slp_supply, before.total_slp_supply);
+    assert_eq!(config.reserve_lamports, before.reserve_lamports);
+    assert_eq!(config.fee_bps, before.fee_bps);
+    assert_eq!(config.validator_count, 1);
+    assert_eq!((config.bump, config.authority_bump), (before.bump, before.authority_bump));
+    assert_eq!(config.pending_admin, Pubkey::default());
+    assert_eq!(config.pending_change, ParameterChange::None);
+    assert_eq!(config.fee_recipient, Pubkey::default());
+    assert_eq!(config.pending_fee_recipient, Pubkey::default());
+
+    // Deposits and the new admin flows work on the migrated config
+    send(&mut context, &[pool.deposit_sol_ix(2 * LAMPORTS_PER_SOL)], &[]).await.unwrap();
+    assert_eq!(
+        pool_config(&mut context, &pool).await.reserve_lamports,
+        before.reserve_lamports + 2 * LAMPORTS_PER_SOL
+    );
+    let update_fee = admin_ix(
+        &context,
+        &pool,
+        stake_pool::instruction::UpdateFee {
+            fee_type: FeeType::Withdrawal,
+            new_fee_bps: 50,
+        }
+        .data(),
+    );
+    send(&mut context, &[update_fee], &[]).await.unwrap();
+    assert_ne!(pool_config(&mut context, &pool).await.pending_change, ParameterChange::None);
+}
+
+#[tokio::test]
+async fn only_the_admin_can_migrate_and_only_once() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    let admin = context.payer.pubkey();
+
+    // A config already in the current layout has nothing to migrate
+    assert_error(
+        send(&mut context, &[migrate_pool_config_ix(&pool, &admin)], &[]).await,
+        StakePoolError::PoolConfigAlreadyMigrated.into(),
+    );
+
+    downgrade(&mut context, &pool).await;
+    context.get_new_latest_blockhash().await.unwrap();
+    let stranger = Keypair::new();
+    assert_error(
+        send(&mut context, &[migrate_pool_config_ix(&pool, &stranger.pubkey())], &[&stranger]).await,
+        StakePoolError::InvalidAuthority.into(),
+    );
+
+    send(&mut context, &[migrate_pool_config_ix(&pool, &admin)], &[]).await.unwrap();
+    context.get_new_latest_blockhash().await.unwrap();
+    assert_error(
+        send(&mut context, &[migrate_pool_config_ix(&pool, &admin)], &[]).await,
+        StakePoolError::PoolConfigAlreadyMigrated.into(),
+    );
+}
@@ -0,0 +1,107 @@
+//! update_pool_balance in program-test: SOL sent straight to the reserve
+//! vault is booked as rewards within the epoch cap, and a shortfall is
+//! written off the reserve.
+//! Needs the SBF build of the program: `cargo test-sbf -p stake_pool`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use common::*;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{instruction::Instruction, signature::Signer};
+use stake_pool::state::PoolConfig;
+
+fn update_pool_balance_ix(context: &ProgramTestContext, pool: &Pool) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::UpdatePoolBalance {
+            cranker: context.payer.pubkey(),
+            pool_config: pool.config,
+            reserve_vault: pool.reserve_vault,
+            pool_authority: pool.authority,
+            slp_mint: pool.slp_mint,
+            fee_recipient: None,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::UpdatePoolBalance {}.data(),
+    }
+}
+
+fn donate_ix(context: &ProgramTestContext, pool: &Pool, lamports: u64) -> Instruction {
+    system_instruction::transfer(&context.payer.pubkey(), &pool.reserve_vault, lamports)
+}
+
+async fn next_epoch(context: &mut ProgramTestContext) {
+    let epoch = current_epoch(context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+}
+
+#[tokio::test]
+async fn a_donation_is_booked_as_rewards() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    next_epoch(&mut context).await;
+    let before = pool_config(&mut context, &pool).await;
+    let donation = LAMPORTS_PER_SOL / 100;
+
+    let donate = donate_ix(&context, &pool, donation);
+    let update = update_pool_balance_ix(&context, &pool);
+    send(&mut context, &[donate, update], &[]).await.unwrap();
+
+    let after = pool_config(&mut context, &pool).await;
+    assert_eq!(after.reserve_lamports, before.reserve_lamports + donation);
+    assert_eq!(after.total_slp_supply, before.total_slp_supply);
+    assert!(after.exchange_rate() > before.exchange_rate());
+    assert_eq!(after.last_balance_update_epoch, current_epoch(&mut context).await);
+}
+
+#[tokio::test]
+async fn donations_are_booked_at_most_once_an_epoch_within_the_cap() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    next_epoch(&mut context).await;
+    let before = pool_config(&mut context, &pool).await;
+    let cap = PoolConfig::max_epoch_rewards(before.total_staked_lamports + before.reserve_lamports);
+
+    let donate = donate_ix(&context, &pool, LAMPORTS_PER_SOL);
+    let update = update_pool_balance_ix(&context, &pool);
+    send(&mut context, &[donate, update], &[]).await.unwrap();
+    let booked = pool_config(&mut context, &pool).await;
+    assert_eq!(booked.reserve_lamports, before.reserve_lamports + cap);
+
+    // The rest waits for a later epoch
+    let donate = donate_ix(&context, &pool, 1);
+    let update = update_pool_balance_ix(&context, &pool);
+    send(&mut context, &[donate, update], &[]).await.unwrap();
+    assert_eq!(pool_config(&mut context, &pool).await.reserve_lamports, booked.reserve_lamports);
+
+    next_epoch(&mut context).await;
+    let update = update_pool_balance_ix(&context, &pool);
+    send(&mut context, &[update], &[]).await.unwrap();
+    let next_cap = PoolConfig::max_epoch_rewards(booked.total_staked_lamports + booked.reserve_lamports);
+    assert_eq!(
+        pool_config(&mut context, &pool).await.reserve_lamports,
+        booked.reserve_lamports + next_cap
+    );
+}
+
+#[tokio::test]
+async fn a_shortfall_is_written_off_the_reserve() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    let before = pool_config(&mut context, &pool).await;
+    let shortfall = LAMPORTS_PER_SOL;
+
+    let mut vault = context.banks_client.get_account(pool.reserve_vault).await.unwrap().unwrap();
+    vault.lamports -= shortfall;
+    context.set_account(&pool.reserve_vault, &vault.into());
+
+    // Shortfalls are written off straight away, even in the pool's first epoch
+    let update = update_pool_balance_ix(&context, &pool);
+    send(&mut context, &[update], &[]).await.unwrap();
+
+    let after = pool_config(&mut context, &pool).await;
+    assert_eq!(after.reserve_lamports, before.reserve_lamports - shortfall);
+    assert!(after.exchange_rate() < before.exchange_rate());
+}
@@ -0,0 +1,150 @@
+//! Property tests for PoolConfig state math: deposits and withdrawals, applied
+//! the way deposit_sol and withdraw_sol apply them, must never lower the
+//! exchange rate, and no input may panic.
+
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+use stake_pool::state::*;
+
+const MAX_LAMPORTS: u64 = 1 << 60;
+
+#[derive(Debug, Clone)]
+enum Action {
+    Deposit { lamports: u64, with_referrer: bool },
+    /// Withdraw this share (in bps) of the outstanding supply
+    Withdraw { supply_bps: u16 },
+    Rewards { lamports: u64 },
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        (MIN_DEPOSIT_LAMPORTS..MAX_LAMPORTS >> 8, any::<bool>())
+            .prop_map(|(lamports, with_referrer)| Action::Deposit { lamports, with_referrer }),
+        (1u16..=10_000).prop_map(|supply_bps| Action::Withdraw { supply_bps }),
+        (0..MAX_LAMPORTS >> 8).prop_map(|lamports| Action::Rewards { lamports }),
+    ]
+}
+
+fn pool(fee_bps: u16, fee_recipient: bool) -> PoolConfig {
+    PoolConfig {
+        fee_recipient: if fee_recipient { Pubkey::new_unique() } else { Pubkey::default() },
+        deposit_fee_bps: fee_bps,
+        withdrawal_fee_bps: fee_bps,
+        referral_fee_bps: DEFAULT_REFERRAL_FEE_BPS,
+        ..Default::default()
+    }
+}
+
+/// Apply an action the way its handler updates the pool
+fn apply(pool: &mut PoolConfig, action: &Action) {
+    match *action {
+        Action::Deposit { lamports, with_referrer } => {
+            let Ok(quote) = pool.quote_deposit(lamports, with_referrer) else {
+                return;
+            };
+            // User shares, dead shares and both fee mints all land in supply
+            pool.reserve_lamports += lamports;
+            pool.total_slp_supply += quote.slp_minted;
+        }
+        Action::Withdraw { supply_bps } => {
+            let slp_amount = (pool.total_slp_supply as u128 * supply_bps as u128 / 10_000) as u64;
+            if slp_amount == 0 {
+                return;
+            }
+            let Ok(quote) = pool.quote_withdraw(slp_amount) else {
+                return;
+            };
+            if quote.sol_out == 0 || quote.sol_out > pool.reserve_lamports {
+                return;
+            }
+            // The withdrawal fee changes hands; only the rest is burned
+            pool.reserve_lamports -= quote.sol_out;
+            pool.total_slp_supply -= quote.slp_burned;
+        }
+        Action::Rewards { lamports } => {
+            pool.total_staked_lamports += lamports;
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn exchange_rate_never_falls(
+        fee_bps in 0u16..=MAX_FEE_BPS,
+        fee_recipient: bool,
+        first_deposit in MINIMUM_SLP_SUPPLY + 1..MAX_LAMPORTS >> 8,
+        actions in prop::collection::vec(action(), 1..32),
+    ) {
+        let mut pool = pool(fee_bps, fee_recipient);
+        apply(&mut pool, &Action::Deposit { lamports: first_deposit, with_referrer: false });
+        prop_assert!(pool.total_slp_supply >= MINIMUM_SLP_SUPPLY);
+
+        for action in &actions {
+            let rate_before = pool.exchange_rate();
+            apply(&mut pool, action);
+            prop_assert!(pool.check_exchange_rate(rate_before).is_ok(), "{:?} lowered the rate", action);
+        }
+    }
+
+    #[test]
+    fn deposit_then_withdraw_never_profits(
+        total_staked_lamports in 0..MAX_LAMPORTS,
+        reserve_lamports in 0..MAX_LAMPORTS,
+        total_slp_supply in 1..MAX_LAMPORTS,
+        fee_bps in 0u16..=MAX_FEE_BPS,
+        lamports in MIN_DEPOSIT_LAMPORTS..MAX_LAMPORTS,
+    ) {
+        let mut pool = PoolConfig {
+            total_staked_lamports,
+            reserve_lamports,
+            total_slp_supply,
+            ..pool(fee_bps, true)
+        };
+        let Ok(deposit) = pool.quote_deposit(lamports, false) else {
+            return Ok(());
+        };
+        apply(&mut pool, &Action::Deposit { lamports, with_referrer: false });
+
+        let redeemed = pool.calculate_sol_for_withdrawal(deposit.user_slp).unwrap();
+        prop_assert!(redeemed <= lamports);
+    }
+
+    #[test]
+    fn pool_math_never_panics(
+        total_staked_lamports: u64,
+        reserve_lamports: u64,
+        claimable_lamports: u64,
+        total_slp_supply: u64,
+        fee_recipient: bool,
+        fee_bps: u16,
+        referral_fee_bps: u16,
+        amount: u64,
+        with_referrer: bool,
+    ) {
+        let pool = PoolConfig {
+            total_staked_lamports,
+            reserve_lamports,
+            claimable_lamports,
+            total_slp_supply,
+            referral_fee_bps,
+            ..pool(fee_bps, fee_recipient)
+        };
+
+        let _ = pool.exchange_rate();
+        let _ = pool.dead_shares();
+        let _ = PoolConfig::max_epoch_rewards(amount);
+        let _ = pool.check_exchange_rate(amount);
+        let _ = pool.check_reserve(amount);
+        let _ = pool.calculate_slp_for_deposit(amount);
+        let _ = pool.calculate_sol_for_withdrawal(amount);
+        let _ = pool.calculate_fee_shares(amount);
+        let _ = pool.protocol_fee(amount, fee_bps);
+        let _ = pool.referral_fee(amount);
+        let _ = pool.calculate_reserve_amount(amount);
+        let _ = pool.target_reserve_lamports();
+        let _ = pool.instant_unstake_fee_bps(amount);
+        let _ = pool.reserve_excess_lamports();
+        let _ = pool.quote_deposit(amount, with_referrer);
+        let _ = pool.quote_withdraw(amount);
+    }
+}
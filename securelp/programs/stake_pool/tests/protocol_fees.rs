@@ -0,0 +1,64 @@
+//! Protocol fee split and slpSOL fee share minting on PoolConfig.
+
+use anchor_lang::prelude::Pubkey;
+use stake_pool::state::*;
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// 100 SOL pool at a 1.0 exchange rate
+fn pool(fee_recipient: Pubkey) -> PoolConfig {
+    PoolConfig {
+        total_staked_lamports: 90 * LAMPORTS_PER_SOL,
+        reserve_lamports: 10 * LAMPORTS_PER_SOL,
+        total_slp_supply: 100 * LAMPORTS_PER_SOL,
+        fee_recipient,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn fees_are_off_until_a_recipient_is_set() {
+    let pool = pool(Pubkey::default());
+    assert_eq!(pool.protocol_fee(LAMPORTS_PER_SOL, MAX_FEE_BPS).unwrap(), 0);
+}
+
+#[test]
+fn fee_is_charged_in_bps() {
+    let pool = pool(Pubkey::new_unique());
+    assert_eq!(pool.protocol_fee(LAMPORTS_PER_SOL, 100).unwrap(), LAMPORTS_PER_SOL / 100);
+}
+
+#[test]
+fn fee_shares_are_worth_the_fee() {
+    let mut pool = pool(Pubkey::new_unique());
+
+    // 10 SOL of rewards land in the pool, 1 SOL of which is the protocol's
+    pool.total_staked_lamports += 10 * LAMPORTS_PER_SOL;
+    let fee_lamports = LAMPORTS_PER_SOL;
+    let shares = pool.calculate_fee_shares(fee_lamports).unwrap();
+    pool.total_slp_supply += shares;
+
+    let value = pool.calculate_sol_for_withdrawal(shares).unwrap();
+    assert!(fee_lamports - value <= 1);
+}
+
+#[test]
+fn no_fee_shares_for_an_empty_pool() {
+    let pool = PoolConfig {
+        fee_recipient: Pubkey::new_unique(),
+        ..Default::default()
+    };
+    assert_eq!(pool.calculate_fee_shares(LAMPORTS_PER_SOL).unwrap(), 0);
+}
+
+#[test]
+fn referrer_takes_its_share_of_the_deposit_fee() {
+    let mut pool = pool(Pubkey::new_unique());
+    pool.referral_fee_bps = DEFAULT_REFERRAL_FEE_BPS;
+
+    let deposit_fee = pool.protocol_fee(10 * LAMPORTS_PER_SOL, 50).unwrap();
+    assert_eq!(pool.referral_fee(deposit_fee).unwrap(), deposit_fee / 2);
+
+    pool.referral_fee_bps = MAX_REFERRAL_FEE_BPS;
+    assert_eq!(pool.referral_fee(deposit_fee).unwrap(), deposit_fee);
+}
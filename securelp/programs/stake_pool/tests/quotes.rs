@@ -0,0 +1,59 @@
+//! Deposit and withdrawal quotes on PoolConfig, as returned by quote_deposit
+//! and quote_withdraw.
+
+use anchor_lang::prelude::Pubkey;
+use stake_pool::state::*;
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// 100 SOL pool at a 1.0 exchange rate with fees on
+fn pool() -> PoolConfig {
+    PoolConfig {
+        total_staked_lamports: 90 * LAMPORTS_PER_SOL,
+        reserve_lamports: 10 * LAMPORTS_PER_SOL,
+        total_slp_supply: 100 * LAMPORTS_PER_SOL,
+        fee_recipient: Pubkey::new_unique(),
+        deposit_fee_bps: 50,
+        withdrawal_fee_bps: 30,
+        referral_fee_bps: DEFAULT_REFERRAL_FEE_BPS,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn deposit_quote_adds_up() {
+    let pool = pool();
+    let quote = pool.quote_deposit(10 * LAMPORTS_PER_SOL, true).unwrap();
+
+    assert_eq!(quote.slp_minted, pool.calculate_slp_for_deposit(10 * LAMPORTS_PER_SOL).unwrap());
+    assert_eq!(quote.deposit_fee, quote.slp_minted / 200);
+    assert_eq!(quote.referral_fee, quote.deposit_fee / 2);
+    assert_eq!(quote.dead_shares, 0);
+    assert_eq!(quote.user_slp + quote.deposit_fee, quote.slp_minted);
+
+    let no_referrer = pool.quote_deposit(10 * LAMPORTS_PER_SOL, false).unwrap();
+    assert_eq!(no_referrer.referral_fee, 0);
+    assert_eq!(no_referrer.user_slp, quote.user_slp);
+}
+
+#[test]
+fn first_deposit_quote_locks_dead_shares() {
+    let pool = PoolConfig::default();
+    let quote = pool.quote_deposit(LAMPORTS_PER_SOL, false).unwrap();
+
+    assert_eq!(quote.dead_shares, MINIMUM_SLP_SUPPLY);
+    assert_eq!(quote.user_slp, quote.slp_minted - MINIMUM_SLP_SUPPLY);
+}
+
+#[test]
+fn withdraw_quote_matches_withdraw_sol() {
+    let pool = pool();
+    let quote = pool.quote_withdraw(LAMPORTS_PER_SOL).unwrap();
+
+    assert_eq!(quote.withdrawal_fee, pool.protocol_fee(LAMPORTS_PER_SOL, 30).unwrap());
+    assert_eq!(quote.slp_burned, LAMPORTS_PER_SOL - quote.withdrawal_fee);
+    assert_eq!(
+        (quote.sol_out, quote.liquidity_fee),
+        pool.quote_withdraw_sol(quote.slp_burned).unwrap()
+    );
+}
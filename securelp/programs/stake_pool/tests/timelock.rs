@@ -0,0 +1,125 @@
+//! Timelocked parameter changes for stake_pool: update_fee and unpausing
+//! queue a change that execute_parameter_change applies once
+//! PARAMETER_TIMELOCK_SLOTS have passed.
+//! Needs the SBF build of the program: `cargo test-sbf -p stake_pool`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::InstructionData;
+use common::*;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::instruction::Instruction;
+use stake_pool::errors::StakePoolError;
+use stake_pool::state::{FeeType, ParameterChange};
+
+fn update_fee_ix(context: &ProgramTestContext, pool: &Pool, new_fee_bps: u16) -> Instruction {
+    let data = stake_pool::instruction::UpdateFee {
+        fee_type: FeeType::Withdrawal,
+        new_fee_bps,
+    };
+    admin_ix(context, pool, data.data())
+}
+
+fn set_paused_ix(context: &ProgramTestContext, pool: &Pool, paused: bool) -> Instruction {
+    admin_ix(context, pool, stake_pool::instruction::SetPaused { paused }.data())
+}
+
+fn execute_ix(context: &ProgramTestContext, pool: &Pool) -> Instruction {
+    admin_ix(context, pool, stake_pool::instruction::ExecuteParameterChange {}.data())
+}
+
+fn cancel_ix(context: &ProgramTestContext, pool: &Pool) -> Instruction {
+    admin_ix(context, pool, stake_pool::instruction::CancelParameterChange {}.data())
+}
+
+#[tokio::test]
+async fn fee_change_waits_for_the_timelock() {
+    let (mut context, pool) = setup(LAMPORTS_PER_SOL).await;
+
+    let queue = update_fee_ix(&context, &pool, 30);
+    send(&mut context, &[queue], &[]).await.unwrap();
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(
+        config.pending_change,
+        ParameterChange::Fee { fee_type: FeeType::Withdrawal, new_fee_bps: 30 }
+    );
+
+    let execute = execute_ix(&context, &pool);
+    assert_error(send(&mut context, &[execute], &[]).await, StakePoolError::TimelockNotElapsed.into());
+    assert_eq!(pool_config(&mut context, &pool).await.withdrawal_fee_bps, 0);
+
+    wait_out_the_timelock(&mut context).await;
+    let execute = execute_ix(&context, &pool);
+    send(&mut context, &[execute], &[]).await.unwrap();
+
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.withdrawal_fee_bps, 30);
+    assert_eq!(config.pending_change, ParameterChange::None);
+    assert_eq!(config.pending_change_slot, 0);
+}
+
+#[tokio::test]
+async fn only_one_change_can_be_queued() {
+    let (mut context, pool) = setup(LAMPORTS_PER_SOL).await;
+
+    let queue = update_fee_ix(&context, &pool, 30);
+    send(&mut context, &[queue], &[]).await.unwrap();
+
+    let second = update_fee_ix(&context, &pool, 50);
+    assert_error(send(&mut context, &[second], &[]).await, StakePoolError::ParameterChangePending.into());
+
+    // Pausing is immediate, but queueing the unpause has to wait its turn
+    let pause = set_paused_ix(&context, &pool, true);
+    send(&mut context, &[pause], &[]).await.unwrap();
+    let unpause = set_paused_ix(&context, &pool, false);
+    assert_error(send(&mut context, &[unpause], &[]).await, StakePoolError::ParameterChangePending.into());
+}
+
+#[tokio::test]
+async fn queued_change_can_be_cancelled() {
+    let (mut context, pool) = setup(LAMPORTS_PER_SOL).await;
+
+    let queue = update_fee_ix(&context, &pool, 30);
+    let cancel = cancel_ix(&context, &pool);
+    send(&mut context, &[queue, cancel], &[]).await.unwrap();
+    assert_eq!(pool_config(&mut context, &pool).await.pending_change, ParameterChange::None);
+
+    wait_out_the_timelock(&mut context).await;
+    let execute = execute_ix(&context, &pool);
+    assert_error(
+        send(&mut context, &[execute], &[]).await,
+        StakePoolError::NoPendingParameterChange.into(),
+    );
+    let cancel = cancel_ix(&context, &pool);
+    assert_error(
+        send(&mut context, &[cancel], &[]).await,
+        StakePoolError::NoPendingParameterChange.into(),
+    );
+    assert_eq!(pool_config(&mut context, &pool).await.withdrawal_fee_bps, 0);
+}
+
+#[tokio::test]
+async fn emergency_pause_clears_a_queued_unpause() {
+    let (mut context, pool) = setup(LAMPORTS_PER_SOL).await;
+
+    let pause = set_paused_ix(&context, &pool, true);
+    let unpause = set_paused_ix(&context, &pool, false);
+    send(&mut context, &[pause, unpause], &[]).await.unwrap();
+    let config = pool_config(&mut context, &pool).await;
+    assert!(config.paused);
+    assert_eq!(config.pending_change, ParameterChange::Unpause);
+
+    let pause = set_paused_ix(&context, &pool, true);
+    send(&mut context, &[pause], &[]).await.unwrap();
+    assert_eq!(pool_config(&mut context, &pool).await.pending_change, ParameterChange::None);
+
+    // The old unpause can't be executed once its timelock would have passed
+    wait_out_the_timelock(&mut context).await;
+    let execute = execute_ix(&context, &pool);
+    assert_error(
+        send(&mut context, &[execute], &[]).await,
+        StakePoolError::NoPendingParameterChange.into(),
+    );
+    assert!(pool_config(&mut context, &pool).await.paused);
+}
@@ -0,0 +1,134 @@
+//! Per-user stake position tracking tests for stake_pool.
+//! Needs the SBF build of the program: `cargo test-sbf -p stake_pool`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use common::*;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{instruction::Instruction, signature::Signer};
+use solana_sdk_ids::system_program;
+use stake_pool::state::{UserStake, USER_STAKE_SEED};
+
+fn user_stake_address(context: &ProgramTestContext, pool: &Pool) -> Pubkey {
+    Pubkey::find_program_address(
+        &[USER_STAKE_SEED, pool.config.as_ref(), context.payer.pubkey().as_ref()],
+        &stake_pool::ID,
+    )
+    .0
+}
+
+fn initialize_user_stake_ix(context: &ProgramTestContext, pool: &Pool) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::InitializeUserStake {
+            user: context.payer.pubkey(),
+            pool_config: pool.config,
+            user_stake: user_stake_address(context, pool),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::InitializeUserStake {}.data(),
+    }
+}
+
+fn tracked_deposit_ix(context: &ProgramTestContext, pool: &Pool, amount_lamports: u64) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::DepositSol {
+            user: context.payer.pubkey(),
+            pool_config: pool.config,
+            pool_authority: pool.authority,
+            reserve_vault: pool.reserve_vault,
+            slp_mint: pool.slp_mint,
+            user_slp_account: pool.user_slp,
+            fee_recipient: None,
+            referrer: None,
+            user_stake: Some(user_stake_address(context, pool)),
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::DepositSol { amount_lamports }.data(),
+    }
+}
+
+fn tracked_withdraw_ix(context: &ProgramTestContext, pool: &Pool, slp_amount: u64) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::WithdrawSol {
+            user: context.payer.pubkey(),
+            pool_config: pool.config,
+            reserve_vault: pool.reserve_vault,
+            slp_mint: pool.slp_mint,
+            user_slp_account: pool.user_slp,
+            fee_recipient: None,
+            user_stake: Some(user_stake_address(context, pool)),
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::WithdrawSol { slp_amount }.data(),
+    }
+}
+
+async fn user_stake(context: &mut ProgramTestContext, pool: &Pool) -> UserStake {
+    let address = user_stake_address(context, pool);
+    let account = context.banks_client.get_account(address).await.unwrap().unwrap();
+    UserStake::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn deposits_and_withdrawals_update_the_position() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+
+    let initialize = initialize_user_stake_ix(&context, &pool);
+    let deposit = tracked_deposit_ix(&context, &pool, 4 * LAMPORTS_PER_SOL);
+    send(&mut context, &[initialize, deposit], &[]).await.unwrap();
+
+    let position = user_stake(&mut context, &pool).await;
+    assert_eq!(position.owner, context.payer.pubkey());
+    assert_eq!(position.total_sol_deposited, 4 * LAMPORTS_PER_SOL);
+    assert_eq!(position.total_slp_received, 4 * LAMPORTS_PER_SOL);
+    assert!(position.first_deposit_ts > 0);
+
+    let withdraw = tracked_withdraw_ix(&context, &pool, LAMPORTS_PER_SOL);
+    send(&mut context, &[withdraw], &[]).await.unwrap();
+
+    let position = user_stake(&mut context, &pool).await;
+    assert_eq!(position.total_slp_redeemed, LAMPORTS_PER_SOL);
+    assert!(position.total_sol_withdrawn > 0);
+    assert!(position.total_sol_withdrawn <= LAMPORTS_PER_SOL);
+    assert_eq!(position.total_sol_deposited, 4 * LAMPORTS_PER_SOL);
+}
+
+#[tokio::test]
+async fn close_returns_rent_to_the_owner() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    let initialize = initialize_user_stake_ix(&context, &pool);
+    send(&mut context, &[initialize], &[]).await.unwrap();
+
+    let address = user_stake_address(&context, &pool);
+    let rent = context.banks_client.get_balance(address).await.unwrap();
+    let payer = context.payer.pubkey();
+    let before = context.banks_client.get_balance(payer).await.unwrap();
+
+    let close = Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::CloseUserStake {
+            user: payer,
+            pool_config: pool.config,
+            user_stake: address,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::CloseUserStake {}.data(),
+    };
+    send(&mut context, &[close], &[]).await.unwrap();
+
+    assert!(context.banks_client.get_account(address).await.unwrap().is_none());
+    // Rent back, less the transaction fee
+    assert_eq!(context.banks_client.get_balance(payer).await.unwrap(), before + rent - 5_000);
+}
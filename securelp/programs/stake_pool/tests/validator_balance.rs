@@ -0,0 +1,206 @@
+//! update_validator_list_balance in program-test: stake account rewards are
+//! booked within the epoch cap with the protocol's cut minted as slpSOL, and
+//! slashing is written off in full.
+//! Needs the SBF build of the program: `cargo test-sbf -p stake_pool`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use common::*;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    signature::Signer,
+};
+use stake_pool::state::PoolConfig;
+
+fn update_ix(context: &ProgramTestContext, pool: &Pool, fee_recipient: Pubkey) -> Instruction {
+    let mut accounts = stake_pool::accounts::UpdateValidatorListBalance {
+        cranker: context.payer.pubkey(),
+        pool_config: pool.config,
+        validator_list: pool.validator_list,
+        pool_authority: pool.authority,
+        slp_mint: pool.slp_mint,
+        fee_recipient: Some(fee_recipient),
+        token_program: spl_token::ID,
+    }
+    .to_account_metas(None);
+    accounts.push(AccountMeta::new_readonly(pool.validator_stake(), false));
+    accounts.push(AccountMeta::new_readonly(pool.transient_stake(), false));
+
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts,
+        data: stake_pool::instruction::UpdateValidatorListBalance { start_index: 0 }.data(),
+    }
+}
+
+/// Pool with a fee recipient and 9 SOL of active validator stake, its
+/// balance already synced this epoch
+async fn setup_with_fees_and_stake() -> (ProgramTestContext, Pool, Pubkey) {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    let admin = context.payer.pubkey();
+    let treasury = create_token_account(&mut context, &pool.slp_mint, &admin, 0).await;
+
+    let set_fee_recipient = set_fee_recipient_ix(&context, &pool, treasury);
+    send(&mut context, &[set_fee_recipient], &[]).await.unwrap();
+    wait_out_the_timelock(&mut context).await;
+    let execute = admin_ix(&context, &pool, stake_pool::instruction::ExecuteParameterChange {}.data());
+    send(&mut context, &[execute], &[]).await.unwrap();
+
+    let delegate = delegate_stake_ix(&context, &pool);
+    send(&mut context, &[delegate], &[]).await.unwrap();
+    next_epoch(&mut context).await;
+    let update = update_ix(&context, &pool, treasury);
+    send(&mut context, &[update], &[]).await.unwrap();
+
+    (context, pool, treasury)
+}
+
+/// Warp to the next epoch, with a fresh blockhash so the same crank can be
+/// sent again
+async fn next_epoch(context: &mut ProgramTestContext) {
+    let epoch = current_epoch(context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+    context.get_new_latest_blockhash().await.unwrap();
+}
+
+/// Set the validator stake account to `lamports`, replacing whatever the
+/// epoch change paid it
+async fn set_stake_lamports(context: &mut ProgramTestContext, pool: &Pool, lamports: u64) {
+    let mut stake = context.banks_client.get_account(pool.validator_stake()).await.unwrap().unwrap();
+    stake.lamports = lamports;
+    context.set_account(&pool.validator_stake(), &stake.into());
+}
+
+/// slpSOL the crank mints for `rewards` booked on top of `before`
+fn expected_fee_shares(before: &PoolConfig, rewards: u64) -> u64 {
+    let mut booked = before.clone();
+    booked.total_staked_lamports += rewards;
+    let protocol_fee = booked.protocol_fee(rewards, booked.fee_bps).unwrap();
+    booked.calculate_fee_shares(protocol_fee).unwrap()
+}
+
+#[tokio::test]
+async fn rewards_raise_the_rate_and_mint_the_protocol_fee() {
+    let (mut context, pool, treasury) = setup_with_fees_and_stake().await;
+    let before = pool_config(&mut context, &pool).await;
+    let staked = validator_entry(&mut context, &pool).await.staked_lamports;
+    let rewards = staked / 400;
+
+    next_epoch(&mut context).await;
+    set_stake_lamports(&mut context, &pool, staked + rewards).await;
+    let update = update_ix(&context, &pool, treasury);
+    send(&mut context, &[update], &[]).await.unwrap();
+
+    let after = pool_config(&mut context, &pool).await;
+    let fee_shares = expected_fee_shares(&before, rewards);
+    assert!(fee_shares > 0);
+    assert_eq!(after.total_staked_lamports, before.total_staked_lamports + rewards);
+    assert_eq!(token_balance(&mut context, &treasury).await, fee_shares);
+    assert_eq!(after.total_slp_supply, before.total_slp_supply + fee_shares);
+    assert!(after.exchange_rate() > before.exchange_rate());
+
+    let validator = validator_entry(&mut context, &pool).await;
+    assert_eq!(validator.staked_lamports, staked + rewards);
+    assert_eq!(validator.last_update_epoch, current_epoch(&mut context).await);
+}
+
+#[tokio::test]
+async fn rewards_above_the_epoch_cap_are_deferred() {
+    let (mut context, pool, treasury) = setup_with_fees_and_stake().await;
+    let before = pool_config(&mut context, &pool).await;
+    let staked = validator_entry(&mut context, &pool).await.staked_lamports;
+    let rewards = staked / 50;
+    let cap = PoolConfig::max_epoch_rewards(staked);
+
+    next_epoch(&mut context).await;
+    set_stake_lamports(&mut context, &pool, staked + rewards).await;
+    let update = update_ix(&context, &pool, treasury);
+    send(&mut context, &[update], &[]).await.unwrap();
+
+    let capped = pool_config(&mut context, &pool).await;
+    assert_eq!(capped.total_staked_lamports, before.total_staked_lamports + cap);
+    assert_eq!(validator_entry(&mut context, &pool).await.staked_lamports, staked + cap);
+
+    // The rest is booked by the next epoch's update, within its own cap
+    next_epoch(&mut context).await;
+    set_stake_lamports(&mut context, &pool, staked + rewards).await;
+    let update = update_ix(&context, &pool, treasury);
+    send(&mut context, &[update], &[]).await.unwrap();
+
+    let next_cap = PoolConfig::max_epoch_rewards(staked + cap);
+    let booked = (rewards - cap).min(next_cap);
+    assert_eq!(
+        pool_config(&mut context, &pool).await.total_staked_lamports,
+        capped.total_staked_lamports + booked
+    );
+}
+
+#[tokio::test]
+async fn slashing_lowers_the_rate_and_mints_no_fee() {
+    let (mut context, pool, treasury) = setup_with_fees_and_stake().await;
+    let before = pool_config(&mut context, &pool).await;
+    let staked = validator_entry(&mut context, &pool).await.staked_lamports;
+    let slashed = LAMPORTS_PER_SOL;
+
+    next_epoch(&mut context).await;
+    set_stake_lamports(&mut context, &pool, staked - slashed).await;
+    let update = update_ix(&context, &pool, treasury);
+    send(&mut context, &[update], &[]).await.unwrap();
+
+    let after = pool_config(&mut context, &pool).await;
+    assert_eq!(after.total_staked_lamports, before.total_staked_lamports - slashed);
+    assert_eq!(after.total_slp_supply, before.total_slp_supply);
+    assert_eq!(token_balance(&mut context, &treasury).await, 0);
+    assert!(after.exchange_rate() < before.exchange_rate());
+    assert_eq!(validator_entry(&mut context, &pool).await.staked_lamports, staked - slashed);
+}
+
+#[tokio::test]
+async fn balance_is_synced_once_an_epoch() {
+    let (mut context, pool, treasury) = setup_with_fees_and_stake().await;
+    let before = pool_config(&mut context, &pool).await;
+    let staked = validator_entry(&mut context, &pool).await.staked_lamports;
+
+    // Already updated this epoch, so the change waits for the next one
+    set_stake_lamports(&mut context, &pool, staked + staked / 400).await;
+    context.get_new_latest_blockhash().await.unwrap();
+    let update = update_ix(&context, &pool, treasury);
+    send(&mut context, &[update], &[]).await.unwrap();
+    assert_eq!(
+        pool_config(&mut context, &pool).await.total_staked_lamports,
+        before.total_staked_lamports
+    );
+}
+
+/// Anchor's instruction discriminator for harvest_rewards
+fn harvest_rewards_discriminator() -> Vec<u8> {
+    hash(b"global:harvest_rewards").to_bytes()[..8].to_vec()
+}
+
+#[cfg(feature = "devnet")]
+#[test]
+fn harvest_rewards_discriminator_matches_the_devnet_build() {
+    use anchor_lang::Discriminator;
+    assert_eq!(stake_pool::instruction::HarvestRewards::DISCRIMINATOR, harvest_rewards_discriminator());
+}
+
+/// Simulated rewards are devnet only: the default build reads real stake
+/// account balances and has no harvest_rewards instruction
+#[cfg(not(feature = "devnet"))]
+#[tokio::test]
+async fn harvest_rewards_is_not_in_the_default_build() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+
+    let mut harvest = update_validator_list_balance_ix(&context, &pool);
+    harvest.data = harvest_rewards_discriminator();
+    assert_error(
+        send(&mut context, &[harvest], &[]).await,
+        anchor_lang::error::ErrorCode::InstructionFallbackNotFound.into(),
+    );
+}
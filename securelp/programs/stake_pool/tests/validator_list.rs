@@ -0,0 +1,72 @@
+//! Record layout and bookkeeping of the zero-copy ValidatorList.
+
+use anchor_lang::prelude::Pubkey;
+use stake_pool::state::*;
+
+/// Zeroed list account data with room for `max_validators`, 8-byte aligned
+/// like account data on-chain
+fn list_data(max_validators: u32) -> Vec<u64> {
+    let mut data = vec![0u64; ValidatorList::space(max_validators).div_ceil(8)];
+    let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut data);
+    let header: &mut ValidatorList =
+        bytemuck::from_bytes_mut(&mut bytes[8..ValidatorList::HEADER_LEN]);
+    header.max_validators = max_validators;
+    data
+}
+
+fn entry(vote_account: Pubkey) -> ValidatorEntry {
+    ValidatorEntry {
+        vote_account,
+        active: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn records_are_packed_without_padding() {
+    assert_eq!(std::mem::size_of::<ValidatorEntry>(), ValidatorEntry::LEN);
+    assert_eq!(ValidatorList::HEADER_LEN % 8, 0);
+    // Hundreds of validators fit well inside the 10MiB account limit
+    assert!(ValidatorList::space(MAX_VALIDATORS) < 10 * 1024 * 1024);
+}
+
+#[test]
+fn push_find_and_swap_remove() {
+    let mut data = list_data(3);
+    let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut data);
+    let votes: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+
+    for (index, vote) in votes[..3].iter().enumerate() {
+        assert_eq!(ValidatorList::push(bytes, entry(*vote)).unwrap(), index as u32);
+    }
+    assert!(ValidatorList::push(bytes, entry(votes[3])).is_err());
+
+    ValidatorList::find_mut(bytes, &votes[1]).unwrap().staked_lamports = 42;
+    assert!(ValidatorList::find_mut(bytes, &votes[3]).is_err());
+
+    // The last validator moves into the freed slot
+    let removed = ValidatorList::swap_remove(bytes, &votes[0]).unwrap();
+    assert_eq!(removed.vote_account, votes[0]);
+    let remaining: Vec<Pubkey> = ValidatorList::validators(bytes)
+        .unwrap()
+        .iter()
+        .map(|validator| validator.vote_account)
+        .collect();
+    assert_eq!(remaining, vec![votes[2], votes[1]]);
+    assert_eq!(ValidatorList::find_mut(bytes, &votes[1]).unwrap().staked_lamports, 42);
+}
+
+#[test]
+fn up_to_date_only_once_every_validator_is_synced() {
+    let mut data = list_data(2);
+    let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut data);
+    let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+    ValidatorList::push(bytes, entry(first)).unwrap();
+    ValidatorList::push(bytes, entry(second)).unwrap();
+
+    ValidatorList::find_mut(bytes, &first).unwrap().last_update_epoch = 5;
+    assert!(!ValidatorList::is_up_to_date(bytes, 5).unwrap());
+
+    ValidatorList::find_mut(bytes, &second).unwrap().last_update_epoch = 5;
+    assert!(ValidatorList::is_up_to_date(bytes, 5).unwrap());
+}
@@ -0,0 +1,233 @@
+//! Validator lifecycle tests for stake_pool: the validator list, weights, deactivation,
+//! rebalance and removal.
+//! Needs the SBF build of the program: `cargo test-sbf -p stake_pool`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{instruction::Instruction, signature::Signer};
+use solana_sdk_ids::system_program;
+use stake_pool::state::{ValidatorList, INITIAL_VALIDATOR_LIST_CAPACITY, MAX_VALIDATORS};
+
+fn validator_admin_ix(context: &ProgramTestContext, pool: &Pool, data: Vec<u8>) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::ValidatorAdminAction {
+            admin: context.payer.pubkey(),
+            pool_config: pool.config,
+            validator_list: pool.validator_list,
+            vote_account: pool.vote_account,
+        }
+        .to_account_metas(None),
+        data,
+    }
+}
+
+fn update_validator_score_ix(
+    context: &ProgramTestContext,
+    pool: &Pool,
+    commission_bps: u16,
+    uptime_bps: u16,
+) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::UpdateValidatorScore {
+            oracle: context.payer.pubkey(),
+            pool_config: pool.config,
+            validator_list: pool.validator_list,
+            vote_account: pool.vote_account,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::UpdateValidatorScore {
+            commission_bps,
+            uptime_bps,
+        }
+        .data(),
+    }
+}
+
+fn remove_validator_ix(context: &ProgramTestContext, pool: &Pool) -> Instruction {
+    validator_admin_ix(
+        context,
+        pool,
+        stake_pool::instruction::RemoveValidator {}.data(),
+    )
+}
+
+#[tokio::test]
+async fn set_validator_weight_updates_pool_total() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+
+    let set_weight = validator_admin_ix(
+        &context,
+        &pool,
+        stake_pool::instruction::SetValidatorWeight { weight: 250 }.data(),
+    );
+    send(&mut context, &[set_weight], &[]).await.unwrap();
+
+    let validator = validator_entry(&mut context, &pool).await;
+    assert_eq!(validator.target_weight, 250);
+    assert_eq!(pool_config(&mut context, &pool).await.total_validator_weight, 250);
+
+    // The only validator holds its full target, so there is nothing to rebalance
+    let delegate = delegate_stake_ix(&context, &pool);
+    send(&mut context, &[delegate], &[]).await.unwrap();
+    let rebalance = rebalance_ix(&context, &pool);
+    assert!(send(&mut context, &[rebalance], &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn score_oracle_sets_validator_weight() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+
+    // The admin is the oracle until another one is set
+    let score = update_validator_score_ix(&context, &pool, 1_000, 9_000);
+    send(&mut context, &[score], &[]).await.unwrap();
+
+    let validator = validator_entry(&mut context, &pool).await;
+    assert_eq!(validator.commission_bps, 1_000);
+    assert_eq!(validator.uptime_bps, 9_000);
+    assert_eq!(validator.target_weight, 81);
+    assert_eq!(pool_config(&mut context, &pool).await.total_validator_weight, 81);
+
+    let out_of_range = update_validator_score_ix(&context, &pool, 10_001, 9_000);
+    assert!(send(&mut context, &[out_of_range], &[]).await.is_err());
+
+    let set_oracle = admin_ix(
+        &context,
+        &pool,
+        stake_pool::instruction::SetScoreOracle {
+            score_oracle: Pubkey::new_unique(),
+        }
+        .data(),
+    );
+    send(&mut context, &[set_oracle], &[]).await.unwrap();
+
+    let not_oracle = update_validator_score_ix(&context, &pool, 0, 10_000);
+    assert!(send(&mut context, &[not_oracle], &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn deactivated_validator_is_drained_and_removed() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+
+    let delegate = delegate_stake_ix(&context, &pool);
+    send(&mut context, &[delegate], &[]).await.unwrap();
+    let epoch = current_epoch(&mut context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+
+    // Removal needs a deactivated validator
+    let remove = remove_validator_ix(&context, &pool);
+    assert!(send(&mut context, &[remove], &[]).await.is_err());
+
+    let deactivate = validator_admin_ix(
+        &context,
+        &pool,
+        stake_pool::instruction::DeactivateValidator {}.data(),
+    );
+    send(&mut context, &[deactivate], &[]).await.unwrap();
+    assert_eq!(pool_config(&mut context, &pool).await.total_validator_weight, 0);
+
+    // Rebalancing needs this epoch's balances
+    let rebalance = rebalance_ix(&context, &pool);
+    assert!(send(&mut context, &[rebalance], &[]).await.is_err());
+    let update = update_validator_list_balance_ix(&context, &pool);
+    send(&mut context, &[update], &[]).await.unwrap();
+
+    // ...that no longer holds stake
+    let remove = remove_validator_ix(&context, &pool);
+    assert!(send(&mut context, &[remove], &[]).await.is_err());
+
+    // A deactivated validator targets zero, so rebalance takes everything
+    let rebalance = rebalance_ix(&context, &pool);
+    send(&mut context, &[rebalance], &[]).await.unwrap();
+    let validator = validator_entry(&mut context, &pool).await;
+    assert_eq!(validator.staked_lamports, 0);
+    assert_eq!(validator.deactivating_ticket_lamports, 0);
+
+    let epoch = current_epoch(&mut context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+    let withdraw = withdraw_deactivated_stake_ix(&context, &pool);
+    send(&mut context, &[withdraw], &[]).await.unwrap();
+
+    // Rebalanced stake returns to the reserve, not to ticket claims
+    let config = pool_config(&mut context, &pool).await;
+    assert!(config.reserve_lamports >= 10 * LAMPORTS_PER_SOL);
+    assert_eq!(config.claimable_lamports, 0);
+
+    let remove = remove_validator_ix(&context, &pool);
+    send(&mut context, &[remove], &[]).await.unwrap();
+    let (header, validators) = validator_list(&mut context, &pool).await;
+    assert_eq!(header.validator_count, 0);
+    assert!(validators.is_empty());
+    assert_eq!(pool_config(&mut context, &pool).await.validator_count, 0);
+}
+
+#[tokio::test]
+async fn validator_list_grows_past_initial_capacity() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+
+    let duplicate = add_validator_ix(&context, &pool, &pool.vote_account);
+    assert!(send(&mut context, &[duplicate], &[]).await.is_err());
+
+    let realloc_ix = |context: &ProgramTestContext, max_validators: u32| Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::ReallocValidatorList {
+            admin: context.payer.pubkey(),
+            pool_config: pool.config,
+            validator_list: pool.validator_list,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::ReallocValidatorList { max_validators }.data(),
+    };
+
+    // The list fills up at its initial capacity...
+    let others: Vec<Pubkey> = (1..=INITIAL_VALIDATOR_LIST_CAPACITY)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+    let (fits, overflow) = others.split_at(others.len() - 1);
+    for batch in fits.chunks(5) {
+        let adds: Vec<_> = batch
+            .iter()
+            .map(|vote| add_validator_ix(&context, &pool, vote))
+            .collect();
+        send(&mut context, &adds, &[]).await.unwrap();
+    }
+    let add = add_validator_ix(&context, &pool, &overflow[0]);
+    assert!(send(&mut context, &[add], &[]).await.is_err());
+
+    // ...grows by at most 10KiB per call and never shrinks
+    let too_far = realloc_ix(&context, MAX_VALIDATORS);
+    assert!(send(&mut context, &[too_far], &[]).await.is_err());
+    let grow = realloc_ix(&context, 2 * INITIAL_VALIDATOR_LIST_CAPACITY);
+    send(&mut context, &[grow], &[]).await.unwrap();
+    let shrink = realloc_ix(&context, INITIAL_VALIDATOR_LIST_CAPACITY);
+    assert!(send(&mut context, &[shrink], &[]).await.is_err());
+
+    let add = add_validator_ix(&context, &pool, &overflow[0]);
+    send(&mut context, &[add], &[]).await.unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(pool.validator_list)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        account.data.len(),
+        ValidatorList::space(2 * INITIAL_VALIDATOR_LIST_CAPACITY)
+    );
+    let (header, validators) = validator_list(&mut context, &pool).await;
+    assert_eq!(header.max_validators, 2 * INITIAL_VALIDATOR_LIST_CAPACITY);
+    assert_eq!(validators.len() as u32, INITIAL_VALIDATOR_LIST_CAPACITY + 1);
+    assert_eq!(validators.last().unwrap().vote_account, overflow[0]);
+    assert_eq!(
+        pool_config(&mut context, &pool).await.validator_count,
+        INITIAL_VALIDATOR_LIST_CAPACITY + 1
+    );
+}
@@ -0,0 +1,108 @@
+//! withdraw_stake tests for stake_pool, run against the native stake program in program-test.
+//! Needs the SBF build of the program: `cargo test-sbf -p stake_pool`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::{AnchorDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use common::*;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+};
+use solana_sdk_ids::{stake, system_program, sysvar};
+use solana_stake_interface::state::StakeStateV2;
+
+fn withdraw_stake_ix(
+    context: &ProgramTestContext,
+    pool: &Pool,
+    split_stake: &Keypair,
+    slp_amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::WithdrawStake {
+            user: context.payer.pubkey(),
+            pool_config: pool.config,
+            pool_authority: pool.authority,
+            validator_list: pool.validator_list,
+            vote_account: pool.vote_account,
+            validator_stake: pool.validator_stake(),
+            split_stake: split_stake.pubkey(),
+            slp_mint: pool.slp_mint,
+            user_slp_account: pool.user_slp,
+            fee_recipient: None,
+            clock: sysvar::clock::ID,
+            stake_program: stake::ID,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::WithdrawStake { slp_amount }.data(),
+    }
+}
+
+/// Pool with 9 SOL of active validator stake and an updated validator list
+async fn setup_with_active_stake() -> (ProgramTestContext, Pool) {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    let delegate = delegate_stake_ix(&context, &pool);
+    send(&mut context, &[delegate], &[]).await.unwrap();
+
+    let epoch = current_epoch(&mut context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+    let update = update_validator_list_balance_ix(&context, &pool);
+    send(&mut context, &[update], &[]).await.unwrap();
+
+    (context, pool)
+}
+
+#[tokio::test]
+async fn withdraw_stake_splits_a_stake_account_for_the_user() {
+    let (mut context, pool) = setup_with_active_stake().await;
+    let before = pool_config(&mut context, &pool).await;
+    let validator_before = validator_entry(&mut context, &pool).await;
+    let reserve_before = before.reserve_lamports;
+
+    let split_stake = Keypair::new();
+    let slp_amount = 2 * LAMPORTS_PER_SOL;
+    let withdraw = withdraw_stake_ix(&context, &pool, &split_stake, slp_amount);
+    send(&mut context, &[withdraw], &[&split_stake]).await.unwrap();
+
+    let stake_lamports = before.calculate_sol_for_withdrawal(slp_amount).unwrap();
+    let account = context
+        .banks_client
+        .get_account(split_stake.pubkey())
+        .await
+        .unwrap()
+        .expect("split stake account created");
+    assert_eq!(account.owner, stake::ID);
+    match StakeStateV2::deserialize(&mut account.data.as_slice()).unwrap() {
+        StakeStateV2::Stake(meta, stake, _) => {
+            assert_eq!(meta.authorized.staker, context.payer.pubkey());
+            assert_eq!(meta.authorized.withdrawer, context.payer.pubkey());
+            assert_eq!(stake.delegation.voter_pubkey, pool.vote_account);
+            assert_eq!(stake.delegation.stake, stake_lamports);
+        }
+        other => panic!("split stake not delegated: {other:?}"),
+    }
+
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.total_staked_lamports, before.total_staked_lamports - stake_lamports);
+    assert_eq!(config.total_slp_supply, before.total_slp_supply - slp_amount);
+    assert_eq!(config.reserve_lamports, reserve_before);
+
+    let validator = validator_entry(&mut context, &pool).await;
+    assert_eq!(validator.staked_lamports, validator_before.staked_lamports - stake_lamports);
+}
+
+#[tokio::test]
+async fn withdraw_stake_keeps_the_validator_above_minimum_delegation() {
+    let (mut context, pool) = setup_with_active_stake().await;
+
+    // All of the validator's stake would leave nothing behind
+    let split_stake = Keypair::new();
+    let withdraw = withdraw_stake_ix(&context, &pool, &split_stake, 9 * LAMPORTS_PER_SOL);
+    assert!(send(&mut context, &[withdraw], &[&split_stake]).await.is_err());
+}
@@ -0,0 +1,201 @@
+//! Delayed unstake tests for stake_pool: ticket, deactivation, cooldown and claim.
+//! Needs the SBF build of the program: `cargo test-sbf -p stake_pool`.
+#![cfg(feature = "test-sbf")]
+
+mod common;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use common::*;
+use stake_pool::errors::StakePoolError;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{instruction::Instruction, signature::Signer};
+use solana_sdk_ids::system_program;
+use solana_stake_interface::state::StakeStateV2;
+use stake_pool::state::WithdrawalTicket;
+
+fn request_withdrawal_ix(
+    context: &ProgramTestContext,
+    pool: &Pool,
+    slp_amount: u64,
+    ticket_id: u64,
+) -> Instruction {
+    let user = context.payer.pubkey();
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::RequestWithdrawal {
+            user,
+            pool_config: pool.config,
+            withdrawal_ticket: pool.withdrawal_ticket(&user, ticket_id),
+            slp_mint: pool.slp_mint,
+            user_slp_account: pool.user_slp,
+            fee_recipient: None,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::RequestWithdrawal {
+            slp_amount,
+            ticket_id,
+        }
+        .data(),
+    }
+}
+
+fn claim_withdrawal_ix(context: &ProgramTestContext, pool: &Pool, ticket: Pubkey) -> Instruction {
+    Instruction {
+        program_id: stake_pool::ID,
+        accounts: stake_pool::accounts::ClaimWithdrawal {
+            user: context.payer.pubkey(),
+            pool_config: pool.config,
+            withdrawal_ticket: ticket,
+            reserve_vault: pool.reserve_vault,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: stake_pool::instruction::ClaimWithdrawal {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn ticket_is_claimable_after_stake_cooldown() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    let user = context.payer.pubkey();
+    let ticket = pool.withdrawal_ticket(&user, 0);
+
+    // Stake almost everything so the reserve cannot cover the exit
+    let delegate = delegate_stake_ix(&context, &pool);
+    send(&mut context, &[delegate], &[]).await.unwrap();
+    let epoch = current_epoch(&mut context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+
+    // Nothing is owed to tickets yet
+    let deactivate = deactivate_stake_ix(&context, &pool);
+    assert_error(send(&mut context, &[deactivate], &[]).await, StakePoolError::NoUnstakeNeeded.into());
+
+    let request = request_withdrawal_ix(&context, &pool, 4 * LAMPORTS_PER_SOL, 0);
+    send(&mut context, &[request], &[]).await.unwrap();
+
+    let account = context.banks_client.get_account(ticket).await.unwrap().unwrap();
+    let ticket_state = WithdrawalTicket::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(ticket_state.owner, user);
+    assert_eq!(ticket_state.lamports, 4 * LAMPORTS_PER_SOL);
+
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.ticket_lamports_owed, 4 * LAMPORTS_PER_SOL);
+    assert_eq!(config.total_slp_supply, 6 * LAMPORTS_PER_SOL);
+
+    // Nothing has been unstaked yet
+    let early_claim = claim_withdrawal_ix(&context, &pool, ticket);
+    assert!(send(&mut context, &[early_claim], &[]).await.is_err());
+
+    // The pool unstakes exactly what tickets are owed
+    let deactivate = deactivate_stake_ix(&context, &pool);
+    send(&mut context, &[deactivate], &[]).await.unwrap();
+    let validator = validator_entry(&mut context, &pool).await;
+    assert_eq!(validator.staked_lamports, 5 * LAMPORTS_PER_SOL);
+    assert!(validator.deactivating_lamports > 4 * LAMPORTS_PER_SOL);
+
+    // Once the tickets are covered there is nothing left to unstake
+    context.get_new_latest_blockhash().await.unwrap();
+    let again = deactivate_stake_ix(&context, &pool);
+    assert_error(send(&mut context, &[again], &[]).await, StakePoolError::NoUnstakeNeeded.into());
+
+    let epoch = current_epoch(&mut context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+
+    let withdraw = withdraw_deactivated_stake_ix(&context, &pool);
+    send(&mut context, &[withdraw], &[]).await.unwrap();
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.claimable_lamports, 4 * LAMPORTS_PER_SOL);
+    assert_eq!(config.deactivating_lamports, 0);
+
+    let balance_before = context.banks_client.get_balance(user).await.unwrap();
+    let claim = claim_withdrawal_ix(&context, &pool, ticket);
+    send(&mut context, &[claim], &[]).await.unwrap();
+    let balance_after = context.banks_client.get_balance(user).await.unwrap();
+
+    // Ticket SOL plus the ticket's rent, less the transaction fee
+    assert!(balance_after > balance_before + 4 * LAMPORTS_PER_SOL);
+    assert!(context.banks_client.get_account(ticket).await.unwrap().is_none());
+
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.ticket_lamports_owed, 0);
+    assert_eq!(config.claimable_lamports, 0);
+}
+
+#[tokio::test]
+async fn tickets_are_paid_in_request_order() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    let user = context.payer.pubkey();
+    let (first, second) = (pool.withdrawal_ticket(&user, 0), pool.withdrawal_ticket(&user, 1));
+
+    let delegate = delegate_stake_ix(&context, &pool);
+    send(&mut context, &[delegate], &[]).await.unwrap();
+    let epoch = current_epoch(&mut context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+
+    // Unstake only enough for the first ticket, then request the second
+    let request = request_withdrawal_ix(&context, &pool, 2 * LAMPORTS_PER_SOL, 0);
+    let deactivate = deactivate_stake_ix(&context, &pool);
+    send(&mut context, &[request, deactivate], &[]).await.unwrap();
+    let request = request_withdrawal_ix(&context, &pool, 2 * LAMPORTS_PER_SOL, 1);
+    send(&mut context, &[request], &[]).await.unwrap();
+    let epoch = current_epoch(&mut context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+    let withdraw = withdraw_deactivated_stake_ix(&context, &pool);
+    send(&mut context, &[withdraw], &[]).await.unwrap();
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.claimable_lamports, 2 * LAMPORTS_PER_SOL);
+
+    // The SOL is the first ticket's, even though it would cover the second
+    let claim_second = claim_withdrawal_ix(&context, &pool, second);
+    assert_error(
+        send(&mut context, &[claim_second], &[]).await,
+        StakePoolError::TicketNotClaimable.into(),
+    );
+    let claim_first = claim_withdrawal_ix(&context, &pool, first);
+    send(&mut context, &[claim_first], &[]).await.unwrap();
+
+    let deactivate = deactivate_stake_ix(&context, &pool);
+    send(&mut context, &[deactivate], &[]).await.unwrap();
+    let epoch = current_epoch(&mut context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+    let withdraw = withdraw_deactivated_stake_ix(&context, &pool);
+    send(&mut context, &[withdraw], &[]).await.unwrap();
+
+    let claim_second = claim_withdrawal_ix(&context, &pool, second);
+    send(&mut context, &[claim_second], &[]).await.unwrap();
+
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.ticket_lamports_owed, 0);
+    assert_eq!(config.ticket_lamports_requested, 4 * LAMPORTS_PER_SOL);
+    assert_eq!(config.ticket_lamports_funded, 4 * LAMPORTS_PER_SOL);
+}
+
+#[tokio::test]
+async fn deactivation_leaves_the_validator_its_minimum_stake() {
+    let (mut context, pool) = setup(10 * LAMPORTS_PER_SOL).await;
+    let delegate = delegate_stake_ix(&context, &pool);
+    send(&mut context, &[delegate], &[]).await.unwrap();
+    let epoch = current_epoch(&mut context).await;
+    context.warp_to_epoch(epoch + 1).unwrap();
+
+    // Tickets owe more than the validator can give up
+    let staked = validator_entry(&mut context, &pool).await.staked_lamports;
+    let request = request_withdrawal_ix(&context, &pool, staked, 0);
+    let deactivate = deactivate_stake_ix(&context, &pool);
+    send(&mut context, &[request, deactivate], &[]).await.unwrap();
+
+    let validator = validator_entry(&mut context, &pool).await;
+    let stake_rent = Rent::default().minimum_balance(StakeStateV2::size_of());
+    assert!(validator.staked_lamports > stake_rent);
+    assert!(validator.staked_lamports < staked);
+    let config = pool_config(&mut context, &pool).await;
+    assert_eq!(config.deactivating_lamports, validator.deactivating_lamports);
+    assert_eq!(
+        validator.deactivating_ticket_lamports,
+        staked - validator.staked_lamports
+    );
+}
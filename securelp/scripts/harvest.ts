@@ -4,6 +4,10 @@
  * Simulates staking rewards by calling harvest_rewards instruction.
  * This increases the exchange rate (slpSOL becomes worth more SOL).
  * 
+ * harvest_rewards only exists when stake_pool is built with the devnet
+ * feature (`anchor build -- --features devnet`). Real deployments use the
+ * update_validator_list_balance crank instead.
+ * 
  * Usage:
  *   cd securelp
  *   ANCHOR_PROVIDER_URL=https://api.devnet.solana.com \
@@ -14,6 +18,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program, AnchorProvider } from "@coral-xyz/anchor";
 import { PublicKey, Connection, Keypair, clusterApiUrl } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import * as fs from "fs";
 import * as path from "path";
 
@@ -27,6 +32,7 @@ const STAKE_POOL_PROGRAM_ID = new PublicKey("EyWBdqo6J5KEzQSvPYhsGFXjJfC6kkmTMGo
 
 // Seeds
 const POOL_CONFIG_SEED = Buffer.from("pool_config");
+const POOL_AUTHORITY_SEED = Buffer.from("pool_authority");
 
 async function main() {
   // Setup connection
@@ -70,6 +76,11 @@ async function main() {
     STAKE_POOL_PROGRAM_ID
   );
 
+  const [poolAuthorityPda] = PublicKey.findProgramAddressSync(
+    [POOL_AUTHORITY_SEED, poolConfigPda.toBuffer()],
+    STAKE_POOL_PROGRAM_ID
+  );
+
   console.log("Pool Config PDA:", poolConfigPda.toString());
 
   // Fetch current pool state
@@ -89,7 +100,7 @@ async function main() {
   console.log(`  slpSOL Supply: ${slpSupply / 1e9}`);
   console.log(`  Exchange Rate: ${currentRate.toFixed(6)} SOL per slpSOL`);
   console.log(`  Last Harvest Epoch: ${(poolConfig.lastHarvestEpoch as any).toNumber()}`);
-  console.log(`  Fee: ${poolConfig.feeBps} bps`);
+  console.log(`  Reward Fee: ${poolConfig.feeBps} bps`);
 
   // Get current epoch
   const epochInfo = await connection.getEpochInfo();
@@ -104,13 +115,22 @@ async function main() {
   // Harvest rewards
   console.log("\n🌾 Harvesting rewards...");
   
+  // The reward fee is minted to the fee recipient once one is set
+  const feeRecipient = (poolConfig.feeRecipient as PublicKey).equals(PublicKey.default)
+    ? null
+    : (poolConfig.feeRecipient as PublicKey);
+
   try {
     const tx = await stakePoolProgram.methods
       .harvestRewards()
       .accounts({
         cranker: walletKeypair.publicKey,
         poolConfig: poolConfigPda,
-      })
+        poolAuthority: poolAuthorityPda,
+        slpMint: poolConfig.slpMint as PublicKey,
+        feeRecipient,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      } as any)
       .rpc();
 
     console.log(`  ✅ Transaction: ${tx}`);
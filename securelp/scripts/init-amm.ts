@@ -226,6 +226,9 @@ async function main() {
         reserveVault: reserveVault,
         slpMint: slpMint,
         userSlpAccount: userSlpAccount,
+        feeRecipient: null,
+        referrer: null,
+        userStake: null,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })